@@ -0,0 +1,455 @@
+//! Encoding of v2.1.0 request bodies into bytes.
+//!
+//! An [`Encoder`] turns a [`Body`] into an [`EncodedBody`]: the payload together with the
+//! `Content-Type` Postman would send for it.
+//!
+//! - `raw` bodies are sent as is, typed from `options.raw.language` (`text/plain` by default);
+//! - `urlencoded` bodies use `application/x-www-form-urlencoded` escaping;
+//! - `formdata` bodies become `multipart/form-data`, one part per file for fields with several
+//!   file sources;
+//! - `file` bodies are the file content, with no `Content-Type`;
+//! - `graphql` bodies are wrapped in a `{"query": ..., "variables": ...}` JSON envelope, with
+//!   `operationName` when the body names one.
+//!
+//! Disabled bodies and disabled parameters are skipped. File sources are read through a
+//! [`FileResolver`], which defaults to reading from disk.
+//!
+//! ```
+//! use postman_collection::body::Encoder;
+//! use postman_collection::v2_1_0::Body;
+//!
+//! let body: Body = serde_json::from_str(r#"{
+//!   "mode": "formdata",
+//!   "formdata": [{ "key": "avatar", "type": "file", "src": "/photos/me.png" }]
+//! }"#)?;
+//!
+//! let encoder = Encoder::new()
+//!     .with_boundary("XyZ")
+//!     .with_file_resolver(|src: &str| Ok(format!("<{src}>").into_bytes()));
+//! let encoded = encoder.encode(&body)?.unwrap();
+//!
+//! assert_eq!(encoded.content_type.as_deref(), Some("multipart/form-data; boundary=XyZ"));
+//! assert_eq!(
+//!     String::from_utf8(encoded.bytes)?,
+//!     "--XyZ\r\n\
+//!      Content-Disposition: form-data; name=\"avatar\"; filename=\"me.png\"\r\n\
+//!      Content-Type: application/octet-stream\r\n\
+//!      \r\n\
+//!      </photos/me.png>\r\n\
+//!      --XyZ--\r\n",
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::de::IgnoredAny;
+use serde_json::{Map, Value};
+
+use crate::v2_1_0::{Body, FormParameterSrcUnion, GraphQlBody, Mode, RawLanguage};
+use crate::{Error, Result};
+
+/// Reads the files that `formdata` and `file` bodies refer to.
+pub trait FileResolver: Send + Sync {
+    /// The content of the file at `src`, as written in the collection.
+    fn read(&self, src: &str) -> io::Result<Vec<u8>>;
+}
+
+impl<F> FileResolver for F
+where
+    F: Fn(&str) -> io::Result<Vec<u8>> + Send + Sync,
+{
+    fn read(&self, src: &str) -> io::Result<Vec<u8>> {
+        self(src)
+    }
+}
+
+/// Reads files from disk, resolving relative sources against an optional root directory.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FsFileResolver {
+    root: Option<PathBuf>,
+}
+
+impl FsFileResolver {
+    /// Resolves relative sources against the current directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves relative sources against `root`, typically the collection's directory.
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        FsFileResolver {
+            root: Some(root.into()),
+        }
+    }
+}
+
+impl FileResolver for FsFileResolver {
+    fn read(&self, src: &str) -> io::Result<Vec<u8>> {
+        match self.root.as_ref() {
+            Some(root) => fs::read(root.join(src)),
+            None => fs::read(src),
+        }
+    }
+}
+
+/// An encoded body and the `Content-Type` that describes it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncodedBody {
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// Encodes bodies. See the [module documentation](self).
+#[derive(Clone)]
+pub struct Encoder {
+    files: Arc<dyn FileResolver>,
+    boundary: Option<String>,
+}
+
+impl fmt::Debug for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encoder")
+            .field("boundary", &self.boundary)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoder {
+    /// An encoder that reads files with [`FsFileResolver::new`].
+    pub fn new() -> Self {
+        Encoder {
+            files: Arc::new(FsFileResolver::new()),
+            boundary: None,
+        }
+    }
+
+    /// Reads files through `files` instead of from disk.
+    pub fn with_file_resolver(mut self, files: impl FileResolver + 'static) -> Self {
+        self.files = Arc::new(files);
+        self
+    }
+
+    /// Uses `boundary` for multipart bodies. By default the boundary is derived from the parts,
+    /// so the same body always encodes the same way.
+    pub fn with_boundary(mut self, boundary: impl Into<String>) -> Self {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Encodes `body` according to its [`Mode`]. Returns `None` for a disabled body or one
+    /// without a mode.
+    pub fn encode(&self, body: &Body) -> Result<Option<EncodedBody>> {
+        if body.disabled.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let encoded = match body.mode.as_ref() {
+            None => return Ok(None),
+            Some(Mode::Raw) => EncodedBody {
+                content_type: Some(
+                    body.raw_language()
+                        .map_or("text/plain", RawLanguage::content_type)
+                        .to_owned(),
+                ),
+                bytes: body.raw.clone().unwrap_or_default().into_bytes(),
+            },
+            Some(Mode::Urlencoded) => {
+                let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+                for param in body.urlencoded.iter().flatten() {
+                    if !param.disabled.unwrap_or(false) {
+                        serializer
+                            .append_pair(&param.key, param.value.as_deref().unwrap_or_default());
+                    }
+                }
+                EncodedBody {
+                    content_type: Some("application/x-www-form-urlencoded".to_owned()),
+                    bytes: serializer.finish().into_bytes(),
+                }
+            }
+            Some(Mode::Formdata) => self.multipart(body)?,
+            Some(Mode::File) => {
+                let file = body.file.as_ref();
+                let bytes = match (
+                    file.and_then(|file| file.content.as_ref()),
+                    file.and_then(|file| file.src.as_deref()),
+                ) {
+                    (Some(content), _) => content.clone().into_bytes(),
+                    (None, Some(src)) => self.read(src)?,
+                    (None, None) => Vec::new(),
+                };
+                EncodedBody {
+                    content_type: None,
+                    bytes,
+                }
+            }
+            Some(Mode::Graphql) => EncodedBody {
+                content_type: Some("application/json".to_owned()),
+                bytes: serde_json::to_vec(&graphql_envelope(body.graphql.as_ref())?)?,
+            },
+        };
+        Ok(Some(encoded))
+    }
+
+    fn read(&self, src: &str) -> Result<Vec<u8>> {
+        self.files.read(src).map_err(|source| Error::File {
+            src: src.to_owned(),
+            source,
+        })
+    }
+
+    fn multipart(&self, body: &Body) -> Result<EncodedBody> {
+        let mut parts = Vec::new();
+        for field in body.formdata.iter().flatten() {
+            if field.disabled.unwrap_or(false) {
+                continue;
+            }
+            if field.form_parameter_type.as_deref() != Some("file") {
+                parts.push(Part {
+                    name: field.key.clone(),
+                    filename: None,
+                    content_type: field.content_type.clone(),
+                    bytes: field.value.clone().unwrap_or_default().into_bytes(),
+                });
+                continue;
+            }
+
+            let sources = match field.src.as_ref() {
+                Some(FormParameterSrcUnion::File(src)) => std::slice::from_ref(src),
+                Some(FormParameterSrcUnion::Files(sources)) => sources.as_slice(),
+                None => &[],
+            };
+            for src in sources {
+                parts.push(Part {
+                    name: field.key.clone(),
+                    filename: Some(
+                        Path::new(src).file_name().map_or_else(
+                            || src.clone(),
+                            |name| name.to_string_lossy().into_owned(),
+                        ),
+                    ),
+                    content_type: Some(
+                        field
+                            .content_type
+                            .clone()
+                            .unwrap_or_else(|| "application/octet-stream".to_owned()),
+                    ),
+                    bytes: self.read(src)?,
+                });
+            }
+        }
+
+        let boundary = self.boundary.clone().unwrap_or_else(|| boundary(&parts));
+        let mut bytes = Vec::new();
+        for part in &parts {
+            bytes.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+            let mut disposition = format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                quote(&part.name)
+            );
+            if let Some(filename) = part.filename.as_deref() {
+                disposition.push_str(&format!("; filename=\"{}\"", quote(filename)));
+            }
+            bytes.extend_from_slice(disposition.as_bytes());
+            bytes.extend_from_slice(b"\r\n");
+            if let Some(content_type) = part.content_type.as_deref() {
+                bytes.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+            }
+            bytes.extend_from_slice(b"\r\n");
+            bytes.extend_from_slice(&part.bytes);
+            bytes.extend_from_slice(b"\r\n");
+        }
+        bytes.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+        Ok(EncodedBody {
+            content_type: Some(format!("multipart/form-data; boundary={boundary}")),
+            bytes,
+        })
+    }
+}
+
+impl Body {
+    /// Encodes the body with a default [`Encoder`], reading files from disk.
+    pub fn encode(&self) -> Result<Option<EncodedBody>> {
+        Encoder::new().encode(self)
+    }
+
+    /// The language of a `raw` body, from `options.raw.language`.
+    pub fn raw_language(&self) -> Option<&RawLanguage> {
+        self.options.as_ref()?.raw.as_ref()?.language.as_ref()
+    }
+
+    /// The `raw` content, re-indented when it is JSON. Content that does not parse, for example
+    /// because of an unquoted `{{variable}}`, is returned unchanged.
+    pub fn pretty_raw(&self) -> Option<Cow<'_, str>> {
+        let raw = self.raw.as_deref()?;
+        if self.raw_language() != Some(&RawLanguage::Json) {
+            return Some(Cow::Borrowed(raw));
+        }
+        match serde_json::from_str::<IgnoredAny>(raw) {
+            Ok(_) => Some(Cow::Owned(indent_json(raw))),
+            Err(_) => Some(Cow::Borrowed(raw)),
+        }
+    }
+}
+
+impl RawLanguage {
+    /// The `Content-Type` Postman sends for the language, `text/plain` for languages without
+    /// one of their own.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            RawLanguage::Json => "application/json",
+            RawLanguage::Javascript => "application/javascript",
+            RawLanguage::Html => "text/html",
+            RawLanguage::Xml => "application/xml",
+            RawLanguage::Text | RawLanguage::Graphql | RawLanguage::Other(_) => "text/plain",
+        }
+    }
+
+    /// The language of a body sent with `content_type`, `None` for types without one.
+    pub(crate) fn from_content_type(content_type: &str) -> Option<RawLanguage> {
+        let content_type = content_type.to_ascii_lowercase();
+        let language = if content_type.contains("json") {
+            RawLanguage::Json
+        } else if content_type.contains("xml") {
+            RawLanguage::Xml
+        } else if content_type.contains("html") {
+            RawLanguage::Html
+        } else if content_type.contains("javascript") {
+            RawLanguage::Javascript
+        } else if content_type.starts_with("text/plain") {
+            RawLanguage::Text
+        } else {
+            return None;
+        };
+        Some(language)
+    }
+}
+
+/// `{"query": ..., "variables": ..., "operationName": ...}`, with string `variables` parsed as
+/// JSON.
+pub(crate) fn graphql_envelope(graphql: Option<&GraphQlBody>) -> Result<Value> {
+    let mut envelope = Map::new();
+    let query = graphql
+        .and_then(|graphql| graphql.query.clone())
+        .unwrap_or_default();
+    envelope.insert("query".to_owned(), Value::String(query));
+
+    let Some(graphql) = graphql else {
+        return Ok(Value::Object(envelope));
+    };
+    if let Some(variables) = graphql.parse_variables()? {
+        envelope.insert("variables".to_owned(), variables);
+    }
+    if let Some(operation_name) = graphql.operation_name.clone() {
+        envelope.insert("operationName".to_owned(), Value::String(operation_name));
+    }
+    Ok(Value::Object(envelope))
+}
+
+/// Re-indents valid JSON with two spaces, like `serde_json::to_string_pretty` but keeping the
+/// order of object keys.
+fn indent_json(json: &str) -> String {
+    let mut pretty = String::with_capacity(json.len() * 2);
+    let mut depth = 0;
+    let mut chars = json.chars().peekable();
+    let newline = |pretty: &mut String, depth: usize| {
+        pretty.push('\n');
+        pretty.push_str(&"  ".repeat(depth));
+    };
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                pretty.push(ch);
+                while let Some(ch) = chars.next() {
+                    pretty.push(ch);
+                    match ch {
+                        '\\' => pretty.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                pretty.push(ch);
+                while chars.next_if(|ch| ch.is_ascii_whitespace()).is_some() {}
+                if let Some(close) =
+                    chars.next_if(|next| matches!((ch, next), ('{', '}') | ('[', ']')))
+                {
+                    pretty.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut pretty, depth);
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                newline(&mut pretty, depth);
+                pretty.push(ch);
+            }
+            ',' => {
+                pretty.push(ch);
+                newline(&mut pretty, depth);
+            }
+            ':' => pretty.push_str(": "),
+            _ if ch.is_ascii_whitespace() => {}
+            _ => pretty.push(ch),
+        }
+    }
+    pretty
+}
+
+struct Part {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    bytes: Vec<u8>,
+}
+
+/// A boundary derived from the parts, derived again until no part contains it. It is always 42
+/// characters long, within the 70 that RFC 2046 allows.
+fn boundary(parts: &[Part]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.name.bytes().chain(part.bytes.iter().copied()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    loop {
+        let boundary = format!("--------------------------{hash:016x}");
+        if !parts
+            .iter()
+            .any(|part| contains(&part.bytes, boundary.as_bytes()))
+        {
+            return boundary;
+        }
+        hash = (hash.rotate_left(7) ^ 0x9e37_79b9_7f4a_7c15).wrapping_mul(0x0100_0000_01b3);
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Escapes a `Content-Disposition` parameter the way browsers do.
+fn quote(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
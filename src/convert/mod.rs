@@ -0,0 +1,146 @@
+//! Conversions between the supported Postman Collection versions.
+//!
+//! Converting between versions is not always lossless. Every conversion returns the converted
+//! spec together with a [`ConversionReport`] listing anything that could not be carried over.
+//! Each entry is located with a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) into the
+//! *source* document.
+//!
+//! Moving between v2.0.0 and v2.1.0 is also available through the standard conversion traits:
+//! `From<v2_0_0::Spec> for v2_1_0::Spec` never loses information, while
+//! `TryFrom<v2_1_0::Spec> for v2_0_0::Spec` fails with [`Error::LossyConversion`] instead of
+//! dropping anything.
+//!
+//! [`Error::LossyConversion`]: crate::Error::LossyConversion
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::{v1_0_0, v2_0_0, v2_1_0};
+
+mod v1;
+mod v2;
+
+/// The result of a version conversion.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Converted<T> {
+    /// The converted spec.
+    pub spec: T,
+
+    /// Everything that could not be carried over into `spec`.
+    pub report: ConversionReport,
+}
+
+/// Findings collected while converting a spec between versions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConversionReport {
+    pub warnings: Vec<ConversionWarning>,
+}
+
+impl ConversionReport {
+    /// Returns `true` if nothing was lost or rearranged during the conversion.
+    pub fn is_lossless(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    fn push(&mut self, location: impl Into<String>, kind: ConversionWarningKind) {
+        self.warnings.push(ConversionWarning {
+            location: location.into(),
+            kind,
+        });
+    }
+}
+
+impl fmt::Display for ConversionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, warning) in self.warnings.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{warning}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single finding of a version conversion.
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("{location}: {kind}")]
+pub struct ConversionWarning {
+    /// JSON Pointer to the offending value in the source document.
+    pub location: String,
+
+    pub kind: ConversionWarningKind,
+}
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ConversionWarningKind {
+    /// An order list references a folder or request id that does not exist.
+    #[error("references unknown id {id}")]
+    MissingReference { id: String },
+    /// A folder or request is referenced more than once, or a folder contains itself.
+    #[error("references id {id} which was already placed in the tree")]
+    RepeatedReference { id: String },
+    /// A folder or request is not referenced by any order list. It was kept anyway.
+    #[error("is not referenced by any order and was appended to its parent")]
+    Unordered,
+    /// A value has no equivalent in the target version and was dropped.
+    #[error("{reason}; the value was dropped")]
+    Dropped { reason: String },
+}
+
+fn dropped(reason: impl Into<String>) -> ConversionWarningKind {
+    ConversionWarningKind::Dropped {
+        reason: reason.into(),
+    }
+}
+
+/// Converts a v1.0.0 collection into a v2.1.0 collection.
+///
+/// The folder tree is rebuilt from `order`, `folders_order` and `Folder::order`. Raw header
+/// strings become header lists, `dataMode`/`data`/`rawModeData` become a [`v2_1_0::Body`], and
+/// `preRequestScript`/`tests` become events.
+///
+/// Bookkeeping fields that v2 collections do not have, such as `timestamp`, `time`,
+/// `collectionId` and a request's `folder`, are not carried over and not reported either.
+///
+/// ```
+/// use postman_collection::{convert, from_str, PostmanCollection};
+///
+/// let input = r#"{
+///   "id": "c1",
+///   "name": "Legacy",
+///   "order": ["r1"],
+///   "requests": [{
+///     "id": "r1",
+///     "name": "Ping",
+///     "method": "GET",
+///     "url": "https://example.com/ping",
+///     "headers": "Accept: application/json\n"
+///   }]
+/// }"#;
+///
+/// let PostmanCollection::V1_0_0(spec) = from_str(input)? else { unreachable!() };
+/// let converted = convert::v1_0_0_to_v2_1_0(&spec);
+/// assert_eq!(converted.spec.info.name, "Legacy");
+/// assert!(converted.report.is_lossless());
+/// # Ok::<(), postman_collection::Error>(())
+/// ```
+pub fn v1_0_0_to_v2_1_0(spec: &v1_0_0::Spec) -> Converted<v2_1_0::Spec> {
+    v1::convert(spec)
+}
+
+/// Converts a v2.1.0 collection into a v2.0.0 collection, dropping whatever v2.0.0 cannot
+/// express.
+///
+/// v2.0.0 has no EdgeGrid auth, GraphQL bodies, body options, multi-file form parameters,
+/// response names or response timings, and stores auth attributes as a map rather than a list.
+/// Use `v2_0_0::Spec::try_from` to refuse the conversion instead.
+pub fn v2_1_0_to_v2_0_0(spec: &v2_1_0::Spec) -> Converted<v2_0_0::Spec> {
+    v2::downgrade(spec)
+}
+
+/// A single v1.0.0 request as a v2.1.0 request, ignoring anything that cannot be carried over.
+pub(crate) fn v1_request(request: &v1_0_0::Request) -> v2_1_0::RequestClass {
+    v1::request(request, "", &mut ConversionReport::default())
+}
@@ -0,0 +1,894 @@
+//! v1.0.0 to v2.1.0 conversion.
+
+use serde_json::Value;
+
+use super::{ConversionReport, ConversionWarningKind, Converted, dropped};
+use crate::v1_0_0::order::{Node, OrderIssue, Tree};
+use crate::{v1_0_0, v2_1_0};
+
+pub(super) fn convert(spec: &v1_0_0::Spec) -> Converted<v2_1_0::Spec> {
+    let mut report = ConversionReport::default();
+    let tree = Tree::build(spec);
+    record_order_issues(spec, &tree.issues, &mut report);
+
+    let converted = v2_1_0::Spec {
+        auth: spec.auth.as_ref().map(auth),
+        event: spec
+            .events
+            .as_ref()
+            .map(|events| events.iter().map(event).collect()),
+        info: v2_1_0::Information {
+            postman_id: Some(spec.id.clone()),
+            description: non_empty(spec.description.as_deref())
+                .map(v2_1_0::DescriptionUnion::String),
+            name: spec.name.clone(),
+            schema: v2_1_0::SCHEMA_URL.to_owned(),
+            version: None,
+        },
+        item: items(spec, &tree.roots, &mut report),
+        variable: spec
+            .variables
+            .as_ref()
+            .map(|variables| variables.iter().map(variable).collect()),
+    };
+
+    Converted {
+        spec: converted,
+        report,
+    }
+}
+
+fn record_order_issues(spec: &v1_0_0::Spec, issues: &[OrderIssue], report: &mut ConversionReport) {
+    let folders = spec.folders.as_deref().unwrap_or_default();
+    let owner_location = |owner: &str, field: &str| {
+        if owner == spec.id {
+            return format!("/{field}");
+        }
+        match folders.iter().position(|folder| folder.id == owner) {
+            Some(index) => format!("/folders/{index}/{field}"),
+            None => format!("/{field}"),
+        }
+    };
+
+    for issue in issues {
+        match issue {
+            OrderIssue::MissingFolder { referenced_by, id } => report.push(
+                owner_location(referenced_by, "folders_order"),
+                ConversionWarningKind::MissingReference { id: id.clone() },
+            ),
+            OrderIssue::MissingRequest { referenced_by, id } => report.push(
+                owner_location(referenced_by, "order"),
+                ConversionWarningKind::MissingReference { id: id.clone() },
+            ),
+            OrderIssue::RepeatedFolder { referenced_by, id } => report.push(
+                owner_location(referenced_by, "folders_order"),
+                ConversionWarningKind::RepeatedReference { id: id.clone() },
+            ),
+            OrderIssue::RepeatedRequest { referenced_by, id } => report.push(
+                owner_location(referenced_by, "order"),
+                ConversionWarningKind::RepeatedReference { id: id.clone() },
+            ),
+            OrderIssue::UnorderedFolder { index } => report.push(
+                format!("/folders/{index}"),
+                ConversionWarningKind::Unordered,
+            ),
+            OrderIssue::UnorderedRequest { index } => report.push(
+                format!("/requests/{index}"),
+                ConversionWarningKind::Unordered,
+            ),
+        }
+    }
+}
+
+fn items(spec: &v1_0_0::Spec, nodes: &[Node], report: &mut ConversionReport) -> Vec<v2_1_0::Items> {
+    let folders = spec.folders.as_deref().unwrap_or_default();
+
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Folder { index, children } => {
+                let folder = &folders[*index];
+                v2_1_0::Items::ItemGroup(v2_1_0::ItemGroup {
+                    auth: folder.auth.as_ref().map(auth),
+                    description: non_empty(Some(&folder.description))
+                        .map(v2_1_0::DescriptionUnion::String),
+                    event: folder
+                        .events
+                        .as_ref()
+                        .map(|events| events.iter().map(event).collect()),
+                    item: items(spec, children, report),
+                    name: Some(folder.name.clone()),
+                    variable: folder
+                        .variables
+                        .as_ref()
+                        .map(|variables| variables.iter().map(variable).collect()),
+                })
+            }
+            Node::Request(index) => {
+                v2_1_0::Items::Item(item(spec, *index, &format!("/requests/{index}"), report))
+            }
+        })
+        .collect()
+}
+
+fn item(
+    spec: &v1_0_0::Spec,
+    index: usize,
+    location: &str,
+    report: &mut ConversionReport,
+) -> v2_1_0::Item {
+    let source = &spec.requests[index];
+    let request = request(source, location, report);
+
+    let response = source.responses.as_ref().map(|responses| {
+        responses
+            .iter()
+            .enumerate()
+            .map(|(response_index, response)| {
+                self::response(
+                    spec,
+                    response,
+                    &format!("{location}/responses/{response_index}"),
+                    report,
+                )
+            })
+            .collect()
+    });
+
+    v2_1_0::Item {
+        description: request.description.clone(),
+        event: events(source),
+        id: Some(source.id.clone()),
+        name: Some(source.name.clone()),
+        protocol_profile_behavior: source.protocol_profile_behavior.as_ref().map(|behavior| {
+            v2_1_0::ProtocolProfileBehavior {
+                disable_body_pruning: behavior.disable_body_pruning,
+            }
+        }),
+        request: v2_1_0::RequestUnion::RequestClass(request),
+        response,
+        variable: source
+            .variables
+            .as_ref()
+            .map(|variables| variables.iter().map(variable).collect()),
+    }
+}
+
+pub(super) fn request(
+    source: &v1_0_0::Request,
+    location: &str,
+    report: &mut ConversionReport,
+) -> v2_1_0::RequestClass {
+    let description = non_empty(source.description.as_deref()).map(|content| {
+        match source.description_format.as_ref() {
+            Some(format) => v2_1_0::DescriptionUnion::Description(v2_1_0::Description {
+                content: Some(content),
+                description_type: Some(
+                    match format {
+                        v1_0_0::DescriptionFormat::Html => "text/html",
+                        v1_0_0::DescriptionFormat::Markdown => "text/markdown",
+                    }
+                    .to_owned(),
+                ),
+                version: None,
+            }),
+            None => v2_1_0::DescriptionUnion::String(content),
+        }
+    });
+
+    let headers = headers(source, location, report);
+
+    v2_1_0::RequestClass {
+        auth: request_auth(source, location, report),
+        body: body(source, location, report),
+        certificate: None,
+        description,
+        header: (!headers.is_empty()).then_some(v2_1_0::HeaderUnion::HeaderArray(headers)),
+        method: Some(source.method.clone()),
+        proxy: None,
+        url: Some(url(source, location, report)),
+    }
+}
+
+/// Prefers the structured `headerData` list and falls back to parsing the raw `headers` string,
+/// where Postman marks disabled headers with a leading `//`.
+fn headers(
+    source: &v1_0_0::Request,
+    location: &str,
+    report: &mut ConversionReport,
+) -> Vec<v2_1_0::Header> {
+    let header_data = source.header_data.as_deref().unwrap_or_default();
+    let structured = header_data
+        .iter()
+        .any(|header| matches!(header, Some(v1_0_0::Header::HeaderClass(_))));
+
+    if structured {
+        return header_data
+            .iter()
+            .enumerate()
+            .filter_map(|(index, header)| match header {
+                Some(v1_0_0::Header::HeaderClass(header)) => Some(v2_1_0::Header {
+                    description: non_empty(header.description.as_deref())
+                        .map(v2_1_0::DescriptionUnion::String),
+                    disabled: disabled(header.enabled),
+                    key: header.key.clone().unwrap_or_default(),
+                    value: header.value.clone().unwrap_or_default(),
+                }),
+                Some(_) => {
+                    report.push(
+                        format!("{location}/headerData/{index}"),
+                        dropped("header entry is not a key/value object"),
+                    );
+                    None
+                }
+                None => None,
+            })
+            .collect();
+    }
+
+    source
+        .headers
+        .lines()
+        .enumerate()
+        .filter_map(|(line_number, line)| {
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+            let (disabled, line) = match line.strip_prefix("//") {
+                Some(line) => (Some(true), line.trim_start()),
+                None => (None, line),
+            };
+            let Some((key, value)) = line.split_once(':') else {
+                report.push(
+                    format!("{location}/headers"),
+                    dropped(format!(
+                        "header line {} has no `:` separator",
+                        line_number + 1
+                    )),
+                );
+                return None;
+            };
+            Some(v2_1_0::Header {
+                description: None,
+                disabled,
+                key: key.trim().to_owned(),
+                value: value.trim().to_owned(),
+            })
+        })
+        .collect()
+}
+
+fn url(source: &v1_0_0::Request, location: &str, report: &mut ConversionReport) -> v2_1_0::Url {
+    let query: Vec<v2_1_0::QueryParam> = source
+        .query_params
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, param)| match param {
+            Some(v1_0_0::UrlParam::UrlParamClass(param)) => Some(v2_1_0::QueryParam {
+                description: non_empty(param.description.as_deref())
+                    .map(v2_1_0::DescriptionUnion::String),
+                disabled: None,
+                key: param.key.clone(),
+                value: param.value.clone(),
+            }),
+            Some(_) => {
+                report.push(
+                    format!("{location}/queryParams/{index}"),
+                    dropped("query parameter is not a key/value object"),
+                );
+                None
+            }
+            None => None,
+        })
+        .collect();
+
+    let variables = path_variables(source, location, report);
+
+    if query.is_empty() && variables.is_empty() {
+        return v2_1_0::Url::String(source.url.clone());
+    }
+
+    v2_1_0::Url::UrlClass(v2_1_0::UrlClass {
+        hash: None,
+        host: None,
+        path: None,
+        port: None,
+        protocol: None,
+        query: (!query.is_empty()).then_some(query),
+        raw: Some(source.url.clone()),
+        variable: (!variables.is_empty()).then_some(variables),
+    })
+}
+
+/// Prefers `pathVariableData`, which keeps descriptions, over the `pathVariables` map.
+fn path_variables(
+    source: &v1_0_0::Request,
+    location: &str,
+    report: &mut ConversionReport,
+) -> Vec<v2_1_0::Variable> {
+    let data = source.path_variable_data.as_deref().unwrap_or_default();
+    if !data.is_empty() {
+        return data
+            .iter()
+            .enumerate()
+            .filter_map(|(index, variable)| match variable {
+                Some(v1_0_0::PathVariable::PathVariableClass(variable)) => Some(path_variable(
+                    variable.key.clone(),
+                    variable.value.clone().map(Value::String),
+                    variable.description.as_deref(),
+                )),
+                Some(_) => {
+                    report.push(
+                        format!("{location}/pathVariableData/{index}"),
+                        dropped("path variable is not a key/value object"),
+                    );
+                    None
+                }
+                None => None,
+            })
+            .collect();
+    }
+
+    match source.path_variables.as_ref() {
+        Some(v1_0_0::PathVariables::AnythingMap(map)) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            keys.into_iter()
+                .map(|key| path_variable(Some(key.clone()), map[key].clone(), None))
+                .collect()
+        }
+        Some(v1_0_0::PathVariables::String(value)) if !value.is_empty() => {
+            report.push(
+                format!("{location}/pathVariables"),
+                dropped("path variables given as a string cannot be mapped to variables"),
+            );
+            Vec::new()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn path_variable(
+    key: Option<String>,
+    value: Option<Value>,
+    description: Option<&str>,
+) -> v2_1_0::Variable {
+    v2_1_0::Variable {
+        description: non_empty(description).map(v2_1_0::DescriptionUnion::String),
+        disabled: None,
+        id: None,
+        key,
+        name: None,
+        system: None,
+        variable_type: None,
+        value,
+    }
+}
+
+fn body(
+    source: &v1_0_0::Request,
+    location: &str,
+    report: &mut ConversionReport,
+) -> Option<v2_1_0::Body> {
+    let data = source.data.as_deref().unwrap_or_default();
+    let empty = v2_1_0::Body {
+        disabled: source.data_disabled.filter(|disabled| *disabled),
+        file: None,
+        formdata: None,
+        graphql: None,
+        mode: None,
+        options: None,
+        raw: None,
+        urlencoded: None,
+    };
+
+    let Some(mode) = source.data_mode.as_ref() else {
+        if !data.is_empty()
+            || matches!(&source.raw_mode_data, Some(v1_0_0::RawModeData::String(raw)) if !raw.is_empty())
+        {
+            report.push(
+                format!("{location}/dataMode"),
+                dropped("request data is present but no dataMode selects it"),
+            );
+        }
+        return None;
+    };
+
+    Some(match mode {
+        v1_0_0::DataMode::Raw => {
+            let raw = match source.raw_mode_data.as_ref() {
+                Some(v1_0_0::RawModeData::String(raw)) => Some(raw.clone()),
+                Some(v1_0_0::RawModeData::AnythingArray(_)) => {
+                    report.push(
+                        format!("{location}/rawModeData"),
+                        dropped("raw body given as an array has no v2 equivalent"),
+                    );
+                    None
+                }
+                None => None,
+            };
+            v2_1_0::Body {
+                mode: Some(v2_1_0::Mode::Raw),
+                raw,
+                ..empty
+            }
+        }
+        v1_0_0::DataMode::Params => v2_1_0::Body {
+            mode: Some(v2_1_0::Mode::Formdata),
+            formdata: Some(data.iter().map(form_parameter).collect()),
+            ..empty
+        },
+        v1_0_0::DataMode::Urlencoded => v2_1_0::Body {
+            mode: Some(v2_1_0::Mode::Urlencoded),
+            urlencoded: Some(
+                data.iter()
+                    .map(|datum| v2_1_0::UrlEncodedParameter {
+                        description: non_empty(datum.description.as_deref())
+                            .map(v2_1_0::DescriptionUnion::String),
+                        disabled: disabled(datum.enabled),
+                        key: datum.key.clone().unwrap_or_default(),
+                        parameter_type: datum_type(datum).map(str::to_owned),
+                        value: datum.value.clone(),
+                    })
+                    .collect(),
+            ),
+            ..empty
+        },
+        v1_0_0::DataMode::Binary => {
+            report.push(
+                format!("{location}/dataMode"),
+                dropped("v1 exports do not store the file of a binary body"),
+            );
+            v2_1_0::Body {
+                mode: Some(v2_1_0::Mode::File),
+                file: Some(v2_1_0::File {
+                    content: None,
+                    src: None,
+                }),
+                ..empty
+            }
+        }
+    })
+}
+
+fn form_parameter(datum: &v1_0_0::Datum) -> v2_1_0::FormParameter {
+    let is_file = datum_type(datum) == Some("file");
+
+    v2_1_0::FormParameter {
+        content_type: datum.content_type.clone(),
+        description: non_empty(datum.description.as_deref()).map(v2_1_0::DescriptionUnion::String),
+        disabled: disabled(datum.enabled),
+        key: datum.key.clone().unwrap_or_default(),
+        form_parameter_type: datum_type(datum).map(str::to_owned),
+        value: if is_file { None } else { datum.value.clone() },
+        src: if is_file {
+            datum.value.clone().map(v2_1_0::FormParameterSrcUnion::File)
+        } else {
+            None
+        },
+    }
+}
+
+fn datum_type(datum: &v1_0_0::Datum) -> Option<&str> {
+    datum.datum_type.as_ref().and_then(Value::as_str)
+}
+
+/// Uses the explicit `auth` block, or rebuilds one from the legacy `currentHelper` and
+/// `helperAttributes` pair.
+fn request_auth(
+    source: &v1_0_0::Request,
+    location: &str,
+    report: &mut ConversionReport,
+) -> Option<v2_1_0::Auth> {
+    if let Some(source_auth) = source.auth.as_ref() {
+        return Some(auth(source_auth));
+    }
+
+    let attributes = match source.helper_attributes.as_ref()? {
+        v1_0_0::HelperAttributes::Object(attributes) => attributes,
+        v1_0_0::HelperAttributes::String(value) => {
+            if !value.is_empty() {
+                report.push(
+                    format!("{location}/helperAttributes"),
+                    dropped("helper attributes given as a string cannot be mapped to auth"),
+                );
+            }
+            return None;
+        }
+    };
+
+    let helper = attributes
+        .get("id")
+        .and_then(Value::as_str)
+        .or(source.current_helper.as_deref());
+    let auth_type = match helper {
+        Some("awsSigV4") => v2_1_0::AuthType::Awsv4,
+        Some("basic") => v2_1_0::AuthType::Basic,
+        Some("bearer") => v2_1_0::AuthType::Bearer,
+        Some("digest") => v2_1_0::AuthType::Digest,
+        Some("hawk") => v2_1_0::AuthType::Hawk,
+        Some("ntlm") => v2_1_0::AuthType::Ntlm,
+        Some("oAuth1") => v2_1_0::AuthType::Oauth1,
+        Some("oAuth2") => v2_1_0::AuthType::Oauth2,
+        Some("normal") | None if attributes.is_empty() => return None,
+        other => {
+            report.push(
+                format!("{location}/helperAttributes"),
+                dropped(format!(
+                    "helper {} has no v2 auth equivalent",
+                    other.unwrap_or("without an id")
+                )),
+            );
+            return None;
+        }
+    };
+
+    let mut keys: Vec<&String> = attributes.keys().filter(|key| *key != "id").collect();
+    keys.sort();
+    let attributes = keys
+        .into_iter()
+        .map(|key| v2_1_0::AuthAttribute {
+            key: key.clone(),
+            auth_type: None,
+            value: Some(attributes[key].clone()),
+        })
+        .collect();
+
+    Some(auth_with_attributes(auth_type, attributes))
+}
+
+fn auth_with_attributes(
+    auth_type: v2_1_0::AuthType,
+    attributes: Vec<v2_1_0::AuthAttribute>,
+) -> v2_1_0::Auth {
+    let mut converted = v2_1_0::Auth {
+        api_key: None,
+        awsv4: None,
+        basic: None,
+        bearer: None,
+        digest: None,
+        edgegrid: None,
+        hawk: None,
+        noauth: None,
+        ntlm: None,
+        oauth1: None,
+        oauth2: None,
+        auth_type: auth_type.clone(),
+    };
+    let slot = match auth_type {
+        v2_1_0::AuthType::Apikey => &mut converted.api_key,
+        v2_1_0::AuthType::Awsv4 => &mut converted.awsv4,
+        v2_1_0::AuthType::Basic => &mut converted.basic,
+        v2_1_0::AuthType::Bearer => &mut converted.bearer,
+        v2_1_0::AuthType::Digest => &mut converted.digest,
+        v2_1_0::AuthType::Edgegrid => &mut converted.edgegrid,
+        v2_1_0::AuthType::Hawk => &mut converted.hawk,
+        v2_1_0::AuthType::Ntlm => &mut converted.ntlm,
+        v2_1_0::AuthType::Oauth1 => &mut converted.oauth1,
+        v2_1_0::AuthType::Oauth2 => &mut converted.oauth2,
+        v2_1_0::AuthType::Noauth => return converted,
+    };
+    *slot = Some(attributes);
+    converted
+}
+
+fn auth(source: &v1_0_0::Auth) -> v2_1_0::Auth {
+    let attributes = |attributes: &Option<Vec<v1_0_0::AuthAttribute>>| {
+        attributes.as_ref().map(|attributes| {
+            attributes
+                .iter()
+                .map(|attribute| v2_1_0::AuthAttribute {
+                    key: attribute.key.clone(),
+                    auth_type: attribute.auth_type.clone(),
+                    value: attribute.value.clone(),
+                })
+                .collect()
+        })
+    };
+
+    v2_1_0::Auth {
+        api_key: attributes(&source.api_key),
+        awsv4: attributes(&source.awsv4),
+        basic: attributes(&source.basic),
+        bearer: attributes(&source.bearer),
+        digest: attributes(&source.digest),
+        edgegrid: None,
+        hawk: attributes(&source.hawk),
+        noauth: source.noauth.clone(),
+        ntlm: attributes(&source.ntlm),
+        oauth1: attributes(&source.oauth1),
+        oauth2: attributes(&source.oauth2),
+        auth_type: match source.auth_type {
+            v1_0_0::AuthType::Apikey => v2_1_0::AuthType::Apikey,
+            v1_0_0::AuthType::Awsv4 => v2_1_0::AuthType::Awsv4,
+            v1_0_0::AuthType::Basic => v2_1_0::AuthType::Basic,
+            v1_0_0::AuthType::Bearer => v2_1_0::AuthType::Bearer,
+            v1_0_0::AuthType::Digest => v2_1_0::AuthType::Digest,
+            v1_0_0::AuthType::Hawk => v2_1_0::AuthType::Hawk,
+            v1_0_0::AuthType::Noauth => v2_1_0::AuthType::Noauth,
+            v1_0_0::AuthType::Ntlm => v2_1_0::AuthType::Ntlm,
+            v1_0_0::AuthType::Oauth1 => v2_1_0::AuthType::Oauth1,
+            v1_0_0::AuthType::Oauth2 => v2_1_0::AuthType::Oauth2,
+        },
+    }
+}
+
+/// Merges the request's `events` with events synthesized from `preRequestScript` and `tests`.
+/// An explicit event wins over the legacy script field listening on the same name.
+fn events(source: &v1_0_0::Request) -> Option<Vec<v2_1_0::Event>> {
+    let mut events: Vec<v2_1_0::Event> = source
+        .events
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(event)
+        .collect();
+
+    for (listen, script) in [
+        ("prerequest", source.pre_request_script.as_deref()),
+        ("test", source.tests.as_deref()),
+    ] {
+        let Some(script) = non_empty(script) else {
+            continue;
+        };
+        if events.iter().any(|event| event.listen == listen) {
+            continue;
+        }
+        events.push(v2_1_0::Event {
+            disabled: None,
+            id: None,
+            listen: listen.to_owned(),
+            script: Some(v2_1_0::Script {
+                exec: Some(v2_1_0::Host::StringArray(
+                    script.lines().map(str::to_owned).collect(),
+                )),
+                id: None,
+                name: None,
+                src: None,
+                script_type: Some("text/javascript".to_owned()),
+            }),
+        });
+    }
+
+    (source.events.is_some() || !events.is_empty()).then_some(events)
+}
+
+fn event(source: &v1_0_0::Event) -> v2_1_0::Event {
+    v2_1_0::Event {
+        disabled: source.disabled,
+        id: source.id.clone(),
+        listen: source.listen.clone(),
+        script: source.script.as_ref().map(|script| v2_1_0::Script {
+            exec: script.exec.as_ref().map(host),
+            id: script.id.clone(),
+            name: script.name.clone(),
+            src: script.src.as_ref().map(script_url),
+            script_type: script.script_type.clone(),
+        }),
+    }
+}
+
+fn host(source: &v1_0_0::Host) -> v2_1_0::Host {
+    match source {
+        v1_0_0::Host::String(value) => v2_1_0::Host::String(value.clone()),
+        v1_0_0::Host::StringArray(values) => v2_1_0::Host::StringArray(values.clone()),
+    }
+}
+
+fn script_url(source: &v1_0_0::Url) -> v2_1_0::Url {
+    match source {
+        v1_0_0::Url::String(value) => v2_1_0::Url::String(value.clone()),
+        v1_0_0::Url::UrlClass(url) => v2_1_0::Url::UrlClass(v2_1_0::UrlClass {
+            hash: url.hash.clone(),
+            host: url.host.as_ref().map(host),
+            path: url.path.as_ref().map(|path| match path {
+                v1_0_0::UrlPath::String(value) => v2_1_0::UrlPath::String(value.clone()),
+                v1_0_0::UrlPath::UnionArray(elements) => v2_1_0::UrlPath::UnionArray(
+                    elements
+                        .iter()
+                        .map(|element| match element {
+                            v1_0_0::PathElement::String(value) => {
+                                v2_1_0::PathElement::String(value.clone())
+                            }
+                            v1_0_0::PathElement::PathClass(path) => {
+                                v2_1_0::PathElement::PathClass(v2_1_0::PathClass {
+                                    path_type: path.path_type.clone(),
+                                    value: path.value.clone(),
+                                })
+                            }
+                        })
+                        .collect(),
+                ),
+            }),
+            port: url.port.clone(),
+            protocol: url.protocol.clone(),
+            query: url.query.as_ref().map(|query| {
+                query
+                    .iter()
+                    .map(|param| v2_1_0::QueryParam {
+                        description: param.description.as_ref().map(description),
+                        disabled: param.disabled,
+                        key: param.key.clone(),
+                        value: param.value.clone(),
+                    })
+                    .collect()
+            }),
+            raw: url.raw.clone(),
+            variable: url
+                .variable
+                .as_ref()
+                .map(|variables| variables.iter().map(variable).collect()),
+        }),
+    }
+}
+
+fn description(source: &v1_0_0::Description) -> v2_1_0::DescriptionUnion {
+    match source {
+        v1_0_0::Description::String(value) => v2_1_0::DescriptionUnion::String(value.clone()),
+        v1_0_0::Description::DescriptionClass(description) => {
+            v2_1_0::DescriptionUnion::Description(v2_1_0::Description {
+                content: description.content.clone(),
+                description_type: description.description_type.clone(),
+                version: description.version.clone(),
+            })
+        }
+    }
+}
+
+fn variable(source: &v1_0_0::Variable) -> v2_1_0::Variable {
+    v2_1_0::Variable {
+        description: source.description.as_ref().map(description),
+        disabled: source.disabled,
+        id: source.id.clone(),
+        key: source.key.clone(),
+        name: source.name.clone(),
+        system: source.system,
+        variable_type: source
+            .variable_type
+            .as_ref()
+            .map(|variable_type| match variable_type {
+                v1_0_0::VariableType::Any => v2_1_0::VariableType::Any,
+                v1_0_0::VariableType::Boolean => v2_1_0::VariableType::Boolean,
+                v1_0_0::VariableType::Number => v2_1_0::VariableType::Number,
+                v1_0_0::VariableType::String => v2_1_0::VariableType::String,
+            }),
+        value: source.value.clone(),
+    }
+}
+
+fn response(
+    spec: &v1_0_0::Spec,
+    source: &v1_0_0::ResponseClass,
+    location: &str,
+    report: &mut ConversionReport,
+) -> v2_1_0::ResponseClass {
+    let code = source.response_code.code;
+    if code.fract() != 0.0 {
+        report.push(
+            format!("{location}/responseCode/code"),
+            dropped(format!("response code {code} is not an integer")),
+        );
+    }
+    if source.response_code.detail.is_some() {
+        report.push(
+            format!("{location}/responseCode/detail"),
+            dropped("response code details have no v2 equivalent"),
+        );
+    }
+    for (field, present) in [
+        ("language", source.language.is_some()),
+        ("mime", source.mime.is_some()),
+        ("rawDataType", source.raw_data_type.is_some()),
+    ] {
+        if present {
+            report.push(
+                format!("{location}/{field}"),
+                dropped(format!("{field} has no v2 equivalent")),
+            );
+        }
+    }
+
+    // A v1 response refers to the request it was recorded for by id. Anything else cannot be
+    // represented as an original request.
+    let original_request = match source.request.as_ref() {
+        Some(v1_0_0::PathVariables::String(id)) => {
+            match spec.requests.iter().position(|request| &request.id == id) {
+                Some(index) => Some(v2_1_0::RequestUnion::RequestClass(request(
+                    &spec.requests[index],
+                    &format!("/requests/{index}"),
+                    &mut ConversionReport::default(),
+                ))),
+                None => {
+                    report.push(
+                        format!("{location}/request"),
+                        ConversionWarningKind::MissingReference { id: id.clone() },
+                    );
+                    None
+                }
+            }
+        }
+        Some(v1_0_0::PathVariables::AnythingMap(_)) => {
+            report.push(
+                format!("{location}/request"),
+                dropped("inline original requests cannot be mapped to a v2 request"),
+            );
+            None
+        }
+        None => None,
+    };
+
+    v2_1_0::ResponseClass {
+        body: source.text.clone(),
+        code: (code.fract() == 0.0).then_some(code as i64),
+        cookie: source
+            .cookies
+            .as_ref()
+            .map(|cookies| cookies.iter().map(cookie).collect()),
+        header: source.headers.as_ref().map(|headers| {
+            v2_1_0::Headers::UnionArray(
+                headers
+                    .iter()
+                    .map(|header| {
+                        v2_1_0::HeaderElement::Header(v2_1_0::Header {
+                            description: non_empty(header.description.as_deref())
+                                .map(v2_1_0::DescriptionUnion::String),
+                            disabled: None,
+                            key: header.key.clone(),
+                            value: header.value.clone(),
+                        })
+                    })
+                    .collect(),
+            )
+        }),
+        id: Some(source.id.clone()),
+        name: source.name.clone(),
+        original_request,
+        response_time: source.time.as_ref().map(|time| match time {
+            v1_0_0::Time::Double(time) if time.fract() == 0.0 => {
+                v2_1_0::ResponseTime::Integer(*time as i64)
+            }
+            v1_0_0::Time::Double(time) => v2_1_0::ResponseTime::Double(*time),
+            v1_0_0::Time::String(time) => v2_1_0::ResponseTime::String(time.clone()),
+        }),
+        timings: None,
+        status: Some(source.response_code.name.clone()),
+    }
+}
+
+/// v1 cookies carry `expirationDate` and `storeId`, which v2 keeps as cookie extensions.
+fn cookie(source: &v1_0_0::Cookie) -> v2_1_0::Cookie {
+    let extension = |key: &str, value: Value| {
+        Some(serde_json::json!({
+            "key": key,
+            "value": value,
+        }))
+    };
+
+    v2_1_0::Cookie {
+        domain: source.domain.clone(),
+        expires: Some(source.expires.clone()),
+        extensions: Some(vec![
+            extension("expirationDate", Value::from(source.expiration_date)),
+            extension("storeId", Value::String(source.store_id.clone())),
+        ]),
+        host_only: Some(source.host_only),
+        http_only: Some(source.http_only),
+        max_age: None,
+        name: Some(source.name.clone()),
+        path: source.path.clone(),
+        secure: Some(source.secure),
+        session: Some(source.session),
+        value: Some(source.value.clone()),
+    }
+}
+
+fn disabled(enabled: Option<bool>) -> Option<bool> {
+    enabled.map(|enabled| !enabled).filter(|disabled| *disabled)
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.filter(|value| !value.is_empty()).map(str::to_owned)
+}
@@ -0,0 +1,734 @@
+//! Conversions between v2.0.0 and v2.1.0.
+//!
+//! Both versions share most of their types field for field. Those are converted through the
+//! [`Recast`] helper below. Only the types that actually differ are converted by hand:
+//! auth attributes, bodies and form parameters, responses, and the containers that hold them.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::{ConversionReport, Converted, dropped};
+use crate::{Error, v2_0_0, v2_1_0};
+
+impl From<v2_0_0::Spec> for v2_1_0::Spec {
+    fn from(spec: v2_0_0::Spec) -> Self {
+        upgrade::spec(&spec)
+    }
+}
+
+impl TryFrom<v2_1_0::Spec> for v2_0_0::Spec {
+    type Error = Error;
+
+    /// Fails with [`Error::LossyConversion`] if the spec uses anything v2.0.0 cannot express.
+    fn try_from(spec: v2_1_0::Spec) -> Result<Self, Self::Error> {
+        let converted = downgrade(&spec);
+        if converted.report.is_lossless() {
+            Ok(converted.spec)
+        } else {
+            Err(Error::LossyConversion {
+                report: converted.report,
+            })
+        }
+    }
+}
+
+pub(super) fn downgrade(spec: &v2_1_0::Spec) -> Converted<v2_0_0::Spec> {
+    let mut report = ConversionReport::default();
+    let spec = downgrade::spec(spec, &mut report);
+    Converted { spec, report }
+}
+
+/// Field-for-field conversion between types that are identical in both versions.
+trait Recast<T> {
+    fn recast(&self) -> T;
+}
+
+impl<T, U> Recast<Option<U>> for Option<T>
+where
+    T: Recast<U>,
+{
+    fn recast(&self) -> Option<U> {
+        self.as_ref().map(Recast::recast)
+    }
+}
+
+impl<T, U> Recast<Vec<U>> for Vec<T>
+where
+    T: Recast<U>,
+{
+    fn recast(&self) -> Vec<U> {
+        self.iter().map(Recast::recast).collect()
+    }
+}
+
+macro_rules! recast_identity {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Recast<$ty> for $ty {
+                fn recast(&self) -> $ty {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! recast_struct {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        impl Recast<v2_1_0::$name> for v2_0_0::$name {
+            fn recast(&self) -> v2_1_0::$name {
+                v2_1_0::$name { $($field: self.$field.recast()),* }
+            }
+        }
+
+        impl Recast<v2_0_0::$name> for v2_1_0::$name {
+            fn recast(&self) -> v2_0_0::$name {
+                v2_0_0::$name { $($field: self.$field.recast()),* }
+            }
+        }
+    };
+}
+
+macro_rules! recast_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl Recast<v2_1_0::$name> for v2_0_0::$name {
+            fn recast(&self) -> v2_1_0::$name {
+                match self {
+                    $(Self::$variant(value) => v2_1_0::$name::$variant(value.recast())),*
+                }
+            }
+        }
+
+        impl Recast<v2_0_0::$name> for v2_1_0::$name {
+            fn recast(&self) -> v2_0_0::$name {
+                match self {
+                    $(Self::$variant(value) => v2_0_0::$name::$variant(value.recast())),*
+                }
+            }
+        }
+    };
+}
+
+recast_identity!(String, bool, i64, f64, Value);
+
+recast_struct!(Information {
+    postman_id,
+    description,
+    name,
+    schema,
+    version
+});
+recast_struct!(CollectionVersionClass {
+    identifier,
+    major,
+    meta,
+    minor,
+    patch
+});
+recast_struct!(Description {
+    content,
+    description_type,
+    version
+});
+recast_struct!(Event {
+    disabled,
+    id,
+    listen,
+    script
+});
+recast_struct!(Script {
+    exec,
+    id,
+    name,
+    src,
+    script_type
+});
+recast_struct!(UrlClass {
+    hash,
+    host,
+    path,
+    port,
+    protocol,
+    query,
+    raw,
+    variable
+});
+recast_struct!(PathClass { path_type, value });
+recast_struct!(QueryParam {
+    description,
+    disabled,
+    key,
+    value
+});
+recast_struct!(Variable {
+    description,
+    disabled,
+    id,
+    key,
+    name,
+    system,
+    variable_type,
+    value
+});
+recast_struct!(ProtocolProfileBehavior {
+    disable_body_pruning
+});
+recast_struct!(File { content, src });
+recast_struct!(UrlEncodedParameter {
+    description,
+    disabled,
+    key,
+    parameter_type,
+    value
+});
+recast_struct!(Certificate {
+    cert,
+    key,
+    matches,
+    name,
+    passphrase
+});
+recast_struct!(Cert { src });
+recast_struct!(Key { src });
+recast_struct!(Header {
+    description,
+    disabled,
+    key,
+    value
+});
+recast_struct!(ProxyConfig {
+    disabled,
+    host,
+    proxy_config_match,
+    port,
+    tunnel
+});
+recast_struct!(Cookie {
+    domain,
+    expires,
+    extensions,
+    host_only,
+    http_only,
+    max_age,
+    name,
+    path,
+    secure,
+    session,
+    value,
+});
+
+recast_enum!(Host {
+    String,
+    StringArray
+});
+recast_enum!(Url { String, UrlClass });
+recast_enum!(UrlPath { String, UnionArray });
+recast_enum!(PathElement { PathClass, String });
+recast_enum!(DescriptionUnion {
+    Description,
+    String
+});
+recast_enum!(CollectionVersion {
+    CollectionVersionClass,
+    String
+});
+recast_enum!(HeaderUnion {
+    HeaderArray,
+    String
+});
+recast_enum!(Headers { String, UnionArray });
+recast_enum!(HeaderElement { Header, String });
+recast_enum!(ResponseTime {
+    Integer,
+    Double,
+    String
+});
+
+impl Recast<v2_1_0::VariableType> for v2_0_0::VariableType {
+    fn recast(&self) -> v2_1_0::VariableType {
+        match self {
+            Self::Any => v2_1_0::VariableType::Any,
+            Self::Boolean => v2_1_0::VariableType::Boolean,
+            Self::Number => v2_1_0::VariableType::Number,
+            Self::String => v2_1_0::VariableType::String,
+        }
+    }
+}
+
+impl Recast<v2_0_0::VariableType> for v2_1_0::VariableType {
+    fn recast(&self) -> v2_0_0::VariableType {
+        match self {
+            Self::Any => v2_0_0::VariableType::Any,
+            Self::Boolean => v2_0_0::VariableType::Boolean,
+            Self::Number => v2_0_0::VariableType::Number,
+            Self::String => v2_0_0::VariableType::String,
+        }
+    }
+}
+
+mod upgrade {
+    use super::*;
+
+    pub(super) fn spec(spec: &v2_0_0::Spec) -> v2_1_0::Spec {
+        v2_1_0::Spec {
+            auth: spec.auth.as_ref().map(auth),
+            event: spec.event.recast(),
+            info: v2_1_0::Information {
+                schema: v2_1_0::SCHEMA_URL.to_owned(),
+                ..spec.info.recast()
+            },
+            item: items(&spec.item),
+            variable: spec.variable.recast(),
+        }
+    }
+
+    fn items(source: &[v2_0_0::Items]) -> Vec<v2_1_0::Items> {
+        source
+            .iter()
+            .map(|item| match item {
+                v2_0_0::Items::Item(item) => v2_1_0::Items::Item(v2_1_0::Item {
+                    description: item.description.recast(),
+                    event: item.event.recast(),
+                    id: item.id.clone(),
+                    name: item.name.clone(),
+                    protocol_profile_behavior: item.protocol_profile_behavior.recast(),
+                    request: request_union(&item.request),
+                    response: item
+                        .response
+                        .as_ref()
+                        .map(|responses| responses.iter().map(response).collect()),
+                    variable: item.variable.recast(),
+                }),
+                v2_0_0::Items::ItemGroup(group) => v2_1_0::Items::ItemGroup(v2_1_0::ItemGroup {
+                    auth: group.auth.as_ref().map(auth),
+                    description: group.description.recast(),
+                    event: group.event.recast(),
+                    item: items(&group.item),
+                    name: group.name.clone(),
+                    variable: group.variable.recast(),
+                }),
+            })
+            .collect()
+    }
+
+    fn request_union(request: &v2_0_0::RequestUnion) -> v2_1_0::RequestUnion {
+        match request {
+            v2_0_0::RequestUnion::String(url) => v2_1_0::RequestUnion::String(url.clone()),
+            v2_0_0::RequestUnion::RequestClass(request) => {
+                v2_1_0::RequestUnion::RequestClass(v2_1_0::RequestClass {
+                    auth: request.auth.as_ref().map(auth),
+                    body: request.body.as_ref().map(body),
+                    certificate: request.certificate.recast(),
+                    description: request.description.recast(),
+                    header: request.header.recast(),
+                    method: request.method.clone(),
+                    proxy: request.proxy.recast(),
+                    url: request.url.recast(),
+                })
+            }
+        }
+    }
+
+    fn body(body: &v2_0_0::Body) -> v2_1_0::Body {
+        v2_1_0::Body {
+            disabled: body.disabled,
+            file: body.file.recast(),
+            formdata: body.formdata.as_ref().map(|parameters| {
+                parameters
+                    .iter()
+                    .map(|parameter| v2_1_0::FormParameter {
+                        content_type: parameter.content_type.clone(),
+                        description: parameter.description.recast(),
+                        disabled: parameter.disabled,
+                        key: parameter.key.clone(),
+                        form_parameter_type: parameter.form_parameter_type.clone(),
+                        value: parameter.value.clone(),
+                        src: parameter
+                            .src
+                            .clone()
+                            .map(v2_1_0::FormParameterSrcUnion::File),
+                    })
+                    .collect()
+            }),
+            graphql: None,
+            mode: body.mode.as_ref().map(|mode| match mode {
+                v2_0_0::Mode::File => v2_1_0::Mode::File,
+                v2_0_0::Mode::Formdata => v2_1_0::Mode::Formdata,
+                v2_0_0::Mode::Raw => v2_1_0::Mode::Raw,
+                v2_0_0::Mode::Urlencoded => v2_1_0::Mode::Urlencoded,
+            }),
+            options: None,
+            raw: body.raw.clone(),
+            urlencoded: body.urlencoded.recast(),
+        }
+    }
+
+    fn response(response: &v2_0_0::ResponseClass) -> v2_1_0::ResponseClass {
+        v2_1_0::ResponseClass {
+            body: response.body.clone(),
+            code: response.code,
+            cookie: response.cookie.recast(),
+            header: response.header.recast(),
+            id: response.id.clone(),
+            name: None,
+            original_request: response.original_request.as_ref().map(request_union),
+            response_time: response.response_time.recast(),
+            timings: None,
+            status: response.status.clone(),
+        }
+    }
+
+    /// v2.0.0 stores auth attributes as a map; v2.1.0 as a list. Keys are sorted so the output
+    /// does not depend on hash map iteration order.
+    fn auth(auth: &v2_0_0::Auth) -> v2_1_0::Auth {
+        let attributes = |map: &Option<HashMap<String, Option<Value>>>| {
+            map.as_ref().map(|map| {
+                let mut attributes: Vec<v2_1_0::AuthAttribute> = map
+                    .iter()
+                    .map(|(key, value)| v2_1_0::AuthAttribute {
+                        key: key.clone(),
+                        auth_type: None,
+                        value: value.clone(),
+                    })
+                    .collect();
+                attributes.sort_by(|left, right| left.key.cmp(&right.key));
+                attributes
+            })
+        };
+
+        v2_1_0::Auth {
+            api_key: attributes(&auth.api_key),
+            awsv4: attributes(&auth.awsv4),
+            basic: attributes(&auth.basic),
+            bearer: attributes(&auth.bearer),
+            digest: attributes(&auth.digest),
+            edgegrid: None,
+            hawk: attributes(&auth.hawk),
+            noauth: auth.noauth.clone(),
+            ntlm: attributes(&auth.ntlm),
+            oauth1: attributes(&auth.oauth1),
+            oauth2: attributes(&auth.oauth2),
+            auth_type: match auth.auth_type {
+                v2_0_0::AuthType::Apikey => v2_1_0::AuthType::Apikey,
+                v2_0_0::AuthType::Awsv4 => v2_1_0::AuthType::Awsv4,
+                v2_0_0::AuthType::Basic => v2_1_0::AuthType::Basic,
+                v2_0_0::AuthType::Bearer => v2_1_0::AuthType::Bearer,
+                v2_0_0::AuthType::Digest => v2_1_0::AuthType::Digest,
+                v2_0_0::AuthType::Hawk => v2_1_0::AuthType::Hawk,
+                v2_0_0::AuthType::Noauth => v2_1_0::AuthType::Noauth,
+                v2_0_0::AuthType::Ntlm => v2_1_0::AuthType::Ntlm,
+                v2_0_0::AuthType::Oauth1 => v2_1_0::AuthType::Oauth1,
+                v2_0_0::AuthType::Oauth2 => v2_1_0::AuthType::Oauth2,
+            },
+        }
+    }
+}
+
+mod downgrade {
+    use super::*;
+
+    pub(super) fn spec(spec: &v2_1_0::Spec, report: &mut ConversionReport) -> v2_0_0::Spec {
+        v2_0_0::Spec {
+            auth: spec
+                .auth
+                .as_ref()
+                .and_then(|spec_auth| auth(spec_auth, "/auth", report)),
+            event: spec.event.recast(),
+            info: v2_0_0::Information {
+                schema: v2_0_0::SCHEMA_URL.to_owned(),
+                ..spec.info.recast()
+            },
+            item: items(&spec.item, "/item", report),
+            variable: spec.variable.recast(),
+        }
+    }
+
+    fn items(
+        source: &[v2_1_0::Items],
+        location: &str,
+        report: &mut ConversionReport,
+    ) -> Vec<v2_0_0::Items> {
+        source
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let location = format!("{location}/{index}");
+                match item {
+                    v2_1_0::Items::Item(item) => v2_0_0::Items::Item(v2_0_0::Item {
+                        description: item.description.recast(),
+                        event: item.event.recast(),
+                        id: item.id.clone(),
+                        name: item.name.clone(),
+                        protocol_profile_behavior: item.protocol_profile_behavior.recast(),
+                        request: request_union(
+                            &item.request,
+                            &format!("{location}/request"),
+                            report,
+                        ),
+                        response: item.response.as_ref().map(|responses| {
+                            responses
+                                .iter()
+                                .enumerate()
+                                .map(|(index, item_response)| {
+                                    response(
+                                        item_response,
+                                        &format!("{location}/response/{index}"),
+                                        report,
+                                    )
+                                })
+                                .collect()
+                        }),
+                        variable: item.variable.recast(),
+                    }),
+                    v2_1_0::Items::ItemGroup(group) => {
+                        v2_0_0::Items::ItemGroup(v2_0_0::ItemGroup {
+                            auth: group.auth.as_ref().and_then(|group_auth| {
+                                auth(group_auth, &format!("{location}/auth"), report)
+                            }),
+                            description: group.description.recast(),
+                            event: group.event.recast(),
+                            item: items(&group.item, &format!("{location}/item"), report),
+                            name: group.name.clone(),
+                            variable: group.variable.recast(),
+                        })
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn request_union(
+        request: &v2_1_0::RequestUnion,
+        location: &str,
+        report: &mut ConversionReport,
+    ) -> v2_0_0::RequestUnion {
+        match request {
+            v2_1_0::RequestUnion::String(url) => v2_0_0::RequestUnion::String(url.clone()),
+            v2_1_0::RequestUnion::RequestClass(request) => {
+                v2_0_0::RequestUnion::RequestClass(v2_0_0::RequestClass {
+                    auth: request.auth.as_ref().and_then(|request_auth| {
+                        auth(request_auth, &format!("{location}/auth"), report)
+                    }),
+                    body: request.body.as_ref().map(|request_body| {
+                        body(request_body, &format!("{location}/body"), report)
+                    }),
+                    certificate: request.certificate.recast(),
+                    description: request.description.recast(),
+                    header: request.header.recast(),
+                    method: request.method.clone(),
+                    proxy: request.proxy.recast(),
+                    url: request.url.recast(),
+                })
+            }
+        }
+    }
+
+    fn body(body: &v2_1_0::Body, location: &str, report: &mut ConversionReport) -> v2_0_0::Body {
+        if body.graphql.is_some() {
+            report.push(
+                format!("{location}/graphql"),
+                dropped("GraphQL bodies are not supported by v2.0.0"),
+            );
+        }
+        if body.options.is_some() {
+            report.push(
+                format!("{location}/options"),
+                dropped("body options are not supported by v2.0.0"),
+            );
+        }
+
+        let mode = body.mode.as_ref().and_then(|mode| match mode {
+            v2_1_0::Mode::File => Some(v2_0_0::Mode::File),
+            v2_1_0::Mode::Formdata => Some(v2_0_0::Mode::Formdata),
+            v2_1_0::Mode::Raw => Some(v2_0_0::Mode::Raw),
+            v2_1_0::Mode::Urlencoded => Some(v2_0_0::Mode::Urlencoded),
+            v2_1_0::Mode::Graphql => {
+                report.push(
+                    format!("{location}/mode"),
+                    dropped("the graphql body mode is not supported by v2.0.0"),
+                );
+                None
+            }
+        });
+
+        v2_0_0::Body {
+            disabled: body.disabled,
+            file: body.file.recast(),
+            formdata: body.formdata.as_ref().map(|parameters| {
+                parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(index, parameter)| {
+                        form_parameter(parameter, &format!("{location}/formdata/{index}"), report)
+                    })
+                    .collect()
+            }),
+            mode,
+            raw: body.raw.clone(),
+            urlencoded: body.urlencoded.recast(),
+        }
+    }
+
+    fn form_parameter(
+        parameter: &v2_1_0::FormParameter,
+        location: &str,
+        report: &mut ConversionReport,
+    ) -> v2_0_0::FormParameter {
+        let src = match parameter.src.as_ref() {
+            None => None,
+            Some(v2_1_0::FormParameterSrcUnion::File(src)) => Some(src.clone()),
+            Some(v2_1_0::FormParameterSrcUnion::Files(sources)) => {
+                if sources.len() > 1 {
+                    report.push(
+                        format!("{location}/src"),
+                        dropped(
+                            "v2.0.0 form parameters hold a single file; only the first was kept",
+                        ),
+                    );
+                }
+                sources.first().cloned()
+            }
+        };
+
+        v2_0_0::FormParameter {
+            content_type: parameter.content_type.clone(),
+            description: parameter.description.recast(),
+            disabled: parameter.disabled,
+            key: parameter.key.clone(),
+            form_parameter_type: parameter.form_parameter_type.clone(),
+            value: parameter.value.clone(),
+            src,
+        }
+    }
+
+    fn response(
+        response: &v2_1_0::ResponseClass,
+        location: &str,
+        report: &mut ConversionReport,
+    ) -> v2_0_0::ResponseClass {
+        if response.name.is_some() {
+            report.push(
+                format!("{location}/name"),
+                dropped("response names are not supported by v2.0.0"),
+            );
+        }
+        if response.timings.is_some() {
+            report.push(
+                format!("{location}/timings"),
+                dropped("response timings are not supported by v2.0.0"),
+            );
+        }
+
+        v2_0_0::ResponseClass {
+            body: response.body.clone(),
+            code: response.code,
+            cookie: response.cookie.recast(),
+            header: response.header.recast(),
+            id: response.id.clone(),
+            original_request: response.original_request.as_ref().map(|request| {
+                request_union(request, &format!("{location}/originalRequest"), report)
+            }),
+            response_time: response.response_time.recast(),
+            status: response.status.clone(),
+        }
+    }
+
+    /// Folds attribute lists into maps. A list can say things a map cannot: the same key twice,
+    /// or an explicit attribute `type` that disagrees with the value.
+    fn auth(
+        auth: &v2_1_0::Auth,
+        location: &str,
+        report: &mut ConversionReport,
+    ) -> Option<v2_0_0::Auth> {
+        let auth_type = match auth.auth_type {
+            v2_1_0::AuthType::Apikey => v2_0_0::AuthType::Apikey,
+            v2_1_0::AuthType::Awsv4 => v2_0_0::AuthType::Awsv4,
+            v2_1_0::AuthType::Basic => v2_0_0::AuthType::Basic,
+            v2_1_0::AuthType::Bearer => v2_0_0::AuthType::Bearer,
+            v2_1_0::AuthType::Digest => v2_0_0::AuthType::Digest,
+            v2_1_0::AuthType::Hawk => v2_0_0::AuthType::Hawk,
+            v2_1_0::AuthType::Noauth => v2_0_0::AuthType::Noauth,
+            v2_1_0::AuthType::Ntlm => v2_0_0::AuthType::Ntlm,
+            v2_1_0::AuthType::Oauth1 => v2_0_0::AuthType::Oauth1,
+            v2_1_0::AuthType::Oauth2 => v2_0_0::AuthType::Oauth2,
+            v2_1_0::AuthType::Edgegrid => {
+                report.push(
+                    location,
+                    dropped("EdgeGrid auth is not supported by v2.0.0"),
+                );
+                return None;
+            }
+        };
+        if auth.edgegrid.is_some() {
+            report.push(
+                format!("{location}/edgegrid"),
+                dropped("EdgeGrid auth is not supported by v2.0.0"),
+            );
+        }
+
+        let mut attributes = |field: &str, attributes: &Option<Vec<v2_1_0::AuthAttribute>>| {
+            attributes.as_ref().map(|attributes| {
+                let mut map = HashMap::new();
+                for (index, attribute) in attributes.iter().enumerate() {
+                    let attribute_location = format!("{location}/{field}/{index}");
+                    match attribute.auth_type.as_deref() {
+                        Some(attribute_type)
+                            if !type_matches(attribute_type, attribute.value.as_ref()) =>
+                        {
+                            report.push(
+                                format!("{attribute_location}/type"),
+                                dropped(format!(
+                                    "v2.0.0 auth attributes cannot declare the type {attribute_type}"
+                                )),
+                            );
+                        }
+                        _ => {}
+                    }
+                    if map
+                        .insert(attribute.key.clone(), attribute.value.clone())
+                        .is_some()
+                    {
+                        report.push(
+                            attribute_location,
+                            dropped(format!(
+                                "v2.0.0 auth attributes cannot repeat the key {}",
+                                attribute.key
+                            )),
+                        );
+                    }
+                }
+                map
+            })
+        };
+
+        Some(v2_0_0::Auth {
+            api_key: attributes("apikey", &auth.api_key),
+            awsv4: attributes("awsv4", &auth.awsv4),
+            basic: attributes("basic", &auth.basic),
+            bearer: attributes("bearer", &auth.bearer),
+            digest: attributes("digest", &auth.digest),
+            hawk: attributes("hawk", &auth.hawk),
+            noauth: auth.noauth.clone(),
+            ntlm: attributes("ntlm", &auth.ntlm),
+            oauth1: attributes("oauth1", &auth.oauth1),
+            oauth2: attributes("oauth2", &auth.oauth2),
+            auth_type,
+        })
+    }
+
+    /// Postman writes a `type` next to every attribute value. It carries no information when it
+    /// just names the JSON type of the value, so only a disagreeing type is reported as lost.
+    fn type_matches(attribute_type: &str, value: Option<&Value>) -> bool {
+        matches!(
+            (attribute_type, value),
+            ("any", _)
+                | (_, None | Some(Value::Null))
+                | ("string", Some(Value::String(_)))
+                | ("boolean", Some(Value::Bool(_)))
+                | ("number", Some(Value::Number(_)))
+                | ("array", Some(Value::Array(_)))
+                | ("object", Some(Value::Object(_)))
+        )
+    }
+}
@@ -0,0 +1,459 @@
+//! Conversion between collection requests and curl command lines.
+//!
+//! A [`Generator`] turns a [`RequestClass`], an [`Item`] or a v1.0.0
+//! [`Request`](v1_0_0::Request) into a `curl` invocation for a POSIX shell, with every argument
+//! quoted so that the command can be pasted as is:
+//!
+//! - the method is upper-cased and only spelled out when curl would not pick it by itself;
+//! - `:pathVariables` are expanded from the URL's `variable` list and disabled query parameters
+//!   are left out;
+//! - enabled headers become `-H` options;
+//! - `raw` and `graphql` bodies are sent with `--data-raw`, `urlencoded` bodies with one
+//!   `--data-urlencode` per parameter, `formdata` bodies with `-F`/`--form-string` and `file`
+//!   bodies with `--data-binary @file`. The `Content-Type` the [`body`](crate::body) module
+//!   would send is added unless a header already sets one. Bodies are kept for every method,
+//!   like Postman's own code generator does;
+//! - `basic` auth becomes `-u`, `bearer` auth an `Authorization` header and `apikey` auth a
+//!   header or query parameter. A header set on the request wins over the auth. Other auth
+//!   schemes are left out;
+//! - an enabled [`ProxyConfig`](crate::v2_1_0::ProxyConfig) becomes `--proxy`, and the files of
+//!   a [`Certificate`](crate::v2_1_0::Certificate) become `--cert`, `--key` and `--pass`.
+//!
+//! `{{variables}}` are kept as written unless [`Generator::with_shell_variables`] turns them
+//! into shell variables, so the command can be run after exporting them.
+//!
+//! ```
+//! use postman_collection::curl::Generator;
+//! use postman_collection::v2_1_0::RequestClass;
+//!
+//! let request: RequestClass = serde_json::from_str(r#"{
+//!   "method": "post",
+//!   "url": "{{baseUrl}}/users",
+//!   "header": [{ "key": "Accept", "value": "application/json" }],
+//!   "body": {
+//!     "mode": "raw",
+//!     "raw": "{\"name\": \"O'Brien\"}",
+//!     "options": { "raw": { "language": "json" } }
+//!   }
+//! }"#)?;
+//!
+//! assert_eq!(
+//!     request.to_curl()?,
+//!     r#"curl '{{baseUrl}}/users' -H 'Accept: application/json' -H 'Content-Type: application/json' --data-raw '{"name": "O'\''Brien"}'"#,
+//! );
+//! assert_eq!(
+//!     Generator::new().with_shell_variables().with_line_breaks().generate(&request)?,
+//!     "curl \"${baseUrl}/users\" \\\n  \
+//!      -H 'Accept: application/json' \\\n  \
+//!      -H 'Content-Type: application/json' \\\n  \
+//!      --data-raw '{\"name\": \"O'\\''Brien\"}'",
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`parse`] goes the other way and reads a command, quoted for a POSIX shell or for Windows
+//! `cmd.exe`, into an [`Item`]:
+//!
+//! ```
+//! use postman_collection::v2_1_0::{Item, Mode, RequestUnion};
+//!
+//! let item = Item::from_curl(
+//!     "curl https://example.com/login -u 'ada:s3cret' --data-urlencode 'note=hi there'",
+//! )?;
+//! let RequestUnion::RequestClass(request) = &item.request else { unreachable!() };
+//!
+//! assert_eq!(request.method.as_deref(), Some("POST"));
+//! assert_eq!(request.body.as_ref().unwrap().mode, Some(Mode::Urlencoded));
+//! assert_eq!(
+//!     request.to_curl()?,
+//!     "curl https://example.com/login -u ada:s3cret --data-urlencode 'note=hi there'",
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use serde_json::Value;
+
+use crate::body::graphql_envelope;
+use crate::v2_1_0::auth::{ApiKeyAuth, ApiKeyLocation, AuthScheme, BasicAuth, BearerAuth};
+use crate::v2_1_0::{
+    AuthType, Body, FormParameterSrcUnion, HeaderUnion, Item, Mode, QueryParam, RawLanguage,
+    RequestClass, RequestUnion, Url, UrlClass,
+};
+use crate::{Result, v1_0_0};
+
+mod parse;
+
+pub use parse::*;
+
+/// Renders requests as curl commands. See the [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct Generator {
+    shell_variables: bool,
+    line_breaks: bool,
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Generator::default()
+    }
+
+    /// Writes `{{name}}` placeholders as `${name}` shell variables instead of leaving them in
+    /// the command. Characters that cannot appear in a shell variable name are replaced with
+    /// `_`, so `{{$guid}}` becomes `${_guid}` and `{{api-key}}` becomes `${api_key}`.
+    pub fn with_shell_variables(mut self) -> Self {
+        self.shell_variables = true;
+        self
+    }
+
+    /// Puts every option on a line of its own, ending the previous line with ` \`.
+    pub fn with_line_breaks(mut self) -> Self {
+        self.line_breaks = true;
+        self
+    }
+
+    /// Renders `request`. Fails when the attributes of a rendered auth scheme or the GraphQL
+    /// variables cannot be read.
+    pub fn generate(&self, request: &RequestClass) -> Result<String> {
+        let method = request
+            .method
+            .as_deref()
+            .map_or_else(|| "GET".to_owned(), str::to_ascii_uppercase);
+        let mut url = request
+            .url
+            .as_ref()
+            .map(|url| url.to_class().expand_path_variables().value)
+            .unwrap_or_default();
+        let headers: Vec<_> = request
+            .header
+            .iter()
+            .flat_map(HeaderUnion::enabled)
+            .collect();
+        let has_header = |name: &str| {
+            headers
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case(name))
+        };
+
+        let mut auth = Vec::new();
+        match request.auth.as_ref() {
+            Some(basic) if basic.auth_type == AuthType::Basic && !has_header("Authorization") => {
+                let basic = BasicAuth::from_auth(basic)?;
+                let credentials =
+                    format!("{}:{}", basic.username, basic.password.unwrap_or_default());
+                auth.push(format!("-u {}", self.quote(&credentials)));
+            }
+            Some(bearer)
+                if bearer.auth_type == AuthType::Bearer && !has_header("Authorization") =>
+            {
+                let bearer = BearerAuth::from_auth(bearer)?;
+                auth.push(self.header("Authorization", &format!("Bearer {}", bearer.token)));
+            }
+            Some(api_key) if api_key.auth_type == AuthType::Apikey => {
+                let api_key = ApiKeyAuth::from_auth(api_key)?;
+                if api_key.location == ApiKeyLocation::Query {
+                    url.query.get_or_insert_with(Vec::new).push(QueryParam {
+                        description: None,
+                        disabled: None,
+                        key: Some(api_key.key),
+                        value: Some(api_key.value),
+                    });
+                } else if !has_header(&api_key.key) {
+                    auth.push(self.header(&api_key.key, &api_key.value));
+                }
+            }
+            _ => {}
+        }
+
+        let (content_type, data) = match request.body.as_ref() {
+            Some(body) if !body.disabled.unwrap_or(false) => self.body(body)?,
+            _ => (None, Vec::new()),
+        };
+        // curl writes the multipart `Content-Type` itself, with the boundary it picks.
+        let multipart = request.body.as_ref().and_then(|body| body.mode.as_ref())
+            == Some(&Mode::Formdata)
+            && !data.is_empty();
+
+        let mut command = String::from("curl ");
+        match method.as_str() {
+            "HEAD" => command.push_str("--head "),
+            "GET" if data.is_empty() => {}
+            "POST" if !data.is_empty() => {}
+            _ => command.push_str(&format!("-X {} ", self.quote(&method))),
+        }
+        command.push_str(&self.quote(&url.to_string()));
+        let mut args = vec![command];
+        for (key, value) in &headers {
+            if !(multipart && key.eq_ignore_ascii_case("Content-Type")) {
+                args.push(self.header(key, value));
+            }
+        }
+        args.extend(auth);
+        if let Some(content_type) = content_type.filter(|_| !has_header("Content-Type")) {
+            args.push(self.header("Content-Type", content_type));
+        }
+        args.extend(data);
+
+        if let Some(proxy) = request.proxy.as_ref() {
+            if let Some(host) = proxy
+                .host
+                .as_deref()
+                .filter(|_| !proxy.disabled.unwrap_or(false))
+            {
+                let address = match proxy.port {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host.to_owned(),
+                };
+                args.push(format!("--proxy {}", self.quote(&address)));
+                if proxy.tunnel.unwrap_or(false) {
+                    args.push("--proxytunnel".to_owned());
+                }
+            }
+        }
+        if let Some(certificate) = request.certificate.as_ref() {
+            let cert = certificate.cert.as_ref().and_then(|cert| cert.src.as_ref());
+            if let Some(cert) = cert.and_then(Value::as_str) {
+                args.push(format!("--cert {}", self.quote(cert)));
+            }
+            let key = certificate.key.as_ref().and_then(|key| key.src.as_ref());
+            if let Some(key) = key.and_then(Value::as_str) {
+                args.push(format!("--key {}", self.quote(key)));
+            }
+            if let Some(passphrase) = certificate.passphrase.as_deref() {
+                args.push(format!("--pass {}", self.quote(passphrase)));
+            }
+        }
+
+        Ok(args.join(if self.line_breaks { " \\\n  " } else { " " }))
+    }
+
+    /// Renders the item's request. A request given as a plain URL string becomes a plain `GET`.
+    pub fn generate_item(&self, item: &Item) -> Result<String> {
+        match &item.request {
+            RequestUnion::RequestClass(request) => self.generate(request),
+            RequestUnion::String(url) => self.generate(&RequestClass {
+                url: Some(Url::UrlClass(UrlClass::parse(url))),
+                ..RequestClass::default()
+            }),
+        }
+    }
+
+    /// Renders a v1.0.0 request after reading its headers, data and query parameters as
+    /// [`convert`](crate::convert) does.
+    pub fn generate_v1(&self, request: &v1_0_0::Request) -> Result<String> {
+        self.generate(&crate::convert::v1_request(request))
+    }
+
+    /// The `Content-Type` to add and the options that send the body.
+    fn body(&self, body: &Body) -> Result<(Option<&'static str>, Vec<String>)> {
+        let mut args = Vec::new();
+        let content_type = match body.mode.as_ref() {
+            None => None,
+            Some(Mode::Raw) => {
+                let raw = body.raw.as_deref().unwrap_or_default();
+                args.push(format!("--data-raw {}", self.quote(raw)));
+                Some(
+                    body.raw_language()
+                        .map_or("text/plain", RawLanguage::content_type),
+                )
+            }
+            Some(Mode::Urlencoded) => {
+                for param in body.urlencoded.iter().flatten() {
+                    if param.disabled.unwrap_or(false) {
+                        continue;
+                    }
+                    let pair = format!(
+                        "{}={}",
+                        encode_form_key(&param.key),
+                        param.value.as_deref().unwrap_or_default()
+                    );
+                    args.push(format!("--data-urlencode {}", self.quote(&pair)));
+                }
+                None
+            }
+            Some(Mode::Formdata) => {
+                for field in body.formdata.iter().flatten() {
+                    if field.disabled.unwrap_or(false) {
+                        continue;
+                    }
+                    let content_type = field
+                        .content_type
+                        .as_deref()
+                        .map(|content_type| format!(";type={content_type}"))
+                        .unwrap_or_default();
+                    if field.form_parameter_type.as_deref() != Some("file") {
+                        let value = field.value.as_deref().unwrap_or_default();
+                        args.push(if content_type.is_empty() {
+                            format!(
+                                "--form-string {}",
+                                self.quote(&format!("{}={value}", field.key))
+                            )
+                        } else {
+                            let part = format!("{}={}{content_type}", field.key, form_quote(value));
+                            format!("-F {}", self.quote(&part))
+                        });
+                        continue;
+                    }
+
+                    let sources = match field.src.as_ref() {
+                        Some(FormParameterSrcUnion::File(src)) => std::slice::from_ref(src),
+                        Some(FormParameterSrcUnion::Files(sources)) => sources.as_slice(),
+                        None => &[],
+                    };
+                    for src in sources {
+                        let part = format!("{}=@{}{content_type}", field.key, form_quote(src));
+                        args.push(format!("-F {}", self.quote(&part)));
+                    }
+                }
+                None
+            }
+            Some(Mode::File) => {
+                let file = body.file.as_ref();
+                match (
+                    file.and_then(|file| file.content.as_deref()),
+                    file.and_then(|file| file.src.as_deref()),
+                ) {
+                    (Some(content), _) => args.push(format!("--data-raw {}", self.quote(content))),
+                    (None, Some(src)) => {
+                        args.push(format!("--data-binary {}", self.quote(&format!("@{src}"))));
+                    }
+                    (None, None) => {}
+                }
+                None
+            }
+            Some(Mode::Graphql) => {
+                let envelope = graphql_envelope(body.graphql.as_ref())?;
+                args.push(format!("--data-raw {}", self.quote(&envelope.to_string())));
+                Some("application/json")
+            }
+        };
+        Ok((content_type.filter(|_| !args.is_empty()), args))
+    }
+
+    fn header(&self, key: &str, value: &str) -> String {
+        // `Name:` would make curl drop the header; `Name;` sends it empty.
+        let header = if value.is_empty() {
+            format!("{key};")
+        } else {
+            format!("{key}: {value}")
+        };
+        format!("-H {}", self.quote(&header))
+    }
+
+    /// Quotes `word` for a POSIX shell. With shell variables, words holding placeholders are
+    /// double-quoted so that the variables expand.
+    fn quote(&self, word: &str) -> String {
+        if self.shell_variables && word.contains("{{") {
+            let quoted = double_quote(word);
+            if quoted.contains("${") {
+                return quoted;
+            }
+        }
+        single_quote(word)
+    }
+}
+
+impl RequestClass {
+    /// Renders the request as a single-line curl command. See the
+    /// [module documentation](crate::curl).
+    pub fn to_curl(&self) -> Result<String> {
+        Generator::new().generate(self)
+    }
+}
+
+impl Item {
+    /// Renders the item's request as a single-line curl command.
+    pub fn to_curl(&self) -> Result<String> {
+        Generator::new().generate_item(self)
+    }
+}
+
+impl v1_0_0::Request {
+    /// Renders the request as a single-line curl command, after converting it as
+    /// [`Generator::generate_v1`] does.
+    pub fn to_curl(&self) -> Result<String> {
+        Generator::new().generate_v1(self)
+    }
+}
+
+/// Leaves words made of characters the shell never treats specially bare, and single-quotes
+/// anything else.
+fn single_quote(word: &str) -> String {
+    let bare = !word.is_empty()
+        && word
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "%+,-./:=@_".contains(ch));
+    if bare {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Double-quotes `word`, with every `{{name}}` placeholder turned into `${name}`. A `!` is
+/// single-quoted on its own, as interactive shells expand it even inside double quotes.
+fn double_quote(word: &str) -> String {
+    let mut quoted = String::from("\"");
+    let mut rest = word;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("{{") {
+            if let Some(end) = after.find("}}") {
+                let name = &after[..end];
+                if !name.is_empty() && !name.contains(['{', '}']) {
+                    quoted.push_str(&format!("${{{}}}", shell_name(name)));
+                    rest = &after[end + 2..];
+                    continue;
+                }
+            }
+        }
+        let ch = rest.chars().next().expect("rest is not empty");
+        match ch {
+            '"' | '\\' | '$' | '`' => {
+                quoted.push('\\');
+                quoted.push(ch);
+            }
+            '!' => quoted.push_str("\"'!'\""),
+            _ => quoted.push(ch),
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A shell variable name for a Postman variable name.
+fn shell_name(name: &str) -> String {
+    let mut shell: String = name
+        .trim()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect();
+    if shell.starts_with(|ch: char| ch.is_ascii_digit()) {
+        shell.insert(0, '_');
+    }
+    shell
+}
+
+/// curl expects the name in `--data-urlencode name=value` to be encoded already.
+/// Placeholders are kept as they are, so that they can still be resolved.
+fn encode_form_key(key: &str) -> String {
+    let mut encoded = String::new();
+    let mut rest = key;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        encoded.extend(url::form_urlencoded::byte_serialize(
+            &rest.as_bytes()[..start],
+        ));
+        encoded.push_str(&rest[start..start + end + 2]);
+        rest = &rest[start + end + 2..];
+    }
+    encoded.extend(url::form_urlencoded::byte_serialize(rest.as_bytes()));
+    encoded
+}
+
+/// Quotes a `-F` value or file name, so that `;`, `,` and a leading `@` or `<` are read
+/// literally by curl.
+fn form_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\""))
+}
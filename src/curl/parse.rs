@@ -0,0 +1,1036 @@
+use std::iter::{Peekable, repeat_n};
+use std::str::Chars;
+
+use serde_json::Value;
+
+use crate::v2_1_0::auth::{AuthScheme, BasicAuth, BearerAuth};
+use crate::v2_1_0::{
+    Auth, Body, BodyOptions, Cert, Certificate, File, FormParameter, FormParameterSrcUnion, Header,
+    HeaderUnion, Item, Key, Mode, ProxyConfig, RawLanguage, RawOptions, RequestClass, RequestUnion,
+    Url, UrlClass, UrlEncodedParameter,
+};
+use crate::{Error, Result};
+
+/// How a curl command line is quoted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shell {
+    /// `sh`, `bash` and `zsh`: single quotes, double quotes, `$'...'` strings, backslash escapes
+    /// and `\` line continuations.
+    Posix,
+    /// Windows `cmd.exe`: `^` escapes and line continuations, then double quotes and backslashes
+    /// as the C runtime splits a command line into arguments.
+    Cmd,
+}
+
+impl Shell {
+    /// Guesses the shell a command was written for: [`Shell::Cmd`] when it calls `curl.exe` or
+    /// uses `^` escapes, [`Shell::Posix`] otherwise.
+    pub fn detect(command: &str) -> Shell {
+        let command = command.trim_start();
+        let cmd = command
+            .get(..8)
+            .is_some_and(|start| start.eq_ignore_ascii_case("curl.exe"))
+            || command.contains("^\"")
+            || command.contains("^\n")
+            || command.contains("^\r\n");
+        if cmd { Shell::Cmd } else { Shell::Posix }
+    }
+}
+
+/// Parses a curl command line into an item, guessing its quoting with [`Shell::detect`]. See
+/// [`parse_with`].
+pub fn parse(command: &str) -> Result<Item> {
+    parse_with(command, Shell::detect(command))
+}
+
+/// Parses a curl command line quoted for `shell` into an item named after its URL.
+///
+/// The URL, method, headers, `-u` basic auth, `--oauth2-bearer` tokens, proxy and client
+/// certificate are read into the request. Data options (`-d`, `--data-raw`, `--data-binary`,
+/// `--data-urlencode`, `--json`) are joined with `&` as curl does: the result becomes a
+/// `urlencoded` body when it is a list of `name=value` pairs and the `Content-Type` does not say
+/// otherwise, and a `raw` body typed from the `Content-Type` when it is not. A single
+/// `@file` argument becomes a `file` body, and `-F`/`--form-string` fields a `formdata` body.
+/// With `-G`, the data is appended to the query string instead.
+///
+/// Options that have no equivalent in a collection, such as `--compressed` or `-o`, are
+/// skipped. Fails when the command does not start with `curl`, has no URL or several, uses an
+/// option curl does not have, or leaves a quote open.
+pub fn parse_with(command: &str, shell: Shell) -> Result<Item> {
+    let words = match shell {
+        Shell::Posix => posix_words(command)?,
+        Shell::Cmd => cmd_words(command),
+    };
+    let mut words = words.into_iter();
+    let program = words.next().unwrap_or_default();
+    let name = program.rsplit(['/', '\\']).next().unwrap_or_default();
+    if !name.eq_ignore_ascii_case("curl") && !name.eq_ignore_ascii_case("curl.exe") {
+        return Err(invalid(format!("expected `curl`, found `{program}`")));
+    }
+
+    let mut parsed = Parsed::default();
+    while let Some(word) = words.next() {
+        if let Some(long) = word.strip_prefix("--") {
+            let Some((option, takes_value)) = long_option(long) else {
+                return Err(invalid(format!("unknown option `{word}`")));
+            };
+            let value = if takes_value {
+                Some(value_for(&word, &mut words)?)
+            } else {
+                None
+            };
+            parsed.apply(option, value)?;
+        } else if let Some(short) = word.strip_prefix('-').filter(|short| !short.is_empty()) {
+            for (index, flag) in short.char_indices() {
+                let Some(&(_, long)) = SHORT.iter().find(|(short, _)| *short == flag) else {
+                    return Err(invalid(format!("unknown option `-{flag}`")));
+                };
+                if !LONG_WITH_VALUE.contains(&long) {
+                    parsed.apply(long, None)?;
+                    continue;
+                }
+                let attached = &short[index + flag.len_utf8()..];
+                let value = if attached.is_empty() {
+                    value_for(&word, &mut words)?
+                } else {
+                    attached.to_owned()
+                };
+                parsed.apply(long, Some(value))?;
+                break;
+            }
+        } else {
+            parsed.apply("url", Some(word))?;
+        }
+    }
+    parsed.into_item()
+}
+
+impl Item {
+    /// Parses a curl command line with [`curl::parse`](crate::curl::parse).
+    pub fn from_curl(command: &str) -> Result<Item> {
+        parse(command)
+    }
+}
+
+/// Short options and the long options they stand for.
+const SHORT: [(char, &str); 59] = [
+    ('#', "progress-bar"),
+    ('0', "http1.0"),
+    ('1', "tlsv1"),
+    ('2', "sslv2"),
+    ('3', "sslv3"),
+    ('4', "ipv4"),
+    ('6', "ipv6"),
+    (':', "next"),
+    ('a', "append"),
+    ('A', "user-agent"),
+    ('b', "cookie"),
+    ('B', "use-ascii"),
+    ('c', "cookie-jar"),
+    ('C', "continue-at"),
+    ('d', "data"),
+    ('D', "dump-header"),
+    ('e', "referer"),
+    ('E', "cert"),
+    ('f', "fail"),
+    ('F', "form"),
+    ('g', "globoff"),
+    ('G', "get"),
+    ('h', "help"),
+    ('H', "header"),
+    ('i', "include"),
+    ('I', "head"),
+    ('j', "junk-session-cookies"),
+    ('J', "remote-header-name"),
+    ('k', "insecure"),
+    ('K', "config"),
+    ('l', "list-only"),
+    ('L', "location"),
+    ('m', "max-time"),
+    ('M', "manual"),
+    ('n', "netrc"),
+    ('N', "no-buffer"),
+    ('o', "output"),
+    ('O', "remote-name"),
+    ('p', "proxytunnel"),
+    ('P', "ftp-port"),
+    ('q', "disable"),
+    ('Q', "quote"),
+    ('r', "range"),
+    ('R', "remote-time"),
+    ('s', "silent"),
+    ('S', "show-error"),
+    ('t', "telnet-option"),
+    ('T', "upload-file"),
+    ('u', "user"),
+    ('U', "proxy-user"),
+    ('v', "verbose"),
+    ('V', "version"),
+    ('w', "write-out"),
+    ('x', "proxy"),
+    ('X', "request"),
+    ('y', "speed-time"),
+    ('Y', "speed-limit"),
+    ('z', "time-cond"),
+    ('Z', "parallel"),
+];
+
+/// Long options followed by a value, including those that are read but skipped. Each of them
+/// can also be written `--expand-<option>`.
+const LONG_WITH_VALUE: [&str; 140] = [
+    "abstract-unix-socket",
+    "alt-svc",
+    "aws-sigv4",
+    "cacert",
+    "capath",
+    "cert",
+    "cert-type",
+    "ciphers",
+    "config",
+    "connect-timeout",
+    "connect-to",
+    "continue-at",
+    "cookie",
+    "cookie-jar",
+    "create-file-mode",
+    "crlfile",
+    "curves",
+    "data",
+    "data-ascii",
+    "data-binary",
+    "data-raw",
+    "data-urlencode",
+    "delegation",
+    "dns-interface",
+    "dns-ipv4-addr",
+    "dns-ipv6-addr",
+    "dns-servers",
+    "doh-url",
+    "dump-header",
+    "ech",
+    "egd-file",
+    "engine",
+    "etag-compare",
+    "etag-save",
+    "expect100-timeout",
+    "form",
+    "form-string",
+    "ftp-account",
+    "ftp-alternative-to-user",
+    "ftp-method",
+    "ftp-port",
+    "ftp-ssl-ccc-mode",
+    "happy-eyeballs-timeout-ms",
+    "haproxy-clientip",
+    "header",
+    "hostpubmd5",
+    "hostpubsha256",
+    "hsts",
+    "interface",
+    "ip-tos",
+    "ipfs-gateway",
+    "json",
+    "keepalive-cnt",
+    "keepalive-time",
+    "key",
+    "key-type",
+    "krb",
+    "libcurl",
+    "limit-rate",
+    "local-port",
+    "login-options",
+    "mail-auth",
+    "mail-from",
+    "mail-rcpt",
+    "max-filesize",
+    "max-redirs",
+    "max-time",
+    "netrc-file",
+    "noproxy",
+    "oauth2-bearer",
+    "output",
+    "output-dir",
+    "parallel-max",
+    "pass",
+    "pinnedpubkey",
+    "preproxy",
+    "proto",
+    "proto-default",
+    "proto-redir",
+    "proxy",
+    "proxy-cacert",
+    "proxy-capath",
+    "proxy-cert",
+    "proxy-cert-type",
+    "proxy-ciphers",
+    "proxy-crlfile",
+    "proxy-header",
+    "proxy-key",
+    "proxy-key-type",
+    "proxy-pass",
+    "proxy-pinnedpubkey",
+    "proxy-service-name",
+    "proxy-tls13-ciphers",
+    "proxy-tlsauthtype",
+    "proxy-tlspassword",
+    "proxy-tlsuser",
+    "proxy-user",
+    "proxy1.0",
+    "pubkey",
+    "quote",
+    "random-file",
+    "range",
+    "rate",
+    "referer",
+    "request",
+    "request-target",
+    "resolve",
+    "retry",
+    "retry-delay",
+    "retry-max-time",
+    "sasl-authzid",
+    "service-name",
+    "socks4",
+    "socks4a",
+    "socks5",
+    "socks5-gssapi-service",
+    "socks5-hostname",
+    "speed-limit",
+    "speed-time",
+    "stderr",
+    "telnet-option",
+    "tftp-blksize",
+    "time-cond",
+    "tls-max",
+    "tls13-ciphers",
+    "tlsauthtype",
+    "tlspassword",
+    "tlsuser",
+    "trace",
+    "trace-ascii",
+    "trace-config",
+    "unix-socket",
+    "upload-file",
+    "url",
+    "url-query",
+    "user",
+    "user-agent",
+    "variable",
+    "vlan-priority",
+    "write-out",
+];
+
+/// Long options that take no value. Each of them can also be written `--no-<option>`.
+const LONG_FLAGS: [&str; 122] = [
+    "alpn",
+    "anyauth",
+    "append",
+    "basic",
+    "buffer",
+    "ca-native",
+    "cert-status",
+    "clobber",
+    "compressed",
+    "compressed-ssh",
+    "create-dirs",
+    "crlf",
+    "digest",
+    "disable",
+    "disable-eprt",
+    "disable-epsv",
+    "disallow-username-in-url",
+    "doh-cert-status",
+    "doh-insecure",
+    "fail",
+    "fail-early",
+    "fail-with-body",
+    "false-start",
+    "form-escape",
+    "ftp-create-dirs",
+    "ftp-pasv",
+    "ftp-pret",
+    "ftp-skip-pasv-ip",
+    "ftp-ssl-ccc",
+    "ftp-ssl-control",
+    "get",
+    "globoff",
+    "haproxy-protocol",
+    "head",
+    "help",
+    "http0.9",
+    "http1.0",
+    "http1.1",
+    "http2",
+    "http2-prior-knowledge",
+    "http3",
+    "http3-only",
+    "ignore-content-length",
+    "include",
+    "insecure",
+    "ipv4",
+    "ipv6",
+    "junk-session-cookies",
+    "keepalive",
+    "list-only",
+    "location",
+    "location-trusted",
+    "manual",
+    "mptcp",
+    "negotiate",
+    "netrc",
+    "netrc-optional",
+    "next",
+    "npn",
+    "ntlm",
+    "ntlm-wb",
+    "parallel",
+    "parallel-immediate",
+    "path-as-is",
+    "post301",
+    "post302",
+    "post303",
+    "progress-bar",
+    "progress-meter",
+    "proxy-anyauth",
+    "proxy-basic",
+    "proxy-ca-native",
+    "proxy-digest",
+    "proxy-http2",
+    "proxy-insecure",
+    "proxy-negotiate",
+    "proxy-ntlm",
+    "proxy-ssl-allow-beast",
+    "proxy-ssl-auto-client-cert",
+    "proxytunnel",
+    "raw",
+    "remote-header-name",
+    "remote-name",
+    "remote-name-all",
+    "remote-time",
+    "remove-on-error",
+    "retry-all-errors",
+    "retry-connrefused",
+    "sasl-ir",
+    "sessionid",
+    "show-error",
+    "show-headers",
+    "silent",
+    "skip-existing",
+    "socks5-basic",
+    "socks5-gssapi",
+    "socks5-gssapi-nec",
+    "ssl",
+    "ssl-allow-beast",
+    "ssl-auto-client-cert",
+    "ssl-no-revoke",
+    "ssl-reqd",
+    "ssl-revoke-best-effort",
+    "sslv2",
+    "sslv3",
+    "styled-output",
+    "suppress-connect-headers",
+    "tcp-fastopen",
+    "tcp-nodelay",
+    "tftp-no-options",
+    "tlsv1",
+    "tlsv1.0",
+    "tlsv1.1",
+    "tlsv1.2",
+    "tlsv1.3",
+    "tr-encoding",
+    "trace-ids",
+    "trace-time",
+    "use-ascii",
+    "verbose",
+    "version",
+    "xattr",
+];
+
+/// The option `name` stands for and whether it takes a value, or `None` when curl has no such
+/// option.
+fn long_option(name: &str) -> Option<(&str, bool)> {
+    if LONG_WITH_VALUE.contains(&name) {
+        return Some((name, true));
+    }
+    if LONG_FLAGS.contains(&name) {
+        return Some((name, false));
+    }
+    match (name.strip_prefix("expand-"), name.strip_prefix("no-")) {
+        (Some(option), _) if LONG_WITH_VALUE.contains(&option) => Some((option, true)),
+        (_, Some(flag)) if LONG_FLAGS.contains(&flag) => Some((name, false)),
+        _ => None,
+    }
+}
+
+/// What the options of a command have set so far.
+#[derive(Default)]
+struct Parsed {
+    url: Option<String>,
+    method: Option<String>,
+    head: bool,
+    get: bool,
+    headers: Vec<Header>,
+    data: Vec<Data>,
+    form: Vec<FormParameter>,
+    auth: Option<Auth>,
+    proxy: Option<ProxyConfig>,
+    tunnel: bool,
+    certificate: Option<Certificate>,
+}
+
+enum Data {
+    /// Sent as is, joined to the other data with `&`.
+    Text(String),
+    /// `@file`, read by curl.
+    File(String),
+}
+
+impl Parsed {
+    fn apply(&mut self, option: &str, value: Option<String>) -> Result<()> {
+        let value = value.unwrap_or_default();
+        match option {
+            "url" => match &self.url {
+                Some(url) => {
+                    return Err(invalid(format!(
+                        "expected one URL, found `{url}` and `{value}`"
+                    )));
+                }
+                None => self.url = Some(value),
+            },
+            "request" => self.method = Some(value.to_ascii_uppercase()),
+            "head" => self.head = true,
+            "get" => self.get = true,
+            "header" => {
+                if let Some(key) = value.strip_suffix(';').filter(|key| !key.contains(':')) {
+                    self.header(key.trim(), "");
+                } else if let Some((key, value)) = value.split_once(':') {
+                    // `Name:` asks curl to leave out a header it would send by itself.
+                    if !value.trim().is_empty() {
+                        self.header(key.trim(), value.trim());
+                    }
+                }
+            }
+            "user-agent" => self.header("User-Agent", &value),
+            "referer" => self.header("Referer", &value),
+            // Without `=`, the value names a cookie file.
+            "cookie" if value.contains('=') => self.header("Cookie", &value),
+            "data" | "data-ascii" | "data-binary" => match value.strip_prefix('@') {
+                Some(src) => self.data.push(Data::File(src.to_owned())),
+                None => self.data.push(Data::Text(value)),
+            },
+            "data-raw" => self.data.push(Data::Text(value)),
+            "json" => {
+                self.data.push(Data::Text(value));
+                if !self.has_header("Content-Type") {
+                    self.header("Content-Type", "application/json");
+                }
+                if !self.has_header("Accept") {
+                    self.header("Accept", "application/json");
+                }
+            }
+            "data-urlencode" => {
+                let name_end = value.find(['=', '@']);
+                if name_end.is_some_and(|index| value[index..].starts_with('@')) {
+                    return Err(invalid(format!(
+                        "reading `--data-urlencode {value}` from a file is not supported"
+                    )));
+                }
+                let (name, content) = match name_end {
+                    Some(index) => (&value[..index], &value[index + 1..]),
+                    None => ("", value.as_str()),
+                };
+                let encoded: String =
+                    url::form_urlencoded::byte_serialize(content.as_bytes()).collect();
+                self.data.push(Data::Text(if name.is_empty() {
+                    encoded
+                } else {
+                    format!("{name}={encoded}")
+                }));
+            }
+            "form" => self.form.push(form_field(&value, false)?),
+            "form-string" => self.form.push(form_field(&value, true)?),
+            "user" => {
+                let (username, password) = match value.split_once(':') {
+                    Some((username, password)) => (username, Some(password.to_owned())),
+                    None => (value.as_str(), None),
+                };
+                self.auth = Some(
+                    BasicAuth {
+                        username: username.to_owned(),
+                        password,
+                    }
+                    .to_auth(),
+                );
+            }
+            "oauth2-bearer" => self.auth = Some(BearerAuth { token: value }.to_auth()),
+            "proxy" => {
+                let (host, port) = match value.rsplit_once(':') {
+                    Some((host, port)) => match port.parse() {
+                        Ok(port) => (host, Some(port)),
+                        Err(_) => (value.as_str(), None),
+                    },
+                    None => (value.as_str(), None),
+                };
+                self.proxy = Some(ProxyConfig {
+                    disabled: None,
+                    host: Some(host.to_owned()),
+                    proxy_config_match: None,
+                    port,
+                    tunnel: None,
+                });
+            }
+            "proxytunnel" => self.tunnel = true,
+            "cert" => {
+                self.certificate().cert = Some(Cert {
+                    src: Some(Value::String(value)),
+                });
+            }
+            "key" => {
+                self.certificate().key = Some(Key {
+                    src: Some(Value::String(value)),
+                });
+            }
+            "pass" => self.certificate().passphrase = Some(value),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn header(&mut self, key: &str, value: &str) {
+        self.headers.push(Header {
+            description: None,
+            disabled: None,
+            key: key.to_owned(),
+            value: value.to_owned(),
+        });
+    }
+
+    fn has_header(&self, key: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|header| header.key.eq_ignore_ascii_case(key))
+    }
+
+    fn certificate(&mut self) -> &mut Certificate {
+        self.certificate.get_or_insert(Certificate {
+            cert: None,
+            key: None,
+            matches: None,
+            name: None,
+            passphrase: None,
+        })
+    }
+
+    fn into_item(mut self) -> Result<Item> {
+        let Some(mut url) = self.url.take() else {
+            return Err(invalid("no URL"));
+        };
+
+        let mut body = None;
+        if self.get {
+            let mut query = Vec::new();
+            for data in self.data.drain(..) {
+                match data {
+                    Data::Text(text) => query.push(text),
+                    Data::File(src) => {
+                        return Err(invalid(format!(
+                            "cannot add `@{src}` to the query with `-G`"
+                        )));
+                    }
+                }
+            }
+            if !query.is_empty() {
+                url.push(if url.contains('?') { '&' } else { '?' });
+                url.push_str(&query.join("&"));
+            }
+        } else if !self.form.is_empty() {
+            if !self.data.is_empty() {
+                return Err(invalid("cannot mix `-F` with `-d` options"));
+            }
+            body = Some(Body {
+                mode: Some(Mode::Formdata),
+                formdata: Some(std::mem::take(&mut self.form)),
+                ..Body::default()
+            });
+        } else if !self.data.is_empty() {
+            body = Some(self.data_body()?);
+        }
+
+        let method = match self.method.take() {
+            Some(method) => method,
+            None if self.head => "HEAD".to_owned(),
+            None if body.is_some() => "POST".to_owned(),
+            None => "GET".to_owned(),
+        };
+        if let Some(proxy) = self.proxy.as_mut() {
+            proxy.tunnel = self.tunnel.then_some(true);
+        }
+
+        let request = RequestClass {
+            auth: self.auth,
+            body,
+            certificate: self.certificate,
+            header: (!self.headers.is_empty()).then_some(HeaderUnion::HeaderArray(self.headers)),
+            method: Some(method),
+            proxy: self.proxy,
+            url: Some(Url::UrlClass(UrlClass::parse(&url))),
+            ..RequestClass::default()
+        };
+        Ok(Item {
+            description: None,
+            event: None,
+            id: None,
+            name: Some(url),
+            protocol_profile_behavior: None,
+            request: RequestUnion::RequestClass(request),
+            response: None,
+            variable: None,
+        })
+    }
+
+    fn data_body(&mut self) -> Result<Body> {
+        if let [Data::File(src)] = self.data.as_slice() {
+            return Ok(Body {
+                mode: Some(Mode::File),
+                file: Some(File {
+                    content: None,
+                    src: Some(src.clone()),
+                }),
+                ..Body::default()
+            });
+        }
+
+        let mut texts = Vec::new();
+        for data in &self.data {
+            match data {
+                Data::Text(text) => texts.push(text.as_str()),
+                Data::File(src) => {
+                    return Err(invalid(format!("cannot combine `@{src}` with other data")));
+                }
+            }
+        }
+        let data = texts.join("&");
+
+        let content_type = self
+            .headers
+            .iter()
+            .find(|header| header.key.eq_ignore_ascii_case("Content-Type"))
+            .map(|header| header.value.to_ascii_lowercase());
+        let form = match content_type.as_deref() {
+            Some(content_type) => content_type.starts_with("application/x-www-form-urlencoded"),
+            None => is_form(&data),
+        };
+        if form {
+            return Ok(Body {
+                mode: Some(Mode::Urlencoded),
+                urlencoded: Some(
+                    url::form_urlencoded::parse(data.as_bytes())
+                        .map(|(key, value)| UrlEncodedParameter {
+                            description: None,
+                            disabled: None,
+                            key: key.into_owned(),
+                            parameter_type: Some("text".to_owned()),
+                            value: Some(value.into_owned()),
+                        })
+                        .collect(),
+                ),
+                ..Body::default()
+            });
+        }
+
+        let language = content_type
+            .as_deref()
+            .and_then(RawLanguage::from_content_type);
+        Ok(Body {
+            mode: Some(Mode::Raw),
+            raw: Some(data),
+            options: language.map(|language| BodyOptions {
+                raw: Some(RawOptions {
+                    language: Some(language),
+                    ..RawOptions::default()
+                }),
+                ..BodyOptions::default()
+            }),
+            ..Body::default()
+        })
+    }
+}
+
+/// Whether `data` reads as `name=value` pairs joined with `&`, as opposed to a JSON, XML or
+/// free text payload.
+fn is_form(data: &str) -> bool {
+    !data.is_empty()
+        && !data.starts_with(['{', '[', '<'])
+        && data.split('&').all(|pair| {
+            pair.split_once('=')
+                .is_some_and(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+        })
+}
+
+/// Reads a `-F name=content;type=...` or `--form-string name=content` field.
+fn form_field(value: &str, literal: bool) -> Result<FormParameter> {
+    let Some((key, content)) = value.split_once('=') else {
+        return Err(invalid(format!(
+            "expected `name=content` in form field `{value}`"
+        )));
+    };
+    let mut field = FormParameter {
+        content_type: None,
+        description: None,
+        disabled: None,
+        key: key.to_owned(),
+        form_parameter_type: Some("text".to_owned()),
+        value: None,
+        src: None,
+    };
+    if literal {
+        field.value = Some(content.to_owned());
+        return Ok(field);
+    }
+
+    // `@file` uploads a file and `<file` sends its content; a collection can only hold the
+    // former.
+    let (file, content) = match content.strip_prefix(['@', '<']) {
+        Some(rest) => (true, rest),
+        None => (false, content),
+    };
+    let (content, mut params) = match content.strip_prefix('"') {
+        Some(quoted) => {
+            let mut unquoted = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = None;
+            while let Some((index, ch)) = chars.next() {
+                match ch {
+                    '\\' => unquoted.extend(chars.next().map(|(_, ch)| ch)),
+                    '"' => {
+                        end = Some(index + 1);
+                        break;
+                    }
+                    _ => unquoted.push(ch),
+                }
+            }
+            let Some(end) = end else {
+                return Err(invalid(format!(
+                    "unterminated quote in form field `{value}`"
+                )));
+            };
+            (unquoted, &quoted[end..])
+        }
+        None => match content.find(';') {
+            Some(index) => (content[..index].to_owned(), &content[index..]),
+            None => (content.to_owned(), ""),
+        },
+    };
+    while let Some(rest) = params.strip_prefix(';') {
+        let end = rest.find(';').unwrap_or(rest.len());
+        if let Some(content_type) = rest[..end].trim().strip_prefix("type=") {
+            field.content_type = Some(content_type.to_owned());
+        }
+        params = &rest[end..];
+    }
+
+    if file {
+        field.form_parameter_type = Some("file".to_owned());
+        field.src = Some(FormParameterSrcUnion::File(content));
+    } else {
+        field.value = Some(content);
+    }
+    Ok(field)
+}
+
+/// The value of an option that takes one, which is the next word.
+fn value_for(option: &str, words: &mut impl Iterator<Item = String>) -> Result<String> {
+    words
+        .next()
+        .ok_or_else(|| invalid(format!("`{option}` needs a value")))
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidCurl {
+        message: message.into(),
+    }
+}
+
+/// Splits a POSIX shell command line into words. Variables and other expansions are kept as
+/// written.
+fn posix_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = command.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => words.extend(word.take()),
+            '#' if word.is_none() => {
+                for ch in chars.by_ref() {
+                    if ch == '\n' {
+                        break;
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(ch) => word.get_or_insert_with(String::new).push(ch),
+            },
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => word.push(ch),
+                        None => return Err(invalid("unterminated `'` quote")),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => word.push(ch),
+                            Some('\n') => {}
+                            Some(ch) => {
+                                word.push('\\');
+                                word.push(ch);
+                            }
+                            None => return Err(invalid("unterminated `\"` quote")),
+                        },
+                        Some(ch) => word.push(ch),
+                        None => return Err(invalid("unterminated `\"` quote")),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                let unquoted = ansi_c_string(&mut chars)?;
+                word.get_or_insert_with(String::new).push_str(&unquoted);
+            }
+            _ => word.get_or_insert_with(String::new).push(ch),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Reads the rest of a `$'...'` string, which browsers use for data with control characters.
+fn ansi_c_string(chars: &mut Peekable<Chars<'_>>) -> Result<String> {
+    fn push(bytes: &mut Vec<u8>, ch: char) {
+        bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    let mut bytes = Vec::new();
+    loop {
+        match chars.next() {
+            Some('\'') => break,
+            Some('\\') => match chars.next() {
+                Some('n') => bytes.push(b'\n'),
+                Some('t') => bytes.push(b'\t'),
+                Some('r') => bytes.push(b'\r'),
+                Some('e' | 'E') => bytes.push(0x1b),
+                Some('x') => bytes.push(hex(chars, 2) as u8),
+                Some('u') => push(
+                    &mut bytes,
+                    char::from_u32(hex(chars, 4)).unwrap_or('\u{fffd}'),
+                ),
+                Some('U') => push(
+                    &mut bytes,
+                    char::from_u32(hex(chars, 8)).unwrap_or('\u{fffd}'),
+                ),
+                Some(ch @ ('\\' | '\'' | '"' | '?')) => bytes.push(ch as u8),
+                Some(ch) => {
+                    bytes.push(b'\\');
+                    push(&mut bytes, ch);
+                }
+                None => return Err(invalid("unterminated `$'` quote")),
+            },
+            Some(ch) => push(&mut bytes, ch),
+            None => return Err(invalid("unterminated `$'` quote")),
+        }
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Reads up to `digits` hexadecimal digits.
+fn hex(chars: &mut Peekable<Chars<'_>>, digits: usize) -> u32 {
+    let mut value = 0;
+    for _ in 0..digits {
+        match chars.peek().and_then(|ch| ch.to_digit(16)) {
+            Some(digit) => {
+                value = value * 16 + digit;
+                chars.next();
+            }
+            None => break,
+        }
+    }
+    value
+}
+
+/// Splits a `cmd.exe` command line into words: `cmd.exe` first removes `^` escapes outside of
+/// quotes, then the program splits the line with the C runtime's rules.
+fn cmd_words(command: &str) -> Vec<String> {
+    let mut line = String::new();
+    let mut quoted = false;
+    let mut chars = command.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '^' if !quoted => match chars.next() {
+                Some('\n') | None => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                // An escaped `"` does not start a quote for `cmd.exe`, but still does for the
+                // program.
+                Some(ch) => line.push(ch),
+            },
+            '"' => {
+                quoted = !quoted;
+                line.push(ch);
+            }
+            '\r' | '\n' => line.push(' '),
+            _ => line.push(ch),
+        }
+    }
+
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            ' ' | '\t' if !quoted => words.extend(word.take()),
+            '\\' => {
+                let mut count = 1;
+                while chars.peek() == Some(&'\\') {
+                    chars.next();
+                    count += 1;
+                }
+                let word = word.get_or_insert_with(String::new);
+                if chars.peek() == Some(&'"') {
+                    // Backslashes are only escapes in front of a quote.
+                    word.extend(repeat_n('\\', count / 2));
+                    if count % 2 == 1 {
+                        chars.next();
+                        word.push('"');
+                    }
+                } else {
+                    word.extend(repeat_n('\\', count));
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                if quoted && chars.peek() == Some(&'"') {
+                    chars.next();
+                    word.push('"');
+                } else {
+                    quoted = !quoted;
+                }
+            }
+            _ => word.get_or_insert_with(String::new).push(ch),
+        }
+    }
+    words.extend(word);
+    words
+}
@@ -0,0 +1,149 @@
+//! Postman environment files.
+//!
+//! An environment is exported from Postman as a JSON document with a name and a list of
+//! variable values. The parsing helpers mirror the ones for collections at the crate root, and an
+//! [`Environment`] converts into a [`Scope`] for [`variables::Resolver`](crate::variables::Resolver).
+//!
+//! ```
+//! use postman_collection::environment;
+//! use postman_collection::variables::{Resolver, Scope};
+//!
+//! let env = environment::from_str(r#"{
+//!   "id": "4454509f-00c3-fd32-d56c-ac1537f31415",
+//!   "name": "Staging",
+//!   "values": [
+//!     { "key": "baseUrl", "value": "https://staging.example.com", "enabled": true }
+//!   ]
+//! }"#)?;
+//!
+//! let resolver = Resolver::new().with_scope(Scope::from(&env));
+//! assert_eq!(resolver.resolve_str("{{baseUrl}}/ping").value, "https://staging.example.com/ping");
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+
+use std::{fs::File, io::Read, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Result;
+use crate::variables::{Scope, ScopeKind};
+
+/// A Postman environment.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Environment {
+    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "values", default)]
+    pub values: Vec<Variable>,
+
+    /// `environment` for environment exports and `globals` for globals exports.
+    #[serde(
+        rename = "_postman_variable_scope",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub postman_variable_scope: Option<String>,
+
+    #[serde(
+        rename = "_postman_exported_at",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub postman_exported_at: Option<String>,
+
+    #[serde(
+        rename = "_postman_exported_using",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub postman_exported_using: Option<String>,
+}
+
+/// A variable of an environment or globals file.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Variable {
+    #[serde(rename = "key")]
+    pub key: String,
+
+    /// Usually a string, although older exports also contain numbers and booleans.
+    #[serde(rename = "value", skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+
+    /// Variables are enabled unless this is `false`.
+    #[serde(rename = "enabled", skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// `default` or `secret` in current exports; older exports use `text`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub variable_type: Option<String>,
+}
+
+impl Variable {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+}
+
+impl From<&Environment> for Scope {
+    fn from(environment: &Environment) -> Self {
+        scope(ScopeKind::Environment, &environment.values)
+    }
+}
+
+/// Builds a scope from the enabled variables of an environment or globals file.
+pub(crate) fn scope(kind: ScopeKind, variables: &[Variable]) -> Scope {
+    let mut scope = Scope::new(kind);
+    for variable in variables.iter().filter(|variable| variable.is_enabled()) {
+        let value = match variable.value.as_ref() {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(value)) => value.clone(),
+            Some(value) => value.to_string(),
+        };
+        scope.set(variable.key.clone(), value);
+    }
+    scope
+}
+
+/// Deserialize a Postman environment from a path
+pub fn from_path<P>(path: P) -> Result<Environment>
+where
+    P: AsRef<Path>,
+{
+    from_reader(File::open(path)?)
+}
+
+/// Deserialize a Postman environment from a string slice
+pub fn from_str(input: &str) -> Result<Environment> {
+    from_slice(input.as_bytes())
+}
+
+/// Deserialize a Postman environment from a byte slice
+pub fn from_slice(input: &[u8]) -> Result<Environment> {
+    Ok(serde_json::from_value(crate::value_from_slice(input)?)?)
+}
+
+/// Deserialize a Postman environment from type which implements Read
+pub fn from_reader<R>(mut read: R) -> Result<Environment>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    read.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+/// Serialize a Postman environment to a JSON string
+pub fn to_json(environment: &Environment) -> Result<String> {
+    Ok(serde_json::to_string_pretty(environment)?)
+}
+
+/// Serialize a Postman environment to a YAML string.
+///
+/// Available with the crate feature `yaml`.
+#[cfg(feature = "yaml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+pub fn to_yaml(environment: &Environment) -> Result<String> {
+    Ok(yaml_serde::to_string(environment)?)
+}
@@ -0,0 +1,100 @@
+//! Postman globals files.
+//!
+//! Globals are exported in the same shape as an [environment](crate::environment), with
+//! `_postman_variable_scope` set to `globals`. Very old exports are a bare list of variables,
+//! which is accepted as well. [`Globals`] converts into a [`ScopeKind::Global`] scope, the
+//! lowest precedence there is.
+
+use std::{fs::File, io::Read, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Result;
+use crate::environment::{self, Variable};
+use crate::variables::{Scope, ScopeKind};
+
+/// A Postman globals file.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Globals {
+    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(rename = "values", default)]
+    pub values: Vec<Variable>,
+
+    #[serde(
+        rename = "_postman_variable_scope",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub postman_variable_scope: Option<String>,
+
+    #[serde(
+        rename = "_postman_exported_at",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub postman_exported_at: Option<String>,
+
+    #[serde(
+        rename = "_postman_exported_using",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub postman_exported_using: Option<String>,
+}
+
+impl From<&Globals> for Scope {
+    fn from(globals: &Globals) -> Self {
+        environment::scope(ScopeKind::Global, &globals.values)
+    }
+}
+
+/// Deserialize Postman globals from a path
+pub fn from_path<P>(path: P) -> Result<Globals>
+where
+    P: AsRef<Path>,
+{
+    from_reader(File::open(path)?)
+}
+
+/// Deserialize Postman globals from a string slice
+pub fn from_str(input: &str) -> Result<Globals> {
+    from_slice(input.as_bytes())
+}
+
+/// Deserialize Postman globals from a byte slice
+pub fn from_slice(input: &[u8]) -> Result<Globals> {
+    match crate::value_from_slice(input)? {
+        Value::Array(values) => Ok(Globals {
+            values: serde_json::from_value(Value::Array(values))?,
+            ..Globals::default()
+        }),
+        value => Ok(serde_json::from_value(value)?),
+    }
+}
+
+/// Deserialize Postman globals from type which implements Read
+pub fn from_reader<R>(mut read: R) -> Result<Globals>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    read.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+/// Serialize Postman globals to a JSON string
+pub fn to_json(globals: &Globals) -> Result<String> {
+    Ok(serde_json::to_string_pretty(globals)?)
+}
+
+/// Serialize Postman globals to a YAML string.
+///
+/// Available with the crate feature `yaml`.
+#[cfg(feature = "yaml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+pub fn to_yaml(globals: &Globals) -> Result<String> {
+    Ok(yaml_serde::to_string(globals)?)
+}
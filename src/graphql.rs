@@ -0,0 +1,596 @@
+//! Helpers for `graphql` bodies.
+//!
+//! [`GraphQlBody::parse_variables`] reads the variables Postman stores as a JSON string, and
+//! [`validate`] checks that a query is a syntactically valid GraphQL executable document:
+//! operations and fragments as described in the
+//! [GraphQL specification](https://spec.graphql.org/October2021/#sec-Language). Only the syntax is
+//! checked; nothing is validated against a schema. Resolve `{{variables}}` first, since a
+//! placeholder outside a string is not GraphQL.
+//!
+//! ```
+//! use postman_collection::graphql;
+//! use postman_collection::v2_1_0::{GraphQlBody, GraphQlVariables};
+//!
+//! let body = GraphQlBody {
+//!     query: Some("query User($id: ID!) { user(id: $id) { name } }".to_owned()),
+//!     variables: Some(GraphQlVariables::String(r#"{"id": "42"}"#.to_owned())),
+//!     ..GraphQlBody::default()
+//! };
+//!
+//! body.validate()?;
+//! assert_eq!(body.parse_variables()?.unwrap()["id"], "42");
+//!
+//! let error = graphql::validate("query { user(id: ) }").unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "invalid GraphQL at line 1, column 18: expected a value, found `)`"
+//! );
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+
+use serde_json::Value;
+
+use crate::v2_1_0::{GraphQlBody, GraphQlVariables};
+use crate::{Error, Result};
+
+impl GraphQlBody {
+    /// The variables as JSON. A string is parsed; an empty string or `null` gives `None`.
+    pub fn parse_variables(&self) -> Result<Option<Value>> {
+        match self.variables.as_ref() {
+            None | Some(GraphQlVariables::Json(Value::Null)) => Ok(None),
+            Some(GraphQlVariables::String(text)) if text.trim().is_empty() => Ok(None),
+            Some(GraphQlVariables::String(text)) => Ok(Some(serde_json::from_str(text)?)),
+            Some(GraphQlVariables::Json(value)) => Ok(Some(value.clone())),
+        }
+    }
+
+    /// Checks the syntax of [`query`](GraphQlBody::query). A missing query is an error, as
+    /// GraphQL documents need at least one definition.
+    pub fn validate(&self) -> Result<()> {
+        validate(self.query.as_deref().unwrap_or_default())
+    }
+}
+
+/// Checks that `query` is a syntactically valid GraphQL executable document.
+pub fn validate(query: &str) -> Result<()> {
+    let mut parser = Parser::new(query)?;
+    if parser.peek().kind == Kind::End {
+        return Err(parser.error_at(&parser.peek().clone(), "expected a definition"));
+    }
+    while parser.peek().kind != Kind::End {
+        parser.definition()?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Punctuator,
+    Name,
+    Int,
+    Float,
+    String,
+    End,
+}
+
+#[derive(Clone, Debug)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+struct Lexer<'a> {
+    input: &'a str,
+    position: usize,
+    line: usize,
+    line_start: usize,
+    /// The byte position and column of the last token start, so that columns on long lines
+    /// are counted incrementally.
+    counted: (usize, usize),
+}
+
+impl<'a> Lexer<'a> {
+    fn tokens(input: &'a str) -> Result<Vec<Token<'a>>> {
+        let mut lexer = Lexer {
+            input,
+            position: 0,
+            line: 1,
+            line_start: 0,
+            counted: (0, 1),
+        };
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next()?;
+            let end = token.kind == Kind::End;
+            tokens.push(token);
+            if end {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn peek_byte(&self, offset: usize) -> Option<u8> {
+        self.input.as_bytes().get(self.position + offset).copied()
+    }
+
+    fn newline(&mut self) {
+        self.line += 1;
+        self.line_start = self.position;
+        self.counted = (self.position, 1);
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: impl Into<String>) -> Error {
+        Error::GraphQlSyntax {
+            line: self.line,
+            column: self.input[self.line_start..position].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn skip_ignored(&mut self) {
+        while let Some(byte) = self.peek_byte(0) {
+            match byte {
+                b' ' | b'\t' | b',' => self.position += 1,
+                b'\n' => {
+                    self.position += 1;
+                    self.newline();
+                }
+                b'\r' => {
+                    self.position += 1;
+                    if self.peek_byte(0) == Some(b'\n') {
+                        self.position += 1;
+                    }
+                    self.newline();
+                }
+                b'#' => {
+                    while !matches!(self.peek_byte(0), None | Some(b'\n' | b'\r')) {
+                        self.position += 1;
+                    }
+                }
+                _ if self.input[self.position..].starts_with('\u{feff}') => {
+                    self.position += '\u{feff}'.len_utf8();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn next(&mut self) -> Result<Token<'a>> {
+        self.skip_ignored();
+        let start = self.position;
+        let (counted, column) = self.counted;
+        let column = column + self.input[counted..start].chars().count();
+        self.counted = (start, column);
+        let line = self.line;
+        let token = |lexer: &Lexer<'a>, kind| Token {
+            kind,
+            text: &lexer.input[start..lexer.position],
+            line,
+            column,
+        };
+
+        let Some(byte) = self.peek_byte(0) else {
+            return Ok(token(self, Kind::End));
+        };
+        match byte {
+            b'!' | b'$' | b'&' | b'(' | b')' | b':' | b'=' | b'@' | b'[' | b']' | b'{' | b'|'
+            | b'}' => {
+                self.position += 1;
+                Ok(token(self, Kind::Punctuator))
+            }
+            b'.' => {
+                if self.input[start..].starts_with("...") {
+                    self.position += 3;
+                    Ok(token(self, Kind::Punctuator))
+                } else {
+                    Err(self.error("unexpected `.`, did you mean `...`?"))
+                }
+            }
+            b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                while matches!(
+                    self.peek_byte(0),
+                    Some(b'_' | b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z')
+                ) {
+                    self.position += 1;
+                }
+                Ok(token(self, Kind::Name))
+            }
+            b'-' | b'0'..=b'9' => {
+                let kind = self.number()?;
+                Ok(token(self, kind))
+            }
+            b'"' => {
+                if self.input[start..].starts_with("\"\"\"") {
+                    self.block_string()?;
+                } else {
+                    self.string()?;
+                }
+                Ok(token(self, Kind::String))
+            }
+            _ => {
+                let found = self.input[start..].chars().next().unwrap_or_default();
+                Err(self.error(format!("unexpected character `{found}`")))
+            }
+        }
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while matches!(self.peek_byte(0), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn number(&mut self) -> Result<Kind> {
+        let start = self.position;
+        if self.peek_byte(0) == Some(b'-') {
+            self.position += 1;
+        }
+        let first = self.peek_byte(0);
+        let digits = self.digits();
+        if digits == 0 {
+            return Err(self.error("expected a digit"));
+        }
+        if first == Some(b'0') && digits > 1 {
+            return Err(self.error_at(start, "numbers must not have leading zeros"));
+        }
+
+        let mut kind = Kind::Int;
+        if self.peek_byte(0) == Some(b'.') {
+            self.position += 1;
+            if self.digits() == 0 {
+                return Err(self.error("expected a digit after `.`"));
+            }
+            kind = Kind::Float;
+        }
+        if matches!(self.peek_byte(0), Some(b'e' | b'E')) {
+            self.position += 1;
+            if matches!(self.peek_byte(0), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if self.digits() == 0 {
+                return Err(self.error("expected a digit in the exponent"));
+            }
+            kind = Kind::Float;
+        }
+        if matches!(
+            self.peek_byte(0),
+            Some(b'.' | b'_' | b'a'..=b'z' | b'A'..=b'Z')
+        ) {
+            return Err(self.error("expected a separator after the number"));
+        }
+        Ok(kind)
+    }
+
+    fn string(&mut self) -> Result<()> {
+        let start = self.position;
+        self.position += 1;
+        loop {
+            match self.peek_byte(0) {
+                None | Some(b'\n' | b'\r') => {
+                    return Err(self.error_at(start, "unterminated string"));
+                }
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(());
+                }
+                Some(b'\\') => match self.peek_byte(1) {
+                    Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {
+                        self.position += 2;
+                    }
+                    Some(b'u') => {
+                        let hex = self.input.get(self.position + 2..self.position + 6);
+                        if !hex.is_some_and(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                        {
+                            return Err(self.error("invalid unicode escape"));
+                        }
+                        self.position += 6;
+                    }
+                    _ => return Err(self.error("invalid escape sequence")),
+                },
+                Some(_) => {
+                    let ch = self.input[self.position..]
+                        .chars()
+                        .next()
+                        .unwrap_or_default();
+                    self.position += ch.len_utf8();
+                }
+            }
+        }
+    }
+
+    fn block_string(&mut self) -> Result<()> {
+        self.position += 3;
+        loop {
+            let rest = &self.input[self.position..];
+            if rest.is_empty() {
+                return Err(self.error("unterminated block string"));
+            }
+            if rest.starts_with("\"\"\"") {
+                self.position += 3;
+                return Ok(());
+            }
+            if rest.starts_with("\\\"\"\"") {
+                self.position += 4;
+                continue;
+            }
+            match self.peek_byte(0) {
+                Some(b'\n') => {
+                    self.position += 1;
+                    self.newline();
+                }
+                Some(b'\r') => {
+                    self.position += 1;
+                    if self.peek_byte(0) == Some(b'\n') {
+                        self.position += 1;
+                    }
+                    self.newline();
+                }
+                _ => {
+                    let ch = rest.chars().next().unwrap_or_default();
+                    self.position += ch.len_utf8();
+                }
+            }
+        }
+    }
+}
+
+/// How deep selection sets, list and object values, and list types may nest. Deeper documents
+/// are rejected rather than parsed with unbounded recursion.
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    index: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Result<Self> {
+        Ok(Parser {
+            tokens: Lexer::tokens(input)?,
+            index: 0,
+            depth: 0,
+        })
+    }
+
+    fn peek(&self) -> &Token<'a> {
+        &self.tokens[self.index]
+    }
+
+    fn advance(&mut self) -> Token<'a> {
+        let token = self.tokens[self.index].clone();
+        if token.kind != Kind::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn is(&self, text: &str) -> bool {
+        let token = self.peek();
+        matches!(token.kind, Kind::Punctuator | Kind::Name) && token.text == text
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let matched = self.is(text);
+        if matched {
+            self.advance();
+        }
+        matched
+    }
+
+    fn error_at(&self, token: &Token<'_>, expected: &str) -> Error {
+        let found = match token.kind {
+            Kind::End => "the end of the document".to_owned(),
+            _ => format!("`{}`", token.text),
+        };
+        Error::GraphQlSyntax {
+            line: token.line,
+            column: token.column,
+            message: format!("{expected}, found {found}"),
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, failing past [`MAX_DEPTH`].
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        if self.depth == MAX_DEPTH {
+            let token = self.peek();
+            return Err(Error::GraphQlSyntax {
+                line: token.line,
+                column: token.column,
+                message: format!("nesting exceeds {MAX_DEPTH} levels"),
+            });
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn expect(&mut self, text: &str) -> Result<()> {
+        if self.eat(text) {
+            Ok(())
+        } else {
+            Err(self.error_at(self.peek(), &format!("expected `{text}`")))
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        if self.peek().kind == Kind::Name {
+            Ok(self.advance().text)
+        } else {
+            Err(self.error_at(self.peek(), "expected a name"))
+        }
+    }
+
+    fn definition(&mut self) -> Result<()> {
+        if self.is("{") {
+            return self.selection_set();
+        }
+        match self.peek().text {
+            "query" | "mutation" | "subscription" if self.peek().kind == Kind::Name => {
+                self.advance();
+                if self.peek().kind == Kind::Name {
+                    self.advance();
+                }
+                if self.is("(") {
+                    self.variable_definitions()?;
+                }
+                self.directives(false)?;
+                self.selection_set()
+            }
+            "fragment" if self.peek().kind == Kind::Name => {
+                self.advance();
+                let name = self.peek().clone();
+                if self.name()? == "on" {
+                    return Err(self.error_at(&name, "expected a fragment name"));
+                }
+                self.type_condition()?;
+                self.directives(false)?;
+                self.selection_set()
+            }
+            _ => Err(self.error_at(
+                self.peek(),
+                "expected `query`, `mutation`, `subscription`, `fragment` or `{`",
+            )),
+        }
+    }
+
+    fn variable_definitions(&mut self) -> Result<()> {
+        self.expect("(")?;
+        loop {
+            self.variable()?;
+            self.expect(":")?;
+            self.type_reference()?;
+            if self.eat("=") {
+                self.value(true)?;
+            }
+            self.directives(true)?;
+            if self.eat(")") {
+                return Ok(());
+            }
+        }
+    }
+
+    fn variable(&mut self) -> Result<()> {
+        self.expect("$")?;
+        self.name().map(drop)
+    }
+
+    fn type_reference(&mut self) -> Result<()> {
+        if self.eat("[") {
+            self.nested(|parser| {
+                parser.type_reference()?;
+                parser.expect("]")
+            })?;
+        } else {
+            self.name()?;
+        }
+        self.eat("!");
+        Ok(())
+    }
+
+    fn type_condition(&mut self) -> Result<()> {
+        self.expect("on")?;
+        self.name().map(drop)
+    }
+
+    fn directives(&mut self, constant: bool) -> Result<()> {
+        while self.eat("@") {
+            self.name()?;
+            if self.is("(") {
+                self.arguments(constant)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn arguments(&mut self, constant: bool) -> Result<()> {
+        self.expect("(")?;
+        loop {
+            self.name()?;
+            self.expect(":")?;
+            self.value(constant)?;
+            if self.eat(")") {
+                return Ok(());
+            }
+        }
+    }
+
+    fn selection_set(&mut self) -> Result<()> {
+        self.expect("{")?;
+        self.nested(|parser| {
+            loop {
+                parser.selection()?;
+                if parser.eat("}") {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    fn selection(&mut self) -> Result<()> {
+        if self.eat("...") {
+            if self.is("on") {
+                self.type_condition()?;
+            } else if self.peek().kind == Kind::Name {
+                self.name()?;
+                return self.directives(false);
+            }
+            self.directives(false)?;
+            return self.selection_set();
+        }
+
+        self.name()?;
+        if self.eat(":") {
+            self.name()?;
+        }
+        if self.is("(") {
+            self.arguments(false)?;
+        }
+        self.directives(false)?;
+        if self.is("{") {
+            self.selection_set()?;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, constant: bool) -> Result<()> {
+        let token = self.peek().clone();
+        match token.kind {
+            Kind::Int | Kind::Float | Kind::String | Kind::Name => {
+                self.advance();
+                Ok(())
+            }
+            Kind::Punctuator if token.text == "$" && !constant => self.variable(),
+            Kind::Punctuator if token.text == "[" => {
+                self.advance();
+                self.nested(|parser| {
+                    while !parser.eat("]") {
+                        parser.value(constant)?;
+                    }
+                    Ok(())
+                })
+            }
+            Kind::Punctuator if token.text == "{" => {
+                self.advance();
+                self.nested(|parser| {
+                    while !parser.eat("}") {
+                        parser.name()?;
+                        parser.expect(":")?;
+                        parser.value(constant)?;
+                    }
+                    Ok(())
+                })
+            }
+            _ => Err(self.error_at(&token, "expected a value")),
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde_json::{Map, Value};
+
+use super::{
+    Content, Cookie, Creator, Entry, Har, Header, Log, Param, PostData, QueryParam, Request,
+    Response, Timings, VERSION,
+};
+use crate::body::Encoder;
+use crate::environment::Environment;
+use crate::globals::Globals;
+use crate::v2_1_0::auth::effective_auth;
+use crate::v2_1_0::iter::ItemEntry;
+use crate::v2_1_0::{
+    self, Body, FormParameterSrcUnion, HeaderUnion, Headers, Mode, RequestClass, RequestUnion,
+    ResponseClass, ResponseTime, Spec, Url,
+};
+use crate::variables::dynamic::{iso_timestamp, unix_millis};
+use crate::variables::{Resolver, Scope};
+use crate::{PostmanCollection, Result, convert};
+
+const HTTP_VERSION: &str = "HTTP/1.1";
+
+/// Builds HAR documents from collections. See the [module documentation](super).
+///
+/// Every request of the collection becomes an [`Entry`], in the order of [`Spec::items`]:
+///
+/// - `{{variables}}` are resolved from the globals, the collection, folder and item variables
+///   and the environment, and path variables are expanded. Placeholders without a value are
+///   left in place;
+/// - the effective auth is applied as the runner applies it: basic, bearer and API key auth add
+///   a header or query parameter, other schemes are left out;
+/// - the body is encoded with the [`Encoder`], except for `formdata` bodies, which are listed
+///   as [`PostData::params`] without reading the files they upload;
+/// - the response is the first saved example of the request, and has status `0` when there is
+///   none. Its `timings` are read from the `dns`, `tcp`, `secureHandshake`, `firstByte` and
+///   `download` phases Postman records; without them the whole `responseTime` counts as
+///   waiting for the response.
+#[derive(Clone, Debug, Default)]
+pub struct Exporter {
+    resolver: Resolver,
+    encoder: Encoder,
+    started_date_time: Option<String>,
+}
+
+impl Exporter {
+    pub fn new() -> Self {
+        Exporter::default()
+    }
+
+    pub fn with_environment(mut self, environment: &Environment) -> Self {
+        self.resolver.push(Scope::from(environment));
+        self
+    }
+
+    pub fn with_globals(mut self, globals: &Globals) -> Self {
+        self.resolver.push(Scope::from(globals));
+        self
+    }
+
+    /// Encodes bodies with `encoder`, for example to read `file` bodies through a custom
+    /// [`FileResolver`](crate::body::FileResolver).
+    pub fn with_encoder(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// The ISO 8601 time every entry is stamped with, instead of the time of the export.
+    pub fn with_started_date_time(mut self, started_date_time: impl Into<String>) -> Self {
+        self.started_date_time = Some(started_date_time.into());
+        self
+    }
+
+    /// Exports a collection of any version. v1.0.0 and v2.0.0 collections are converted to
+    /// v2.1.0 first, ignoring whatever the conversion cannot carry over.
+    pub fn export(&self, collection: &PostmanCollection) -> Result<Har> {
+        let spec = match collection {
+            PostmanCollection::V1_0_0(spec) => Cow::Owned(convert::v1_0_0_to_v2_1_0(spec).spec),
+            PostmanCollection::V2_0_0(spec) => Cow::Owned(Spec::from(spec.clone())),
+            PostmanCollection::V2_1_0(spec) => Cow::Borrowed(spec),
+        };
+        self.export_spec(&spec)
+    }
+
+    /// Exports a v2.1.0 collection. Fails when the auth attributes of a request cannot be read
+    /// or its body cannot be encoded.
+    pub fn export_spec(&self, spec: &Spec) -> Result<Har> {
+        let started_date_time = self
+            .started_date_time
+            .clone()
+            .unwrap_or_else(|| iso_timestamp(unix_millis(SystemTime::now())));
+        let entries = spec
+            .items()
+            .map(|entry| self.entry(spec, &entry, &started_date_time))
+            .collect::<Result<_>>()?;
+
+        Ok(Har {
+            log: Log {
+                version: VERSION.to_owned(),
+                creator: Creator {
+                    name: env!("CARGO_PKG_NAME").to_owned(),
+                    version: env!("CARGO_PKG_VERSION").to_owned(),
+                    comment: None,
+                },
+                browser: None,
+                pages: None,
+                entries,
+                comment: Some(spec.info.name.clone()).filter(|name| !name.is_empty()),
+            },
+        })
+    }
+
+    fn entry(&self, spec: &Spec, entry: &ItemEntry<'_>, started_date_time: &str) -> Result<Entry> {
+        let mut request = match &entry.item.request {
+            RequestUnion::RequestClass(request) => request.clone(),
+            RequestUnion::String(url) => RequestClass {
+                url: Some(Url::String(url.clone())),
+                ..RequestClass::default()
+            },
+        };
+        if request.auth.is_none() {
+            request.auth = effective_auth(spec, &entry.ancestors, entry.item)
+                .map(|effective| effective.auth.clone());
+        }
+
+        let mut resolver = self.resolver.clone();
+        for scope in Resolver::for_entry(spec, entry).scopes() {
+            resolver.push(scope.clone());
+        }
+        let mut request = resolver.resolve_request(&request).value;
+        request.apply_auth()?;
+
+        let example = entry.item.response.iter().flatten().next();
+        let timings = timings(example);
+        let time = [timings.blocked, timings.dns, timings.connect]
+            .into_iter()
+            .flatten()
+            .chain([timings.send, timings.wait, timings.receive])
+            .filter(|phase| *phase > 0.0)
+            .sum();
+
+        Ok(Entry {
+            pageref: None,
+            started_date_time: started_date_time.to_owned(),
+            time,
+            request: self.request(&request)?,
+            response: response(example),
+            cache: Default::default(),
+            timings,
+            server_ip_address: None,
+            connection: None,
+            comment: Some(entry.path.clone()),
+            other: Map::new(),
+        })
+    }
+
+    fn request(&self, request: &RequestClass) -> Result<Request> {
+        let url = request
+            .url
+            .as_ref()
+            .map(|url| url.to_class().expand_path_variables().value)
+            .unwrap_or_default();
+        let mut headers: Vec<Header> = request
+            .header
+            .iter()
+            .flat_map(HeaderUnion::enabled)
+            .map(|(name, value)| header(name, value))
+            .collect();
+        let cookies = headers
+            .iter()
+            .filter(|header| header.name.eq_ignore_ascii_case("Cookie"))
+            .flat_map(|header| header.value.split(';'))
+            .filter_map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                Some(Cookie {
+                    name: name.trim().to_owned(),
+                    value: value.trim().to_owned(),
+                    ..Cookie::default()
+                })
+            })
+            .collect();
+        let query_string = url
+            .query
+            .iter()
+            .flatten()
+            .filter(|param| !param.disabled.unwrap_or(false))
+            .filter_map(|param| {
+                Some(QueryParam {
+                    name: param.key.clone()?,
+                    value: param.value.clone().unwrap_or_default(),
+                    comment: None,
+                })
+            })
+            .collect();
+
+        let (post_data, body_size) = match request.body.as_ref() {
+            Some(body) => self.post_data(body, &mut headers)?,
+            None => (None, 0),
+        };
+
+        Ok(Request {
+            method: request
+                .method
+                .as_deref()
+                .map_or_else(|| "GET".to_owned(), str::to_ascii_uppercase),
+            url: url.to_url().map_or_else(|_| url.to_string(), String::from),
+            http_version: HTTP_VERSION.to_owned(),
+            cookies,
+            headers,
+            query_string,
+            post_data,
+            headers_size: -1,
+            body_size,
+            comment: None,
+        })
+    }
+
+    /// The body as `postData`, and its size. Adds the `Content-Type` of the encoded body to
+    /// `headers` unless one is set.
+    fn post_data(&self, body: &Body, headers: &mut Vec<Header>) -> Result<(Option<PostData>, i64)> {
+        let content_type = headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case("Content-Type"))
+            .map(|header| header.value.clone());
+
+        if body.mode == Some(Mode::Formdata) && !body.disabled.unwrap_or(false) {
+            let mut params = Vec::new();
+            for field in body.formdata.iter().flatten() {
+                if field.disabled.unwrap_or(false) {
+                    continue;
+                }
+                if field.form_parameter_type.as_deref() != Some("file") {
+                    params.push(Param {
+                        name: field.key.clone(),
+                        value: Some(field.value.clone().unwrap_or_default()),
+                        content_type: field.content_type.clone(),
+                        ..Param::default()
+                    });
+                    continue;
+                }
+                let sources = match field.src.as_ref() {
+                    Some(FormParameterSrcUnion::File(src)) => std::slice::from_ref(src),
+                    Some(FormParameterSrcUnion::Files(sources)) => sources.as_slice(),
+                    None => &[],
+                };
+                params.extend(sources.iter().map(|src| {
+                    Param {
+                        name: field.key.clone(),
+                        file_name: Some(
+                            Path::new(src)
+                                .file_name()
+                                .map_or_else(|| src.clone(), |name| name.to_string_lossy().into()),
+                        ),
+                        content_type: field.content_type.clone(),
+                        ..Param::default()
+                    }
+                }));
+            }
+            let post_data = PostData {
+                mime_type: content_type.unwrap_or_else(|| "multipart/form-data".to_owned()),
+                params,
+                text: None,
+                comment: None,
+            };
+            return Ok((Some(post_data), -1));
+        }
+
+        let Some(encoded) = self.encoder.encode(body)? else {
+            return Ok((None, 0));
+        };
+        let mime_type = match (content_type, encoded.content_type) {
+            (Some(content_type), _) => content_type,
+            (None, Some(content_type)) => {
+                headers.push(header("Content-Type", &content_type));
+                content_type
+            }
+            (None, None) => "application/octet-stream".to_owned(),
+        };
+        let params = match body.mode {
+            Some(Mode::Urlencoded) => body
+                .urlencoded
+                .iter()
+                .flatten()
+                .filter(|param| !param.disabled.unwrap_or(false))
+                .map(|param| Param {
+                    name: param.key.clone(),
+                    value: Some(param.value.clone().unwrap_or_default()),
+                    ..Param::default()
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let post_data = PostData {
+            mime_type,
+            params,
+            text: Some(String::from_utf8_lossy(&encoded.bytes).into_owned()),
+            comment: None,
+        };
+        Ok((Some(post_data), encoded.bytes.len() as i64))
+    }
+}
+
+impl Spec {
+    /// Exports the collection with a default [`Exporter`].
+    pub fn to_har(&self) -> Result<Har> {
+        Exporter::new().export_spec(self)
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header {
+        name: name.to_owned(),
+        value: value.to_owned(),
+        comment: None,
+    }
+}
+
+fn response(example: Option<&ResponseClass>) -> Response {
+    let Some(example) = example else {
+        return Response {
+            content: Content {
+                mime_type: "x-unknown".to_owned(),
+                ..Content::default()
+            },
+            headers_size: -1,
+            body_size: -1,
+            ..Response::default()
+        };
+    };
+
+    let headers: Vec<Header> = example
+        .header
+        .iter()
+        .flat_map(Headers::enabled)
+        .map(|(name, value)| header(name, value))
+        .collect();
+    let find = |name: &str| {
+        headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case(name))
+            .map(|header| header.value.clone())
+    };
+    let cookies = example
+        .cookie
+        .iter()
+        .flatten()
+        .map(|cookie: &v2_1_0::Cookie| Cookie {
+            name: cookie.name.clone().unwrap_or_default(),
+            value: cookie.value.clone().unwrap_or_default(),
+            path: Some(cookie.path.clone()).filter(|path| !path.is_empty()),
+            domain: Some(cookie.domain.clone()).filter(|domain| !domain.is_empty()),
+            expires: cookie.expires.clone(),
+            http_only: cookie.http_only,
+            secure: cookie.secure,
+            comment: None,
+        })
+        .collect();
+    let size = example.body.as_deref().map_or(0, str::len) as i64;
+
+    Response {
+        status: example.code.unwrap_or_default(),
+        status_text: example.status.clone().unwrap_or_default(),
+        http_version: HTTP_VERSION.to_owned(),
+        cookies,
+        content: Content {
+            size,
+            compression: None,
+            mime_type: find("Content-Type").unwrap_or_default(),
+            text: example.body.clone(),
+            encoding: None,
+            comment: None,
+        },
+        redirect_url: find("Location").unwrap_or_default(),
+        headers,
+        headers_size: -1,
+        body_size: size,
+        comment: example.name.clone(),
+    }
+}
+
+fn timings(example: Option<&ResponseClass>) -> Timings {
+    let response_time = example
+        .and_then(|example| example.response_time.as_ref())
+        .and_then(|time| match time {
+            ResponseTime::Integer(time) => Some(*time as f64),
+            ResponseTime::Double(time) => Some(*time),
+            ResponseTime::String(time) => time.trim().parse().ok(),
+        });
+    let phases = example
+        .and_then(|example| example.timings.as_ref())
+        .and_then(Value::as_object);
+    let phase = |name: &str| phases.and_then(|phases| phases.get(name)?.as_f64());
+
+    let (first_byte, download) = (phase("firstByte"), phase("download"));
+    if first_byte.is_none() && download.is_none() {
+        return Timings {
+            send: 0.0,
+            wait: response_time.unwrap_or_default(),
+            receive: 0.0,
+            ..Timings::default()
+        };
+    }
+    let ssl = phase("secureHandshake");
+    Timings {
+        blocked: phase("wait"),
+        dns: phase("dns"),
+        connect: match (phase("tcp"), ssl) {
+            (Some(tcp), Some(ssl)) => Some(tcp + ssl),
+            (tcp, _) => tcp,
+        },
+        send: 0.0,
+        wait: first_byte.unwrap_or_default(),
+        receive: download.unwrap_or_default(),
+        ssl,
+        comment: None,
+    }
+}
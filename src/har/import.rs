@@ -0,0 +1,412 @@
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+use super::{Entry, Har, PostData, Timings};
+use crate::v2_1_0::{
+    self, Body, BodyOptions, FormParameter, FormParameterSrcUnion, Header, HeaderElement,
+    HeaderUnion, Headers, Information, Item, ItemGroup, Items, Mode, RawLanguage, RawOptions,
+    RequestClass, RequestUnion, ResponseClass, ResponseTime, Spec, Url, UrlClass,
+    UrlEncodedParameter,
+};
+
+/// The collection name used when none is given.
+const DEFAULT_NAME: &str = "HAR import";
+
+/// Request headers that describe the capture rather than the request, and are left out.
+const SKIPPED_HEADERS: [&str; 2] = ["content-length", "host"];
+
+/// Path extensions of images, fonts, stylesheets, scripts and media.
+const STATIC_EXTENSIONS: [&str; 20] = [
+    "css", "js", "mjs", "map", "png", "jpg", "jpeg", "gif", "svg", "ico", "webp", "avif", "bmp",
+    "woff", "woff2", "ttf", "otf", "eot", "mp4", "webm",
+];
+
+/// Chrome's `_resourceType` values for static assets.
+const STATIC_RESOURCE_TYPES: [&str; 5] = ["image", "stylesheet", "script", "font", "media"];
+
+/// Builds a v2.1.0 collection from a HAR document. See the [module documentation](super).
+///
+/// Only `http` and `https` entries are imported. Each becomes an [`Item`] named after its
+/// method and path, such as `GET /users/7`, inside a folder for its host and folders for the
+/// first segments of its path. The last path segment never becomes a folder, so with the
+/// default depth of one `/users` sits in the host folder and `/users/7` in its `users` folder.
+///
+/// The response of an entry, unless there was none, is stored as the item's example, with the
+/// HAR timings as the `dns`, `tcp`, `secureHandshake`, `firstByte` and `download` phases
+/// Postman records. Base64-encoded response bodies are left out.
+///
+/// Calls with the same method, URL and body are imported once, from their first entry.
+#[derive(Clone, Debug)]
+pub struct Importer {
+    name: String,
+    path_depth: usize,
+    skip_static_assets: bool,
+    keep_duplicates: bool,
+    hosts: Vec<String>,
+    excluded_hosts: Vec<String>,
+}
+
+impl Default for Importer {
+    fn default() -> Self {
+        Importer {
+            name: DEFAULT_NAME.to_owned(),
+            path_depth: 1,
+            skip_static_assets: false,
+            keep_duplicates: false,
+            hosts: Vec::new(),
+            excluded_hosts: Vec::new(),
+        }
+    }
+}
+
+impl Importer {
+    pub fn new() -> Self {
+        Importer::default()
+    }
+
+    /// The name of the collection, `HAR import` by default.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// How many leading path segments become folders. `0` puts every request directly in its
+    /// host folder.
+    pub fn with_path_depth(mut self, depth: usize) -> Self {
+        self.path_depth = depth;
+        self
+    }
+
+    /// Leaves out images, fonts, stylesheets, scripts and media, recognized by the type of the
+    /// response, the extension of the path or the `_resourceType` browsers record.
+    pub fn skip_static_assets(mut self) -> Self {
+        self.skip_static_assets = true;
+        self
+    }
+
+    /// Imports every entry, including repeated calls.
+    pub fn keep_duplicates(mut self) -> Self {
+        self.keep_duplicates = true;
+        self
+    }
+
+    /// Only imports entries for `host` and its subdomains. May be given several times.
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into());
+        self
+    }
+
+    /// Leaves out entries for `host` and its subdomains, such as analytics or CDN hosts. May be
+    /// given several times, and wins over [`with_host`](Self::with_host).
+    pub fn without_host(mut self, host: impl Into<String>) -> Self {
+        self.excluded_hosts.push(host.into());
+        self
+    }
+
+    pub fn import(&self, har: &Har) -> Spec {
+        let mut items = Vec::new();
+        let mut seen = HashSet::new();
+        for entry in &har.log.entries {
+            let Ok(url) = ::url::Url::parse(&entry.request.url) else {
+                continue;
+            };
+            let Some(host) = url
+                .host_str()
+                .filter(|_| matches!(url.scheme(), "http" | "https"))
+            else {
+                continue;
+            };
+            if !self.selects(host) || (self.skip_static_assets && is_static(entry, &url)) {
+                continue;
+            }
+            let method = entry.request.method.to_ascii_uppercase();
+            let body = entry
+                .request
+                .post_data
+                .as_ref()
+                .and_then(|post_data| post_data.text.clone());
+            if !self.keep_duplicates && !seen.insert((method.clone(), url.to_string(), body)) {
+                continue;
+            }
+
+            let mut folder = ItemGroup::items_of(
+                &mut items,
+                &match url.port() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host.to_owned(),
+                },
+            );
+            let segments: Vec<&str> = url
+                .path_segments()
+                .into_iter()
+                .flatten()
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            let depth = self.path_depth.min(segments.len().saturating_sub(1));
+            for segment in &segments[..depth] {
+                folder = ItemGroup::items_of(folder, segment);
+            }
+            folder.push(Items::Item(item(entry, method, url.path())));
+        }
+
+        Spec {
+            info: Information {
+                name: self.name.clone(),
+                schema: v2_1_0::SCHEMA_URL.to_owned(),
+                ..Information::default()
+            },
+            item: items,
+            ..Spec::default()
+        }
+    }
+
+    fn selects(&self, host: &str) -> bool {
+        (self.hosts.is_empty() || self.hosts.iter().any(|filter| matches_host(host, filter)))
+            && !self
+                .excluded_hosts
+                .iter()
+                .any(|filter| matches_host(host, filter))
+    }
+}
+
+impl Spec {
+    /// Imports a HAR document with a default [`Importer`].
+    pub fn from_har(har: &Har) -> Spec {
+        Importer::new().import(har)
+    }
+}
+
+fn matches_host(host: &str, filter: &str) -> bool {
+    let filter = filter.trim_start_matches("*.");
+    host.eq_ignore_ascii_case(filter)
+        || host.len() > filter.len()
+            && host.as_bytes()[host.len() - filter.len() - 1] == b'.'
+            && host[host.len() - filter.len()..].eq_ignore_ascii_case(filter)
+}
+
+fn is_static(entry: &Entry, url: &::url::Url) -> bool {
+    let resource_type = entry.other.get("_resourceType").and_then(Value::as_str);
+    if resource_type.is_some_and(|kind| STATIC_RESOURCE_TYPES.contains(&kind)) {
+        return true;
+    }
+    let mime_type = entry.response.content.mime_type.to_ascii_lowercase();
+    if ["image/", "font/", "audio/", "video/", "text/css"]
+        .iter()
+        .any(|prefix| mime_type.starts_with(prefix))
+        || mime_type.contains("javascript")
+    {
+        return true;
+    }
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(_, extension)| {
+            STATIC_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        })
+}
+
+fn item(entry: &Entry, method: String, path: &str) -> Item {
+    let name = format!("{method} {path}");
+    let body = entry.request.post_data.as_ref().and_then(body);
+    let multipart = body.as_ref().and_then(|body| body.mode.as_ref()) == Some(&Mode::Formdata);
+    let headers: Vec<Header> = entry
+        .request
+        .headers
+        .iter()
+        .filter(|header| {
+            let name = header.name.to_ascii_lowercase();
+            !name.starts_with(':')
+                && !SKIPPED_HEADERS.contains(&name.as_str())
+                // The boundary of the captured body no longer applies.
+                && (!multipart || name != "content-type")
+        })
+        .map(|header| Header {
+            description: None,
+            disabled: None,
+            key: header.name.clone(),
+            value: header.value.clone(),
+        })
+        .collect();
+
+    let request = RequestClass {
+        body,
+        header: (!headers.is_empty()).then_some(HeaderUnion::HeaderArray(headers)),
+        method: Some(method),
+        url: Some(Url::UrlClass(UrlClass::parse(&entry.request.url))),
+        ..RequestClass::default()
+    };
+    let response = (entry.response.status != 0).then(|| response(entry, &request));
+    Item {
+        description: None,
+        event: None,
+        id: None,
+        name: Some(name),
+        protocol_profile_behavior: None,
+        request: RequestUnion::RequestClass(request),
+        response: response.map(|response| vec![response]),
+        variable: None,
+    }
+}
+
+fn body(post_data: &PostData) -> Option<Body> {
+    let mime_type = post_data.mime_type.to_ascii_lowercase();
+    if mime_type.starts_with("multipart/form-data") && !post_data.params.is_empty() {
+        let formdata = post_data
+            .params
+            .iter()
+            .map(|param| match param.file_name.as_ref() {
+                Some(file_name) => FormParameter {
+                    content_type: param.content_type.clone(),
+                    description: None,
+                    disabled: None,
+                    key: param.name.clone(),
+                    form_parameter_type: Some("file".to_owned()),
+                    value: None,
+                    src: Some(FormParameterSrcUnion::File(file_name.clone())),
+                },
+                None => FormParameter {
+                    content_type: param.content_type.clone(),
+                    description: None,
+                    disabled: None,
+                    key: param.name.clone(),
+                    form_parameter_type: Some("text".to_owned()),
+                    value: param.value.clone(),
+                    src: None,
+                },
+            })
+            .collect();
+        return Some(Body {
+            mode: Some(Mode::Formdata),
+            formdata: Some(formdata),
+            ..Body::default()
+        });
+    }
+
+    if mime_type.starts_with("application/x-www-form-urlencoded") {
+        let urlencoded: Vec<_> = if post_data.params.is_empty() {
+            ::url::form_urlencoded::parse(post_data.text.as_deref().unwrap_or_default().as_bytes())
+                .map(|(key, value)| (key.into_owned(), Some(value.into_owned())))
+                .collect()
+        } else {
+            post_data
+                .params
+                .iter()
+                .map(|param| (param.name.clone(), param.value.clone()))
+                .collect()
+        };
+        return Some(Body {
+            mode: Some(Mode::Urlencoded),
+            urlencoded: Some(
+                urlencoded
+                    .into_iter()
+                    .map(|(key, value)| UrlEncodedParameter {
+                        description: None,
+                        disabled: None,
+                        key,
+                        parameter_type: Some("text".to_owned()),
+                        value,
+                    })
+                    .collect(),
+            ),
+            ..Body::default()
+        });
+    }
+
+    let raw = post_data.text.clone()?;
+    Some(Body {
+        mode: Some(Mode::Raw),
+        raw: Some(raw),
+        options: RawLanguage::from_content_type(&mime_type).map(|language| BodyOptions {
+            raw: Some(RawOptions {
+                language: Some(language),
+                ..RawOptions::default()
+            }),
+            ..BodyOptions::default()
+        }),
+        ..Body::default()
+    })
+}
+
+fn response(entry: &Entry, request: &RequestClass) -> ResponseClass {
+    let response = &entry.response;
+    let headers = response
+        .headers
+        .iter()
+        .map(|header| {
+            HeaderElement::Header(Header {
+                description: None,
+                disabled: None,
+                key: header.name.clone(),
+                value: header.value.clone(),
+            })
+        })
+        .collect();
+    let cookies: Vec<_> = response
+        .cookies
+        .iter()
+        .map(|cookie| v2_1_0::Cookie {
+            domain: cookie.domain.clone().unwrap_or_default(),
+            expires: cookie.expires.clone(),
+            extensions: None,
+            host_only: None,
+            http_only: cookie.http_only,
+            max_age: None,
+            name: Some(cookie.name.clone()),
+            path: cookie.path.clone().unwrap_or_default(),
+            secure: cookie.secure,
+            session: None,
+            value: Some(cookie.value.clone()),
+        })
+        .collect();
+    let status = Some(response.status_text.clone()).filter(|status| !status.is_empty());
+
+    ResponseClass {
+        body: response
+            .content
+            .text
+            .clone()
+            .filter(|_| response.content.encoding.is_none()),
+        code: Some(response.status),
+        cookie: (!cookies.is_empty()).then_some(cookies),
+        header: Some(Headers::UnionArray(headers)),
+        id: None,
+        name: Some(match status.as_deref() {
+            Some(status) => format!("{} {status}", response.status),
+            None => response.status.to_string(),
+        }),
+        original_request: Some(RequestUnion::RequestClass(request.clone())),
+        response_time: (entry.time >= 0.0).then(|| {
+            if entry.time.fract() == 0.0 {
+                ResponseTime::Integer(entry.time as i64)
+            } else {
+                ResponseTime::Double(entry.time)
+            }
+        }),
+        timings: phases(&entry.timings),
+        status,
+    }
+}
+
+/// The HAR timings as Postman's phases. HAR counts the TLS handshake as part of `connect`,
+/// Postman keeps it apart from `tcp`.
+fn phases(timings: &Timings) -> Option<Value> {
+    let known = |phase: Option<f64>| phase.filter(|phase| *phase >= 0.0);
+    let ssl = known(timings.ssl);
+    let tcp = known(timings.connect).map(|connect| connect - ssl.unwrap_or_default());
+
+    let mut phases = Map::new();
+    for (name, phase) in [
+        ("wait", known(timings.blocked)),
+        ("dns", known(timings.dns)),
+        ("tcp", tcp),
+        ("secureHandshake", ssl),
+        ("firstByte", known(Some(timings.wait))),
+        ("download", known(Some(timings.receive))),
+    ] {
+        if let Some(phase) = phase.and_then(serde_json::Number::from_f64) {
+            phases.insert(name.to_owned(), Value::Number(phase));
+        }
+    }
+    (!phases.is_empty()).then_some(Value::Object(phases))
+}
@@ -0,0 +1,460 @@
+//! HTTP Archive ([HAR 1.2](http://www.softwareishard.com/blog/har-12-spec/)) documents.
+//!
+//! The types in this module model a HAR file. Fields the format marks as optional are
+//! `Option`s, and lists a producer may leave out read as empty. Custom fields of an entry, which
+//! the format prefixes with `_`, are kept in [`Entry::other`].
+//!
+//! An [`Exporter`] builds a HAR document from a collection, with one entry per request and the
+//! first saved response of each request as the entry's response:
+//!
+//! ```
+//! use postman_collection::from_str;
+//! use postman_collection::har::Exporter;
+//!
+//! let collection = from_str(r#"{
+//!   "info": {
+//!     "name": "Users",
+//!     "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+//!   },
+//!   "variable": [{ "key": "baseUrl", "value": "https://example.com" }],
+//!   "item": [{
+//!     "name": "List users",
+//!     "request": "{{baseUrl}}/users?limit=2",
+//!     "response": [{
+//!       "name": "OK",
+//!       "code": 200,
+//!       "status": "OK",
+//!       "header": [{ "key": "Content-Type", "value": "application/json" }],
+//!       "body": "[]",
+//!       "responseTime": 42
+//!     }]
+//!   }]
+//! }"#)?;
+//!
+//! let har = Exporter::new()
+//!     .with_started_date_time("2024-05-01T12:00:00.000Z")
+//!     .export(&collection)?;
+//! let entry = &har.log.entries[0];
+//!
+//! assert_eq!(entry.request.url, "https://example.com/users?limit=2");
+//! assert_eq!(entry.request.query_string[0].value, "2");
+//! assert_eq!(entry.response.status, 200);
+//! assert_eq!(entry.response.content.mime_type, "application/json");
+//! assert_eq!(entry.time, 42.0);
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+//!
+//! An [`Importer`] goes the other way and turns captured traffic into a collection, with a
+//! folder per host and the captured responses as examples:
+//!
+//! ```
+//! use postman_collection::har::{Har, Importer};
+//! use postman_collection::v2_1_0::Items;
+//!
+//! let har: Har = serde_json::from_str(r#"{
+//!   "log": {
+//!     "version": "1.2",
+//!     "creator": { "name": "browser", "version": "1" },
+//!     "entries": [
+//!       {
+//!         "startedDateTime": "2024-05-01T12:00:00.000Z",
+//!         "time": 42,
+//!         "request": { "method": "GET", "url": "https://api.example.com/users/7" },
+//!         "response": {
+//!           "status": 200,
+//!           "statusText": "OK",
+//!           "content": { "size": 2, "mimeType": "application/json", "text": "{}" }
+//!         }
+//!       },
+//!       {
+//!         "startedDateTime": "2024-05-01T12:00:01.000Z",
+//!         "time": 3,
+//!         "request": { "method": "GET", "url": "https://cdn.example.com/logo.png" },
+//!         "response": { "status": 200, "content": { "mimeType": "image/png" } }
+//!       }
+//!     ]
+//!   }
+//! }"#)?;
+//!
+//! let spec = Importer::new()
+//!     .with_name("Captured")
+//!     .skip_static_assets()
+//!     .import(&har);
+//! let entries: Vec<_> = spec.items().collect();
+//!
+//! assert_eq!(entries.len(), 1);
+//! assert_eq!(entries[0].path, r"api.example.com/users/GET \/users\/7");
+//! assert_eq!(entries[0].item.response.as_ref().unwrap()[0].code, Some(200));
+//! assert!(matches!(&spec.item[0], Items::ItemGroup(group) if group.item.len() == 1));
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+mod export;
+mod import;
+
+pub use export::*;
+pub use import::*;
+
+/// The HAR version this module writes.
+pub const VERSION: &str = "1.2";
+
+/// The root of a HAR document.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Har {
+    #[serde(rename = "log")]
+    pub log: Log,
+}
+
+/// The exported data.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Log {
+    #[serde(rename = "version", default)]
+    pub version: String,
+
+    #[serde(rename = "creator", default)]
+    pub creator: Creator,
+
+    #[serde(rename = "browser", skip_serializing_if = "Option::is_none")]
+    pub browser: Option<Creator>,
+
+    #[serde(rename = "pages", skip_serializing_if = "Option::is_none")]
+    pub pages: Option<Vec<Page>>,
+
+    #[serde(rename = "entries", default)]
+    pub entries: Vec<Entry>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// The application, or browser, that wrote the log.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Creator {
+    #[serde(rename = "name", default)]
+    pub name: String,
+
+    #[serde(rename = "version", default)]
+    pub version: String,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A page that entries can belong to.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Page {
+    /// When the page load started, in ISO 8601 format.
+    #[serde(rename = "startedDateTime", default)]
+    pub started_date_time: String,
+
+    /// Referenced by [`Entry::pageref`].
+    #[serde(rename = "id", default)]
+    pub id: String,
+
+    #[serde(rename = "title", default)]
+    pub title: String,
+
+    #[serde(rename = "pageTimings", default)]
+    pub page_timings: PageTimings,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Page load milestones, in milliseconds since the page load started.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct PageTimings {
+    #[serde(rename = "onContentLoad", skip_serializing_if = "Option::is_none")]
+    pub on_content_load: Option<f64>,
+
+    #[serde(rename = "onLoad", skip_serializing_if = "Option::is_none")]
+    pub on_load: Option<f64>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// One request and its response.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Entry {
+    #[serde(rename = "pageref", skip_serializing_if = "Option::is_none")]
+    pub pageref: Option<String>,
+
+    /// When the request started, in ISO 8601 format.
+    #[serde(rename = "startedDateTime", default)]
+    pub started_date_time: String,
+
+    /// The total time of the request in milliseconds: the sum of the [`timings`](Self::timings)
+    /// that are known.
+    #[serde(rename = "time", default)]
+    pub time: f64,
+
+    #[serde(rename = "request")]
+    pub request: Request,
+
+    #[serde(rename = "response")]
+    pub response: Response,
+
+    #[serde(rename = "cache", default)]
+    pub cache: Cache,
+
+    #[serde(rename = "timings", default)]
+    pub timings: Timings,
+
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
+    pub server_ip_address: Option<String>,
+
+    #[serde(rename = "connection", skip_serializing_if = "Option::is_none")]
+    pub connection: Option<String>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// Custom fields such as `_resourceType`, as browsers write them.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A request as it was sent.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Request {
+    #[serde(rename = "method", default)]
+    pub method: String,
+
+    /// The absolute URL, fragments excluded.
+    #[serde(rename = "url", default)]
+    pub url: String,
+
+    #[serde(rename = "httpVersion", default)]
+    pub http_version: String,
+
+    #[serde(rename = "cookies", default)]
+    pub cookies: Vec<Cookie>,
+
+    #[serde(rename = "headers", default)]
+    pub headers: Vec<Header>,
+
+    #[serde(rename = "queryString", default)]
+    pub query_string: Vec<QueryParam>,
+
+    #[serde(rename = "postData", skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<PostData>,
+
+    /// `-1` when unknown.
+    #[serde(rename = "headersSize", default = "unknown_size")]
+    pub headers_size: i64,
+
+    /// `-1` when unknown.
+    #[serde(rename = "bodySize", default = "unknown_size")]
+    pub body_size: i64,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A response as it was received.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Response {
+    /// `0` when no response was received.
+    #[serde(rename = "status", default)]
+    pub status: i64,
+
+    #[serde(rename = "statusText", default)]
+    pub status_text: String,
+
+    #[serde(rename = "httpVersion", default)]
+    pub http_version: String,
+
+    #[serde(rename = "cookies", default)]
+    pub cookies: Vec<Cookie>,
+
+    #[serde(rename = "headers", default)]
+    pub headers: Vec<Header>,
+
+    #[serde(rename = "content", default)]
+    pub content: Content,
+
+    /// The target of a `Location` header.
+    #[serde(rename = "redirectURL", default)]
+    pub redirect_url: String,
+
+    /// `-1` when unknown.
+    #[serde(rename = "headersSize", default = "unknown_size")]
+    pub headers_size: i64,
+
+    /// `-1` when unknown.
+    #[serde(rename = "bodySize", default = "unknown_size")]
+    pub body_size: i64,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A cookie sent with a request or set by a response.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Cookie {
+    #[serde(rename = "name", default)]
+    pub name: String,
+
+    #[serde(rename = "value", default)]
+    pub value: String,
+
+    #[serde(rename = "path", skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    #[serde(rename = "domain", skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    /// In ISO 8601 format.
+    #[serde(rename = "expires", skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+
+    #[serde(rename = "httpOnly", skip_serializing_if = "Option::is_none")]
+    pub http_only: Option<bool>,
+
+    #[serde(rename = "secure", skip_serializing_if = "Option::is_none")]
+    pub secure: Option<bool>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A request or response header.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Header {
+    #[serde(rename = "name", default)]
+    pub name: String,
+
+    #[serde(rename = "value", default)]
+    pub value: String,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A query string parameter.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct QueryParam {
+    #[serde(rename = "name", default)]
+    pub name: String,
+
+    #[serde(rename = "value", default)]
+    pub value: String,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A request body.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct PostData {
+    #[serde(rename = "mimeType", default)]
+    pub mime_type: String,
+
+    /// The fields of a form body.
+    #[serde(rename = "params", default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<Param>,
+
+    #[serde(rename = "text", skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A field of a form body.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Param {
+    #[serde(rename = "name", default)]
+    pub name: String,
+
+    #[serde(rename = "value", skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    /// The name of an uploaded file.
+    #[serde(rename = "fileName", skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// A response body.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Content {
+    /// The length of the decoded body in bytes.
+    #[serde(rename = "size", default)]
+    pub size: i64,
+
+    /// The number of bytes saved by compression.
+    #[serde(rename = "compression", skip_serializing_if = "Option::is_none")]
+    pub compression: Option<i64>,
+
+    #[serde(rename = "mimeType", default)]
+    pub mime_type: String,
+
+    #[serde(rename = "text", skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// `base64` when [`text`](Self::text) is base64-encoded binary content.
+    #[serde(rename = "encoding", skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// What the browser cache held before and after the request.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Cache {
+    #[serde(rename = "beforeRequest", skip_serializing_if = "Option::is_none")]
+    pub before_request: Option<Value>,
+
+    #[serde(rename = "afterRequest", skip_serializing_if = "Option::is_none")]
+    pub after_request: Option<Value>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// The phases of a request, in milliseconds. Optional phases may also be `-1` when they do not
+/// apply.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Timings {
+    /// Time spent in a queue waiting for a connection.
+    #[serde(rename = "blocked", skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<f64>,
+
+    #[serde(rename = "dns", skip_serializing_if = "Option::is_none")]
+    pub dns: Option<f64>,
+
+    /// Time to open the connection, including [`ssl`](Self::ssl).
+    #[serde(rename = "connect", skip_serializing_if = "Option::is_none")]
+    pub connect: Option<f64>,
+
+    #[serde(rename = "send", default)]
+    pub send: f64,
+
+    /// Time waiting for the first byte of the response.
+    #[serde(rename = "wait", default)]
+    pub wait: f64,
+
+    #[serde(rename = "receive", default)]
+    pub receive: f64,
+
+    #[serde(rename = "ssl", skip_serializing_if = "Option::is_none")]
+    pub ssl: Option<f64>,
+
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
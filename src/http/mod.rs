@@ -0,0 +1,154 @@
+//! Conversion of collection requests into [`http::Request`] values.
+//!
+//! Available with the crate feature `http`.
+//!
+//! The conversion expects a request whose variables are already resolved, for example with
+//! [`Resolver::resolve_request`](crate::variables::Resolver::resolve_request). It follows what
+//! Postman sends:
+//!
+//! - the method defaults to `GET` and is upper-cased;
+//! - the URL is validated with [`Url::to_url`], so leftover `{{variables}}` or `:pathVariables`
+//!   are errors, and disabled query parameters are left out;
+//! - only enabled headers are sent;
+//! - the body is encoded with a [`body::Encoder`](crate::body::Encoder), and its
+//!   `Content-Type` is added unless a header already sets one. Multipart bodies always use
+//!   their own `Content-Type`, which carries the boundary;
+//! - `GET`, `COPY`, `HEAD`, `PURGE` and `UNLOCK` requests are sent without a body unless
+//!   [`ProtocolProfileBehavior::disable_body_pruning`] is set.
+//!
+//! Auth settings are not applied.
+//!
+//! ```
+//! use postman_collection::v2_1_0::RequestClass;
+//!
+//! let request: RequestClass = serde_json::from_str(r#"{
+//!   "method": "post",
+//!   "url": "https://example.com/login?debug",
+//!   "header": [
+//!     { "key": "Accept", "value": "application/json" },
+//!     { "key": "X-Trace", "value": "1", "disabled": true }
+//!   ],
+//!   "body": { "mode": "urlencoded", "urlencoded": [{ "key": "user", "value": "ada lovelace" }] }
+//! }"#)?;
+//!
+//! let request = request.to_http()?;
+//! assert_eq!(request.method(), "POST");
+//! assert_eq!(request.headers()["content-type"], "application/x-www-form-urlencoded");
+//! assert!(request.headers().get("x-trace").is_none());
+//! assert_eq!(request.body(), b"user=ada+lovelace");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::http::header::CONTENT_TYPE;
+use ::http::{Method, Request};
+
+use crate::body::Encoder;
+use crate::v2_1_0::{HeaderUnion, Item, ProtocolProfileBehavior, RequestClass, RequestUnion, Url};
+use crate::{Result, v1_0_0};
+
+/// Methods whose body Postman drops unless body pruning is disabled.
+const PRUNED_METHODS: [&str; 5] = ["GET", "COPY", "HEAD", "PURGE", "UNLOCK"];
+
+impl RequestClass {
+    /// Converts the request, pruning the body of `GET`-like methods. See the
+    /// [module documentation](crate::http).
+    pub fn to_http(&self) -> Result<Request<Vec<u8>>> {
+        self.to_http_with(None)
+    }
+
+    /// Converts the request with the protocol behavior of the item it belongs to.
+    pub fn to_http_with(
+        &self,
+        behavior: Option<&ProtocolProfileBehavior>,
+    ) -> Result<Request<Vec<u8>>> {
+        self.to_http_with_encoder(behavior, &Encoder::new())
+    }
+
+    /// Like [`to_http_with`](Self::to_http_with), encoding the body with `encoder`, for example
+    /// to read form files through a custom [`FileResolver`](crate::body::FileResolver).
+    pub fn to_http_with_encoder(
+        &self,
+        behavior: Option<&ProtocolProfileBehavior>,
+        encoder: &Encoder,
+    ) -> Result<Request<Vec<u8>>> {
+        let method = self
+            .method
+            .as_deref()
+            .map_or_else(|| "GET".to_owned(), str::to_ascii_uppercase);
+        let url = self
+            .url
+            .as_ref()
+            .map_or_else(|| Url::String(String::new()).to_url(), Url::to_url)?;
+
+        let prune = PRUNED_METHODS.contains(&method.as_str())
+            && !behavior
+                .and_then(|behavior| behavior.disable_body_pruning)
+                .unwrap_or(false);
+        let encoded = match self.body.as_ref() {
+            Some(body) if !prune => encoder.encode(body)?,
+            _ => None,
+        };
+        // A multipart body only parses with the boundary it was encoded with.
+        let multipart = encoded
+            .as_ref()
+            .and_then(|encoded| encoded.content_type.as_deref())
+            .is_some_and(|content_type| content_type.starts_with("multipart/"));
+
+        let mut builder = Request::builder()
+            .method(Method::from_bytes(method.as_bytes()).map_err(::http::Error::from)?)
+            .uri(url.as_str());
+        let mut has_content_type = false;
+        for (key, value) in self.header.iter().flat_map(HeaderUnion::enabled) {
+            if key.eq_ignore_ascii_case(CONTENT_TYPE.as_str()) {
+                if multipart {
+                    continue;
+                }
+                has_content_type = true;
+            }
+            builder = builder.header(key, value);
+        }
+
+        let bytes = match encoded {
+            Some(encoded) => {
+                if let Some(content_type) = encoded.content_type.filter(|_| !has_content_type) {
+                    builder = builder.header(CONTENT_TYPE, content_type);
+                }
+                encoded.bytes
+            }
+            None => Vec::new(),
+        };
+        Ok(builder.body(bytes)?)
+    }
+}
+
+impl Item {
+    /// Converts the item's request, honouring the item's
+    /// [`protocol_profile_behavior`](Item::protocol_profile_behavior). A request given as a
+    /// plain URL string becomes a `GET` without headers or body.
+    pub fn to_http(&self) -> Result<Request<Vec<u8>>> {
+        match &self.request {
+            RequestUnion::RequestClass(request) => {
+                request.to_http_with(self.protocol_profile_behavior.as_ref())
+            }
+            RequestUnion::String(url) => {
+                let url = Url::String(url.clone()).to_url()?;
+                Ok(Request::builder().uri(url.as_str()).body(Vec::new())?)
+            }
+        }
+    }
+}
+
+impl v1_0_0::Request {
+    /// Converts the request the same way as a v2.1.0 request, after reading its headers, data
+    /// and query parameters as [`convert`](crate::convert) does. Path variables are not
+    /// expanded; see [`expand_path_variables`](v1_0_0::Request::expand_path_variables).
+    pub fn to_http(&self) -> Result<Request<Vec<u8>>> {
+        let behavior =
+            self.protocol_profile_behavior
+                .as_ref()
+                .map(|behavior| ProtocolProfileBehavior {
+                    disable_body_pruning: behavior.disable_body_pruning,
+                });
+        crate::convert::v1_request(self).to_http_with(behavior.as_ref())
+    }
+}
@@ -6,6 +6,10 @@
 //! JSON serialization is available by default.
 //! Enable the crate feature `yaml` to also accept YAML input and serialize parsed
 //! collections with `to_yaml`.
+//! Enable the crate feature `http` to convert requests into `http::Request` values and run
+//! collections with `runner::Runner`.
+//! Enable the crate feature `scripts` to also run pre-request and test scripts in an embedded
+//! JavaScript engine.
 
 use std::{fs::File, io::Read, path::Path};
 
@@ -13,9 +17,27 @@ pub use errors::{Error, Result};
 use serde::{Deserialize, Deserializer, Serialize, de};
 use serde_json::{Map, Value};
 
+pub mod body;
+pub mod convert;
+pub mod curl;
+pub mod environment;
+pub mod globals;
+pub mod graphql;
+pub mod har;
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub mod http;
+pub mod model;
+pub mod openapi;
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub mod runner;
+pub mod swagger;
+mod tree;
 pub mod v1_0_0;
 pub mod v2_0_0;
 pub mod v2_1_0;
+pub mod variables;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct SchemaVersion {
@@ -57,6 +79,59 @@ pub mod errors {
         UnrecognizedSpecFileVersion { schema: String },
         #[error("unsupported Postman Collection file version: {version}")]
         UnsupportedSpecFileVersion { version: String },
+        #[error("conversion would lose information:\n{report}")]
+        LossyConversion {
+            report: crate::convert::ConversionReport,
+        },
+        #[error("{scheme} auth is missing the `{key}` attribute")]
+        MissingAuthAttribute {
+            scheme: &'static str,
+            key: &'static str,
+        },
+        #[error("{scheme} auth attribute `{key}` should be {expected}, found {found}")]
+        InvalidAuthAttribute {
+            scheme: &'static str,
+            key: &'static str,
+            expected: &'static str,
+            found: String,
+        },
+        #[error("could not read file `{src}`: {source}")]
+        File { src: String, source: std::io::Error },
+        #[error("URL `{url}` still contains variables: {}", variables.join(", "))]
+        UnresolvedUrl { url: String, variables: Vec<String> },
+        #[cfg(feature = "http")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+        #[error("HTTP error: {0}")]
+        Http(#[from] ::http::Error),
+        #[error("invalid URL `{url}`: {source}")]
+        InvalidUrl {
+            url: String,
+            source: url::ParseError,
+        },
+        #[cfg(feature = "http")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+        #[error("request failed: {0}")]
+        Backend(Box<dyn std::error::Error + Send + Sync>),
+        #[cfg(feature = "http")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+        #[error("no folder or request named `{name}`")]
+        UnknownFolder { name: String },
+        #[cfg(feature = "scripts")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "scripts")))]
+        #[error("script failed: {message}")]
+        Script { message: String },
+        #[error("invalid GraphQL at line {line}, column {column}: {message}")]
+        GraphQlSyntax {
+            line: usize,
+            column: usize,
+            message: String,
+        },
+        #[error("invalid curl command: {message}")]
+        InvalidCurl { message: String },
+        #[error("unsupported OpenAPI version `{version}`; expected 3.x")]
+        UnsupportedOpenApiVersion { version: String },
+        #[error("unsupported Swagger version `{version}`; expected 2.0")]
+        UnsupportedSwaggerVersion { version: String },
     }
 }
 
@@ -139,6 +214,75 @@ impl PostmanCollection {
             Self::V2_1_0(spec) => &spec.info.name,
         }
     }
+
+    /// Iterates over every request of the collection, depth first, with its folder path.
+    ///
+    /// See `Spec::items` in the version modules for the details of each version.
+    pub fn items(&self) -> impl Iterator<Item = CollectionItem<'_>> {
+        let items: Box<dyn Iterator<Item = CollectionItem<'_>>> = match self {
+            Self::V1_0_0(spec) => Box::new(spec.items().map(CollectionItem::V1_0_0)),
+            Self::V2_0_0(spec) => Box::new(spec.items().map(CollectionItem::V2_0_0)),
+            Self::V2_1_0(spec) => Box::new(spec.items().map(CollectionItem::V2_1_0)),
+        };
+        items
+    }
+
+    /// Like [`items`](Self::items), but hands out mutable requests so they can be edited in
+    /// place.
+    pub fn items_mut(&mut self) -> impl Iterator<Item = CollectionItemMut<'_>> {
+        let items: Box<dyn Iterator<Item = CollectionItemMut<'_>>> = match self {
+            Self::V1_0_0(spec) => Box::new(spec.items_mut().map(CollectionItemMut::V1_0_0)),
+            Self::V2_0_0(spec) => Box::new(spec.items_mut().map(CollectionItemMut::V2_0_0)),
+            Self::V2_1_0(spec) => Box::new(spec.items_mut().map(CollectionItemMut::V2_1_0)),
+        };
+        items
+    }
+}
+
+/// A request yielded by [`PostmanCollection::items`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum CollectionItem<'a> {
+    #[allow(non_camel_case_types)]
+    V1_0_0(v1_0_0::iter::ItemEntry<'a>),
+    #[allow(non_camel_case_types)]
+    V2_0_0(v2_0_0::iter::ItemEntry<'a>),
+    #[allow(non_camel_case_types)]
+    V2_1_0(v2_1_0::iter::ItemEntry<'a>),
+}
+
+impl CollectionItem<'_> {
+    /// Folder and request names joined with `/`, such as `Users/Admin/Create user`. A `/` or
+    /// `\` within a name is escaped with a backslash.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::V1_0_0(entry) => &entry.path,
+            Self::V2_0_0(entry) => &entry.path,
+            Self::V2_1_0(entry) => &entry.path,
+        }
+    }
+}
+
+/// A request yielded by [`PostmanCollection::items_mut`].
+#[derive(Debug, PartialEq)]
+pub enum CollectionItemMut<'a> {
+    #[allow(non_camel_case_types)]
+    V1_0_0(v1_0_0::iter::ItemEntryMut<'a>),
+    #[allow(non_camel_case_types)]
+    V2_0_0(v2_0_0::iter::ItemEntryMut<'a>),
+    #[allow(non_camel_case_types)]
+    V2_1_0(v2_1_0::iter::ItemEntryMut<'a>),
+}
+
+impl CollectionItemMut<'_> {
+    /// Folder and request names joined with `/`, such as `Users/Admin/Create user`. A `/` or
+    /// `\` within a name is escaped with a backslash.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::V1_0_0(entry) => &entry.path,
+            Self::V2_0_0(entry) => &entry.path,
+            Self::V2_1_0(entry) => &entry.path,
+        }
+    }
 }
 
 /// Deserialize a Postman Collection from a path
@@ -156,6 +300,11 @@ pub fn from_str(input: &str) -> Result<PostmanCollection> {
 
 /// Deserialize a Postman Collection from a byte slice
 pub fn from_slice(input: &[u8]) -> Result<PostmanCollection> {
+    PostmanCollection::from_value(value_from_slice(input)?)
+}
+
+/// Parses JSON, falling back to YAML when the `yaml` feature is enabled.
+pub(crate) fn value_from_slice(input: &[u8]) -> Result<Value> {
     #[cfg(feature = "yaml")]
     let value = match serde_json::from_slice::<Value>(input) {
         Ok(value) => value,
@@ -168,7 +317,7 @@ pub fn from_slice(input: &[u8]) -> Result<PostmanCollection> {
     #[cfg(not(feature = "yaml"))]
     let value = serde_json::from_slice::<Value>(input)?;
 
-    PostmanCollection::from_value(value)
+    Ok(value)
 }
 
 /// Deserialize a Postman Collection from type which implements Read
@@ -0,0 +1,569 @@
+//! A version-independent view of a Postman Collection.
+//!
+//! The version modules mirror the JSON schemas closely, which means code that only cares about
+//! "the requests in this collection" has to be written once per version. The types in this
+//! module normalize all supported versions into a single tree of folders and requests with typed
+//! auth, bodies, variables, events and saved examples.
+//!
+//! Any [`PostmanCollection`] converts into a [`Collection`]. v1.0.0 and v2.0.0 documents are
+//! first converted to v2.1.0 with the [`convert`](crate::convert) module. The [`From`]
+//! conversions drop the report of that step; [`Collection::from_collection`] returns it.
+//!
+//! ```
+//! use postman_collection::{from_str, model::Collection};
+//!
+//! let input = r#"{
+//!   "info": {
+//!     "name": "Example",
+//!     "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+//!   },
+//!   "item": [{ "name": "Ping", "request": "https://example.com/ping" }]
+//! }"#;
+//!
+//! let collection = Collection::from(from_str(input)?);
+//! assert_eq!(collection.requests().count(), 1);
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+
+use serde_json::Value;
+
+use crate::convert::{ConversionReport, Converted};
+use crate::{PostmanCollection, convert, v2_1_0};
+
+/// A collection with its folder tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Collection {
+    pub id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub auth: Option<Auth>,
+    pub variables: Vec<Variable>,
+    pub events: Vec<Event>,
+    pub items: Vec<Node>,
+}
+
+impl Collection {
+    /// Converts a collection of any version, together with the report of converting a v1.0.0
+    /// collection to v2.1.0 first. The report is empty for v2 collections.
+    pub fn from_collection(collection: &PostmanCollection) -> Converted<Collection> {
+        match collection {
+            PostmanCollection::V1_0_0(spec) => {
+                let converted = convert::v1_0_0_to_v2_1_0(spec);
+                Converted {
+                    spec: Collection::from(&converted.spec),
+                    report: converted.report,
+                }
+            }
+            collection => Converted {
+                spec: Collection::from(collection),
+                report: ConversionReport::default(),
+            },
+        }
+    }
+
+    /// All requests of the collection, depth first, in document order.
+    pub fn requests(&self) -> impl Iterator<Item = &Request> {
+        requests(&self.items)
+    }
+}
+
+/// An entry of the folder tree.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    Folder(Folder),
+    Request(Request),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Folder {
+    pub name: String,
+    pub description: Option<String>,
+    pub auth: Option<Auth>,
+    pub variables: Vec<Variable>,
+    pub events: Vec<Event>,
+    pub items: Vec<Node>,
+}
+
+impl Folder {
+    /// All requests below this folder, depth first, in document order.
+    pub fn requests(&self) -> impl Iterator<Item = &Request> {
+        requests(&self.items)
+    }
+}
+
+fn requests(nodes: &[Node]) -> Box<dyn Iterator<Item = &Request> + '_> {
+    Box::new(nodes.iter().flat_map(|node| match node {
+        Node::Folder(folder) => requests(&folder.items),
+        Node::Request(request) => Box::new(std::iter::once(request)),
+    }))
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Request {
+    pub id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    /// The HTTP method. Postman assumes `GET` when a collection leaves it out.
+    pub method: String,
+    pub url: Url,
+    pub headers: Vec<Header>,
+    /// The request body. A body that Postman marks as disabled is left out.
+    pub body: Option<Body>,
+    /// `None` means the request inherits the auth of its folders or collection.
+    pub auth: Option<Auth>,
+    pub variables: Vec<Variable>,
+    pub events: Vec<Event>,
+    /// Saved example responses.
+    pub examples: Vec<Example>,
+    /// Whether Postman sends the body even for methods that usually have none, such as `GET`.
+    pub disable_body_pruning: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Url {
+    /// The URL as written in the collection, including any `{{variables}}`.
+    pub raw: String,
+    pub query: Vec<Param>,
+    /// Values for `:name` path segments.
+    pub path_variables: Vec<Variable>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Header {
+    pub key: String,
+    pub value: String,
+    pub enabled: bool,
+    pub description: Option<String>,
+}
+
+/// A key/value pair of a query string or URL-encoded body.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Param {
+    pub key: String,
+    pub value: Option<String>,
+    pub enabled: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Body {
+    Raw {
+        content: String,
+        /// The editor language Postman recorded, such as `json` or `xml`.
+        language: Option<String>,
+    },
+    UrlEncoded(Vec<Param>),
+    FormData(Vec<FormField>),
+    File {
+        src: Option<String>,
+    },
+    GraphQl {
+        query: String,
+        variables: Option<Value>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormField {
+    pub key: String,
+    pub value: FormValue,
+    pub enabled: bool,
+    pub content_type: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormValue {
+    Text(String),
+    Files(Vec<String>),
+}
+
+/// A configured auth scheme and its parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Auth {
+    pub kind: AuthKind,
+    pub params: Vec<AuthParam>,
+}
+
+impl Auth {
+    /// Looks up a parameter such as `username` or `token`.
+    pub fn param(&self, key: &str) -> Option<&Value> {
+        self.params
+            .iter()
+            .find(|param| param.key == key)
+            .map(|param| &param.value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AuthKind {
+    NoAuth,
+    ApiKey,
+    AwsV4,
+    Basic,
+    Bearer,
+    Digest,
+    EdgeGrid,
+    Hawk,
+    Ntlm,
+    OAuth1,
+    OAuth2,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthParam {
+    pub key: String,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Variable {
+    pub key: String,
+    pub value: Option<Value>,
+    pub enabled: bool,
+    pub description: Option<String>,
+}
+
+/// A script that runs when `listen` fires, usually `prerequest` or `test`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Event {
+    pub listen: String,
+    pub script: String,
+    pub enabled: bool,
+}
+
+/// A saved example response.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Example {
+    pub name: Option<String>,
+    pub code: Option<i64>,
+    pub status: Option<String>,
+    pub headers: Vec<Header>,
+    pub body: Option<String>,
+    /// Response time in milliseconds, when recorded as a number.
+    pub response_time: Option<f64>,
+    pub original_request: Option<Box<Request>>,
+}
+
+impl From<PostmanCollection> for Collection {
+    fn from(collection: PostmanCollection) -> Self {
+        match collection {
+            PostmanCollection::V1_0_0(spec) => Self::from(&convert::v1_0_0_to_v2_1_0(&spec).spec),
+            PostmanCollection::V2_0_0(spec) => Self::from(&v2_1_0::Spec::from(spec)),
+            PostmanCollection::V2_1_0(spec) => Self::from(&spec),
+        }
+    }
+}
+
+impl From<&PostmanCollection> for Collection {
+    fn from(collection: &PostmanCollection) -> Self {
+        match collection {
+            PostmanCollection::V2_1_0(spec) => Self::from(spec),
+            collection => Self::from(collection.clone()),
+        }
+    }
+}
+
+impl From<&v2_1_0::Spec> for Collection {
+    fn from(spec: &v2_1_0::Spec) -> Self {
+        Collection {
+            id: spec.info.postman_id.clone(),
+            name: spec.info.name.clone(),
+            description: description(spec.info.description.as_ref()),
+            version: spec.info.version.as_ref().map(|version| match version {
+                v2_1_0::CollectionVersion::String(version) => version.clone(),
+                v2_1_0::CollectionVersion::CollectionVersionClass(version) => {
+                    let mut rendered =
+                        format!("{}.{}.{}", version.major, version.minor, version.patch);
+                    if let Some(identifier) = version.identifier.as_deref() {
+                        rendered.push('-');
+                        rendered.push_str(identifier);
+                    }
+                    rendered
+                }
+            }),
+            auth: spec.auth.as_ref().map(auth),
+            variables: variables(spec.variable.as_deref()),
+            events: events(spec.event.as_deref()),
+            items: spec.item.iter().map(node).collect(),
+        }
+    }
+}
+
+fn node(item: &v2_1_0::Items) -> Node {
+    match item {
+        v2_1_0::Items::ItemGroup(group) => Node::Folder(Folder {
+            name: group.name.clone().unwrap_or_default(),
+            description: description(group.description.as_ref()),
+            auth: group.auth.as_ref().map(auth),
+            variables: variables(group.variable.as_deref()),
+            events: events(group.event.as_deref()),
+            items: group.item.iter().map(node).collect(),
+        }),
+        v2_1_0::Items::Item(item) => {
+            let mut request = request(&item.request);
+            request.id = item.id.clone();
+            request.name = item.name.clone().unwrap_or_default();
+            request.description = description(item.description.as_ref()).or(request.description);
+            request.variables = variables(item.variable.as_deref());
+            request.events = events(item.event.as_deref());
+            request.examples = item.response.iter().flatten().map(example).collect();
+            request.disable_body_pruning = item
+                .protocol_profile_behavior
+                .as_ref()
+                .and_then(|behavior| behavior.disable_body_pruning)
+                .unwrap_or(false);
+            Node::Request(request)
+        }
+    }
+}
+
+fn request(request: &v2_1_0::RequestUnion) -> Request {
+    let request = match request {
+        v2_1_0::RequestUnion::String(url) => {
+            return Request {
+                method: "GET".to_owned(),
+                url: Url {
+                    raw: url.clone(),
+                    ..Url::default()
+                },
+                ..Request::default()
+            };
+        }
+        v2_1_0::RequestUnion::RequestClass(request) => request,
+    };
+
+    Request {
+        description: description(request.description.as_ref()),
+        method: request.method.clone().unwrap_or_else(|| "GET".to_owned()),
+        url: request.url.as_ref().map(url).unwrap_or_default(),
+        headers: match request.header.as_ref() {
+            Some(v2_1_0::HeaderUnion::HeaderArray(headers)) => headers.iter().map(header).collect(),
+            Some(v2_1_0::HeaderUnion::String(headers)) => parse_headers(headers),
+            None => Vec::new(),
+        },
+        body: request.body.as_ref().and_then(body),
+        auth: request.auth.as_ref().map(auth),
+        ..Request::default()
+    }
+}
+
+fn url(url: &v2_1_0::Url) -> Url {
+    match url {
+        v2_1_0::Url::String(raw) => Url {
+            raw: raw.clone(),
+            ..Url::default()
+        },
+        v2_1_0::Url::UrlClass(url) => Url {
+            raw: url.raw.clone().unwrap_or_else(|| url.to_string()),
+            query: url
+                .query
+                .iter()
+                .flatten()
+                .map(|param| Param {
+                    key: param.key.clone().unwrap_or_default(),
+                    value: param.value.clone(),
+                    enabled: !param.disabled.unwrap_or(false),
+                    description: description(param.description.as_ref()),
+                })
+                .collect(),
+            path_variables: variables(url.variable.as_deref()),
+        },
+    }
+}
+
+fn header(header: &v2_1_0::Header) -> Header {
+    Header {
+        key: header.key.clone(),
+        value: header.value.clone(),
+        enabled: !header.disabled.unwrap_or(false),
+        description: description(header.description.as_ref()),
+    }
+}
+
+/// Parses the `Key: Value` lines Postman accepts in place of a header list.
+fn parse_headers(headers: &str) -> Vec<Header> {
+    headers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| Header {
+            key: key.trim().to_owned(),
+            value: value.trim().to_owned(),
+            enabled: true,
+            description: None,
+        })
+        .collect()
+}
+
+fn body(body: &v2_1_0::Body) -> Option<Body> {
+    if body.disabled.unwrap_or(false) {
+        return None;
+    }
+
+    Some(match body.mode.as_ref()? {
+        v2_1_0::Mode::Raw => Body::Raw {
+            content: body.raw.clone().unwrap_or_default(),
+            language: body
+                .options
+                .as_ref()
+                .and_then(|options| options.raw.as_ref())
+                .and_then(|raw| raw.language.as_ref())
+                .map(|language| language.as_str().to_owned()),
+        },
+        v2_1_0::Mode::Urlencoded => Body::UrlEncoded(
+            body.urlencoded
+                .iter()
+                .flatten()
+                .map(|param| Param {
+                    key: param.key.clone(),
+                    value: param.value.clone(),
+                    enabled: !param.disabled.unwrap_or(false),
+                    description: description(param.description.as_ref()),
+                })
+                .collect(),
+        ),
+        v2_1_0::Mode::Formdata => Body::FormData(
+            body.formdata
+                .iter()
+                .flatten()
+                .map(|field| FormField {
+                    key: field.key.clone(),
+                    value: match (field.form_parameter_type.as_deref(), field.src.as_ref()) {
+                        (Some("file"), Some(v2_1_0::FormParameterSrcUnion::File(src))) => {
+                            FormValue::Files(vec![src.clone()])
+                        }
+                        (Some("file"), Some(v2_1_0::FormParameterSrcUnion::Files(sources))) => {
+                            FormValue::Files(sources.clone())
+                        }
+                        (Some("file"), None) => FormValue::Files(Vec::new()),
+                        _ => FormValue::Text(field.value.clone().unwrap_or_default()),
+                    },
+                    enabled: !field.disabled.unwrap_or(false),
+                    content_type: field.content_type.clone(),
+                    description: description(field.description.as_ref()),
+                })
+                .collect(),
+        ),
+        v2_1_0::Mode::File => Body::File {
+            src: body.file.as_ref().and_then(|file| file.src.clone()),
+        },
+        v2_1_0::Mode::Graphql => Body::GraphQl {
+            query: body
+                .graphql
+                .as_ref()
+                .and_then(|graphql| graphql.query.clone())
+                .unwrap_or_default(),
+            variables: body
+                .graphql
+                .as_ref()
+                .and_then(|graphql| graphql.variables.as_ref())
+                .map(|variables| match variables {
+                    v2_1_0::GraphQlVariables::String(text) => Value::String(text.clone()),
+                    v2_1_0::GraphQlVariables::Json(value) => value.clone(),
+                }),
+        },
+    })
+}
+
+fn auth(auth: &v2_1_0::Auth) -> Auth {
+    let (kind, attributes) = match auth.auth_type {
+        v2_1_0::AuthType::Noauth => (AuthKind::NoAuth, None),
+        v2_1_0::AuthType::Apikey => (AuthKind::ApiKey, auth.api_key.as_ref()),
+        v2_1_0::AuthType::Awsv4 => (AuthKind::AwsV4, auth.awsv4.as_ref()),
+        v2_1_0::AuthType::Basic => (AuthKind::Basic, auth.basic.as_ref()),
+        v2_1_0::AuthType::Bearer => (AuthKind::Bearer, auth.bearer.as_ref()),
+        v2_1_0::AuthType::Digest => (AuthKind::Digest, auth.digest.as_ref()),
+        v2_1_0::AuthType::Edgegrid => (AuthKind::EdgeGrid, auth.edgegrid.as_ref()),
+        v2_1_0::AuthType::Hawk => (AuthKind::Hawk, auth.hawk.as_ref()),
+        v2_1_0::AuthType::Ntlm => (AuthKind::Ntlm, auth.ntlm.as_ref()),
+        v2_1_0::AuthType::Oauth1 => (AuthKind::OAuth1, auth.oauth1.as_ref()),
+        v2_1_0::AuthType::Oauth2 => (AuthKind::OAuth2, auth.oauth2.as_ref()),
+    };
+
+    Auth {
+        kind,
+        params: attributes
+            .into_iter()
+            .flatten()
+            .map(|attribute| AuthParam {
+                key: attribute.key.clone(),
+                value: attribute.value.clone().unwrap_or(Value::Null),
+            })
+            .collect(),
+    }
+}
+
+fn variables(variables: Option<&[v2_1_0::Variable]>) -> Vec<Variable> {
+    variables
+        .unwrap_or_default()
+        .iter()
+        .map(|variable| Variable {
+            key: variable
+                .key
+                .clone()
+                .or_else(|| variable.id.clone())
+                .or_else(|| variable.name.clone())
+                .unwrap_or_default(),
+            value: variable.value.clone(),
+            enabled: !variable.disabled.unwrap_or(false),
+            description: description(variable.description.as_ref()),
+        })
+        .collect()
+}
+
+fn events(events: Option<&[v2_1_0::Event]>) -> Vec<Event> {
+    events
+        .unwrap_or_default()
+        .iter()
+        .map(|event| Event {
+            listen: event.listen.clone(),
+            script: match event
+                .script
+                .as_ref()
+                .and_then(|script| script.exec.as_ref())
+            {
+                Some(v2_1_0::Host::String(script)) => script.clone(),
+                Some(v2_1_0::Host::StringArray(lines)) => lines.join("\n"),
+                None => String::new(),
+            },
+            enabled: !event.disabled.unwrap_or(false),
+        })
+        .collect()
+}
+
+fn example(response: &v2_1_0::ResponseClass) -> Example {
+    Example {
+        name: response.name.clone(),
+        code: response.code,
+        status: response.status.clone(),
+        headers: match response.header.as_ref() {
+            // Header lines without a `:` are not headers and are skipped.
+            Some(v2_1_0::Headers::UnionArray(headers)) => headers
+                .iter()
+                .filter_map(|element| match element {
+                    v2_1_0::HeaderElement::Header(header) => Some(self::header(header)),
+                    v2_1_0::HeaderElement::String(line) => parse_headers(line).pop(),
+                })
+                .collect(),
+            Some(v2_1_0::Headers::String(headers)) => parse_headers(headers),
+            None => Vec::new(),
+        },
+        body: response.body.clone(),
+        response_time: match response.response_time.as_ref() {
+            Some(v2_1_0::ResponseTime::Integer(time)) => Some(*time as f64),
+            Some(v2_1_0::ResponseTime::Double(time)) => Some(*time),
+            Some(v2_1_0::ResponseTime::String(time)) => time.parse().ok(),
+            None => None,
+        },
+        original_request: response
+            .original_request
+            .as_ref()
+            .map(|original| Box::new(request(original))),
+    }
+}
+
+fn description(description: Option<&v2_1_0::DescriptionUnion>) -> Option<String> {
+    description?.text().map(str::to_owned)
+}
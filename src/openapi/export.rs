@@ -0,0 +1,1057 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{Map, Value, json};
+
+use super::{
+    Components, IGNORED_HEADERS, Info, MediaType, OAuthFlow, OAuthFlows, OpenApi, Operation,
+    Parameter, ParameterLocation, PathItem, ReferenceOr, RequestBody, Response,
+    SecurityRequirement, SecurityScheme, Server, ServerVariable, Tag,
+};
+use crate::body::graphql_envelope;
+use crate::v2_1_0::auth::{ApiKeyLocation, AuthSource, OAuth2Auth, TypedAuth, effective_auth};
+use crate::v2_1_0::url::path_segments;
+use crate::v2_1_0::{
+    Auth, Body, CollectionVersion, DescriptionUnion, HeaderUnion, Headers, Item, ItemGroup, Mode,
+    RawLanguage, RequestClass, RequestUnion, ResponseClass, Spec, Url, UrlClass,
+};
+use crate::variables::placeholders;
+
+/// The OpenAPI version of exported documents.
+const VERSION: &str = "3.1.0";
+
+impl Spec {
+    /// Describes the collection as an OpenAPI 3.1 document:
+    ///
+    /// - every request becomes an operation for its method and path, tagged with the name of
+    ///   its innermost folder and named after the item. The first request wins when several
+    ///   share a method and path; methods OpenAPI does not describe are left out;
+    /// - the protocol, host and port become servers, with `{{variables}}` as server variables
+    ///   that default to the collection variables. Operations on another server than the first
+    ///   list it as their own;
+    /// - `:name` path variables and `{{variables}}` in the path become `{name}` path
+    ///   parameters. Query parameters, enabled headers and cookies become parameters too;
+    /// - the body becomes the request body, and saved responses become responses by status
+    ///   code with their bodies as examples. Schemas are inferred from the examples with
+    ///   [`infer_schema`];
+    /// - basic, bearer, digest, API key and OAuth 2.0 auth become security schemes. Requests
+    ///   whose effective auth is not the collection's carry requirements of their own.
+    ///
+    /// Folder, request and collection descriptions are carried over.
+    pub fn to_openapi(&self) -> OpenApi {
+        let mut exporter = Exporter {
+            spec: self,
+            variables: self
+                .variable
+                .iter()
+                .flatten()
+                .filter_map(|variable| {
+                    let key = variable.key.as_deref().or(variable.id.as_deref())?;
+                    Some((key, value_text(variable.value.as_ref())))
+                })
+                .collect(),
+            servers: Vec::new(),
+            schemes: BTreeMap::new(),
+            security: None,
+            tags: Vec::new(),
+            operation_ids: BTreeSet::new(),
+        };
+        exporter.security = self
+            .auth
+            .as_ref()
+            .and_then(|auth| exporter.requirements(auth))
+            .filter(|requirements| !requirements.is_empty());
+
+        let mut paths: BTreeMap<String, PathItem> = BTreeMap::new();
+        for entry in self.items() {
+            let request = match &entry.item.request {
+                RequestUnion::RequestClass(request) => Cow::Borrowed(request),
+                RequestUnion::String(url) => Cow::Owned(RequestClass {
+                    url: Some(Url::String(url.clone())),
+                    ..RequestClass::default()
+                }),
+            };
+            let method = request
+                .method
+                .as_deref()
+                .unwrap_or("GET")
+                .to_ascii_uppercase();
+            let url = request.url.as_ref().map(Url::to_class).unwrap_or_default();
+            let (path, path_parameters) = exporter.path(&url);
+
+            let Some(slot) = paths
+                .entry(path.clone())
+                .or_default()
+                .operation_mut(&method)
+            else {
+                continue;
+            };
+            if slot.is_some() {
+                continue;
+            }
+            *slot = Some(exporter.operation(
+                &entry.ancestors,
+                entry.item,
+                &request,
+                &url,
+                path_parameters,
+                &format!("{method} {path}"),
+            ));
+        }
+        paths.retain(|_, path_item| path_item.operations().next().is_some());
+
+        OpenApi {
+            openapi: VERSION.to_owned(),
+            info: Info {
+                title: self.info.name.clone(),
+                description: text(self.info.description.as_ref()),
+                version: version(self.info.version.as_ref()),
+                ..Info::default()
+            },
+            servers: exporter.servers,
+            paths,
+            components: (!exporter.schemes.is_empty()).then(|| Components {
+                security_schemes: exporter
+                    .schemes
+                    .into_iter()
+                    .map(|(name, scheme)| (name, ReferenceOr::Item(scheme)))
+                    .collect(),
+                ..Components::default()
+            }),
+            security: exporter.security,
+            tags: exporter.tags,
+            extensions: Map::new(),
+        }
+    }
+}
+
+/// Infers a [JSON Schema](https://json-schema.org/) from an example value, as OpenAPI 3.1
+/// uses it:
+///
+/// - objects list their members as `properties`, all of them `required`;
+/// - arrays describe their `items` with one schema that fits every element;
+/// - whole numbers are `integer`s, other numbers `number`s;
+/// - strings get a `format` when they look like a `date`, `date-time`, `uuid`, `email` or
+///   `uri`.
+///
+/// Where elements differ, `integer` and `number` widen to `number`, properties missing from
+/// some objects are no longer required, `null` makes a type nullable and other mixes become
+/// `anyOf`.
+///
+/// ```
+/// use postman_collection::openapi::infer_schema;
+/// use serde_json::json;
+///
+/// let schema = infer_schema(&json!({ "id": 1, "tags": ["a", null] }));
+/// assert_eq!(
+///     schema,
+///     json!({
+///         "type": "object",
+///         "properties": {
+///             "id": { "type": "integer" },
+///             "tags": { "type": "array", "items": { "type": ["string", "null"] } }
+///         },
+///         "required": ["id", "tags"]
+///     })
+/// );
+/// ```
+pub fn infer_schema(example: &Value) -> Value {
+    match example {
+        Value::Null => json!({ "type": "null" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(text) => match string_format(text) {
+            Some(format) => json!({ "type": "string", "format": format }),
+            None => json!({ "type": "string" }),
+        },
+        Value::Array(elements) => json!({
+            "type": "array",
+            "items": elements
+                .iter()
+                .map(infer_schema)
+                .reduce(merge_schemas)
+                .unwrap_or_else(|| json!({})),
+        }),
+        Value::Object(members) => {
+            let properties: Map<String, Value> = members
+                .iter()
+                .map(|(name, member)| (name.clone(), infer_schema(member)))
+                .collect();
+            let mut schema = json!({ "type": "object", "properties": properties });
+            if !members.is_empty() {
+                schema["required"] = members.keys().cloned().collect();
+            }
+            schema
+        }
+    }
+}
+
+struct Exporter<'a> {
+    spec: &'a Spec,
+    /// Collection variable values, for server variable defaults and path parameter examples.
+    variables: BTreeMap<&'a str, String>,
+    servers: Vec<Server>,
+    schemes: BTreeMap<String, SecurityScheme>,
+    /// The requirements of the collection's auth.
+    security: Option<Vec<SecurityRequirement>>,
+    tags: Vec<Tag>,
+    operation_ids: BTreeSet<String>,
+}
+
+impl Exporter<'_> {
+    fn operation(
+        &mut self,
+        ancestors: &[&ItemGroup],
+        item: &Item,
+        request: &RequestClass,
+        url: &UrlClass,
+        path_parameters: Vec<Parameter>,
+        fallback_name: &str,
+    ) -> Operation {
+        let mut parameters = Vec::new();
+        for parameter in path_parameters {
+            add_parameter(&mut parameters, parameter);
+        }
+        for query in url.query.iter().flatten() {
+            let Some(name) = query.key.as_deref().filter(|name| !name.is_empty()) else {
+                continue;
+            };
+            add_parameter(
+                &mut parameters,
+                parameter(
+                    name,
+                    ParameterLocation::Query,
+                    query.value.as_deref(),
+                    query.description.as_ref(),
+                ),
+            );
+        }
+        let headers = headers(request.header.as_ref());
+        for &(name, value, description) in &headers {
+            if name.eq_ignore_ascii_case("cookie") {
+                for cookie in value.split(';').filter(|cookie| !cookie.trim().is_empty()) {
+                    let (name, value) = cookie.split_once('=').unwrap_or((cookie, ""));
+                    add_parameter(
+                        &mut parameters,
+                        parameter(
+                            name.trim(),
+                            ParameterLocation::Cookie,
+                            Some(value.trim()),
+                            None,
+                        ),
+                    );
+                }
+            } else if !IGNORED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                add_parameter(
+                    &mut parameters,
+                    parameter(name, ParameterLocation::Header, Some(value), description),
+                );
+            }
+        }
+
+        let content_type = headers
+            .iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value, _)| essence(value));
+        let request_body = request
+            .body
+            .as_ref()
+            .filter(|body| !body.disabled.unwrap_or(false))
+            .and_then(|body| request_body(body, content_type));
+
+        let mut responses: BTreeMap<String, Vec<&ResponseClass>> = BTreeMap::new();
+        for example in item.response.iter().flatten() {
+            let code = example
+                .code
+                .map_or_else(|| "default".to_owned(), |code| code.to_string());
+            responses.entry(code).or_default().push(example);
+        }
+        let mut responses: BTreeMap<String, ReferenceOr<Response>> = responses
+            .into_iter()
+            .map(|(code, examples)| (code, ReferenceOr::Item(response(&examples))))
+            .collect();
+        if responses.is_empty() {
+            responses.insert(
+                "200".to_owned(),
+                ReferenceOr::Item(Response {
+                    description: "Successful response".to_owned(),
+                    ..Response::default()
+                }),
+            );
+        }
+
+        let tag = ancestors.last().and_then(|folder| self.tag(folder));
+        let name = item.name.as_deref().unwrap_or(fallback_name);
+        let server = self.server(url);
+        let operation_security = match effective_auth(self.spec, ancestors, item) {
+            Some(effective) if effective.source != AuthSource::Collection => {
+                self.requirements(effective.auth)
+            }
+            _ => None,
+        };
+
+        Operation {
+            tags: tag.into_iter().collect(),
+            summary: item.name.clone(),
+            description: text(request.description.as_ref())
+                .or_else(|| text(item.description.as_ref())),
+            operation_id: Some(self.operation_id(name)),
+            parameters: parameters.into_iter().map(ReferenceOr::Item).collect(),
+            request_body: request_body.map(ReferenceOr::Item),
+            responses,
+            security: operation_security.filter(|requirements| {
+                Some(requirements) != self.security.as_ref()
+                    && !(requirements.is_empty() && self.security.is_none())
+            }),
+            servers: server
+                .filter(|server| self.servers.first() != Some(server))
+                .into_iter()
+                .collect(),
+            ..Operation::default()
+        }
+    }
+
+    /// The OpenAPI path of a URL and the parameters of its templated segments.
+    fn path(&self, url: &UrlClass) -> (String, Vec<Parameter>) {
+        let mut parameters = Vec::new();
+        let mut segments = Vec::new();
+        for segment in path_segments(url.path.as_ref()) {
+            if let Some(name) = segment.strip_prefix(':').filter(|name| !name.is_empty()) {
+                let variable = url.variable.iter().flatten().find(|variable| {
+                    variable.key.as_deref().or(variable.id.as_deref()) == Some(name)
+                });
+                let value = variable.map(|variable| value_text(variable.value.as_ref()));
+                parameters.push(parameter(
+                    name,
+                    ParameterLocation::Path,
+                    value.as_deref(),
+                    variable.and_then(|variable| variable.description.as_ref()),
+                ));
+                segments.push(format!("{{{name}}}"));
+                continue;
+            }
+
+            let mut rendered = segment.to_owned();
+            for name in placeholders(segment) {
+                rendered = rendered.replace(&format!("{{{{{name}}}}}"), &format!("{{{name}}}"));
+                parameters.push(parameter(
+                    name,
+                    ParameterLocation::Path,
+                    self.variables.get(name).map(String::as_str),
+                    None,
+                ));
+            }
+            segments.push(rendered);
+        }
+        (format!("/{}", segments.join("/")), parameters)
+    }
+
+    /// The server of a URL's protocol, host and port, added to the document's servers.
+    fn server(&mut self, url: &UrlClass) -> Option<Server> {
+        let origin = UrlClass {
+            protocol: url.protocol.clone(),
+            host: url.host.clone(),
+            port: url.port.clone(),
+            ..UrlClass::default()
+        }
+        .to_string();
+        if origin.is_empty() {
+            return None;
+        }
+
+        let mut server = Server {
+            url: origin.clone(),
+            ..Server::default()
+        };
+        for name in placeholders(&origin) {
+            server.url = server
+                .url
+                .replace(&format!("{{{{{name}}}}}"), &format!("{{{name}}}"));
+            server.variables.insert(
+                name.to_owned(),
+                ServerVariable {
+                    default: self.variables.get(name).cloned().unwrap_or_default(),
+                    ..ServerVariable::default()
+                },
+            );
+        }
+        if !self.servers.contains(&server) {
+            self.servers.push(server.clone());
+        }
+        Some(server)
+    }
+
+    /// The tag of a folder, added to the document's tags with the folder's description.
+    fn tag(&mut self, folder: &ItemGroup) -> Option<String> {
+        let name = folder.name.as_deref()?;
+        if !self.tags.iter().any(|tag| tag.name == name) {
+            self.tags.push(Tag {
+                name: name.to_owned(),
+                description: text(folder.description.as_ref()),
+            });
+        }
+        Some(name.to_owned())
+    }
+
+    /// `name` in lower camel case, numbered when another operation already has the ID.
+    fn operation_id(&mut self, name: &str) -> String {
+        let base = camel_case(name);
+        let base = if base.is_empty() {
+            "operation".to_owned()
+        } else {
+            base
+        };
+        let mut id = base.clone();
+        let mut number = 1;
+        while self.operation_ids.contains(&id) {
+            number += 1;
+            id = format!("{base}_{number}");
+        }
+        self.operation_ids.insert(id.clone());
+        id
+    }
+
+    /// The security requirements of `auth`, registering its scheme. `noauth` gives an empty
+    /// list, and schemes OpenAPI cannot describe give `None`.
+    fn requirements(&mut self, auth: &Auth) -> Option<Vec<SecurityRequirement>> {
+        let (name, scheme, scopes) = match auth.typed().ok()? {
+            TypedAuth::NoAuth => return Some(Vec::new()),
+            TypedAuth::Basic(_) => ("basicAuth", http("basic"), Vec::new()),
+            TypedAuth::Bearer(_) => ("bearerAuth", http("bearer"), Vec::new()),
+            TypedAuth::Digest(_) => ("digestAuth", http("digest"), Vec::new()),
+            TypedAuth::ApiKey(api_key) => (
+                "apiKeyAuth",
+                SecurityScheme::ApiKey {
+                    name: api_key.key,
+                    location: match api_key.location {
+                        ApiKeyLocation::Header => "header",
+                        ApiKeyLocation::Query => "query",
+                    }
+                    .to_owned(),
+                    description: None,
+                },
+                Vec::new(),
+            ),
+            TypedAuth::OAuth2(oauth2) => {
+                let scopes: Vec<String> = oauth2
+                    .scope
+                    .iter()
+                    .flat_map(|scope| scope.split_whitespace())
+                    .map(str::to_owned)
+                    .collect();
+                ("oauth2", oauth2_scheme(&oauth2, &scopes), scopes)
+            }
+            _ => return None,
+        };
+
+        // Schemes that differ but would share a name, such as API keys in two headers, are
+        // numbered.
+        let mut key = name.to_owned();
+        let mut number = 1;
+        loop {
+            match self.schemes.get(&key) {
+                Some(existing) if *existing == scheme => break,
+                Some(_) => {
+                    number += 1;
+                    key = format!("{name}_{number}");
+                }
+                None => {
+                    self.schemes.insert(key.clone(), scheme);
+                    break;
+                }
+            }
+        }
+        Some(vec![BTreeMap::from([(key, scopes)])])
+    }
+}
+
+fn http(scheme: &str) -> SecurityScheme {
+    SecurityScheme::Http {
+        scheme: scheme.to_owned(),
+        bearer_format: None,
+        description: None,
+    }
+}
+
+/// An OAuth 2.0 scheme with the one flow of Postman's grant type.
+fn oauth2_scheme(oauth2: &OAuth2Auth, scopes: &[String]) -> SecurityScheme {
+    let grant_type = oauth2.grant_type.as_deref().unwrap_or("authorization_code");
+    let flow = OAuthFlow {
+        authorization_url: matches!(
+            grant_type,
+            "implicit" | "authorization_code" | "authorization_code_with_pkce"
+        )
+        .then(|| oauth2.auth_url.clone())
+        .flatten(),
+        token_url: (grant_type != "implicit")
+            .then(|| oauth2.access_token_url.clone())
+            .flatten(),
+        refresh_url: None,
+        scopes: scopes
+            .iter()
+            .map(|scope| (scope.clone(), String::new()))
+            .collect(),
+    };
+    let mut flows = OAuthFlows::default();
+    match grant_type {
+        "client_credentials" => flows.client_credentials = Some(flow),
+        "password_credentials" => flows.password = Some(flow),
+        "implicit" => flows.implicit = Some(flow),
+        _ => flows.authorization_code = Some(flow),
+    }
+    SecurityScheme::OAuth2 {
+        flows: Box::new(flows),
+        description: None,
+    }
+}
+
+/// The request body of a Postman body, `None` for empty bodies.
+fn request_body(body: &Body, content_type: Option<String>) -> Option<RequestBody> {
+    let (media_type, media) = match body.mode.as_ref()? {
+        Mode::Raw => {
+            let raw = body.raw.as_deref().filter(|raw| !raw.trim().is_empty())?;
+            let media_type = content_type.unwrap_or_else(|| {
+                body.raw_language()
+                    .map_or("text/plain", RawLanguage::content_type)
+                    .to_owned()
+            });
+            let example = text_example(&media_type, raw);
+            (media_type, media(example))
+        }
+        Mode::Urlencoded => (
+            "application/x-www-form-urlencoded".to_owned(),
+            form(
+                body.urlencoded
+                    .iter()
+                    .flatten()
+                    .filter(|parameter| !parameter.disabled.unwrap_or(false))
+                    .map(|parameter| {
+                        let value = parameter.value.as_deref().unwrap_or_default();
+                        (parameter.key.as_str(), Some(scalar(value)))
+                    }),
+            ),
+        ),
+        Mode::Formdata => (
+            "multipart/form-data".to_owned(),
+            form(
+                body.formdata
+                    .iter()
+                    .flatten()
+                    .filter(|parameter| !parameter.disabled.unwrap_or(false))
+                    .map(|parameter| {
+                        let value = (parameter.form_parameter_type.as_deref() != Some("file"))
+                            .then(|| scalar(parameter.value.as_deref().unwrap_or_default()));
+                        (parameter.key.as_str(), value)
+                    }),
+            ),
+        ),
+        Mode::File => (
+            content_type.unwrap_or_else(|| "application/octet-stream".to_owned()),
+            MediaType {
+                schema: Some(json!({ "type": "string", "format": "binary" })),
+                ..MediaType::default()
+            },
+        ),
+        Mode::Graphql => {
+            let envelope = graphql_envelope(body.graphql.as_ref()).unwrap_or_else(|_| {
+                let query = body
+                    .graphql
+                    .as_ref()
+                    .and_then(|graphql| graphql.query.clone());
+                json!({ "query": query.unwrap_or_default() })
+            });
+            ("application/json".to_owned(), media(envelope))
+        }
+    };
+    Some(RequestBody {
+        content: BTreeMap::from([(media_type, media)]),
+        ..RequestBody::default()
+    })
+}
+
+/// An object media type for form fields. Fields without a value are files.
+fn form<'a>(fields: impl Iterator<Item = (&'a str, Option<Value>)>) -> MediaType {
+    let mut properties = Map::new();
+    let mut example = Map::new();
+    for (name, value) in fields {
+        let schema = match &value {
+            Some(value) => infer_schema(value),
+            None => json!({ "type": "string", "format": "binary" }),
+        };
+        properties.insert(name.to_owned(), schema);
+        if let Some(value) = value {
+            example.insert(name.to_owned(), value);
+        }
+    }
+    MediaType {
+        schema: Some(json!({ "type": "object", "properties": properties })),
+        example: (!example.is_empty()).then_some(Value::Object(example)),
+        ..MediaType::default()
+    }
+}
+
+/// A response for the saved responses with one status code. The first of them gives the
+/// description and headers; every body becomes an example of its media type.
+fn response(examples: &[&ResponseClass]) -> Response {
+    let first = examples[0];
+    let headers = first
+        .header
+        .iter()
+        .flat_map(Headers::enabled)
+        .filter(|(name, _)| !name.eq_ignore_ascii_case("content-type"))
+        .map(|(name, value)| {
+            let header = json!({ "schema": { "type": "string" }, "example": value });
+            (name.to_owned(), header)
+        })
+        .collect();
+
+    let mut bodies: BTreeMap<String, Vec<(String, Value)>> = BTreeMap::new();
+    for (index, example) in examples.iter().enumerate() {
+        let Some(body) = example
+            .body
+            .as_deref()
+            .filter(|body| !body.trim().is_empty())
+        else {
+            continue;
+        };
+        let media_type = example
+            .header
+            .iter()
+            .flat_map(Headers::enabled)
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| essence(value))
+            .unwrap_or_else(|| {
+                match serde_json::from_str::<Value>(body) {
+                    Ok(_) => "application/json",
+                    Err(_) => "text/plain",
+                }
+                .to_owned()
+            });
+        let name = example
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("example{}", index + 1));
+        let value = text_example(&media_type, body);
+        bodies.entry(media_type).or_default().push((name, value));
+    }
+
+    let content = bodies
+        .into_iter()
+        .map(|(media_type, mut values)| {
+            let schema = values
+                .iter()
+                .map(|(_, value)| infer_schema(value))
+                .reduce(merge_schemas);
+            let media = if values.len() == 1 {
+                MediaType {
+                    schema,
+                    example: values.pop().map(|(_, value)| value),
+                    ..MediaType::default()
+                }
+            } else {
+                let mut examples = BTreeMap::new();
+                for (name, value) in values {
+                    let mut key = name.clone();
+                    let mut number = 1;
+                    while examples.contains_key(&key) {
+                        number += 1;
+                        key = format!("{name}_{number}");
+                    }
+                    examples.insert(key, json!({ "value": value }));
+                }
+                MediaType {
+                    schema,
+                    examples,
+                    ..MediaType::default()
+                }
+            };
+            (media_type, media)
+        })
+        .collect();
+
+    Response {
+        description: first
+            .name
+            .clone()
+            .or_else(|| first.status.clone())
+            .unwrap_or_else(|| "Response".to_owned()),
+        headers,
+        content,
+        ..Response::default()
+    }
+}
+
+/// A media type with an example and the schema inferred from it.
+fn media(example: Value) -> MediaType {
+    MediaType {
+        schema: Some(infer_schema(&example)),
+        example: Some(example),
+        ..MediaType::default()
+    }
+}
+
+/// Body text as an example: parsed for JSON media types, a string otherwise.
+fn text_example(media_type: &str, text: &str) -> Value {
+    if media_type.to_ascii_lowercase().contains("json") {
+        if let Ok(value) = serde_json::from_str(text) {
+            return value;
+        }
+    }
+    Value::String(text.to_owned())
+}
+
+/// A parameter with an example taken from its Postman value.
+fn parameter(
+    name: &str,
+    location: ParameterLocation,
+    value: Option<&str>,
+    description: Option<&DescriptionUnion>,
+) -> Parameter {
+    let example = value.filter(|value| !value.is_empty()).map(scalar);
+    Parameter {
+        name: name.to_owned(),
+        location,
+        description: text(description),
+        required: (location == ParameterLocation::Path).then_some(true),
+        schema: Some(
+            example
+                .as_ref()
+                .map_or_else(|| json!({ "type": "string" }), infer_schema),
+        ),
+        example,
+        ..Parameter::default()
+    }
+}
+
+/// Adds `parameter` unless one with the same name and location is already there.
+fn add_parameter(parameters: &mut Vec<Parameter>, parameter: Parameter) {
+    if !parameters
+        .iter()
+        .any(|existing| existing.name == parameter.name && existing.location == parameter.location)
+    {
+        parameters.push(parameter);
+    }
+}
+
+/// A string value as the number or boolean it spells exactly, or as a string.
+fn scalar(value: &str) -> Value {
+    if let Ok(parsed @ (Value::Number(_) | Value::Bool(_))) = serde_json::from_str(value) {
+        let canonical = parsed.to_string();
+        if canonical == value {
+            return parsed;
+        }
+    }
+    Value::String(value.to_owned())
+}
+
+/// Enabled request headers, with their descriptions.
+fn headers(header: Option<&HeaderUnion>) -> Vec<(&str, &str, Option<&DescriptionUnion>)> {
+    match header {
+        Some(HeaderUnion::HeaderArray(headers)) => headers
+            .iter()
+            .filter(|header| !header.disabled.unwrap_or(false))
+            .map(|header| {
+                (
+                    header.key.as_str(),
+                    header.value.as_str(),
+                    header.description.as_ref(),
+                )
+            })
+            .collect(),
+        Some(header) => header
+            .enabled()
+            .into_iter()
+            .map(|(name, value)| (name, value, None))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// A media type without its parameters, such as `application/json` for
+/// `application/json; charset=utf-8`.
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_owned()
+}
+
+fn text(description: Option<&DescriptionUnion>) -> Option<String> {
+    description?.text().map(str::to_owned)
+}
+
+fn value_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// The collection version, such as `1.2.0` or `2.0.0-beta`, or `1.0.0` without one.
+fn version(version: Option<&CollectionVersion>) -> String {
+    match version {
+        Some(CollectionVersion::String(version)) => version.clone(),
+        Some(CollectionVersion::CollectionVersionClass(version)) => {
+            let mut rendered = format!("{}.{}.{}", version.major, version.minor, version.patch);
+            if let Some(identifier) = version.identifier.as_deref() {
+                rendered.push('-');
+                rendered.push_str(identifier);
+            }
+            rendered
+        }
+        None => "1.0.0".to_owned(),
+    }
+}
+
+/// `name` in lower camel case, such as `getAUser` for `Get a user`.
+fn camel_case(name: &str) -> String {
+    let mut camel = String::new();
+    for word in name
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let mut chars = word.chars();
+        let Some(first) = chars.next() else {
+            continue;
+        };
+        if camel.is_empty() {
+            if word.chars().all(|ch| !ch.is_lowercase()) {
+                camel.push_str(&word.to_lowercase());
+            } else {
+                camel.extend(first.to_lowercase());
+                camel.push_str(chars.as_str());
+            }
+        } else {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+/// The `format` of a string that looks like a date, date-time, UUID, email address or URI.
+fn string_format(text: &str) -> Option<&'static str> {
+    if !text.is_ascii() || text.contains(char::is_whitespace) {
+        return None;
+    }
+    let digits = |text: &str| !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit());
+    let date = |text: &str| {
+        text.len() == 10
+            && digits(&text[..4])
+            && &text[4..5] == "-"
+            && digits(&text[5..7])
+            && &text[7..8] == "-"
+            && digits(&text[8..])
+    };
+    let time = |text: &str| {
+        text.len() == 8
+            && digits(&text[..2])
+            && &text[2..3] == ":"
+            && digits(&text[3..5])
+            && &text[5..6] == ":"
+            && digits(&text[6..])
+    };
+    let offset = |text: &str| {
+        // Fractional seconds, then `Z` or `+hh:mm`.
+        let text = match text.strip_prefix('.') {
+            Some(fraction) => fraction.trim_start_matches(|ch: char| ch.is_ascii_digit()),
+            None => text,
+        };
+        text.eq_ignore_ascii_case("z")
+            || (text.len() == 6
+                && (text.starts_with('+') || text.starts_with('-'))
+                && time(&format!("{}:00", &text[1..])))
+    };
+    let uuid = |text: &str| {
+        text.len() == 36
+            && text.bytes().enumerate().all(|(index, byte)| match index {
+                8 | 13 | 18 | 23 => byte == b'-',
+                _ => byte.is_ascii_hexdigit(),
+            })
+    };
+    let email = |text: &str| match text.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        }
+        None => false,
+    };
+
+    if date(text) {
+        Some("date")
+    } else if text.len() > 19
+        && date(&text[..10])
+        && text[10..11].eq_ignore_ascii_case("t")
+        && time(&text[11..19])
+        && offset(&text[19..])
+    {
+        Some("date-time")
+    } else if uuid(text) {
+        Some("uuid")
+    } else if text.starts_with("http://") || text.starts_with("https://") {
+        Some("uri")
+    } else if email(text) {
+        Some("email")
+    } else {
+        None
+    }
+}
+
+/// A schema that fits everything either schema fits. See [`infer_schema`].
+fn merge_schemas(first: Value, second: Value) -> Value {
+    if first == second {
+        return first;
+    }
+    let (first, first_nullable) = without_null(first);
+    let (second, second_nullable) = without_null(second);
+    let kind = |schema: &Value| {
+        schema
+            .get("type")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+    };
+
+    let merged = if first == second || second.as_object().is_some_and(Map::is_empty) {
+        first
+    } else if first.as_object().is_some_and(Map::is_empty) {
+        second
+    } else {
+        match (kind(&first).as_deref(), kind(&second).as_deref()) {
+            (Some("integer" | "number"), Some("integer" | "number")) => {
+                json!({ "type": "number" })
+            }
+            // Strings with different formats.
+            (Some("string"), Some("string")) => json!({ "type": "string" }),
+            (Some("array"), Some("array")) => json!({
+                "type": "array",
+                "items": merge_schemas(
+                    first.get("items").cloned().unwrap_or_else(|| json!({})),
+                    second.get("items").cloned().unwrap_or_else(|| json!({})),
+                ),
+            }),
+            (Some("object"), Some("object")) => merge_objects(&first, &second),
+            _ => any_of(first, second),
+        }
+    };
+    if first_nullable || second_nullable {
+        nullable(merged)
+    } else {
+        merged
+    }
+}
+
+/// Object schemas with the properties of both, requiring those both require.
+fn merge_objects(first: &Value, second: &Value) -> Value {
+    let properties = |schema: &Value| {
+        schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let required = |schema: &Value| {
+        schema
+            .get("required")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let mut merged = properties(first);
+    for (name, schema) in properties(second) {
+        let schema = match merged.remove(&name) {
+            Some(existing) => merge_schemas(existing, schema),
+            None => schema,
+        };
+        merged.insert(name, schema);
+    }
+    let second_required = required(second);
+    let required: Vec<Value> = required(first)
+        .into_iter()
+        .filter(|name| second_required.contains(name))
+        .collect();
+
+    let mut schema = json!({ "type": "object", "properties": merged });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// `anyOf` both schemas, merging members of the same type.
+fn any_of(first: Value, second: Value) -> Value {
+    let mut members: Vec<Value> = Vec::new();
+    for schema in [first, second] {
+        let candidates = match schema.get("anyOf").and_then(Value::as_array) {
+            Some(candidates) => candidates.clone(),
+            None => vec![schema],
+        };
+        for candidate in candidates {
+            let same_type = members.iter().position(|member| {
+                member.get("type").is_some_and(Value::is_string)
+                    && member.get("type") == candidate.get("type")
+            });
+            match same_type {
+                Some(index) => {
+                    let member = members.remove(index);
+                    members.insert(index, merge_schemas(member, candidate));
+                }
+                None if !members.contains(&candidate) => members.push(candidate),
+                None => {}
+            }
+        }
+    }
+    json!({ "anyOf": members })
+}
+
+/// The schema without `null`, and whether it allowed `null`. A `null` schema becomes `{}`.
+fn without_null(mut schema: Value) -> (Value, bool) {
+    let null = json!({ "type": "null" });
+    if schema == null {
+        return (json!({}), true);
+    }
+    if let Some(Value::Array(kinds)) = schema.get("type") {
+        let kinds: Vec<Value> = kinds
+            .iter()
+            .filter(|kind| kind.as_str() != Some("null"))
+            .cloned()
+            .collect();
+        if kinds.len() == 1 {
+            schema["type"] = kinds[0].clone();
+            return (schema, true);
+        }
+    }
+    if let Some(members) = schema.get_mut("anyOf").and_then(Value::as_array_mut) {
+        if members.contains(&null) {
+            members.retain(|member| *member != null);
+            return (schema, true);
+        }
+    }
+    (schema, false)
+}
+
+/// The schema, also allowing `null`.
+fn nullable(mut schema: Value) -> Value {
+    if schema.as_object().is_some_and(Map::is_empty) {
+        return json!({ "type": "null" });
+    }
+    if let Some(members) = schema.get_mut("anyOf").and_then(Value::as_array_mut) {
+        members.push(json!({ "type": "null" }));
+        return schema;
+    }
+    if let Some(kind) = schema
+        .get("type")
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+    {
+        schema["type"] = json!([kind, "null"]);
+    }
+    schema
+}
@@ -0,0 +1,647 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use super::{
+    Components, IGNORED_HEADERS, MediaType, OpenApi, Operation, Parameter, ParameterLocation,
+    PathItem, RequestBody, SecurityRequirement, SecurityScheme, Server, component_name,
+    merge_parameters,
+};
+use crate::v2_1_0::auth::{
+    ApiKeyAuth, ApiKeyLocation, AuthScheme, BasicAuth, BearerAuth, DigestAuth, OAuth2Auth,
+};
+use crate::v2_1_0::{
+    self, Auth, Body, BodyOptions, DescriptionUnion, FormParameter, Header, HeaderElement,
+    HeaderUnion, Headers, Information, Item, ItemGroup, Items, Mode, QueryParam, RawLanguage,
+    RawOptions, RequestClass, RequestUnion, ResponseClass, Spec, Url, UrlClass,
+    UrlEncodedParameter, Variable, VariableType,
+};
+
+/// The collection variable that holds the server URL.
+const BASE_URL: &str = "baseUrl";
+
+/// How many nested schemas and references example generation follows.
+const MAX_EXAMPLE_DEPTH: usize = 8;
+
+impl Spec {
+    /// Generates a collection from an OpenAPI 3 document:
+    ///
+    /// - every operation becomes an [`Item`] named after its summary, its `operationId` or its
+    ///   method and path, in a folder for its first tag. Untagged operations sit at the root;
+    /// - the URL starts with `{{baseUrl}}`, a collection variable holding the first server URL
+    ///   with its variables set to their defaults. Operations with servers of their own use
+    ///   the first of them instead;
+    /// - `{param}` path segments become `:param` path variables, and query, header and cookie
+    ///   parameters are added with example values;
+    /// - the request body and the saved responses are generated from the examples of the
+    ///   document, or from the schemas when there are none. JSON media types are preferred;
+    /// - security schemes become [`Auth`] on the collection, and on requests whose
+    ///   requirements differ from the document's. Credentials are left as `{{variables}}`,
+    ///   such as `{{bearerToken}}` or `{{apiKey}}`. Mutual TLS and cookie API keys are left
+    ///   out.
+    ///
+    /// `$ref`s to the document's components are followed; other references are ignored.
+    pub fn from_openapi(document: &OpenApi) -> Spec {
+        let components = Components::default();
+        let importer = Importer {
+            components: document.components.as_ref().unwrap_or(&components),
+        };
+
+        let mut items: Vec<Items> = document
+            .tags
+            .iter()
+            .map(|tag| folder(&tag.name, tag.description.as_deref()))
+            .collect();
+        for (path, path_item) in &document.paths {
+            for (method, operation) in path_item.operations() {
+                let item = importer.item(document, path, path_item, method, operation);
+                match operation.tags.first() {
+                    Some(tag) => ItemGroup::items_of(&mut items, tag).push(Items::Item(item)),
+                    None => items.push(Items::Item(item)),
+                }
+            }
+        }
+        items.retain(|item| !matches!(item, Items::ItemGroup(group) if group.item.is_empty()));
+
+        let server = document.servers.first();
+        Spec {
+            auth: document
+                .security
+                .as_deref()
+                .and_then(|requirements| importer.auth(requirements)),
+            info: Information {
+                name: document.info.title.clone(),
+                description: document
+                    .info
+                    .description
+                    .clone()
+                    .map(DescriptionUnion::String),
+                schema: v2_1_0::SCHEMA_URL.to_owned(),
+                ..Information::default()
+            },
+            item: items,
+            variable: Some(vec![Variable {
+                description: server
+                    .and_then(|server| server.description.clone())
+                    .map(DescriptionUnion::String),
+                disabled: None,
+                id: None,
+                key: Some(BASE_URL.to_owned()),
+                name: None,
+                system: None,
+                variable_type: Some(VariableType::String),
+                value: Some(Value::String(server.map(server_url).unwrap_or_default())),
+            }]),
+            ..Spec::default()
+        }
+    }
+}
+
+struct Importer<'a> {
+    components: &'a Components,
+}
+
+impl Importer<'_> {
+    fn item(
+        &self,
+        document: &OpenApi,
+        path: &str,
+        path_item: &PathItem,
+        method: &str,
+        operation: &Operation,
+    ) -> Item {
+        let parameters = self.parameters(path_item, operation);
+        let of = |location: ParameterLocation| {
+            parameters
+                .iter()
+                .filter(move |parameter| parameter.location == location)
+        };
+
+        let base = match (operation.servers.first(), path_item.servers.first()) {
+            (Some(server), _) | (None, Some(server)) => server_url(server),
+            (None, None) => format!("{{{{{BASE_URL}}}}}"),
+        };
+        let mut url = UrlClass::parse(&format!("{base}{}", path_variables(path)));
+        let query: Vec<QueryParam> = of(ParameterLocation::Query)
+            .map(|parameter| QueryParam {
+                description: description(parameter.description.as_deref()),
+                disabled: None,
+                key: Some(parameter.name.clone()),
+                value: Some(self.parameter_value(parameter)),
+            })
+            .collect();
+        url.query = (!query.is_empty()).then_some(query);
+        let variables: Vec<Variable> = of(ParameterLocation::Path)
+            .map(|parameter| Variable {
+                description: description(parameter.description.as_deref()),
+                disabled: None,
+                id: None,
+                key: Some(parameter.name.clone()),
+                name: None,
+                system: None,
+                variable_type: None,
+                value: Some(Value::String(self.parameter_value(parameter))),
+            })
+            .collect();
+        url.variable = (!variables.is_empty()).then_some(variables);
+        url.raw = Some(url.to_string());
+
+        let mut headers: Vec<Header> = of(ParameterLocation::Header)
+            .filter(|parameter| {
+                !IGNORED_HEADERS.contains(&parameter.name.to_ascii_lowercase().as_str())
+            })
+            .map(|parameter| header(&parameter.name, &self.parameter_value(parameter)))
+            .collect();
+        let cookies: Vec<String> = of(ParameterLocation::Cookie)
+            .map(|parameter| format!("{}={}", parameter.name, self.parameter_value(parameter)))
+            .collect();
+        if !cookies.is_empty() {
+            headers.push(header("Cookie", &cookies.join("; ")));
+        }
+
+        let body = operation
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(&self.components.request_bodies))
+            .and_then(|body| self.body(body));
+        if let Some((media_type, _)) = body.as_ref() {
+            // Postman writes the multipart Content-Type, with its boundary, when sending.
+            if !media_type.starts_with("multipart/") {
+                headers.push(header("Content-Type", media_type));
+            }
+        }
+        let responses: Vec<_> = operation
+            .responses
+            .iter()
+            .filter_map(|(code, response)| {
+                let code = code.parse::<i64>().ok()?;
+                Some((code, response.resolve(&self.components.responses)?))
+            })
+            .collect();
+        if let Some(media_type) = responses
+            .iter()
+            .find(|(code, _)| (200..300).contains(code))
+            .and_then(|(_, response)| preferred(&response.content))
+            .map(|(media_type, _)| media_type)
+        {
+            headers.push(header("Accept", media_type));
+        }
+
+        let auth = match operation.security.as_deref() {
+            Some(requirements) if Some(requirements) != document.security.as_deref() => {
+                self.auth(requirements)
+            }
+            _ => None,
+        };
+        let request = RequestClass {
+            auth,
+            body: body.map(|(_, body)| body),
+            description: description(operation.description.as_deref()),
+            header: (!headers.is_empty()).then_some(HeaderUnion::HeaderArray(headers)),
+            method: Some(method.to_owned()),
+            url: Some(Url::UrlClass(url)),
+            ..RequestClass::default()
+        };
+
+        let examples: Vec<ResponseClass> = responses
+            .into_iter()
+            .map(|(code, response)| {
+                let content = preferred(&response.content);
+                ResponseClass {
+                    body: content.and_then(|(media_type, media)| {
+                        self.media_example(media)
+                            .map(|example| text(&example, media_type))
+                    }),
+                    code: Some(code),
+                    cookie: None,
+                    header: content.map(|(media_type, _)| {
+                        Headers::UnionArray(vec![HeaderElement::Header(header(
+                            "Content-Type",
+                            media_type,
+                        ))])
+                    }),
+                    id: None,
+                    name: Some(if response.description.is_empty() {
+                        code.to_string()
+                    } else {
+                        response.description.clone()
+                    }),
+                    original_request: Some(RequestUnion::RequestClass(request.clone())),
+                    response_time: None,
+                    timings: None,
+                    status: None,
+                }
+            })
+            .collect();
+
+        Item {
+            description: None,
+            event: None,
+            id: None,
+            name: Some(
+                operation
+                    .summary
+                    .clone()
+                    .or_else(|| operation.operation_id.clone())
+                    .unwrap_or_else(|| format!("{method} {path}")),
+            ),
+            protocol_profile_behavior: None,
+            request: RequestUnion::RequestClass(request),
+            response: (!examples.is_empty()).then_some(examples),
+            variable: None,
+        }
+    }
+
+    /// The path's parameters and the operation's, which replace path parameters with the same
+    /// name and location.
+    fn parameters<'a>(
+        &'a self,
+        path_item: &'a PathItem,
+        operation: &'a Operation,
+    ) -> Vec<&'a Parameter> {
+        merge_parameters(
+            path_item
+                .parameters
+                .iter()
+                .chain(&operation.parameters)
+                .filter_map(|parameter| parameter.resolve(&self.components.parameters)),
+            |a, b| a.name == b.name && a.location == b.location,
+        )
+    }
+
+    fn parameter_value(&self, parameter: &Parameter) -> String {
+        let example = parameter
+            .example
+            .clone()
+            .or_else(|| {
+                parameter
+                    .examples
+                    .values()
+                    .find_map(|example| self.named_example(example))
+            })
+            .or_else(|| {
+                let schema = parameter.schema.as_ref()?;
+                Some(self.example(schema, 0))
+            });
+        match example {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(value)) => value,
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|value| match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            Some(value) => value.to_string(),
+        }
+    }
+
+    /// The media type and body of a request body.
+    fn body(&self, request_body: &RequestBody) -> Option<(String, Body)> {
+        let (media_type, media) = preferred(&request_body.content)?;
+        let example = self.media_example(media).unwrap_or(Value::Null);
+        let essence = media_type.to_ascii_lowercase();
+
+        let body = if essence.starts_with("application/x-www-form-urlencoded") {
+            Body {
+                mode: Some(Mode::Urlencoded),
+                urlencoded: Some(
+                    fields(&example)
+                        .map(|(key, value)| UrlEncodedParameter {
+                            description: None,
+                            disabled: None,
+                            key: key.clone(),
+                            parameter_type: Some("text".to_owned()),
+                            value: Some(value),
+                        })
+                        .collect(),
+                ),
+                ..Body::default()
+            }
+        } else if essence.starts_with("multipart/form-data") {
+            let properties = media
+                .schema
+                .as_ref()
+                .and_then(|schema| self.schema(schema, 0))
+                .and_then(|schema| schema.get("properties"));
+            Body {
+                mode: Some(Mode::Formdata),
+                formdata: Some(
+                    fields(&example)
+                        .map(|(key, value)| {
+                            let binary = properties
+                                .and_then(|properties| properties.get(key))
+                                .and_then(|property| self.schema(property, 0))
+                                .and_then(|property| property.get("format"))
+                                .is_some_and(|format| format == "binary");
+                            FormParameter {
+                                content_type: None,
+                                description: None,
+                                disabled: None,
+                                key: key.clone(),
+                                form_parameter_type: Some(
+                                    if binary { "file" } else { "text" }.to_owned(),
+                                ),
+                                value: (!binary).then_some(value),
+                                src: None,
+                            }
+                        })
+                        .collect(),
+                ),
+                ..Body::default()
+            }
+        } else {
+            Body {
+                mode: Some(Mode::Raw),
+                raw: Some(text(&example, media_type)),
+                options: RawLanguage::from_content_type(&essence).map(|language| BodyOptions {
+                    raw: Some(RawOptions {
+                        language: Some(language),
+                        ..RawOptions::default()
+                    }),
+                    ..BodyOptions::default()
+                }),
+                ..Body::default()
+            }
+        };
+        Some((media_type.clone(), body))
+    }
+
+    /// The example of a media type: its `example`, its first named example or one generated
+    /// from its schema.
+    fn media_example(&self, media: &MediaType) -> Option<Value> {
+        media
+            .example
+            .clone()
+            .or_else(|| {
+                media
+                    .examples
+                    .values()
+                    .find_map(|example| self.named_example(example))
+            })
+            .or_else(|| Some(self.example(media.schema.as_ref()?, 0)))
+    }
+
+    /// The `value` of an [example object](https://spec.openapis.org/oas/v3.1.0#example-object),
+    /// following a reference to the components.
+    fn named_example(&self, example: &Value) -> Option<Value> {
+        match example.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let example = self.components.examples.get(&component_name(reference)?)?;
+                example.get("value").cloned()
+            }
+            None => example.get("value").cloned(),
+        }
+    }
+
+    /// `schema`, or the component schema it refers to.
+    fn schema<'a>(&'a self, schema: &'a Value, depth: usize) -> Option<&'a Value> {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(_) if depth > MAX_EXAMPLE_DEPTH => None,
+            Some(reference) => {
+                let schema = self.components.schemas.get(&component_name(reference)?)?;
+                self.schema(schema, depth + 1)
+            }
+            None => Some(schema),
+        }
+    }
+
+    /// A value that matches `schema`: its example, default, constant or first allowed value,
+    /// or a value built from its type.
+    fn example(&self, schema: &Value, depth: usize) -> Value {
+        let Some(schema) = self
+            .schema(schema, depth)
+            .filter(|_| depth <= MAX_EXAMPLE_DEPTH)
+            .and_then(Value::as_object)
+        else {
+            return Value::Null;
+        };
+
+        if let Some(example) = ["example", "default", "const"]
+            .iter()
+            .find_map(|key| schema.get(*key))
+            .or_else(|| schema.get("examples")?.as_array()?.first())
+            .or_else(|| schema.get("enum")?.as_array()?.first())
+        {
+            return example.clone();
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for part in parts {
+                if let Value::Object(part) = self.example(part, depth + 1) {
+                    merged.extend(part);
+                }
+            }
+            return Value::Object(merged);
+        }
+        if let Some(first) = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(*key)?.as_array()?.first())
+        {
+            return self.example(first, depth + 1);
+        }
+
+        let kind = match schema.get("type") {
+            Some(Value::String(kind)) => kind.as_str(),
+            // OpenAPI 3.1 lists types, such as `["string", "null"]`.
+            Some(Value::Array(kinds)) => kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|kind| *kind != "null")
+                .unwrap_or("null"),
+            _ if schema.contains_key("properties") => "object",
+            _ if schema.contains_key("items") => "array",
+            _ => return Value::Null,
+        };
+        match kind {
+            "object" => Value::Object(
+                schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, property)| (name.clone(), self.example(property, depth + 1)))
+                    .collect(),
+            ),
+            "array" => Value::Array(
+                schema
+                    .get("items")
+                    .map(|items| vec![self.example(items, depth + 1)])
+                    .unwrap_or_default(),
+            ),
+            "integer" | "number" => schema.get("minimum").cloned().unwrap_or(Value::from(0)),
+            "boolean" => Value::Bool(true),
+            "string" => Value::String(
+                match schema.get("format").and_then(Value::as_str) {
+                    Some("date") => "1970-01-01",
+                    Some("date-time") => "1970-01-01T00:00:00Z",
+                    Some("email") => "user@example.com",
+                    Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                    Some("uri" | "url") => "https://example.com",
+                    Some("hostname") => "example.com",
+                    Some("ipv4") => "192.0.2.1",
+                    Some("ipv6") => "2001:db8::1",
+                    _ => "string",
+                }
+                .to_owned(),
+            ),
+            _ => Value::Null,
+        }
+    }
+
+    /// The auth of the first requirement that can be expressed in Postman. An empty list
+    /// turns auth off.
+    fn auth(&self, requirements: &[SecurityRequirement]) -> Option<Auth> {
+        if requirements.is_empty() {
+            return Some(Auth::default());
+        }
+        requirements.iter().find_map(|requirement| {
+            let (name, scopes) = requirement.iter().next()?;
+            let scheme = self.components.security_schemes.get(name)?;
+            scheme_auth(scheme.resolve(&self.components.security_schemes)?, scopes)
+        })
+    }
+}
+
+fn scheme_auth(scheme: &SecurityScheme, scopes: &[String]) -> Option<Auth> {
+    let scope = (!scopes.is_empty()).then(|| scopes.join(" "));
+    let auth = match scheme {
+        SecurityScheme::ApiKey { name, location, .. } => ApiKeyAuth {
+            key: name.clone(),
+            value: "{{apiKey}}".to_owned(),
+            location: match location.as_str() {
+                "header" => ApiKeyLocation::Header,
+                "query" => ApiKeyLocation::Query,
+                _ => return None,
+            },
+        }
+        .to_auth(),
+        SecurityScheme::Http { scheme, .. } => match scheme.to_ascii_lowercase().as_str() {
+            "basic" => BasicAuth {
+                username: "{{username}}".to_owned(),
+                password: Some("{{password}}".to_owned()),
+            }
+            .to_auth(),
+            "bearer" => BearerAuth {
+                token: "{{bearerToken}}".to_owned(),
+            }
+            .to_auth(),
+            "digest" => DigestAuth {
+                username: "{{username}}".to_owned(),
+                password: "{{password}}".to_owned(),
+                ..DigestAuth::default()
+            }
+            .to_auth(),
+            _ => return None,
+        },
+        SecurityScheme::OAuth2 { flows, .. } => {
+            let (grant_type, flow) = [
+                ("authorization_code", &flows.authorization_code),
+                ("client_credentials", &flows.client_credentials),
+                ("password_credentials", &flows.password),
+                ("implicit", &flows.implicit),
+            ]
+            .into_iter()
+            .find_map(|(grant_type, flow)| Some((grant_type, flow.as_ref()?)))?;
+            OAuth2Auth {
+                add_token_to: Some("header".to_owned()),
+                grant_type: Some(grant_type.to_owned()),
+                auth_url: flow.authorization_url.clone(),
+                access_token_url: flow.token_url.clone(),
+                scope,
+                ..OAuth2Auth::default()
+            }
+            .to_auth()
+        }
+        SecurityScheme::OpenIdConnect { .. } => OAuth2Auth {
+            add_token_to: Some("header".to_owned()),
+            grant_type: Some("authorization_code".to_owned()),
+            scope,
+            ..OAuth2Auth::default()
+        }
+        .to_auth(),
+        SecurityScheme::MutualTls { .. } => return None,
+    };
+    Some(auth)
+}
+
+/// The server URL with its variables set to their defaults and without a trailing `/`.
+fn server_url(server: &Server) -> String {
+    let mut url = server.url.clone();
+    for (name, variable) in &server.variables {
+        url = url.replace(&format!("{{{name}}}"), &variable.default);
+    }
+    url.trim_end_matches('/').to_owned()
+}
+
+/// The path with `{name}` templates written as `:name` path variables.
+fn path_variables(path: &str) -> String {
+    let mut rendered = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        rendered.push(':');
+        rendered.push_str(&rest[start + 1..start + end]);
+        rest = &rest[start + end + 1..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// The JSON media type of `content` if there is one, otherwise the first.
+fn preferred<T>(content: &BTreeMap<String, T>) -> Option<(&String, &T)> {
+    content
+        .iter()
+        .find(|(media_type, _)| media_type.to_ascii_lowercase().contains("json"))
+        .or_else(|| content.iter().next())
+}
+
+/// The members of an object example as form fields.
+fn fields(example: &Value) -> impl Iterator<Item = (&String, String)> {
+    example
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                Value::Null => String::new(),
+                value => value.to_string(),
+            };
+            (key, value)
+        })
+}
+
+/// An example as body text: strings as they are, anything else as indented JSON.
+fn text(example: &Value, media_type: &str) -> String {
+    match example {
+        Value::String(text) => text.clone(),
+        Value::Null if !media_type.contains("json") => String::new(),
+        example => serde_json::to_string_pretty(example).unwrap_or_default(),
+    }
+}
+
+fn header(key: &str, value: &str) -> Header {
+    Header {
+        description: None,
+        disabled: None,
+        key: key.to_owned(),
+        value: value.to_owned(),
+    }
+}
+
+fn description(description: Option<&str>) -> Option<DescriptionUnion> {
+    description.map(|description| DescriptionUnion::String(description.to_owned()))
+}
+
+fn folder(name: &str, description: Option<&str>) -> Items {
+    Items::ItemGroup(ItemGroup {
+        description: self::description(description),
+        ..ItemGroup::named(name)
+    })
+}
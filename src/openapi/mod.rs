@@ -0,0 +1,628 @@
+//! [OpenAPI 3](https://spec.openapis.org/oas/v3.1.0) documents.
+//!
+//! The types in this module model the parts of an OpenAPI document that describe requests:
+//! servers, paths and their operations, parameters, request bodies, responses and security
+//! schemes. Schemas and examples are kept as JSON values. Fields the types do not model, such
+//! as `x-` extensions, are kept in the `extensions` maps so that a document survives a round
+//! trip.
+//!
+//! [`Spec::from_openapi`](crate::v2_1_0::Spec::from_openapi) generates a collection from a
+//! document, the way Postman's OpenAPI import does:
+//!
+//! ```
+//! use postman_collection::openapi;
+//! use postman_collection::v2_1_0::{Items, RequestUnion, Spec};
+//!
+//! let document = openapi::from_str(r#"{
+//!   "openapi": "3.0.3",
+//!   "info": { "title": "Users", "version": "1.0" },
+//!   "servers": [{ "url": "https://api.example.com/v1" }],
+//!   "paths": {
+//!     "/users/{id}": {
+//!       "get": {
+//!         "tags": ["users"],
+//!         "summary": "Get a user",
+//!         "parameters": [
+//!           { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+//!         ],
+//!         "responses": { "200": { "description": "OK" } }
+//!       }
+//!     }
+//!   }
+//! }"#)?;
+//!
+//! let spec = Spec::from_openapi(&document);
+//! let Items::ItemGroup(users) = &spec.item[0] else { unreachable!() };
+//! let Items::Item(item) = &users.item[0] else { unreachable!() };
+//! let RequestUnion::RequestClass(request) = &item.request else { unreachable!() };
+//!
+//! assert_eq!(users.name.as_deref(), Some("users"));
+//! assert_eq!(item.name.as_deref(), Some("Get a user"));
+//! assert_eq!(request.url.as_ref().unwrap().raw(), "{{baseUrl}}/users/:id");
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+//!
+//! [`Spec::to_openapi`](crate::v2_1_0::Spec::to_openapi) goes the other way, describing a
+//! collection as an OpenAPI 3.1 document with schemas inferred from its example bodies.
+
+use std::collections::BTreeMap;
+use std::{fs::File, io::Read, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{Error, Result};
+
+mod export;
+mod import;
+
+pub use export::*;
+
+/// Header parameters OpenAPI ignores, because other fields describe them.
+const IGNORED_HEADERS: [&str; 3] = ["accept", "content-type", "authorization"];
+
+/// Security requirements: the names of security schemes and the scopes each one needs.
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
+/// The root of an OpenAPI document.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct OpenApi {
+    /// The OpenAPI version, such as `3.0.3` or `3.1.0`.
+    #[serde(rename = "openapi")]
+    pub openapi: String,
+
+    #[serde(rename = "info")]
+    pub info: Info,
+
+    #[serde(rename = "servers", default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+
+    /// Operations by path template, such as `/users/{id}`.
+    #[serde(rename = "paths", default)]
+    pub paths: BTreeMap<String, PathItem>,
+
+    #[serde(rename = "components", skip_serializing_if = "Option::is_none")]
+    pub components: Option<Components>,
+
+    /// The security requirements of every operation that does not set its own. Any one of them
+    /// satisfies the API.
+    #[serde(rename = "security", skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    #[serde(rename = "tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Info {
+    #[serde(rename = "title")]
+    pub title: String,
+
+    #[serde(rename = "summary", skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The version of the API, not of OpenAPI.
+    #[serde(rename = "version")]
+    pub version: String,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Server {
+    /// May be relative, and may contain `{variables}`.
+    #[serde(rename = "url")]
+    pub url: String,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(
+        rename = "variables",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub variables: BTreeMap<String, ServerVariable>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ServerVariable {
+    #[serde(rename = "default")]
+    pub default: String,
+
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The operations of one path.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct PathItem {
+    #[serde(rename = "summary", skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(rename = "get", skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+
+    #[serde(rename = "put", skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+
+    #[serde(rename = "post", skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+
+    #[serde(rename = "delete", skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+
+    #[serde(rename = "options", skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+
+    #[serde(rename = "head", skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+
+    #[serde(rename = "patch", skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+
+    #[serde(rename = "trace", skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+
+    /// Servers for the operations of this path, instead of the document's.
+    #[serde(rename = "servers", default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+
+    /// Parameters shared by the operations of this path.
+    #[serde(rename = "parameters", default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl PathItem {
+    /// The operations that are set, with their upper-case method, in the order Postman lists
+    /// them.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("GET", &self.get),
+            ("POST", &self.post),
+            ("PUT", &self.put),
+            ("PATCH", &self.patch),
+            ("DELETE", &self.delete),
+            ("HEAD", &self.head),
+            ("OPTIONS", &self.options),
+            ("TRACE", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| Some((method, operation.as_ref()?)))
+    }
+
+    /// The slot for the operation of an upper-case `method`, `None` for methods OpenAPI does
+    /// not describe.
+    pub(crate) fn operation_mut(&mut self, method: &str) -> Option<&mut Option<Operation>> {
+        let operation = match method {
+            "GET" => &mut self.get,
+            "POST" => &mut self.post,
+            "PUT" => &mut self.put,
+            "PATCH" => &mut self.patch,
+            "DELETE" => &mut self.delete,
+            "HEAD" => &mut self.head,
+            "OPTIONS" => &mut self.options,
+            "TRACE" => &mut self.trace,
+            _ => return None,
+        };
+        Some(operation)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Operation {
+    #[serde(rename = "tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    #[serde(rename = "summary", skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+
+    #[serde(rename = "parameters", default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+
+    #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<ReferenceOr<RequestBody>>,
+
+    /// Responses by status code, such as `200`, `4XX` or `default`.
+    #[serde(rename = "responses", default)]
+    pub responses: BTreeMap<String, ReferenceOr<Response>>,
+
+    #[serde(rename = "deprecated", skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// Overrides the document's requirements. An empty list turns security off.
+    #[serde(rename = "security", skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    #[serde(rename = "servers", default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+/// Where a [`Parameter`] is sent.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+pub enum ParameterLocation {
+    #[default]
+    #[serde(rename = "query")]
+    Query,
+
+    #[serde(rename = "header")]
+    Header,
+
+    #[serde(rename = "path")]
+    Path,
+
+    #[serde(rename = "cookie")]
+    Cookie,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Parameter {
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "in")]
+    pub location: ParameterLocation,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Always `true` for path parameters.
+    #[serde(rename = "required", skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(rename = "deprecated", skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(rename = "schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+
+    #[serde(rename = "example", skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+
+    /// Named [example objects](https://spec.openapis.org/oas/v3.1.0#example-object).
+    #[serde(
+        rename = "examples",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub examples: BTreeMap<String, Value>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RequestBody {
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The body by media type, such as `application/json`.
+    #[serde(rename = "content", default)]
+    pub content: BTreeMap<String, MediaType>,
+
+    #[serde(rename = "required", skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct MediaType {
+    #[serde(rename = "schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+
+    #[serde(rename = "example", skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+
+    /// Named [example objects](https://spec.openapis.org/oas/v3.1.0#example-object).
+    #[serde(
+        rename = "examples",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub examples: BTreeMap<String, Value>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Response {
+    #[serde(rename = "description", default)]
+    pub description: String,
+
+    /// [Header objects](https://spec.openapis.org/oas/v3.1.0#header-object) by name.
+    #[serde(
+        rename = "headers",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub headers: BTreeMap<String, Value>,
+
+    #[serde(
+        rename = "content",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub content: BTreeMap<String, MediaType>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+/// Reusable objects that the rest of the document refers to with `$ref`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Components {
+    #[serde(
+        rename = "schemas",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub schemas: BTreeMap<String, Value>,
+
+    #[serde(
+        rename = "responses",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub responses: BTreeMap<String, ReferenceOr<Response>>,
+
+    #[serde(
+        rename = "parameters",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub parameters: BTreeMap<String, ReferenceOr<Parameter>>,
+
+    #[serde(
+        rename = "examples",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub examples: BTreeMap<String, Value>,
+
+    #[serde(
+        rename = "requestBodies",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub request_bodies: BTreeMap<String, ReferenceOr<RequestBody>>,
+
+    #[serde(
+        rename = "securitySchemes",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub security_schemes: BTreeMap<String, ReferenceOr<SecurityScheme>>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Tag {
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// How an API is secured.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum SecurityScheme {
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        /// The header, query parameter or cookie name.
+        #[serde(rename = "name")]
+        name: String,
+
+        /// `header`, `query` or `cookie`.
+        #[serde(rename = "in")]
+        location: String,
+
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "http")]
+    Http {
+        /// An HTTP auth scheme such as `basic` or `bearer`.
+        #[serde(rename = "scheme")]
+        scheme: String,
+
+        #[serde(rename = "bearerFormat", skip_serializing_if = "Option::is_none")]
+        bearer_format: Option<String>,
+
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        #[serde(rename = "flows")]
+        flows: Box<OAuthFlows>,
+
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect {
+        #[serde(rename = "openIdConnectUrl")]
+        open_id_connect_url: String,
+
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "mutualTLS")]
+    MutualTls {
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct OAuthFlows {
+    #[serde(rename = "implicit", skip_serializing_if = "Option::is_none")]
+    pub implicit: Option<OAuthFlow>,
+
+    #[serde(rename = "password", skip_serializing_if = "Option::is_none")]
+    pub password: Option<OAuthFlow>,
+
+    #[serde(rename = "clientCredentials", skip_serializing_if = "Option::is_none")]
+    pub client_credentials: Option<OAuthFlow>,
+
+    #[serde(rename = "authorizationCode", skip_serializing_if = "Option::is_none")]
+    pub authorization_code: Option<OAuthFlow>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct OAuthFlow {
+    #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+
+    #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<String>,
+
+    #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+
+    /// Scope descriptions by scope name.
+    #[serde(rename = "scopes", default)]
+    pub scopes: BTreeMap<String, String>,
+}
+
+/// An object, or a `$ref` to one in the [`Components`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ReferenceOr<T> {
+    Reference {
+        #[serde(rename = "$ref")]
+        reference: String,
+    },
+
+    Item(T),
+}
+
+impl<T> ReferenceOr<T> {
+    /// The object, looking a reference up in `components`, the map of the [`Components`]
+    /// field for `T`, such as [`Components::parameters`] for `#/components/parameters/...`
+    /// references. `None` for references that point elsewhere or to missing objects.
+    pub fn resolve<'a>(
+        &'a self,
+        components: &'a BTreeMap<String, ReferenceOr<T>>,
+    ) -> Option<&'a T> {
+        let mut current = self;
+        // References may chain; a bound keeps cycles finite.
+        for _ in 0..=components.len() {
+            match current {
+                ReferenceOr::Item(item) => return Some(item),
+                ReferenceOr::Reference { reference } => {
+                    current = components.get(&component_name(reference)?)?;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The name a `#/components/<kind>/<name>` reference points to.
+pub(crate) fn component_name(reference: &str) -> Option<String> {
+    let name = reference.strip_prefix("#/components/")?.split_once('/')?.1;
+    Some(name.replace("~1", "/").replace("~0", "~"))
+}
+
+/// Path-level parameters followed by operation-level ones, where a later parameter replaces an
+/// earlier one that `same` matches. Both OpenAPI 3 and Swagger 2 identify a parameter by its
+/// name and location.
+pub(crate) fn merge_parameters<'a, P>(
+    parameters: impl IntoIterator<Item = &'a P>,
+    same: impl Fn(&P, &P) -> bool,
+) -> Vec<&'a P> {
+    let mut merged: Vec<&P> = Vec::new();
+    for parameter in parameters {
+        merged.retain(|existing| !same(existing, parameter));
+        merged.push(parameter);
+    }
+    merged
+}
+
+/// Deserialize an OpenAPI document from a path
+pub fn from_path<P>(path: P) -> Result<OpenApi>
+where
+    P: AsRef<Path>,
+{
+    from_reader(File::open(path)?)
+}
+
+/// Deserialize an OpenAPI document from a string slice
+pub fn from_str(input: &str) -> Result<OpenApi> {
+    from_slice(input.as_bytes())
+}
+
+/// Deserialize an OpenAPI document from a byte slice. Fails for documents that are not
+/// OpenAPI 3.
+pub fn from_slice(input: &[u8]) -> Result<OpenApi> {
+    let value = crate::value_from_slice(input)?;
+    let version = value
+        .get("openapi")
+        .or_else(|| value.get("swagger"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if value.get("openapi").is_none() || !version.starts_with("3.") {
+        return Err(Error::UnsupportedOpenApiVersion {
+            version: version.to_owned(),
+        });
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Deserialize an OpenAPI document from type which implements Read
+pub fn from_reader<R>(mut read: R) -> Result<OpenApi>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    read.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+/// Serialize an OpenAPI document to a JSON string
+pub fn to_json(document: &OpenApi) -> Result<String> {
+    Ok(serde_json::to_string_pretty(document)?)
+}
+
+/// Serialize an OpenAPI document to a YAML string.
+///
+/// Available with the crate feature `yaml`.
+#[cfg(feature = "yaml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+pub fn to_yaml(document: &OpenApi) -> Result<String> {
+    Ok(yaml_serde::to_string(document)?)
+}
@@ -0,0 +1,530 @@
+//! A collection runner in the spirit of newman.
+//!
+//! Available with the crate feature `http`.
+//!
+//! A [`Runner`] sends the requests of a collection in order, once per iteration, through a
+//! [`Backend`], and records what happened in a [`RunSummary`]. The crate does not ship an HTTP
+//! client: the backend is whatever sends an [`http::Request`], such as a wrapper around an HTTP
+//! client or a stand-in for a server under test.
+//!
+//! For each request the runner
+//!
+//! 1. runs the enabled `prerequest` scripts of the collection, the enclosing folders and the
+//!    item, in that order, through the [`ScriptHook`] if there is one;
+//! 2. resolves `{{variables}}` from the globals, the collection, folder and item variables, the
+//!    environment and the [`Variables`] set by scripts;
+//! 3. applies the effective auth, following folder and collection inheritance. Basic, bearer and
+//!    API key auth are applied; requests with other schemes are sent without auth;
+//! 4. converts the request as [`RequestClass::to_http_with_encoder`] does, honouring the item's
+//!    protocol profile behavior;
+//! 5. sends it and records the response and how long it took;
+//! 6. runs the enabled `test` scripts with the response.
+//!
+//! A request fails when it cannot be built or sent, when a script fails or when a test fails.
+//! Scripts are skipped when there is no hook. With the crate feature `scripts`,
+//! [`sandbox::Sandbox`] runs them in an embedded JavaScript engine.
+//!
+//! ```
+//! use http::Response;
+//! use postman_collection::from_str;
+//! use postman_collection::runner::Runner;
+//!
+//! let collection = from_str(r#"{
+//!   "info": {
+//!     "name": "Health",
+//!     "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+//!   },
+//!   "variable": [{ "key": "baseUrl", "value": "http://localhost:8080" }],
+//!   "item": [{ "name": "Ping", "request": "{{baseUrl}}/ping" }]
+//! }"#)?;
+//!
+//! let mut runner = Runner::new(|request: http::Request<Vec<u8>>| {
+//!     assert_eq!(request.uri(), "http://localhost:8080/ping");
+//!     Ok(Response::new(b"pong".to_vec()))
+//! })
+//! .with_iterations(2);
+//!
+//! let summary = runner.run(&collection)?;
+//! assert_eq!(summary.executions.len(), 2);
+//! assert!(summary.is_success());
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ::http::{Request, Response};
+
+use crate::body::Encoder;
+use crate::environment::Environment;
+use crate::globals::Globals;
+use crate::v2_1_0::auth::effective_auth;
+use crate::v2_1_0::iter::ItemEntry;
+use crate::v2_1_0::{Event, Host, RequestClass, RequestUnion, Spec, Url};
+use crate::variables::dynamic::DynamicVariables;
+use crate::variables::{Resolver, Scope, ScopeKind};
+use crate::{Error, PostmanCollection, Result, convert};
+
+#[cfg(feature = "scripts")]
+#[cfg_attr(docsrs, doc(cfg(feature = "scripts")))]
+pub mod sandbox;
+
+/// Sends requests on behalf of a [`Runner`].
+pub trait Backend {
+    fn send(&mut self, request: Request<Vec<u8>>) -> Result<Response<Vec<u8>>>;
+}
+
+impl<F> Backend for F
+where
+    F: FnMut(Request<Vec<u8>>) -> Result<Response<Vec<u8>>>,
+{
+    fn send(&mut self, request: Request<Vec<u8>>) -> Result<Response<Vec<u8>>> {
+        self(request)
+    }
+}
+
+/// When a script runs, from [`Event::listen`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Listen {
+    Prerequest,
+    Test,
+}
+
+impl Listen {
+    fn as_str(self) -> &'static str {
+        match self {
+            Listen::Prerequest => "prerequest",
+            Listen::Test => "test",
+        }
+    }
+}
+
+/// The variables that scripts can change. They carry over from one request to the next, and
+/// from one iteration to the next, until the end of the run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variables {
+    pub globals: Scope,
+    pub environment: Scope,
+    /// Collection variables set by scripts, shadowing the ones defined in the collection.
+    pub collection: Scope,
+    /// Local variables, which shadow every other scope.
+    pub local: Scope,
+}
+
+impl Default for Variables {
+    fn default() -> Self {
+        Variables {
+            globals: Scope::new(ScopeKind::Global),
+            environment: Scope::new(ScopeKind::Environment),
+            collection: Scope::new(ScopeKind::Collection),
+            local: Scope::new(ScopeKind::Local),
+        }
+    }
+}
+
+/// The outcome of one `pm.test`-style assertion.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub name: String,
+    /// Why the test failed, or `None` when it passed.
+    pub error: Option<String>,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// What a [`ScriptHook`] sees of the request being run.
+pub struct ScriptContext<'a> {
+    pub listen: Listen,
+    /// The script's `exec` lines, joined with newlines.
+    pub source: String,
+    pub event: &'a Event,
+    /// The item's path, as in [`ItemEntry::path`].
+    pub path: &'a str,
+    /// The iteration, counting from zero.
+    pub iteration: usize,
+    /// The request before variable resolution for `prerequest` scripts, which may change it, and
+    /// the request that was sent for `test` scripts.
+    pub request: &'a mut RequestClass,
+    /// The response, for `test` scripts.
+    pub response: Option<&'a Response<Vec<u8>>>,
+    /// How long the response took, for `test` scripts.
+    pub duration: Option<Duration>,
+    pub variables: &'a mut Variables,
+    definitions: &'a Resolver,
+    collection: &'a Scope,
+}
+
+impl fmt::Debug for ScriptContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptContext")
+            .field("listen", &self.listen)
+            .field("path", &self.path)
+            .field("iteration", &self.iteration)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ScriptContext<'_> {
+    /// A resolver over every variable visible to the request, including the ones scripts have
+    /// set so far.
+    pub fn resolver(&self) -> Resolver {
+        resolver(self.definitions, self.variables)
+    }
+
+    /// The variables the collection itself defines. Unlike `variables.collection`, these are
+    /// never changed by scripts.
+    pub fn collection(&self) -> &Scope {
+        self.collection
+    }
+}
+
+/// Runs `prerequest` and `test` scripts. See the [module documentation](self).
+pub trait ScriptHook {
+    /// Runs one script, returning the tests it recorded. An error marks the request as failed,
+    /// but the run goes on.
+    fn run(&mut self, context: &mut ScriptContext<'_>) -> Result<Vec<TestResult>>;
+}
+
+/// What happened to one request of one iteration.
+#[derive(Debug)]
+pub struct Execution {
+    /// The iteration, counting from zero.
+    pub iteration: usize,
+    /// The item's path, as in [`ItemEntry::path`].
+    pub path: String,
+    /// The request after script changes, variable resolution and auth.
+    pub request: RequestClass,
+    pub response: Option<Response<Vec<u8>>>,
+    /// The time between sending the request and receiving the response.
+    pub duration: Option<Duration>,
+    /// The tests recorded by scripts, in the order they ran.
+    pub tests: Vec<TestResult>,
+    /// Errors building or sending the request, and errors raised by scripts.
+    pub errors: Vec<Error>,
+}
+
+impl Execution {
+    pub fn is_failure(&self) -> bool {
+        !self.errors.is_empty() || self.tests.iter().any(|test| !test.passed())
+    }
+}
+
+/// The outcome of [`Runner::run`].
+#[derive(Debug)]
+pub struct RunSummary {
+    pub executions: Vec<Execution>,
+    /// The variables as the scripts left them.
+    pub variables: Variables,
+    /// Whether the run stopped early because of [`Runner::bail_on_failure`].
+    pub bailed: bool,
+}
+
+impl RunSummary {
+    pub fn failures(&self) -> impl Iterator<Item = &Execution> {
+        self.executions
+            .iter()
+            .filter(|execution| execution.is_failure())
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failures().next().is_none()
+    }
+}
+
+/// Runs collections. See the [module documentation](self).
+pub struct Runner<B> {
+    backend: B,
+    hook: Option<Box<dyn ScriptHook>>,
+    encoder: Encoder,
+    resolver: Resolver,
+    variables: Variables,
+    iterations: usize,
+    folders: Vec<String>,
+    delay: Duration,
+    bail: bool,
+}
+
+impl<B> fmt::Debug for Runner<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Runner")
+            .field("hook", &self.hook.is_some())
+            .field("encoder", &self.encoder)
+            .field("variables", &self.variables)
+            .field("iterations", &self.iterations)
+            .field("folders", &self.folders)
+            .field("delay", &self.delay)
+            .field("bail", &self.bail)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: Backend> Runner<B> {
+    /// A runner for a single iteration over every request, without scripts.
+    pub fn new(backend: B) -> Self {
+        Runner {
+            backend,
+            hook: None,
+            encoder: Encoder::new(),
+            resolver: Resolver::new(),
+            variables: Variables::default(),
+            iterations: 1,
+            folders: Vec::new(),
+            delay: Duration::ZERO,
+            bail: false,
+        }
+    }
+
+    pub fn with_environment(mut self, environment: &Environment) -> Self {
+        self.variables.environment = Scope::from(environment);
+        self
+    }
+
+    pub fn with_globals(mut self, globals: &Globals) -> Self {
+        self.variables.globals = Scope::from(globals);
+        self
+    }
+
+    /// Runs the collection `iterations` times. Variables set by scripts carry over from one
+    /// iteration to the next.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Only runs the requests in folders named `name`, or the requests named `name`. Can be
+    /// given several times; the collection order is kept either way.
+    pub fn with_folder(mut self, name: impl Into<String>) -> Self {
+        self.folders.push(name.into());
+        self
+    }
+
+    /// Waits `delay` between two requests.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Stops the run after the first failed request.
+    pub fn bail_on_failure(mut self) -> Self {
+        self.bail = true;
+        self
+    }
+
+    pub fn with_script_hook(mut self, hook: impl ScriptHook + 'static) -> Self {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Encodes bodies with `encoder`, for example to read form files relative to the
+    /// collection.
+    pub fn with_encoder(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// Expands `{{$guid}}`-style variables with `provider`.
+    pub fn with_dynamic_variables(mut self, provider: impl DynamicVariables + 'static) -> Self {
+        self.resolver = self.resolver.with_dynamic_variables(provider);
+        self
+    }
+
+    /// Runs a collection of any version. v1.0.0 and v2.0.0 collections are converted to v2.1.0
+    /// first, ignoring whatever the conversion cannot carry over.
+    pub fn run(&mut self, collection: &PostmanCollection) -> Result<RunSummary> {
+        let spec = match collection {
+            PostmanCollection::V1_0_0(spec) => Cow::Owned(convert::v1_0_0_to_v2_1_0(spec).spec),
+            PostmanCollection::V2_0_0(spec) => Cow::Owned(Spec::from(spec.clone())),
+            PostmanCollection::V2_1_0(spec) => Cow::Borrowed(spec),
+        };
+        self.run_spec(&spec)
+    }
+
+    /// Runs a v2.1.0 collection. Fails before sending anything when a folder given to
+    /// [`with_folder`](Self::with_folder) does not exist.
+    pub fn run_spec(&mut self, spec: &Spec) -> Result<RunSummary> {
+        let entries: Vec<_> = spec
+            .items()
+            .filter(|entry| self.folders.is_empty() || self.selects(entry))
+            .collect();
+        if let Some(name) = self
+            .folders
+            .iter()
+            .find(|name| !spec.items().any(|entry| names(&entry).any(|n| n == *name)))
+        {
+            return Err(Error::UnknownFolder { name: name.clone() });
+        }
+
+        let mut variables = self.variables.clone();
+        let mut executions = Vec::new();
+        let mut bailed = false;
+        'run: for iteration in 0..self.iterations {
+            for entry in &entries {
+                if !executions.is_empty() && !self.delay.is_zero() {
+                    thread::sleep(self.delay);
+                }
+                let execution = self.execute(spec, entry, iteration, &mut variables);
+                let failed = execution.is_failure();
+                executions.push(execution);
+                if failed && self.bail {
+                    bailed = true;
+                    break 'run;
+                }
+            }
+        }
+
+        Ok(RunSummary {
+            executions,
+            variables,
+            bailed,
+        })
+    }
+
+    fn selects(&self, entry: &ItemEntry<'_>) -> bool {
+        names(entry).any(|name| self.folders.iter().any(|folder| folder == name))
+    }
+
+    fn execute(
+        &mut self,
+        spec: &Spec,
+        entry: &ItemEntry<'_>,
+        iteration: usize,
+        variables: &mut Variables,
+    ) -> Execution {
+        let mut request = match &entry.item.request {
+            RequestUnion::RequestClass(request) => request.clone(),
+            RequestUnion::String(url) => RequestClass {
+                url: Some(Url::String(url.clone())),
+                ..RequestClass::default()
+            },
+        };
+        if request.auth.is_none() {
+            request.auth = effective_auth(spec, &entry.ancestors, entry.item)
+                .map(|effective| effective.auth.clone());
+        }
+
+        let mut definitions = self.resolver.clone();
+        for scope in Resolver::for_entry(spec, entry).scopes() {
+            definitions.push(scope.clone());
+        }
+        let collection = Scope::from_variables(
+            ScopeKind::Collection,
+            spec.variable.as_deref().unwrap_or_default(),
+        );
+        let events: Vec<&Event> = std::iter::once(&spec.event)
+            .chain(entry.ancestors.iter().map(|group| &group.event))
+            .chain([&entry.item.event])
+            .flatten()
+            .flatten()
+            .filter(|event| !event.disabled.unwrap_or(false))
+            .collect();
+
+        let mut execution = Execution {
+            iteration,
+            path: entry.path.clone(),
+            request,
+            response: None,
+            duration: None,
+            tests: Vec::new(),
+            errors: Vec::new(),
+        };
+        let mut script = |listen: Listen, execution: &mut Execution, variables: &mut Variables| {
+            let Some(hook) = self.hook.as_mut() else {
+                return;
+            };
+            for event in events
+                .iter()
+                .filter(|event| event.listen == listen.as_str())
+            {
+                let mut context = ScriptContext {
+                    listen,
+                    source: source(event),
+                    event,
+                    path: &entry.path,
+                    iteration,
+                    request: &mut execution.request,
+                    response: execution.response.as_ref(),
+                    duration: execution.duration,
+                    variables,
+                    definitions: &definitions,
+                    collection: &collection,
+                };
+                match hook.run(&mut context) {
+                    Ok(tests) => execution.tests.extend(tests),
+                    Err(error) => execution.errors.push(error),
+                }
+            }
+        };
+
+        script(Listen::Prerequest, &mut execution, variables);
+
+        execution.request = resolver(&definitions, variables)
+            .resolve_request(&execution.request)
+            .value;
+        if let Some(Url::UrlClass(url)) = execution.request.url.as_mut() {
+            *url = url.expand_path_variables().value;
+        }
+        if let Err(error) = execution.request.apply_auth() {
+            execution.errors.push(error);
+            return execution;
+        }
+        let sent = execution
+            .request
+            .to_http_with_encoder(entry.item.protocol_profile_behavior.as_ref(), &self.encoder)
+            .and_then(|request| {
+                let started = Instant::now();
+                let response = self.backend.send(request);
+                execution.duration = Some(started.elapsed());
+                response
+            });
+        match sent {
+            Ok(response) => execution.response = Some(response),
+            Err(error) => {
+                execution.errors.push(error);
+                return execution;
+            }
+        }
+
+        script(Listen::Test, &mut execution, variables);
+        execution
+    }
+}
+
+/// The names an entry can be selected by: its folders' and its own.
+fn names<'a>(entry: &'a ItemEntry<'_>) -> impl Iterator<Item = &'a str> {
+    entry
+        .ancestors
+        .iter()
+        .filter_map(|group| group.name.as_deref())
+        .chain(entry.item.name.as_deref())
+}
+
+fn resolver(definitions: &Resolver, variables: &Variables) -> Resolver {
+    let mut resolver = definitions.clone();
+    for scope in [
+        &variables.globals,
+        &variables.collection,
+        &variables.environment,
+        &variables.local,
+    ] {
+        resolver.push(scope.clone());
+    }
+    resolver
+}
+
+fn source(event: &Event) -> String {
+    match event
+        .script
+        .as_ref()
+        .and_then(|script| script.exec.as_ref())
+    {
+        Some(Host::String(line)) => line.clone(),
+        Some(Host::StringArray(lines)) => lines.join("\n"),
+        None => String::new(),
+    }
+}
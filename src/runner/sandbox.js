@@ -0,0 +1,387 @@
+// The `pm` API available to scripts run by `runner::sandbox::Sandbox`.
+//
+// `__state` is defined by the sandbox before this file runs. The user script is passed to
+// `__run` as a string, and `__result()` hands the variables, the request and the recorded tests
+// back.
+
+class AssertionError extends Error {
+  constructor(message) {
+    super(message);
+    this.name = "AssertionError";
+  }
+}
+
+function __inspect(value) {
+  if (typeof value === "string") return "'" + value + "'";
+  if (value === undefined) return "undefined";
+  if (typeof value === "function") return "[Function]";
+  if (typeof value === "number" || value instanceof RegExp) return String(value);
+  try {
+    return JSON.stringify(value);
+  } catch (e) {
+    return String(value);
+  }
+}
+
+function __typeOf(value) {
+  if (value === null) return "null";
+  if (Array.isArray(value)) return "array";
+  if (value instanceof RegExp) return "regexp";
+  if (value instanceof Date) return "date";
+  return typeof value;
+}
+
+function __deepEqual(left, right) {
+  if (left === right) return true;
+  if (Number.isNaN(left) && Number.isNaN(right)) return true;
+  if (__typeOf(left) !== __typeOf(right) || typeof left !== "object" || left === null) {
+    return false;
+  }
+  if (Array.isArray(left)) {
+    return left.length === right.length && left.every((item, i) => __deepEqual(item, right[i]));
+  }
+  const keys = Object.keys(left);
+  return (
+    keys.length === Object.keys(right).length &&
+    keys.every((key) => Object.prototype.hasOwnProperty.call(right, key) && __deepEqual(left[key], right[key]))
+  );
+}
+
+function __message(error) {
+  if (error instanceof Error) return error.name + ": " + error.message;
+  return String(error);
+}
+
+// A subset of the chai `expect` API.
+class Assertion {
+  constructor(value) {
+    this._value = value;
+    this._negate = false;
+    this._deep = false;
+  }
+
+  _assert(passed, phrase, detail) {
+    if (this._negate ? passed : !passed) {
+      let message = "expected " + __inspect(this._value) + " to " + (this._negate ? "not " : "") + phrase;
+      if (detail && !this._negate) message += detail;
+      throw new AssertionError(message);
+    }
+    return this;
+  }
+
+  get not() { this._negate = !this._negate; return this; }
+  get deep() { this._deep = true; return this; }
+
+  equal(expected) {
+    return this._deep
+      ? this._assert(__deepEqual(this._value, expected), "deeply equal " + __inspect(expected))
+      : this._assert(this._value === expected, "equal " + __inspect(expected));
+  }
+  eql(expected) { return this._assert(__deepEqual(this._value, expected), "deeply equal " + __inspect(expected)); }
+  above(bound) { return this._assert(this._value > bound, "be above " + bound); }
+  least(bound) { return this._assert(this._value >= bound, "be at least " + bound); }
+  below(bound) { return this._assert(this._value < bound, "be below " + bound); }
+  most(bound) { return this._assert(this._value <= bound, "be at most " + bound); }
+  within(low, high) {
+    return this._assert(this._value >= low && this._value <= high, "be within " + low + ".." + high);
+  }
+
+  a(type) {
+    const article = /^[aeiou]/i.test(type) ? "an " : "a ";
+    return this._assert(__typeOf(this._value) === type.toLowerCase(), "be " + article + type);
+  }
+
+  include(item) {
+    const value = this._value;
+    let found;
+    if (typeof value === "string") found = value.indexOf(item) !== -1;
+    else if (Array.isArray(value)) found = value.some((entry) => this._deep ? __deepEqual(entry, item) : entry === item);
+    else if (value !== null && typeof value === "object" && item !== null && typeof item === "object") {
+      found = Object.keys(item).every((key) => __deepEqual(value[key], item[key]));
+    } else found = false;
+    return this._assert(found, "include " + __inspect(item));
+  }
+
+  property(name, expected) {
+    const value = this._value;
+    const has = value !== null && value !== undefined && Object(value)[name] !== undefined;
+    if (arguments.length < 2) return this._assert(has, "have property " + __inspect(name));
+    const actual = has ? value[name] : undefined;
+    const equal = this._deep ? __deepEqual(actual, expected) : actual === expected;
+    return this._assert(
+      has && equal,
+      "have property " + __inspect(name) + " of " + __inspect(expected),
+      ", but got " + __inspect(actual),
+    );
+  }
+
+  lengthOf(length) {
+    const actual = this._value === null || this._value === undefined ? undefined : this._value.length;
+    return this._assert(actual === length, "have a length of " + length, " but got " + actual);
+  }
+
+  match(pattern) { return this._assert(pattern.test(this._value), "match " + pattern); }
+  oneOf(list) { return this._assert(list.some((item) => __deepEqual(item, this._value)), "be one of " + __inspect(list)); }
+
+  keys(...keys) {
+    const value = this._value;
+    const expected = keys.length === 1 && Array.isArray(keys[0]) ? keys[0] : keys;
+    const actual = value !== null && typeof value === "object" ? Object.keys(value) : [];
+    return this._assert(
+      expected.every((key) => actual.indexOf(key) !== -1),
+      "have keys " + expected.map(__inspect).join(", "),
+    );
+  }
+}
+
+// Assertions read as properties, such as `.to.be.true`.
+const __flags = {
+  "ok": function () { return this._assert(!!this._value, "be truthy"); },
+  "true": function () { return this._assert(this._value === true, "be true"); },
+  "false": function () { return this._assert(this._value === false, "be false"); },
+  "null": function () { return this._assert(this._value === null, "be null"); },
+  "undefined": function () { return this._assert(this._value === undefined, "be undefined"); },
+  "NaN": function () { return this._assert(Number.isNaN(this._value), "be NaN"); },
+  "exist": function () { return this._assert(this._value !== null && this._value !== undefined, "exist"); },
+  "empty": function () {
+    const value = this._value;
+    const size = typeof value === "string" || Array.isArray(value)
+      ? value.length
+      : value !== null && typeof value === "object" ? Object.keys(value).length : NaN;
+    return this._assert(size === 0, "be empty");
+  },
+};
+for (const name of Object.keys(__flags)) {
+  Object.defineProperty(Assertion.prototype, name, { get: __flags[name] });
+}
+for (const word of ["to", "be", "been", "is", "that", "which", "and", "has", "have", "with", "at", "of", "same", "does"]) {
+  Object.defineProperty(Assertion.prototype, word, { get() { return this; } });
+}
+for (const [alias, name] of [
+  ["equals", "equal"], ["eq", "equal"], ["eqls", "eql"], ["gt", "above"], ["greaterThan", "above"],
+  ["gte", "least"], ["lt", "below"], ["lessThan", "below"], ["lte", "most"], ["an", "a"],
+  ["includes", "include"], ["contain", "include"], ["contains", "include"], ["length", "lengthOf"],
+]) {
+  Assertion.prototype[alias] = Assertion.prototype[name];
+}
+
+function __stringify(value) {
+  if (typeof value === "string") return value;
+  if (value === undefined) return "";
+  return JSON.stringify(value);
+}
+
+class VariableScope {
+  constructor(pairs) { this._pairs = pairs.slice(); }
+  _index(key) {
+    for (let i = this._pairs.length - 1; i >= 0; i--) if (this._pairs[i][0] === key) return i;
+    return -1;
+  }
+  has(key) { return this._index(key) !== -1; }
+  get(key) { const i = this._index(key); return i === -1 ? undefined : this._pairs[i][1]; }
+  set(key, value) {
+    const i = this._index(key);
+    if (i === -1) this._pairs.push([key, __stringify(value)]);
+    else this._pairs[i][1] = __stringify(value);
+  }
+  unset(key) { this._pairs = this._pairs.filter((pair) => pair[0] !== key); }
+  clear() { this._pairs = []; }
+  toObject() { return Object.fromEntries(this._pairs); }
+}
+
+// Collection variables set by scripts, over the ones the collection defines. Only the former
+// are written back, so `unset` and `clear` leave the definitions alone.
+class CollectionVariableScope extends VariableScope {
+  constructor(defined, pairs) {
+    super(pairs);
+    this._defined = new VariableScope(defined);
+  }
+  has(key) { return super.has(key) || this._defined.has(key); }
+  get(key) { return super.has(key) ? super.get(key) : this._defined.get(key); }
+  toObject() { return Object.assign(this._defined.toObject(), super.toObject()); }
+}
+
+class HeaderList {
+  constructor(headers) { this._headers = headers; }
+  _enabled() { return this._headers.filter((header) => !header.disabled); }
+  _find(key) {
+    const lower = String(key).toLowerCase();
+    return this._enabled().find((header) => header.key.toLowerCase() === lower);
+  }
+  has(key) { return this._find(key) !== undefined; }
+  get(key) { const header = this._find(key); return header === undefined ? undefined : header.value; }
+  add(header) { this._headers.push(__header(header)); }
+  upsert(header) {
+    header = __header(header);
+    const existing = this._find(header.key);
+    if (existing === undefined) this._headers.push(header);
+    else existing.value = header.value;
+  }
+  remove(key) {
+    const lower = String(key).toLowerCase();
+    this._headers = this._headers.filter((header) => header.key.toLowerCase() !== lower);
+  }
+  all() { return this._enabled().map((header) => ({ key: header.key, value: header.value })); }
+  each(callback) { this.all().forEach(callback); }
+  count() { return this._enabled().length; }
+  toObject() { return Object.fromEntries(this._enabled().map((header) => [header.key, header.value])); }
+}
+
+function __header(header) {
+  if (typeof header === "string") {
+    const colon = header.indexOf(":");
+    return { key: header.slice(0, colon).trim(), value: header.slice(colon + 1).trim(), disabled: false };
+  }
+  return { key: String(header.key), value: __stringify(header.value), disabled: !!header.disabled };
+}
+
+const __tests = [];
+let __error = null;
+
+const pm = {
+  info: { eventName: __state.listen, iteration: __state.iteration, requestName: __state.path },
+  globals: new VariableScope(__state.variables.globals),
+  collectionVariables: new CollectionVariableScope(
+    __state.variables.collectionDefinitions,
+    __state.variables.collection,
+  ),
+  environment: new VariableScope(__state.variables.environment),
+  variables: new VariableScope(__state.variables.local),
+  request: {
+    url: { _raw: __state.request.url, toString() { return this._raw; } },
+    method: __state.request.method,
+    headers: new HeaderList(__state.request.headers),
+    body: __state.request.body === null ? undefined : {
+      mode: "raw",
+      raw: __state.request.body,
+      update(raw) { this.raw = __stringify(raw); },
+      toString() { return this.raw; },
+    },
+  },
+  test(name, callback) {
+    try {
+      callback();
+      __tests.push({ name: String(name), error: null });
+    } catch (error) {
+      __tests.push({ name: String(name), error: __message(error) });
+    }
+  },
+  expect(value) { return new Assertion(value); },
+};
+pm.test.skip = function () {};
+
+// `pm.variables` reads every scope, from the highest precedence down, but writes local
+// variables.
+const __definitions = new VariableScope(__state.variables.definitions);
+pm.variables.get = function (key) {
+  for (const scope of [this, pm.environment, pm.collectionVariables, __definitions, pm.globals]) {
+    if (VariableScope.prototype.has.call(scope, key)) return VariableScope.prototype.get.call(scope, key);
+  }
+  return undefined;
+};
+pm.variables.has = function (key) { return pm.variables.get(key) !== undefined; };
+pm.variables.replaceIn = function (template) {
+  return String(template).replace(/\{\{([^{}]+)\}\}/g, (placeholder, key) => {
+    const value = pm.variables.get(key);
+    return value === undefined ? placeholder : value;
+  });
+};
+
+if (__state.response !== null) {
+  const response = __state.response;
+  const expectStatus = (passed, expected) => {
+    if (!passed) {
+      throw new AssertionError("expected response to have status code " + expected + " but got " + response.code);
+    }
+  };
+  pm.response = {
+    code: response.code,
+    status: response.status,
+    responseTime: response.responseTime,
+    headers: new HeaderList(response.headers),
+    text() { return response.body; },
+    json() { return JSON.parse(response.body); },
+    to: {
+      have: {
+        status(expected) {
+          if (typeof expected === "string") {
+            if (response.status !== expected) {
+              throw new AssertionError("expected response to have status reason '" + expected + "' but got '" + response.status + "'");
+            }
+          } else expectStatus(response.code === expected, expected);
+        },
+        header(key, value) {
+          const actual = pm.response.headers.get(key);
+          if (actual === undefined) throw new AssertionError("expected response to have header " + __inspect(key));
+          if (arguments.length > 1 && actual !== value) {
+            throw new AssertionError("expected response to have header " + __inspect(key) + " with value " + __inspect(value) + " but got " + __inspect(actual));
+          }
+        },
+        body(expected) {
+          if (arguments.length === 0 ? response.body === "" : response.body !== expected) {
+            throw new AssertionError("expected response to have body " + (arguments.length === 0 ? "" : __inspect(expected)));
+          }
+        },
+        jsonBody(path) {
+          let value;
+          try { value = JSON.parse(response.body); } catch (e) { throw new AssertionError("expected response body to be valid JSON"); }
+          if (path !== undefined && String(path).split(".").reduce((v, key) => (v === undefined || v === null ? undefined : v[key]), value) === undefined) {
+            throw new AssertionError("expected response body to have JSON path " + __inspect(path));
+          }
+        },
+      },
+      be: {
+        get ok() { expectStatus(response.code === 200, 200); return true; },
+        get success() {
+          if (response.code < 200 || response.code > 299) {
+            throw new AssertionError("expected response code to be 2XX but found " + response.code);
+          }
+          return true;
+        },
+      },
+    },
+  };
+}
+
+// Legacy `tests["name"] = passed` assertions.
+const tests = {};
+
+const console = { log() {}, info() {}, warn() {}, error() {}, debug() {} };
+
+function __run(source) {
+  try {
+    // Boa stops reading a function body at a stray `}`, silently dropping the rest of the
+    // script. In a block, the stray brace is a syntax error instead.
+    new Function("{\n" + source + "\n}")();
+  } catch (error) {
+    __error = __message(error);
+  }
+}
+
+// A body assigned as a string or an object is sent as that text.
+function __body(body) {
+  if (body === undefined || body === null) return null;
+  return typeof body === "object" && "raw" in body ? __stringify(body.raw) : __stringify(body);
+}
+
+function __result() {
+  for (const name of Object.keys(tests)) {
+    __tests.push({ name, error: tests[name] ? null : "expected a truthy value" });
+  }
+  return {
+    variables: {
+      globals: pm.globals._pairs,
+      collection: pm.collectionVariables._pairs,
+      environment: pm.environment._pairs,
+      local: pm.variables._pairs,
+    },
+    request: {
+      url: String(pm.request.url),
+      method: String(pm.request.method),
+      headers: pm.request.headers._headers,
+      body: __body(pm.request.body),
+    },
+    tests: __tests,
+    error: __error,
+  };
+}
@@ -0,0 +1,316 @@
+//! Runs `prerequest` and `test` scripts in an embedded JavaScript engine.
+//!
+//! Available with the crate feature `scripts`.
+//!
+//! A [`Sandbox`] is a [`ScriptHook`] that runs each script in a fresh
+//! [Boa](https://boajs.dev) context with a subset of Postman's `pm` API:
+//!
+//! - `pm.environment`, `pm.globals`, `pm.collectionVariables` and `pm.variables`, with `get`,
+//!   `set`, `has`, `unset`, `clear` and `toObject`. `pm.collectionVariables` sees the variables
+//!   the collection defines, under the ones scripts have set; its `unset` and `clear` only remove
+//!   the latter. `pm.variables.get` looks through every scope in Postman's order of precedence,
+//!   and `pm.variables.replaceIn` expands `{{placeholders}}`;
+//! - `pm.request`, whose `url`, `method`, `headers` (`get`, `has`, `add`, `upsert`, `remove`,
+//!   `toObject`) and `body` a pre-request script can change. The body is seen as its `raw`
+//!   text, changed with `update` or by assigning `raw`, and a changed body is sent in `raw`
+//!   mode;
+//! - `pm.response` in test scripts, with `code`, `status`, `responseTime`, `headers`, `text()`,
+//!   `json()` and the `to.have.status`, `to.have.header`, `to.have.body`, `to.have.jsonBody`,
+//!   `to.be.ok` and `to.be.success` assertions;
+//! - `pm.test` and a chai-style `pm.expect`, as well as the legacy `tests["name"] = passed`;
+//! - `pm.info` and a silent `console`.
+//!
+//! Variables are strings: values of other types are stored in their JSON form. Changes to
+//! variables and to the request are written back to the [`ScriptContext`], so they carry over to
+//! the request being run and to the rest of the run. An exception that escapes the script fails
+//! the request with [`Error::Script`], after its variable changes are kept.
+//!
+//! ```
+//! use http::Response;
+//! use postman_collection::from_str;
+//! use postman_collection::runner::Runner;
+//! use postman_collection::runner::sandbox::Sandbox;
+//!
+//! let collection = from_str(r#"{
+//!   "info": {
+//!     "name": "Login",
+//!     "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+//!   },
+//!   "item": [{
+//!     "name": "Login",
+//!     "request": "http://localhost:8080/login",
+//!     "event": [{
+//!       "listen": "test",
+//!       "script": { "exec": [
+//!         "pm.test('returns a token', () => {",
+//!         "  pm.response.to.have.status(200);",
+//!         "  pm.expect(pm.response.json()).to.have.property('token');",
+//!         "});",
+//!         "pm.environment.set('token', pm.response.json().token);"
+//!       ] }
+//!     }]
+//!   }]
+//! }"#)?;
+//!
+//! let summary = Runner::new(|_: http::Request<Vec<u8>>| {
+//!     Ok(Response::new(br#"{"token": "abc"}"#.to_vec()))
+//! })
+//! .with_script_hook(Sandbox::new())
+//! .run(&collection)?;
+//!
+//! assert!(summary.is_success());
+//! assert_eq!(summary.executions[0].tests[0].name, "returns a token");
+//! assert_eq!(summary.variables.environment.get("token"), Some("abc"));
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+
+use boa_engine::{Context, Source};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{Listen, ScriptContext, ScriptHook, TestResult};
+use crate::v2_1_0::{Body, Header, HeaderUnion, Mode, Url, UrlClass};
+use crate::{Error, Result};
+
+const PRELUDE: &str = include_str!("sandbox.js");
+
+/// The default for [`Sandbox::with_loop_iteration_limit`].
+pub const DEFAULT_LOOP_ITERATION_LIMIT: u64 = 1_000_000;
+
+/// Runs scripts with Boa. See the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct Sandbox {
+    loop_iteration_limit: u64,
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sandbox {
+    pub fn new() -> Self {
+        Sandbox {
+            loop_iteration_limit: DEFAULT_LOOP_ITERATION_LIMIT,
+        }
+    }
+
+    /// How many iterations a single loop may run before the script is stopped, so that a
+    /// runaway script cannot hang the run.
+    pub fn with_loop_iteration_limit(mut self, limit: u64) -> Self {
+        self.loop_iteration_limit = limit;
+        self
+    }
+}
+
+impl ScriptHook for Sandbox {
+    fn run(&mut self, context: &mut ScriptContext<'_>) -> Result<Vec<TestResult>> {
+        let headers = request_headers(context.request.header.as_ref());
+        let url = context
+            .request
+            .url
+            .as_ref()
+            .map(|url| url.raw().into_owned())
+            .unwrap_or_default();
+        let method = context
+            .request
+            .method
+            .clone()
+            .unwrap_or_else(|| "GET".to_owned());
+        let body = context
+            .request
+            .body
+            .as_ref()
+            .and_then(|body| body.raw.clone());
+        let state = json!({
+            "listen": match context.listen {
+                Listen::Prerequest => "prerequest",
+                Listen::Test => "test",
+            },
+            "iteration": context.iteration,
+            "path": context.path,
+            "variables": {
+                "globals": context.variables.globals.values,
+                "collection": context.variables.collection.values,
+                "collectionDefinitions": context.collection.values,
+                "environment": context.variables.environment.values,
+                "local": context.variables.local.values,
+                "definitions": context
+                    .definitions
+                    .scopes()
+                    .iter()
+                    .flat_map(|scope| &scope.values)
+                    .collect::<Vec<_>>(),
+            },
+            "request": {
+                "url": url,
+                "method": method,
+                "headers": headers,
+                "body": body,
+            },
+            "response": context.response.map(|response| json!({
+                "code": response.status().as_u16(),
+                "status": response.status().canonical_reason().unwrap_or_default(),
+                "headers": response
+                    .headers()
+                    .iter()
+                    .map(|(key, value)| ScriptHeader {
+                        key: key.to_string(),
+                        value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                        disabled: false,
+                    })
+                    .collect::<Vec<_>>(),
+                "body": String::from_utf8_lossy(response.body()),
+                "responseTime": context.duration.map_or(0, |duration| duration.as_millis()),
+            })),
+        });
+        // The script is compiled on its own, so it cannot close the function it runs in.
+        let program = format!(
+            "const __state = {state};\n{PRELUDE}\n__run({});\nJSON.stringify(__result());",
+            serde_json::to_string(&context.source)?
+        );
+
+        let mut engine = Context::default();
+        engine
+            .runtime_limits_mut()
+            .set_loop_iteration_limit(self.loop_iteration_limit);
+        let output = engine
+            .eval(Source::from_bytes(program.as_bytes()))
+            .map_err(|error| Error::Script {
+                message: error.to_string(),
+            })?;
+        let output = output
+            .as_string()
+            .map(|output| output.to_std_string_escaped())
+            .unwrap_or_default();
+        let outcome: Outcome = serde_json::from_str(&output)?;
+
+        let variables = &mut *context.variables;
+        variables.globals.values = outcome.variables.globals;
+        variables.collection.values = outcome.variables.collection;
+        variables.environment.values = outcome.variables.environment;
+        variables.local.values = outcome.variables.local;
+
+        if context.listen == Listen::Prerequest {
+            let request = &mut *context.request;
+            if outcome.request.method != method {
+                request.method = Some(outcome.request.method);
+            }
+            if outcome.request.url != url {
+                let mut parsed = UrlClass::parse(&outcome.request.url);
+                if let Some(Url::UrlClass(previous)) = request.url.as_ref() {
+                    parsed.variable.clone_from(&previous.variable);
+                }
+                request.url = Some(Url::UrlClass(parsed));
+            }
+            if outcome.request.headers != headers {
+                request.header = Some(HeaderUnion::HeaderArray(
+                    outcome
+                        .request
+                        .headers
+                        .into_iter()
+                        .map(|header| Header {
+                            description: None,
+                            disabled: header.disabled.then_some(true),
+                            key: header.key,
+                            value: header.value,
+                        })
+                        .collect(),
+                ));
+            }
+            if outcome.request.body != body {
+                match outcome.request.body {
+                    Some(raw) => {
+                        let body = request.body.get_or_insert_with(Body::default);
+                        body.mode = Some(Mode::Raw);
+                        body.raw = Some(raw);
+                    }
+                    None => request.body = None,
+                }
+            }
+        }
+
+        match outcome.error {
+            Some(message) => Err(Error::Script { message }),
+            None => Ok(outcome
+                .tests
+                .into_iter()
+                .map(|test| TestResult {
+                    name: test.name,
+                    error: test.error,
+                })
+                .collect()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct ScriptHeader {
+    key: String,
+    value: String,
+    #[serde(default)]
+    disabled: bool,
+}
+
+/// The headers of a request, with the `//` marker of the string form read as `disabled`.
+fn request_headers(header: Option<&HeaderUnion>) -> Vec<ScriptHeader> {
+    match header {
+        Some(HeaderUnion::HeaderArray(headers)) => headers
+            .iter()
+            .map(|header| ScriptHeader {
+                key: header.key.clone(),
+                value: header.value.clone(),
+                disabled: header.disabled.unwrap_or(false),
+            })
+            .collect(),
+        Some(HeaderUnion::String(headers)) => headers
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| {
+                let (disabled, line) = match line.strip_prefix("//") {
+                    Some(line) => (true, line),
+                    None => (false, line),
+                };
+                let (key, value) = line.split_once(':')?;
+                Some(ScriptHeader {
+                    key: key.trim().to_owned(),
+                    value: value.trim().to_owned(),
+                    disabled,
+                })
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[derive(Deserialize)]
+struct Outcome {
+    variables: OutcomeVariables,
+    request: OutcomeRequest,
+    tests: Vec<OutcomeTest>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OutcomeVariables {
+    globals: Vec<(String, String)>,
+    collection: Vec<(String, String)>,
+    environment: Vec<(String, String)>,
+    local: Vec<(String, String)>,
+}
+
+#[derive(Deserialize)]
+struct OutcomeRequest {
+    url: String,
+    method: String,
+    headers: Vec<ScriptHeader>,
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OutcomeTest {
+    name: String,
+    error: Option<String>,
+}
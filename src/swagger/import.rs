@@ -0,0 +1,450 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+
+use super::{
+    Operation, Parameter, ParameterLocation, PathItem, Response, SecurityDefinition, Swagger,
+};
+use crate::openapi::{
+    self, Components, MediaType, OAuthFlow, OAuthFlows, OpenApi, ReferenceOr, RequestBody,
+    SecurityScheme, Server,
+};
+use crate::v2_1_0::Spec;
+
+/// The media type of bodies and responses when neither the operation nor the document lists
+/// any.
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+/// Parameter fields that are JSON Schema validation keywords.
+const VALIDATION_KEYWORDS: [&str; 11] = [
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "multipleOf",
+];
+
+impl Spec {
+    /// Generates a collection from a Swagger 2.0 document: the document is converted with
+    /// [`Swagger::to_openapi`] and imported like an OpenAPI 3 document with
+    /// [`Spec::from_openapi`].
+    ///
+    /// `host` and `basePath` make up `{{baseUrl}}`, `formData` parameters become
+    /// [`FormParameter`](crate::v2_1_0::FormParameter)s, or urlencoded fields for operations
+    /// that only consume `application/x-www-form-urlencoded`, and `securityDefinitions` become
+    /// [`Auth`](crate::v2_1_0::Auth).
+    pub fn from_swagger(document: &Swagger) -> Spec {
+        Spec::from_openapi(&document.to_openapi())
+    }
+}
+
+impl Swagger {
+    /// Converts the document to OpenAPI 3.0:
+    ///
+    /// - `schemes`, `host` and `basePath` become a server for each scheme, `https` when none is
+    ///   listed. Operations with schemes of their own get servers of their own;
+    /// - a `body` parameter becomes the request body, with its schema under each media type
+    ///   the operation consumes;
+    /// - `formData` parameters become the properties of a `multipart/form-data` body, or of an
+    ///   `application/x-www-form-urlencoded` body when the operation consumes only that.
+    ///   `file` fields are binary strings;
+    /// - other parameters get a schema from their `type`, `format`, `items`, `default`, `enum`
+    ///   and validation keywords, and an example from `x-example`;
+    /// - response schemas are listed under each media type the operation produces, together
+    ///   with the response `examples`;
+    /// - `definitions` become component schemas, with `#/definitions/...` references rewritten
+    ///   to point to them, and `securityDefinitions` become security schemes.
+    ///
+    /// Operations and documents that list no media types use `application/json`. References
+    /// to `#/parameters/...` and `#/responses/...` are resolved; other references to
+    /// parameters and responses are left out.
+    pub fn to_openapi(&self) -> OpenApi {
+        let mut paths = BTreeMap::new();
+        for (path, path_item) in &self.paths {
+            let mut converted = openapi::PathItem {
+                extensions: path_item.extensions.clone(),
+                ..openapi::PathItem::default()
+            };
+            for (method, operation) in path_item.operations() {
+                if let Some(slot) = converted.operation_mut(method) {
+                    *slot = Some(self.operation(path_item, operation));
+                }
+            }
+            paths.insert(path.clone(), converted);
+        }
+
+        let components = Components {
+            schemas: self
+                .definitions
+                .iter()
+                .map(|(name, definition)| (name.clone(), schema(definition)))
+                .collect(),
+            security_schemes: self
+                .security_definitions
+                .iter()
+                .map(|(name, definition)| {
+                    (name.clone(), ReferenceOr::Item(security_scheme(definition)))
+                })
+                .collect(),
+            ..Components::default()
+        };
+        let empty = components.schemas.is_empty() && components.security_schemes.is_empty();
+
+        OpenApi {
+            openapi: "3.0.3".to_owned(),
+            info: self.info.clone(),
+            servers: self.servers(&self.schemes),
+            paths,
+            components: (!empty).then_some(components),
+            security: self.security.clone(),
+            tags: self.tags.clone(),
+            extensions: self.extensions.clone(),
+        }
+    }
+
+    /// A server for each of `schemes`, or a relative one when there is no host.
+    fn servers(&self, schemes: &[String]) -> Vec<Server> {
+        let base_path = self
+            .base_path
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end_matches('/');
+        let url = match self.host.as_deref() {
+            Some(host) if schemes.is_empty() => vec![format!("https://{host}{base_path}")],
+            Some(host) => schemes
+                .iter()
+                .map(|scheme| format!("{scheme}://{host}{base_path}"))
+                .collect(),
+            None if !base_path.is_empty() => vec![base_path.to_owned()],
+            None => Vec::new(),
+        };
+        url.into_iter()
+            .map(|url| Server {
+                url,
+                ..Server::default()
+            })
+            .collect()
+    }
+
+    fn operation(&self, path_item: &PathItem, operation: &Operation) -> openapi::Operation {
+        let parameters = self.parameters(path_item, operation);
+        let consumes = media_types(operation.consumes.as_deref().unwrap_or(&self.consumes));
+        let produces = media_types(operation.produces.as_deref().unwrap_or(&self.produces));
+
+        let body = parameters
+            .iter()
+            .find(|parameter| parameter.location == ParameterLocation::Body);
+        let fields: Vec<&Parameter> = parameters
+            .iter()
+            .copied()
+            .filter(|parameter| parameter.location == ParameterLocation::FormData)
+            .collect();
+        let request_body = match body {
+            Some(body) => Some(RequestBody {
+                description: body.description.clone(),
+                content: consumes
+                    .iter()
+                    .map(|media_type| {
+                        let media = MediaType {
+                            schema: body.schema.as_ref().map(schema),
+                            ..MediaType::default()
+                        };
+                        (media_type.clone(), media)
+                    })
+                    .collect(),
+                required: body.required,
+            }),
+            None if !fields.is_empty() => Some(form_body(&fields, &consumes)),
+            None => None,
+        };
+
+        let responses = operation
+            .responses
+            .iter()
+            .filter_map(|(code, response)| {
+                let response = match response {
+                    ReferenceOr::Item(response) => response,
+                    ReferenceOr::Reference { reference } => {
+                        self.responses.get(&referenced(reference, "responses")?)?
+                    }
+                };
+                Some((
+                    code.clone(),
+                    ReferenceOr::Item(self::response(response, &produces)),
+                ))
+            })
+            .collect();
+
+        openapi::Operation {
+            tags: operation.tags.clone(),
+            summary: operation.summary.clone(),
+            description: operation.description.clone(),
+            operation_id: operation.operation_id.clone(),
+            parameters: parameters
+                .iter()
+                .filter_map(|parameter| Some(ReferenceOr::Item(self::parameter(parameter)?)))
+                .collect(),
+            request_body: request_body.map(ReferenceOr::Item),
+            responses,
+            deprecated: operation.deprecated,
+            security: operation.security.clone(),
+            servers: if operation.schemes.is_empty() {
+                Vec::new()
+            } else {
+                self.servers(&operation.schemes)
+            },
+            extensions: operation.extensions.clone(),
+        }
+    }
+
+    /// The path's parameters and the operation's, which replace path parameters with the same
+    /// name and location.
+    fn parameters<'a>(
+        &'a self,
+        path_item: &'a PathItem,
+        operation: &'a Operation,
+    ) -> Vec<&'a Parameter> {
+        openapi::merge_parameters(
+            path_item
+                .parameters
+                .iter()
+                .chain(&operation.parameters)
+                .filter_map(|parameter| match parameter {
+                    ReferenceOr::Item(parameter) => Some(parameter),
+                    ReferenceOr::Reference { reference } => {
+                        self.parameters.get(&referenced(reference, "parameters")?)
+                    }
+                }),
+            |a, b| a.name == b.name && a.location == b.location,
+        )
+    }
+}
+
+/// A query, header or path parameter. `None` for body and form fields.
+fn parameter(parameter: &Parameter) -> Option<openapi::Parameter> {
+    let location = match parameter.location {
+        ParameterLocation::Query => openapi::ParameterLocation::Query,
+        ParameterLocation::Header => openapi::ParameterLocation::Header,
+        ParameterLocation::Path => openapi::ParameterLocation::Path,
+        ParameterLocation::FormData | ParameterLocation::Body => return None,
+    };
+    Some(openapi::Parameter {
+        name: parameter.name.clone(),
+        location,
+        description: parameter.description.clone(),
+        required: parameter.required,
+        schema: Some(parameter_schema(parameter)),
+        example: parameter.extensions.get("x-example").cloned(),
+        ..openapi::Parameter::default()
+    })
+}
+
+/// The request body of `formData` parameters.
+fn form_body(fields: &[&Parameter], consumes: &[String]) -> RequestBody {
+    let consumes = |media_type: &str| {
+        consumes
+            .iter()
+            .any(|consumed| consumed.to_ascii_lowercase().starts_with(media_type))
+    };
+    let files = fields
+        .iter()
+        .any(|field| field.parameter_type.as_deref() == Some("file"));
+    let media_type = if !files
+        && consumes("application/x-www-form-urlencoded")
+        && !consumes("multipart/form-data")
+    {
+        "application/x-www-form-urlencoded"
+    } else {
+        "multipart/form-data"
+    };
+
+    let mut properties = Map::new();
+    for field in fields {
+        let mut property = parameter_schema(field);
+        if let (Some(description), Value::Object(property)) = (&field.description, &mut property) {
+            property.insert("description".to_owned(), Value::String(description.clone()));
+        }
+        if let Some(example) = field.extensions.get("x-example") {
+            property["example"] = example.clone();
+        }
+        properties.insert(field.name.clone(), property);
+    }
+    let required: Vec<Value> = fields
+        .iter()
+        .filter(|field| field.required.unwrap_or(false))
+        .map(|field| Value::String(field.name.clone()))
+        .collect();
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+
+    RequestBody {
+        content: BTreeMap::from([(
+            media_type.to_owned(),
+            MediaType {
+                schema: Some(schema),
+                ..MediaType::default()
+            },
+        )]),
+        ..RequestBody::default()
+    }
+}
+
+fn response(response: &Response, produces: &[String]) -> openapi::Response {
+    let mut content: BTreeMap<String, MediaType> = BTreeMap::new();
+    if let Some(schema) = response.schema.as_ref() {
+        for media_type in produces {
+            content.insert(
+                media_type.clone(),
+                MediaType {
+                    schema: Some(self::schema(schema)),
+                    ..MediaType::default()
+                },
+            );
+        }
+    }
+    for (media_type, example) in &response.examples {
+        content.entry(media_type.clone()).or_default().example = Some(example.clone());
+    }
+
+    // Swagger header objects hold the schema keywords themselves.
+    let headers = response
+        .headers
+        .iter()
+        .map(|(name, header)| {
+            let mut keywords = header.as_object().cloned().unwrap_or_default();
+            let mut converted = Map::new();
+            if let Some(description) = keywords.remove("description") {
+                converted.insert("description".to_owned(), description);
+            }
+            converted.insert("schema".to_owned(), schema(&Value::Object(keywords)));
+            (name.clone(), Value::Object(converted))
+        })
+        .collect();
+
+    openapi::Response {
+        description: response.description.clone(),
+        headers,
+        content,
+        extensions: response.extensions.clone(),
+    }
+}
+
+/// The schema of a parameter that is not a `body` parameter.
+fn parameter_schema(parameter: &Parameter) -> Value {
+    if parameter.parameter_type.as_deref() == Some("file") {
+        return json!({ "type": "string", "format": "binary" });
+    }
+
+    let mut schema: Map<String, Value> = parameter
+        .extensions
+        .iter()
+        .filter(|(key, _)| VALIDATION_KEYWORDS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let mut insert = |key: &str, value: Option<Value>| {
+        if let Some(value) = value {
+            schema.insert(key.to_owned(), value);
+        }
+    };
+    insert("type", parameter.parameter_type.clone().map(Value::String));
+    insert("format", parameter.format.clone().map(Value::String));
+    insert("items", parameter.items.as_ref().map(self::schema));
+    insert("default", parameter.default.clone());
+    insert("enum", parameter.allowed.clone().map(Value::Array));
+    Value::Object(schema)
+}
+
+/// `schema` with `#/definitions/...` references pointing to the component schemas.
+fn schema(schema: &Value) -> Value {
+    match schema {
+        Value::Object(members) => Value::Object(
+            members
+                .iter()
+                .map(|(key, value)| {
+                    let value = match (key.as_str(), value) {
+                        ("$ref", Value::String(reference)) => {
+                            Value::String(match reference.strip_prefix("#/definitions/") {
+                                Some(name) => format!("#/components/schemas/{name}"),
+                                None => reference.clone(),
+                            })
+                        }
+                        _ => self::schema(value),
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(self::schema).collect()),
+        value => value.clone(),
+    }
+}
+
+fn security_scheme(definition: &SecurityDefinition) -> SecurityScheme {
+    match definition {
+        SecurityDefinition::Basic { description } => SecurityScheme::Http {
+            scheme: "basic".to_owned(),
+            bearer_format: None,
+            description: description.clone(),
+        },
+        SecurityDefinition::ApiKey {
+            name,
+            location,
+            description,
+        } => SecurityScheme::ApiKey {
+            name: name.clone(),
+            location: location.clone(),
+            description: description.clone(),
+        },
+        SecurityDefinition::OAuth2 {
+            flow,
+            authorization_url,
+            token_url,
+            scopes,
+            description,
+        } => {
+            let converted = OAuthFlow {
+                authorization_url: authorization_url.clone(),
+                token_url: token_url.clone(),
+                refresh_url: None,
+                scopes: scopes.clone(),
+            };
+            let mut flows = OAuthFlows::default();
+            match flow.as_str() {
+                "implicit" => flows.implicit = Some(converted),
+                "password" => flows.password = Some(converted),
+                "application" => flows.client_credentials = Some(converted),
+                _ => flows.authorization_code = Some(converted),
+            }
+            SecurityScheme::OAuth2 {
+                flows: Box::new(flows),
+                description: description.clone(),
+            }
+        }
+    }
+}
+
+/// `media_types`, or `application/json` when there are none.
+fn media_types(media_types: &[String]) -> Vec<String> {
+    if media_types.is_empty() {
+        vec![DEFAULT_MEDIA_TYPE.to_owned()]
+    } else {
+        media_types.to_vec()
+    }
+}
+
+/// The name a `#/<kind>/<name>` reference points to.
+fn referenced(reference: &str, kind: &str) -> Option<String> {
+    let name = reference
+        .strip_prefix("#/")?
+        .strip_prefix(kind)?
+        .strip_prefix('/')?;
+    Some(name.replace("~1", "/").replace("~0", "~"))
+}
@@ -0,0 +1,418 @@
+//! [Swagger 2.0](https://swagger.io/specification/v2/) (OpenAPI 2) documents.
+//!
+//! Swagger 2.0 describes request bodies as `body` and `formData` parameters and lists media
+//! types once per document or operation in `consumes` and `produces`, where OpenAPI 3 has
+//! request bodies and responses keyed by media type. The types in this module model the
+//! Swagger layout; schemas are kept as JSON values and fields the types do not model, such as
+//! `x-` extensions, are kept in the `extensions` maps.
+//!
+//! [`Swagger::to_openapi`] converts a document to the [`openapi`](crate::openapi) model, and
+//! [`Spec::from_swagger`](crate::v2_1_0::Spec::from_swagger) generates a collection from it:
+//!
+//! ```
+//! use postman_collection::swagger;
+//! use postman_collection::v2_1_0::{Items, Mode, RequestUnion, Spec};
+//!
+//! let document = swagger::from_str(r#"{
+//!   "swagger": "2.0",
+//!   "info": { "title": "Pets", "version": "1.0" },
+//!   "host": "api.example.com",
+//!   "basePath": "/v1",
+//!   "schemes": ["https"],
+//!   "paths": {
+//!     "/pets/{id}/photo": {
+//!       "post": {
+//!         "summary": "Upload a photo",
+//!         "consumes": ["multipart/form-data"],
+//!         "parameters": [
+//!           { "name": "id", "in": "path", "required": true, "type": "integer" },
+//!           { "name": "photo", "in": "formData", "type": "file" }
+//!         ],
+//!         "responses": { "204": { "description": "Uploaded" } }
+//!       }
+//!     }
+//!   }
+//! }"#)?;
+//!
+//! let spec = Spec::from_swagger(&document);
+//! let Items::Item(item) = &spec.item[0] else { unreachable!() };
+//! let RequestUnion::RequestClass(request) = &item.request else { unreachable!() };
+//!
+//! assert_eq!(request.url.as_ref().unwrap().raw(), "{{baseUrl}}/pets/:id/photo");
+//! assert_eq!(request.body.as_ref().unwrap().mode, Some(Mode::Formdata));
+//! # Ok::<(), postman_collection::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::{fs::File, io::Read, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::openapi::{Info, ReferenceOr, SecurityRequirement, Tag};
+use crate::{Error, Result};
+
+mod import;
+
+/// The root of a Swagger 2.0 document.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Swagger {
+    /// Always `2.0`.
+    #[serde(rename = "swagger")]
+    pub swagger: String,
+
+    #[serde(rename = "info")]
+    pub info: Info,
+
+    /// The host, and optionally the port, serving the API.
+    #[serde(rename = "host", skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    /// The path every operation path is relative to, such as `/v1`.
+    #[serde(rename = "basePath", skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+
+    /// `http`, `https`, `ws` or `wss`.
+    #[serde(rename = "schemes", default, skip_serializing_if = "Vec::is_empty")]
+    pub schemes: Vec<String>,
+
+    /// The media types operations accept, unless they list their own.
+    #[serde(rename = "consumes", default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes: Vec<String>,
+
+    /// The media types operations respond with, unless they list their own.
+    #[serde(rename = "produces", default, skip_serializing_if = "Vec::is_empty")]
+    pub produces: Vec<String>,
+
+    /// Operations by path template, such as `/users/{id}`.
+    #[serde(rename = "paths", default)]
+    pub paths: BTreeMap<String, PathItem>,
+
+    /// Schemas that `#/definitions/...` references point to.
+    #[serde(
+        rename = "definitions",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub definitions: BTreeMap<String, Value>,
+
+    /// Parameters that `#/parameters/...` references point to.
+    #[serde(
+        rename = "parameters",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub parameters: BTreeMap<String, Parameter>,
+
+    /// Responses that `#/responses/...` references point to.
+    #[serde(
+        rename = "responses",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub responses: BTreeMap<String, Response>,
+
+    #[serde(
+        rename = "securityDefinitions",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub security_definitions: BTreeMap<String, SecurityDefinition>,
+
+    /// The security requirements of every operation that does not set its own.
+    #[serde(rename = "security", skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    #[serde(rename = "tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct PathItem {
+    #[serde(rename = "get", skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+
+    #[serde(rename = "put", skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+
+    #[serde(rename = "post", skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+
+    #[serde(rename = "delete", skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+
+    #[serde(rename = "options", skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+
+    #[serde(rename = "head", skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+
+    #[serde(rename = "patch", skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+
+    /// Parameters of every operation on the path.
+    #[serde(rename = "parameters", default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl PathItem {
+    /// The operations that are set, with their upper-case method, in the order Postman lists
+    /// them.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("GET", &self.get),
+            ("POST", &self.post),
+            ("PUT", &self.put),
+            ("PATCH", &self.patch),
+            ("DELETE", &self.delete),
+            ("HEAD", &self.head),
+            ("OPTIONS", &self.options),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| Some((method, operation.as_ref()?)))
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Operation {
+    #[serde(rename = "tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    #[serde(rename = "summary", skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+
+    /// Overrides the document's [`consumes`](Swagger::consumes).
+    #[serde(rename = "consumes", skip_serializing_if = "Option::is_none")]
+    pub consumes: Option<Vec<String>>,
+
+    /// Overrides the document's [`produces`](Swagger::produces).
+    #[serde(rename = "produces", skip_serializing_if = "Option::is_none")]
+    pub produces: Option<Vec<String>>,
+
+    #[serde(rename = "parameters", default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+
+    /// Responses by status code, such as `200` or `default`.
+    #[serde(rename = "responses", default)]
+    pub responses: BTreeMap<String, ReferenceOr<Response>>,
+
+    /// Overrides the document's [`schemes`](Swagger::schemes).
+    #[serde(rename = "schemes", default, skip_serializing_if = "Vec::is_empty")]
+    pub schemes: Vec<String>,
+
+    #[serde(rename = "deprecated", skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// Overrides the document's requirements. An empty list turns security off.
+    #[serde(rename = "security", skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+/// Where a [`Parameter`] is sent.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+pub enum ParameterLocation {
+    #[default]
+    #[serde(rename = "query")]
+    Query,
+
+    #[serde(rename = "header")]
+    Header,
+
+    #[serde(rename = "path")]
+    Path,
+
+    /// A field of a `application/x-www-form-urlencoded` or `multipart/form-data` body.
+    #[serde(rename = "formData")]
+    FormData,
+
+    /// The request body, described by [`schema`](Parameter::schema).
+    #[serde(rename = "body")]
+    Body,
+}
+
+/// A parameter. `body` parameters describe their value with a [`schema`](Parameter::schema),
+/// the others with [`parameter_type`](Parameter::parameter_type) and the fields next to it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Parameter {
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "in")]
+    pub location: ParameterLocation,
+
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(rename = "required", skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(rename = "schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+
+    /// `string`, `number`, `integer`, `boolean`, `array` or, for `formData`, `file`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub parameter_type: Option<String>,
+
+    #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// The schema of the elements of an `array` parameter.
+    #[serde(rename = "items", skip_serializing_if = "Option::is_none")]
+    pub items: Option<Value>,
+
+    /// How array elements are joined: `csv`, `ssv`, `tsv`, `pipes` or `multi`.
+    #[serde(rename = "collectionFormat", skip_serializing_if = "Option::is_none")]
+    pub collection_format: Option<String>,
+
+    #[serde(rename = "default", skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<Value>>,
+
+    /// Validation keywords such as `minimum` or `pattern`, and `x-` extensions.
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Response {
+    #[serde(rename = "description", default)]
+    pub description: String,
+
+    #[serde(rename = "schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+
+    /// [Header objects](https://swagger.io/specification/v2/#header-object) by name.
+    #[serde(
+        rename = "headers",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub headers: BTreeMap<String, Value>,
+
+    /// Example bodies by media type.
+    #[serde(
+        rename = "examples",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub examples: BTreeMap<String, Value>,
+
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+/// How an API is secured.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum SecurityDefinition {
+    #[serde(rename = "basic")]
+    Basic {
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        /// The header or query parameter name.
+        #[serde(rename = "name")]
+        name: String,
+
+        /// `header` or `query`.
+        #[serde(rename = "in")]
+        location: String,
+
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        /// `implicit`, `password`, `application` or `accessCode`.
+        #[serde(rename = "flow")]
+        flow: String,
+
+        #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+        authorization_url: Option<String>,
+
+        #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+        token_url: Option<String>,
+
+        /// Scope descriptions by scope name.
+        #[serde(rename = "scopes", default)]
+        scopes: BTreeMap<String, String>,
+
+        #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+}
+
+/// Deserialize a Swagger document from a path
+pub fn from_path<P>(path: P) -> Result<Swagger>
+where
+    P: AsRef<Path>,
+{
+    from_reader(File::open(path)?)
+}
+
+/// Deserialize a Swagger document from a string slice
+pub fn from_str(input: &str) -> Result<Swagger> {
+    from_slice(input.as_bytes())
+}
+
+/// Deserialize a Swagger document from a byte slice. Fails for documents that are not
+/// Swagger 2.0.
+pub fn from_slice(input: &[u8]) -> Result<Swagger> {
+    let value = crate::value_from_slice(input)?;
+    let version = value
+        .get("swagger")
+        .or_else(|| value.get("openapi"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if value.get("swagger").is_none() || version != "2.0" {
+        return Err(Error::UnsupportedSwaggerVersion {
+            version: version.to_owned(),
+        });
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Deserialize a Swagger document from type which implements Read
+pub fn from_reader<R>(mut read: R) -> Result<Swagger>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    read.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+/// Serialize a Swagger document to a JSON string
+pub fn to_json(document: &Swagger) -> Result<String> {
+    Ok(serde_json::to_string_pretty(document)?)
+}
+
+/// Serialize a Swagger document to a YAML string.
+///
+/// Available with the crate feature `yaml`.
+#[cfg(feature = "yaml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+pub fn to_yaml(document: &Swagger) -> Result<String> {
+    Ok(yaml_serde::to_string(document)?)
+}
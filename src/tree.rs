@@ -0,0 +1,553 @@
+//! Code shared by the v2.0.0 and v2.1.0 modules, whose item trees have the same shape.
+//!
+//! Each macro expands to items that refer to the `Spec`, `Item`, `ItemGroup` and other model
+//! types through `super::`, so it is invoked from a submodule of the version module.
+
+use std::borrow::Cow;
+
+/// A folder or item name as written in an item path: `\` and `/` are escaped with a backslash.
+pub(crate) fn path_segment(name: &str) -> Cow<'_, str> {
+    if name.contains(['\\', '/']) {
+        Cow::Owned(name.replace('\\', "\\\\").replace('/', "\\/"))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// The contents of a version's `iter` module.
+macro_rules! item_iter {
+    () => {
+        use std::iter::Enumerate;
+        use std::slice;
+
+        use super::{Item, ItemGroup, Items, Spec};
+        use crate::tree::path_segment;
+
+        impl Spec {
+            /// Iterates over every [`Item`] of the collection, depth first and in document order,
+            /// together with the folders that contain it.
+            pub fn items(&self) -> ItemIter<'_> {
+                ItemIter {
+                    stack: vec![self.item.iter().enumerate()],
+                    ancestors: Vec::new(),
+                    indices: Vec::new(),
+                    names: Vec::new(),
+                }
+            }
+
+            /// Like [`items`](Self::items), but hands out mutable items so they can be edited in
+            /// place.
+            pub fn items_mut(&mut self) -> ItemIterMut<'_> {
+                ItemIterMut {
+                    stack: vec![self.item.iter_mut().enumerate()],
+                    ancestors: Vec::new(),
+                    indices: Vec::new(),
+                    names: Vec::new(),
+                }
+            }
+        }
+
+        /// An [`Item`] and the [`ItemGroup`]s it is nested in.
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct ItemEntry<'a> {
+            /// The enclosing folders, outermost first.
+            pub ancestors: Vec<&'a ItemGroup>,
+            /// The position of each enclosing folder within its parent, outermost first, and
+            /// then the position of the item. Unlike the path, it tells apart siblings with the
+            /// same name.
+            pub indices: Vec<usize>,
+            /// Folder and item names joined with `/`, such as `Users/Admin/Create user`. A `/`
+            /// or `\` within a name is escaped with a backslash, so an item named `GET /users`
+            /// in the folder `Users` has the path `Users/GET \/users`. Unnamed folders and items
+            /// are represented by their position within their parent.
+            pub path: String,
+            pub item: &'a Item,
+        }
+
+        /// A mutable [`Item`] and where it is. See [`ItemEntry`].
+        #[derive(Debug, PartialEq)]
+        pub struct ItemEntryMut<'a> {
+            /// The names of the enclosing folders, outermost first.
+            pub ancestors: Vec<Option<&'a str>>,
+            /// See [`ItemEntry::indices`].
+            pub indices: Vec<usize>,
+            /// See [`ItemEntry::path`].
+            pub path: String,
+            pub item: &'a mut Item,
+        }
+
+        /// Iterator returned by [`Spec::items`].
+        #[derive(Clone, Debug)]
+        pub struct ItemIter<'a> {
+            stack: Vec<Enumerate<slice::Iter<'a, Items>>>,
+            ancestors: Vec<&'a ItemGroup>,
+            indices: Vec<usize>,
+            names: Vec<String>,
+        }
+
+        impl<'a> Iterator for ItemIter<'a> {
+            type Item = ItemEntry<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                loop {
+                    match self.stack.last_mut()?.next() {
+                        Some((index, Items::ItemGroup(group))) => {
+                            self.names.push(segment(group.name.as_deref(), index));
+                            self.ancestors.push(group);
+                            self.indices.push(index);
+                            self.stack.push(group.item.iter().enumerate());
+                        }
+                        Some((index, Items::Item(item))) => {
+                            return Some(ItemEntry {
+                                ancestors: self.ancestors.clone(),
+                                indices: indices(&self.indices, index),
+                                path: path(&self.names, item.name.as_deref(), index),
+                                item,
+                            });
+                        }
+                        None => {
+                            self.stack.pop();
+                            self.ancestors.pop();
+                            self.indices.pop();
+                            self.names.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        /// Iterator returned by [`Spec::items_mut`].
+        #[derive(Debug)]
+        pub struct ItemIterMut<'a> {
+            stack: Vec<Enumerate<slice::IterMut<'a, Items>>>,
+            ancestors: Vec<Option<&'a str>>,
+            indices: Vec<usize>,
+            names: Vec<String>,
+        }
+
+        impl<'a> Iterator for ItemIterMut<'a> {
+            type Item = ItemEntryMut<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                loop {
+                    match self.stack.last_mut()?.next() {
+                        Some((index, Items::ItemGroup(group))) => {
+                            let ItemGroup { name, item, .. } = group;
+                            let name: &'a Option<String> = name;
+                            self.names.push(segment(name.as_deref(), index));
+                            self.ancestors.push(name.as_deref());
+                            self.indices.push(index);
+                            self.stack.push(item.iter_mut().enumerate());
+                        }
+                        Some((index, Items::Item(item))) => {
+                            return Some(ItemEntryMut {
+                                ancestors: self.ancestors.clone(),
+                                indices: indices(&self.indices, index),
+                                path: path(&self.names, item.name.as_deref(), index),
+                                item,
+                            });
+                        }
+                        None => {
+                            self.stack.pop();
+                            self.ancestors.pop();
+                            self.indices.pop();
+                            self.names.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        fn segment(name: Option<&str>, index: usize) -> String {
+            name.map_or_else(|| index.to_string(), |name| path_segment(name).into_owned())
+        }
+
+        fn indices(folders: &[usize], index: usize) -> Vec<usize> {
+            let mut indices = folders.to_vec();
+            indices.push(index);
+            indices
+        }
+
+        fn path(names: &[String], name: Option<&str>, index: usize) -> String {
+            let mut path = names.join("/");
+            if !path.is_empty() {
+                path.push('/');
+            }
+            path.push_str(&segment(name, index));
+            path
+        }
+    };
+}
+
+pub(crate) use item_iter;
+
+/// The contents of a version's `visit` module.
+macro_rules! visit {
+    () => {
+        use super::{
+            Auth, Body, Event, Header, HeaderElement, HeaderUnion, Headers, Item, ItemGroup, Items,
+            QueryParam, RequestClass, RequestUnion, ResponseClass, Spec, Url, Variable,
+        };
+
+        /// Read-only visitor. See the [module documentation](self).
+        pub trait Visitor<'a> {
+            fn visit_spec(&mut self, spec: &'a Spec, location: &str) {
+                walk_spec(self, spec, location);
+            }
+
+            fn visit_item_group(&mut self, group: &'a ItemGroup, location: &str) {
+                walk_item_group(self, group, location);
+            }
+
+            fn visit_item(&mut self, item: &'a Item, location: &str) {
+                walk_item(self, item, location);
+            }
+
+            fn visit_request(&mut self, request: &'a RequestClass, location: &str) {
+                walk_request(self, request, location);
+            }
+
+            fn visit_body(&mut self, _body: &'a Body, _location: &str) {}
+
+            fn visit_header(&mut self, _header: &'a Header, _location: &str) {}
+
+            fn visit_query_param(&mut self, _param: &'a QueryParam, _location: &str) {}
+
+            fn visit_variable(&mut self, _variable: &'a Variable, _location: &str) {}
+
+            fn visit_event(&mut self, _event: &'a Event, _location: &str) {}
+
+            fn visit_response(&mut self, response: &'a ResponseClass, location: &str) {
+                walk_response(self, response, location);
+            }
+
+            fn visit_auth(&mut self, _auth: &'a Auth, _location: &str) {}
+        }
+
+        pub fn walk_spec<'a, V>(visitor: &mut V, spec: &'a Spec, location: &str)
+        where
+            V: Visitor<'a> + ?Sized,
+        {
+            if let Some(auth) = spec.auth.as_ref() {
+                visitor.visit_auth(auth, &format!("{location}/auth"));
+            }
+            for (index, event) in spec.event.iter().flatten().enumerate() {
+                visitor.visit_event(event, &format!("{location}/event/{index}"));
+            }
+            for (index, variable) in spec.variable.iter().flatten().enumerate() {
+                visitor.visit_variable(variable, &format!("{location}/variable/{index}"));
+            }
+            walk_items(visitor, &spec.item, location);
+        }
+
+        pub fn walk_item_group<'a, V>(visitor: &mut V, group: &'a ItemGroup, location: &str)
+        where
+            V: Visitor<'a> + ?Sized,
+        {
+            if let Some(auth) = group.auth.as_ref() {
+                visitor.visit_auth(auth, &format!("{location}/auth"));
+            }
+            for (index, event) in group.event.iter().flatten().enumerate() {
+                visitor.visit_event(event, &format!("{location}/event/{index}"));
+            }
+            for (index, variable) in group.variable.iter().flatten().enumerate() {
+                visitor.visit_variable(variable, &format!("{location}/variable/{index}"));
+            }
+            walk_items(visitor, &group.item, location);
+        }
+
+        fn walk_items<'a, V>(visitor: &mut V, items: &'a [Items], location: &str)
+        where
+            V: Visitor<'a> + ?Sized,
+        {
+            for (index, item) in items.iter().enumerate() {
+                let location = format!("{location}/item/{index}");
+                match item {
+                    Items::ItemGroup(group) => visitor.visit_item_group(group, &location),
+                    Items::Item(item) => visitor.visit_item(item, &location),
+                }
+            }
+        }
+
+        pub fn walk_item<'a, V>(visitor: &mut V, item: &'a Item, location: &str)
+        where
+            V: Visitor<'a> + ?Sized,
+        {
+            for (index, event) in item.event.iter().flatten().enumerate() {
+                visitor.visit_event(event, &format!("{location}/event/{index}"));
+            }
+            for (index, variable) in item.variable.iter().flatten().enumerate() {
+                visitor.visit_variable(variable, &format!("{location}/variable/{index}"));
+            }
+            if let RequestUnion::RequestClass(request) = &item.request {
+                visitor.visit_request(request, &format!("{location}/request"));
+            }
+            for (index, response) in item.response.iter().flatten().enumerate() {
+                visitor.visit_response(response, &format!("{location}/response/{index}"));
+            }
+        }
+
+        pub fn walk_request<'a, V>(visitor: &mut V, request: &'a RequestClass, location: &str)
+        where
+            V: Visitor<'a> + ?Sized,
+        {
+            if let Some(auth) = request.auth.as_ref() {
+                visitor.visit_auth(auth, &format!("{location}/auth"));
+            }
+            if let Some(Url::UrlClass(url)) = request.url.as_ref() {
+                for (index, param) in url.query.iter().flatten().enumerate() {
+                    visitor.visit_query_param(param, &format!("{location}/url/query/{index}"));
+                }
+                for (index, variable) in url.variable.iter().flatten().enumerate() {
+                    visitor.visit_variable(variable, &format!("{location}/url/variable/{index}"));
+                }
+            }
+            if let Some(HeaderUnion::HeaderArray(headers)) = request.header.as_ref() {
+                for (index, header) in headers.iter().enumerate() {
+                    visitor.visit_header(header, &format!("{location}/header/{index}"));
+                }
+            }
+            if let Some(body) = request.body.as_ref() {
+                visitor.visit_body(body, &format!("{location}/body"));
+            }
+        }
+
+        pub fn walk_response<'a, V>(visitor: &mut V, response: &'a ResponseClass, location: &str)
+        where
+            V: Visitor<'a> + ?Sized,
+        {
+            if let Some(RequestUnion::RequestClass(request)) = response.original_request.as_ref() {
+                visitor.visit_request(request, &format!("{location}/originalRequest"));
+            }
+            if let Some(Headers::UnionArray(headers)) = response.header.as_ref() {
+                for (index, header) in headers.iter().enumerate() {
+                    if let HeaderElement::Header(header) = header {
+                        visitor.visit_header(header, &format!("{location}/header/{index}"));
+                    }
+                }
+            }
+        }
+
+        /// Visitor that may modify the nodes it visits. See the [module documentation](self).
+        pub trait VisitorMut {
+            fn visit_spec_mut(&mut self, spec: &mut Spec, location: &str) {
+                walk_spec_mut(self, spec, location);
+            }
+
+            fn visit_item_group_mut(&mut self, group: &mut ItemGroup, location: &str) {
+                walk_item_group_mut(self, group, location);
+            }
+
+            fn visit_item_mut(&mut self, item: &mut Item, location: &str) {
+                walk_item_mut(self, item, location);
+            }
+
+            fn visit_request_mut(&mut self, request: &mut RequestClass, location: &str) {
+                walk_request_mut(self, request, location);
+            }
+
+            fn visit_body_mut(&mut self, _body: &mut Body, _location: &str) {}
+
+            fn visit_header_mut(&mut self, _header: &mut Header, _location: &str) {}
+
+            fn visit_query_param_mut(&mut self, _param: &mut QueryParam, _location: &str) {}
+
+            fn visit_variable_mut(&mut self, _variable: &mut Variable, _location: &str) {}
+
+            fn visit_event_mut(&mut self, _event: &mut Event, _location: &str) {}
+
+            fn visit_response_mut(&mut self, response: &mut ResponseClass, location: &str) {
+                walk_response_mut(self, response, location);
+            }
+
+            fn visit_auth_mut(&mut self, _auth: &mut Auth, _location: &str) {}
+        }
+
+        pub fn walk_spec_mut<V>(visitor: &mut V, spec: &mut Spec, location: &str)
+        where
+            V: VisitorMut + ?Sized,
+        {
+            if let Some(auth) = spec.auth.as_mut() {
+                visitor.visit_auth_mut(auth, &format!("{location}/auth"));
+            }
+            for (index, event) in spec.event.iter_mut().flatten().enumerate() {
+                visitor.visit_event_mut(event, &format!("{location}/event/{index}"));
+            }
+            for (index, variable) in spec.variable.iter_mut().flatten().enumerate() {
+                visitor.visit_variable_mut(variable, &format!("{location}/variable/{index}"));
+            }
+            walk_items_mut(visitor, &mut spec.item, location);
+        }
+
+        pub fn walk_item_group_mut<V>(visitor: &mut V, group: &mut ItemGroup, location: &str)
+        where
+            V: VisitorMut + ?Sized,
+        {
+            if let Some(auth) = group.auth.as_mut() {
+                visitor.visit_auth_mut(auth, &format!("{location}/auth"));
+            }
+            for (index, event) in group.event.iter_mut().flatten().enumerate() {
+                visitor.visit_event_mut(event, &format!("{location}/event/{index}"));
+            }
+            for (index, variable) in group.variable.iter_mut().flatten().enumerate() {
+                visitor.visit_variable_mut(variable, &format!("{location}/variable/{index}"));
+            }
+            walk_items_mut(visitor, &mut group.item, location);
+        }
+
+        fn walk_items_mut<V>(visitor: &mut V, items: &mut [Items], location: &str)
+        where
+            V: VisitorMut + ?Sized,
+        {
+            for (index, item) in items.iter_mut().enumerate() {
+                let location = format!("{location}/item/{index}");
+                match item {
+                    Items::ItemGroup(group) => visitor.visit_item_group_mut(group, &location),
+                    Items::Item(item) => visitor.visit_item_mut(item, &location),
+                }
+            }
+        }
+
+        pub fn walk_item_mut<V>(visitor: &mut V, item: &mut Item, location: &str)
+        where
+            V: VisitorMut + ?Sized,
+        {
+            for (index, event) in item.event.iter_mut().flatten().enumerate() {
+                visitor.visit_event_mut(event, &format!("{location}/event/{index}"));
+            }
+            for (index, variable) in item.variable.iter_mut().flatten().enumerate() {
+                visitor.visit_variable_mut(variable, &format!("{location}/variable/{index}"));
+            }
+            if let RequestUnion::RequestClass(request) = &mut item.request {
+                visitor.visit_request_mut(request, &format!("{location}/request"));
+            }
+            for (index, response) in item.response.iter_mut().flatten().enumerate() {
+                visitor.visit_response_mut(response, &format!("{location}/response/{index}"));
+            }
+        }
+
+        pub fn walk_request_mut<V>(visitor: &mut V, request: &mut RequestClass, location: &str)
+        where
+            V: VisitorMut + ?Sized,
+        {
+            if let Some(auth) = request.auth.as_mut() {
+                visitor.visit_auth_mut(auth, &format!("{location}/auth"));
+            }
+            if let Some(Url::UrlClass(url)) = request.url.as_mut() {
+                for (index, param) in url.query.iter_mut().flatten().enumerate() {
+                    visitor.visit_query_param_mut(param, &format!("{location}/url/query/{index}"));
+                }
+                for (index, variable) in url.variable.iter_mut().flatten().enumerate() {
+                    visitor
+                        .visit_variable_mut(variable, &format!("{location}/url/variable/{index}"));
+                }
+            }
+            if let Some(HeaderUnion::HeaderArray(headers)) = request.header.as_mut() {
+                for (index, header) in headers.iter_mut().enumerate() {
+                    visitor.visit_header_mut(header, &format!("{location}/header/{index}"));
+                }
+            }
+            if let Some(body) = request.body.as_mut() {
+                visitor.visit_body_mut(body, &format!("{location}/body"));
+            }
+        }
+
+        pub fn walk_response_mut<V>(visitor: &mut V, response: &mut ResponseClass, location: &str)
+        where
+            V: VisitorMut + ?Sized,
+        {
+            if let Some(RequestUnion::RequestClass(request)) = response.original_request.as_mut() {
+                visitor.visit_request_mut(request, &format!("{location}/originalRequest"));
+            }
+            if let Some(Headers::UnionArray(headers)) = response.header.as_mut() {
+                for (index, header) in headers.iter_mut().enumerate() {
+                    if let HeaderElement::Header(header) = header {
+                        visitor.visit_header_mut(header, &format!("{location}/header/{index}"));
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use visit;
+
+/// Auth inheritance: [`EffectiveAuth`] and where it comes from. Expands in a version's `auth`
+/// module.
+macro_rules! effective_auth {
+    () => {
+        /// Where an [`EffectiveAuth`] was defined.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub enum AuthSource<'a> {
+            /// The request itself.
+            Request,
+            /// An enclosing folder. `depth` counts from the root, so `0` is a top-level folder.
+            Folder {
+                group: &'a super::ItemGroup,
+                depth: usize,
+            },
+            Collection,
+        }
+
+        /// The auth that applies to a request, and where it comes from.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct EffectiveAuth<'a> {
+            pub auth: &'a super::Auth,
+            pub source: AuthSource<'a>,
+        }
+
+        impl EffectiveAuth<'_> {
+            /// Whether the auth was set on a folder or the collection rather than on the request.
+            pub fn is_inherited(&self) -> bool {
+                self.source != AuthSource::Request
+            }
+
+            /// Whether the auth is an explicit `noauth`, wherever it was set.
+            pub fn is_noauth(&self) -> bool {
+                self.auth.auth_type == super::AuthType::Noauth
+            }
+        }
+
+        /// Finds the auth that applies to `item`, given its enclosing folders from the outermost in.
+        ///
+        /// Returns `None` when neither the request, its folders nor the collection set any auth.
+        pub fn effective_auth<'a>(
+            spec: &'a super::Spec,
+            ancestors: &[&'a super::ItemGroup],
+            item: &'a super::Item,
+        ) -> Option<EffectiveAuth<'a>> {
+            if let super::RequestUnion::RequestClass(request) = &item.request {
+                if let Some(auth) = request.auth.as_ref() {
+                    return Some(EffectiveAuth {
+                        auth,
+                        source: AuthSource::Request,
+                    });
+                }
+            }
+
+            let folders = ancestors.iter().enumerate().rev();
+            for (depth, group) in folders {
+                if let Some(auth) = group.auth.as_ref() {
+                    return Some(EffectiveAuth {
+                        auth,
+                        source: AuthSource::Folder { group, depth },
+                    });
+                }
+            }
+
+            spec.auth.as_ref().map(|auth| EffectiveAuth {
+                auth,
+                source: AuthSource::Collection,
+            })
+        }
+
+        impl<'a> super::iter::ItemEntry<'a> {
+            /// The auth that applies to this entry's request. See [`effective_auth`].
+            pub fn effective_auth(&self, spec: &'a super::Spec) -> Option<EffectiveAuth<'a>> {
+                effective_auth(spec, &self.ancestors, self.item)
+            }
+        }
+    };
+}
+
+pub(crate) use effective_auth;
@@ -0,0 +1,123 @@
+//! Depth-first iteration over the requests of a collection.
+//!
+//! v1 collections keep requests in a flat list and describe the folder tree through id lists;
+//! the iterators follow that tree the way the Postman app displays it, folders first.
+
+use std::vec;
+
+use super::order::Tree;
+use super::{Folder, Request, Spec};
+use crate::tree::path_segment;
+
+impl Spec {
+    /// Iterates over every [`Request`] of the collection, depth first, together with the folders
+    /// that contain it.
+    ///
+    /// Requests that no order list mentions are still visited: they appear in the folder they
+    /// name, or at the root.
+    pub fn items(&self) -> ItemIter<'_> {
+        let folders = self.folders.as_deref().unwrap_or_default();
+        let entries: Vec<_> = Tree::build(self)
+            .requests()
+            .into_iter()
+            .map(|(path, index)| {
+                let ancestors: Vec<_> = path.into_iter().map(|index| &folders[index]).collect();
+                let request = &self.requests[index];
+                ItemEntry {
+                    path: join(&ancestors, request),
+                    ancestors,
+                    request,
+                }
+            })
+            .collect();
+
+        ItemIter {
+            entries: entries.into_iter(),
+        }
+    }
+
+    /// Like [`items`](Self::items), but hands out mutable requests so they can be edited in
+    /// place.
+    pub fn items_mut(&mut self) -> ItemIterMut<'_> {
+        let tree = Tree::build(self);
+        let folders = self.folders.as_deref().unwrap_or_default();
+        let paths: Vec<_> = tree
+            .requests()
+            .into_iter()
+            .map(|(path, index)| {
+                let ancestors: Vec<_> = path.into_iter().map(|index| &folders[index]).collect();
+                (join(&ancestors, &self.requests[index]), index)
+            })
+            .collect();
+
+        // The tree visits every request at most once, so each slot is taken at most once.
+        let mut requests: Vec<_> = self.requests.iter_mut().map(Some).collect();
+        let entries: Vec<_> = paths
+            .into_iter()
+            .filter_map(|(path, index)| {
+                requests[index]
+                    .take()
+                    .map(|request| ItemEntryMut { path, request })
+            })
+            .collect();
+
+        ItemIterMut {
+            entries: entries.into_iter(),
+        }
+    }
+}
+
+/// A [`Request`] and the [`Folder`]s it is nested in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemEntry<'a> {
+    /// The enclosing folders, outermost first.
+    pub ancestors: Vec<&'a Folder>,
+    /// Folder and request names joined with `/`, such as `Users/Admin/Create user`. A `/` or
+    /// `\` within a name is escaped with a backslash.
+    pub path: String,
+    pub request: &'a Request,
+}
+
+/// A mutable [`Request`] and its path. See [`ItemEntry::path`].
+#[derive(Debug, PartialEq)]
+pub struct ItemEntryMut<'a> {
+    pub path: String,
+    pub request: &'a mut Request,
+}
+
+/// Iterator returned by [`Spec::items`].
+#[derive(Clone, Debug)]
+pub struct ItemIter<'a> {
+    entries: vec::IntoIter<ItemEntry<'a>>,
+}
+
+impl<'a> Iterator for ItemIter<'a> {
+    type Item = ItemEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+}
+
+/// Iterator returned by [`Spec::items_mut`].
+#[derive(Debug)]
+pub struct ItemIterMut<'a> {
+    entries: vec::IntoIter<ItemEntryMut<'a>>,
+}
+
+impl<'a> Iterator for ItemIterMut<'a> {
+    type Item = ItemEntryMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+}
+
+fn join(ancestors: &[&Folder], request: &Request) -> String {
+    ancestors
+        .iter()
+        .map(|folder| path_segment(&folder.name))
+        .chain([path_segment(&request.name)])
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -4,6 +4,11 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+pub mod iter;
+pub(crate) mod order;
+pub mod url;
+pub mod visit;
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Spec {
     #[serde(rename = "auth")]
@@ -0,0 +1,237 @@
+//! Reconstruction of the folder tree that v1 collections encode through id lists.
+//!
+//! A v1 collection stores its folders and requests as flat arrays. The visible tree is spelled
+//! out by `Spec::folders_order`/`Spec::order` at the root and `Folder::folders_order`/
+//! `Folder::order` below it. Folders come before requests at every level, which is what the
+//! Postman app shows.
+
+use std::collections::{HashMap, HashSet};
+
+use super::Spec;
+
+/// A node of the reconstructed tree, pointing into `Spec::folders` or `Spec::requests`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Node {
+    Folder { index: usize, children: Vec<Node> },
+    Request(usize),
+}
+
+/// Something about the id lists that did not describe a clean tree.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum OrderIssue {
+    /// An order list names an id that is not a folder (or request) of the collection.
+    MissingFolder {
+        referenced_by: String,
+        id: String,
+    },
+    MissingRequest {
+        referenced_by: String,
+        id: String,
+    },
+    /// A folder is listed inside one of its own descendants, or more than once.
+    RepeatedFolder {
+        referenced_by: String,
+        id: String,
+    },
+    RepeatedRequest {
+        referenced_by: String,
+        id: String,
+    },
+    /// A folder or request is not listed in any order and was appended to the tree.
+    UnorderedFolder {
+        index: usize,
+    },
+    UnorderedRequest {
+        index: usize,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Tree {
+    pub roots: Vec<Node>,
+    pub issues: Vec<OrderIssue>,
+}
+
+impl Tree {
+    pub fn build(spec: &Spec) -> Self {
+        let folders = spec.folders.as_deref().unwrap_or_default();
+        let folder_ids: HashMap<&str, usize> = folders
+            .iter()
+            .enumerate()
+            .map(|(index, folder)| (folder.id.as_str(), index))
+            .collect();
+        let request_ids: HashMap<&str, usize> = spec
+            .requests
+            .iter()
+            .enumerate()
+            .map(|(index, request)| (request.id.as_str(), index))
+            .collect();
+
+        let mut builder = Builder {
+            spec,
+            folder_ids,
+            request_ids,
+            placed_folders: HashSet::new(),
+            placed_requests: HashSet::new(),
+            issues: Vec::new(),
+        };
+
+        let root_folders = match spec.folders_order.as_ref() {
+            Some(order) => order.clone(),
+            // Older exports leave the root folder order out; every folder that is not nested
+            // somewhere else is then a root folder.
+            None => {
+                let nested: HashSet<&str> = folders
+                    .iter()
+                    .flat_map(|folder| folder.folders_order.iter().flatten())
+                    .map(String::as_str)
+                    .collect();
+                folders
+                    .iter()
+                    .filter(|folder| !nested.contains(folder.id.as_str()))
+                    .map(|folder| folder.id.clone())
+                    .collect()
+            }
+        };
+
+        let mut roots = builder.level(&spec.id, &root_folders, &spec.order, &mut Vec::new());
+
+        // Anything the id lists forgot about is kept rather than silently dropped: folders go
+        // to the root, requests go to the folder they name (or the root).
+        for index in 0..folders.len() {
+            if !builder.placed_folders.contains(&index) {
+                builder.issues.push(OrderIssue::UnorderedFolder { index });
+                builder.placed_folders.insert(index);
+                let children = builder.children(index, &mut vec![index]);
+                roots.push(Node::Folder { index, children });
+            }
+        }
+        for (index, request) in spec.requests.iter().enumerate() {
+            if builder.placed_requests.insert(index) {
+                builder.issues.push(OrderIssue::UnorderedRequest { index });
+                let parent = request
+                    .folder
+                    .as_deref()
+                    .and_then(|id| builder.folder_ids.get(id).copied());
+                match parent.and_then(|parent| find_folder_children(&mut roots, parent)) {
+                    Some(children) => children.push(Node::Request(index)),
+                    None => roots.push(Node::Request(index)),
+                }
+            }
+        }
+
+        Tree {
+            roots,
+            issues: builder.issues,
+        }
+    }
+}
+
+impl Tree {
+    /// Every request of the tree in depth-first order, with the folder indices leading to it.
+    pub fn requests(&self) -> Vec<(Vec<usize>, usize)> {
+        fn walk(nodes: &[Node], folders: &mut Vec<usize>, out: &mut Vec<(Vec<usize>, usize)>) {
+            for node in nodes {
+                match node {
+                    Node::Folder { index, children } => {
+                        folders.push(*index);
+                        walk(children, folders, out);
+                        folders.pop();
+                    }
+                    Node::Request(index) => out.push((folders.clone(), *index)),
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        walk(&self.roots, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+fn find_folder_children(nodes: &mut [Node], wanted: usize) -> Option<&mut Vec<Node>> {
+    for node in nodes {
+        if let Node::Folder { index, children } = node {
+            if *index == wanted {
+                return Some(children);
+            }
+            if let Some(found) = find_folder_children(children, wanted) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+struct Builder<'a> {
+    spec: &'a Spec,
+    folder_ids: HashMap<&'a str, usize>,
+    request_ids: HashMap<&'a str, usize>,
+    placed_folders: HashSet<usize>,
+    placed_requests: HashSet<usize>,
+    issues: Vec<OrderIssue>,
+}
+
+impl Builder<'_> {
+    fn level(
+        &mut self,
+        owner: &str,
+        folder_order: &[String],
+        request_order: &[String],
+        path: &mut Vec<usize>,
+    ) -> Vec<Node> {
+        let mut nodes = Vec::new();
+
+        for id in folder_order {
+            let Some(&index) = self.folder_ids.get(id.as_str()) else {
+                self.issues.push(OrderIssue::MissingFolder {
+                    referenced_by: owner.to_owned(),
+                    id: id.clone(),
+                });
+                continue;
+            };
+            if path.contains(&index) || !self.placed_folders.insert(index) {
+                self.issues.push(OrderIssue::RepeatedFolder {
+                    referenced_by: owner.to_owned(),
+                    id: id.clone(),
+                });
+                continue;
+            }
+
+            path.push(index);
+            let children = self.children(index, path);
+            path.pop();
+            nodes.push(Node::Folder { index, children });
+        }
+
+        for id in request_order {
+            let Some(&index) = self.request_ids.get(id.as_str()) else {
+                self.issues.push(OrderIssue::MissingRequest {
+                    referenced_by: owner.to_owned(),
+                    id: id.clone(),
+                });
+                continue;
+            };
+            if !self.placed_requests.insert(index) {
+                self.issues.push(OrderIssue::RepeatedRequest {
+                    referenced_by: owner.to_owned(),
+                    id: id.clone(),
+                });
+                continue;
+            }
+            nodes.push(Node::Request(index));
+        }
+
+        nodes
+    }
+
+    fn children(&mut self, index: usize, path: &mut Vec<usize>) -> Vec<Node> {
+        let folder = &self.spec.folders.as_deref().unwrap_or_default()[index];
+        self.level(
+            &folder.id,
+            folder.folders_order.as_deref().unwrap_or_default(),
+            &folder.order,
+            path,
+        )
+    }
+}
@@ -0,0 +1,59 @@
+//! Path variable expansion for v1.0.0 request URLs.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::{PathVariables, Request};
+use crate::v2_1_0::UrlClass;
+use crate::variables::Resolved;
+
+impl Request {
+    /// Replaces every `:name` path segment of [`url`](Request::url) with its value from
+    /// [`path_variables`](Request::path_variables). A string `pathVariables` is read as a JSON
+    /// object.
+    ///
+    /// Values are percent-encoded as in
+    /// [`UrlClass::expand_path_variables`](crate::v2_1_0::UrlClass::expand_path_variables), and
+    /// segments without a value are left in place and reported in [`Resolved::unresolved`].
+    pub fn expand_path_variables(&self) -> Resolved<String> {
+        let values = path_variable_values(self.path_variables.as_ref());
+        let url = UrlClass::parse(&self.url);
+        let expanded = url.expand_path_variables_with(|name| {
+            values.get(name).map(|value| Cow::Borrowed(value.as_str()))
+        });
+
+        let value = if expanded.value.path == url.path {
+            self.url.clone()
+        } else {
+            expanded.value.to_string()
+        };
+        Resolved {
+            value,
+            unresolved: expanded.unresolved,
+        }
+    }
+}
+
+fn path_variable_values(variables: Option<&PathVariables>) -> HashMap<String, String> {
+    let map = match variables {
+        Some(PathVariables::AnythingMap(map)) => Cow::Borrowed(map),
+        Some(PathVariables::String(json)) => match serde_json::from_str(json) {
+            Ok(map) => Cow::Owned(map),
+            Err(_) => return HashMap::new(),
+        },
+        None => return HashMap::new(),
+    };
+
+    map.iter()
+        .filter_map(|(name, value)| {
+            let value = match value.as_ref()? {
+                Value::Null => return None,
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            Some((name.clone(), value))
+        })
+        .collect()
+}
@@ -0,0 +1,236 @@
+//! Visitors over the collection.
+//!
+//! [`Visitor`] and [`VisitorMut`] have one method per node type. Every method receives the
+//! node and its location as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) into the
+//! serialized collection, such as `/requests/3/headerData/1`; the collection itself is at the
+//! empty pointer `""`. The default implementations call the matching `walk_*` function, which
+//! visits the children of the node. An implementation that overrides a method skips the
+//! subtree unless it calls the `walk_*` function itself.
+//!
+//! v1 collections store folders and requests as flat lists, and the visitors follow those lists
+//! in document order. Use [`Spec::items`] to follow the folder tree instead.
+
+use super::{
+    Auth, Datum, Event, Folder, Header, HeaderClass, HeaderElement, Request, ResponseClass, Spec,
+    UrlParam, UrlParamClass, Variable,
+};
+
+/// Read-only visitor. See the [module documentation](self).
+pub trait Visitor<'a> {
+    fn visit_spec(&mut self, spec: &'a Spec, location: &str) {
+        walk_spec(self, spec, location);
+    }
+
+    fn visit_folder(&mut self, folder: &'a Folder, location: &str) {
+        walk_folder(self, folder, location);
+    }
+
+    fn visit_request(&mut self, request: &'a Request, location: &str) {
+        walk_request(self, request, location);
+    }
+
+    /// A field of a form-data or URL-encoded body.
+    fn visit_datum(&mut self, _datum: &'a Datum, _location: &str) {}
+
+    fn visit_header(&mut self, _header: &'a HeaderClass, _location: &str) {}
+
+    fn visit_query_param(&mut self, _param: &'a UrlParamClass, _location: &str) {}
+
+    fn visit_variable(&mut self, _variable: &'a Variable, _location: &str) {}
+
+    fn visit_event(&mut self, _event: &'a Event, _location: &str) {}
+
+    fn visit_response(&mut self, response: &'a ResponseClass, location: &str) {
+        walk_response(self, response, location);
+    }
+
+    fn visit_response_header(&mut self, _header: &'a HeaderElement, _location: &str) {}
+
+    fn visit_auth(&mut self, _auth: &'a Auth, _location: &str) {}
+}
+
+pub fn walk_spec<'a, V>(visitor: &mut V, spec: &'a Spec, location: &str)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    if let Some(auth) = spec.auth.as_ref() {
+        visitor.visit_auth(auth, &format!("{location}/auth"));
+    }
+    for (index, event) in spec.events.iter().flatten().enumerate() {
+        visitor.visit_event(event, &format!("{location}/events/{index}"));
+    }
+    for (index, variable) in spec.variables.iter().flatten().enumerate() {
+        visitor.visit_variable(variable, &format!("{location}/variables/{index}"));
+    }
+    for (index, folder) in spec.folders.iter().flatten().enumerate() {
+        visitor.visit_folder(folder, &format!("{location}/folders/{index}"));
+    }
+    for (index, request) in spec.requests.iter().enumerate() {
+        visitor.visit_request(request, &format!("{location}/requests/{index}"));
+    }
+}
+
+pub fn walk_folder<'a, V>(visitor: &mut V, folder: &'a Folder, location: &str)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    if let Some(auth) = folder.auth.as_ref() {
+        visitor.visit_auth(auth, &format!("{location}/auth"));
+    }
+    for (index, event) in folder.events.iter().flatten().enumerate() {
+        visitor.visit_event(event, &format!("{location}/events/{index}"));
+    }
+    for (index, variable) in folder.variables.iter().flatten().enumerate() {
+        visitor.visit_variable(variable, &format!("{location}/variables/{index}"));
+    }
+}
+
+pub fn walk_request<'a, V>(visitor: &mut V, request: &'a Request, location: &str)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    if let Some(auth) = request.auth.as_ref() {
+        visitor.visit_auth(auth, &format!("{location}/auth"));
+    }
+    for (index, event) in request.events.iter().flatten().enumerate() {
+        visitor.visit_event(event, &format!("{location}/events/{index}"));
+    }
+    for (index, variable) in request.variables.iter().flatten().enumerate() {
+        visitor.visit_variable(variable, &format!("{location}/variables/{index}"));
+    }
+    for (index, param) in request.query_params.iter().flatten().enumerate() {
+        if let Some(UrlParam::UrlParamClass(param)) = param {
+            visitor.visit_query_param(param, &format!("{location}/queryParams/{index}"));
+        }
+    }
+    for (index, header) in request.header_data.iter().flatten().enumerate() {
+        if let Some(Header::HeaderClass(header)) = header {
+            visitor.visit_header(header, &format!("{location}/headerData/{index}"));
+        }
+    }
+    for (index, datum) in request.data.iter().flatten().enumerate() {
+        visitor.visit_datum(datum, &format!("{location}/data/{index}"));
+    }
+    for (index, response) in request.responses.iter().flatten().enumerate() {
+        visitor.visit_response(response, &format!("{location}/responses/{index}"));
+    }
+}
+
+pub fn walk_response<'a, V>(visitor: &mut V, response: &'a ResponseClass, location: &str)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    for (index, header) in response.headers.iter().flatten().enumerate() {
+        visitor.visit_response_header(header, &format!("{location}/headers/{index}"));
+    }
+}
+
+/// Visitor that may modify the nodes it visits. See the [module documentation](self).
+pub trait VisitorMut {
+    fn visit_spec_mut(&mut self, spec: &mut Spec, location: &str) {
+        walk_spec_mut(self, spec, location);
+    }
+
+    fn visit_folder_mut(&mut self, folder: &mut Folder, location: &str) {
+        walk_folder_mut(self, folder, location);
+    }
+
+    fn visit_request_mut(&mut self, request: &mut Request, location: &str) {
+        walk_request_mut(self, request, location);
+    }
+
+    /// A field of a form-data or URL-encoded body.
+    fn visit_datum_mut(&mut self, _datum: &mut Datum, _location: &str) {}
+
+    fn visit_header_mut(&mut self, _header: &mut HeaderClass, _location: &str) {}
+
+    fn visit_query_param_mut(&mut self, _param: &mut UrlParamClass, _location: &str) {}
+
+    fn visit_variable_mut(&mut self, _variable: &mut Variable, _location: &str) {}
+
+    fn visit_event_mut(&mut self, _event: &mut Event, _location: &str) {}
+
+    fn visit_response_mut(&mut self, response: &mut ResponseClass, location: &str) {
+        walk_response_mut(self, response, location);
+    }
+
+    fn visit_response_header_mut(&mut self, _header: &mut HeaderElement, _location: &str) {}
+
+    fn visit_auth_mut(&mut self, _auth: &mut Auth, _location: &str) {}
+}
+
+pub fn walk_spec_mut<V>(visitor: &mut V, spec: &mut Spec, location: &str)
+where
+    V: VisitorMut + ?Sized,
+{
+    if let Some(auth) = spec.auth.as_mut() {
+        visitor.visit_auth_mut(auth, &format!("{location}/auth"));
+    }
+    for (index, event) in spec.events.iter_mut().flatten().enumerate() {
+        visitor.visit_event_mut(event, &format!("{location}/events/{index}"));
+    }
+    for (index, variable) in spec.variables.iter_mut().flatten().enumerate() {
+        visitor.visit_variable_mut(variable, &format!("{location}/variables/{index}"));
+    }
+    for (index, folder) in spec.folders.iter_mut().flatten().enumerate() {
+        visitor.visit_folder_mut(folder, &format!("{location}/folders/{index}"));
+    }
+    for (index, request) in spec.requests.iter_mut().enumerate() {
+        visitor.visit_request_mut(request, &format!("{location}/requests/{index}"));
+    }
+}
+
+pub fn walk_folder_mut<V>(visitor: &mut V, folder: &mut Folder, location: &str)
+where
+    V: VisitorMut + ?Sized,
+{
+    if let Some(auth) = folder.auth.as_mut() {
+        visitor.visit_auth_mut(auth, &format!("{location}/auth"));
+    }
+    for (index, event) in folder.events.iter_mut().flatten().enumerate() {
+        visitor.visit_event_mut(event, &format!("{location}/events/{index}"));
+    }
+    for (index, variable) in folder.variables.iter_mut().flatten().enumerate() {
+        visitor.visit_variable_mut(variable, &format!("{location}/variables/{index}"));
+    }
+}
+
+pub fn walk_request_mut<V>(visitor: &mut V, request: &mut Request, location: &str)
+where
+    V: VisitorMut + ?Sized,
+{
+    if let Some(auth) = request.auth.as_mut() {
+        visitor.visit_auth_mut(auth, &format!("{location}/auth"));
+    }
+    for (index, event) in request.events.iter_mut().flatten().enumerate() {
+        visitor.visit_event_mut(event, &format!("{location}/events/{index}"));
+    }
+    for (index, variable) in request.variables.iter_mut().flatten().enumerate() {
+        visitor.visit_variable_mut(variable, &format!("{location}/variables/{index}"));
+    }
+    for (index, param) in request.query_params.iter_mut().flatten().enumerate() {
+        if let Some(UrlParam::UrlParamClass(param)) = param {
+            visitor.visit_query_param_mut(param, &format!("{location}/queryParams/{index}"));
+        }
+    }
+    for (index, header) in request.header_data.iter_mut().flatten().enumerate() {
+        if let Some(Header::HeaderClass(header)) = header {
+            visitor.visit_header_mut(header, &format!("{location}/headerData/{index}"));
+        }
+    }
+    for (index, datum) in request.data.iter_mut().flatten().enumerate() {
+        visitor.visit_datum_mut(datum, &format!("{location}/data/{index}"));
+    }
+    for (index, response) in request.responses.iter_mut().flatten().enumerate() {
+        visitor.visit_response_mut(response, &format!("{location}/responses/{index}"));
+    }
+}
+
+pub fn walk_response_mut<V>(visitor: &mut V, response: &mut ResponseClass, location: &str)
+where
+    V: VisitorMut + ?Sized,
+{
+    for (index, header) in response.headers.iter_mut().flatten().enumerate() {
+        visitor.visit_response_header_mut(header, &format!("{location}/headers/{index}"));
+    }
+}
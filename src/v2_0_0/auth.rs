@@ -0,0 +1,7 @@
+//! Auth inheritance.
+//!
+//! A request without `auth` inherits the auth of its closest enclosing folder that has one, and
+//! then the auth of the collection. An auth of type [`AuthType::Noauth`](super::AuthType::Noauth) stops the search: it
+//! explicitly turns auth off for everything below it.
+
+crate::tree::effective_auth!();
@@ -0,0 +1,3 @@
+//! Depth-first iteration over the requests of a collection.
+
+crate::tree::item_iter!();
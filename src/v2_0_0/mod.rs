@@ -4,6 +4,13 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+pub mod auth;
+pub mod iter;
+pub mod visit;
+
+/// The `info.schema` URL that identifies a v2.0.0 collection.
+pub const SCHEMA_URL: &str = "https://schema.getpostman.com/json/collection/v2.0.0/collection.json";
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Spec {
     #[serde(rename = "auth")]
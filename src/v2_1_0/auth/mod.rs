@@ -0,0 +1,145 @@
+//! Auth inheritance and typed auth schemes.
+//!
+//! A request without `auth` inherits the auth of its closest enclosing folder that has one, and
+//! then the auth of the collection. An auth of type [`AuthType::Noauth`] stops the search: it
+//! explicitly turns auth off for everything below it.
+//!
+//! [`Auth`](super::Auth) stores the settings of each scheme as a list of key/value attributes. The scheme
+//! types in this module, such as [`BasicAuth`] or [`OAuth2Auth`], read those lists into named
+//! fields and write them back. Reading reports missing required attributes and values of the
+//! wrong JSON type as errors.
+//!
+//! ```
+//! use postman_collection::v2_1_0::Auth;
+//! use postman_collection::v2_1_0::auth::{AuthScheme, BearerAuth, TypedAuth};
+//!
+//! let mut auth: Auth = serde_json::from_str(
+//!     r#"{ "type": "bearer", "bearer": [{ "key": "token", "value": "old", "type": "string" }] }"#,
+//! )?;
+//!
+//! let TypedAuth::Bearer(mut bearer) = auth.typed()? else { unreachable!() };
+//! bearer.token = "new".to_owned();
+//! bearer.write_to(&mut auth);
+//!
+//! assert_eq!(BearerAuth::from_auth(&auth)?.token, "new");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::{AuthType, Header, HeaderUnion, QueryParam, RequestClass, Url};
+use crate::Result;
+
+mod schemes;
+
+pub use schemes::*;
+
+crate::tree::effective_auth!();
+
+impl RequestClass {
+    /// Adds the headers or query parameters of basic, bearer and API key auth. A header the
+    /// request already sets is left alone. Other schemes are not applied, so their attributes
+    /// are not read and cannot fail.
+    pub(crate) fn apply_auth(&mut self) -> Result<()> {
+        let Some(auth) = self.auth.as_ref() else {
+            return Ok(());
+        };
+        let (key, value) = match auth.auth_type {
+            AuthType::Basic => {
+                let basic = BasicAuth::from_auth(auth)?;
+                let credentials =
+                    format!("{}:{}", basic.username, basic.password.unwrap_or_default());
+                (
+                    "Authorization".to_owned(),
+                    format!("Basic {}", base64(credentials.as_bytes())),
+                )
+            }
+            AuthType::Bearer => (
+                "Authorization".to_owned(),
+                format!("Bearer {}", BearerAuth::from_auth(auth)?.token),
+            ),
+            AuthType::Apikey => {
+                let api_key = ApiKeyAuth::from_auth(auth)?;
+                if api_key.location == ApiKeyLocation::Query {
+                    let mut url = self
+                        .url
+                        .as_ref()
+                        .map(|url| url.to_class().into_owned())
+                        .unwrap_or_default();
+                    let query = url.query.get_or_insert_with(Vec::new);
+                    let existing = query.iter_mut().find(|param| {
+                        !param.disabled.unwrap_or(false)
+                            && param.key.as_deref() == Some(api_key.key.as_str())
+                    });
+                    match existing {
+                        Some(param) => param.value = Some(api_key.value),
+                        None => query.push(QueryParam {
+                            description: None,
+                            disabled: None,
+                            key: Some(api_key.key),
+                            value: Some(api_key.value),
+                        }),
+                    }
+                    url.raw = Some(url.to_string());
+                    self.url = Some(Url::UrlClass(url));
+                    return Ok(());
+                }
+                (api_key.key, api_key.value)
+            }
+            _ => return Ok(()),
+        };
+
+        match self
+            .header
+            .get_or_insert_with(|| HeaderUnion::HeaderArray(Vec::new()))
+        {
+            HeaderUnion::HeaderArray(headers) => {
+                let present = headers.iter().any(|header| {
+                    !header.disabled.unwrap_or(false) && header.key.eq_ignore_ascii_case(&key)
+                });
+                if !present {
+                    headers.push(Header {
+                        description: None,
+                        disabled: None,
+                        key,
+                        value,
+                    });
+                }
+            }
+            HeaderUnion::String(headers) => {
+                let present = headers.lines().any(|line| {
+                    !line.trim_start().starts_with("//")
+                        && line
+                            .split_once(':')
+                            .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case(&key))
+                });
+                if !present {
+                    if !headers.is_empty() && !headers.ends_with('\n') {
+                        headers.push('\n');
+                    }
+                    headers.push_str(&format!("{key}: {value}\n"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                output.push(ALPHABET[(triple >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,708 @@
+use serde_json::Value;
+
+use crate::v2_1_0::{Auth, AuthAttribute, AuthType};
+use crate::{Error, Result};
+
+/// A typed view of one auth scheme's attribute list.
+pub trait AuthScheme: Sized {
+    /// The auth type whose attribute list the scheme reads and writes.
+    const AUTH_TYPE: AuthType;
+
+    /// Reads the scheme from an attribute list.
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self>;
+
+    /// Writes the scheme into an attribute list. Attributes the scheme knows are updated in
+    /// place, added, or removed when the field is `None`; all other attributes are kept.
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>);
+
+    /// Reads the scheme from the matching attribute list of `auth`, whatever its current type.
+    fn from_auth(auth: &Auth) -> Result<Self> {
+        Self::from_attributes(attributes(auth, &Self::AUTH_TYPE).unwrap_or_default())
+    }
+
+    /// Writes the scheme into the matching attribute list of `auth` and makes it the active
+    /// auth type. The lists of other schemes are left alone.
+    fn write_to(&self, auth: &mut Auth) {
+        let attributes = attributes_mut(auth, &Self::AUTH_TYPE)
+            .expect("schemes never use the noauth type")
+            .get_or_insert_with(Vec::new);
+        self.write_attributes(attributes);
+        auth.auth_type = Self::AUTH_TYPE;
+    }
+
+    /// A new [`Auth`] holding only this scheme.
+    fn to_auth(&self) -> Auth {
+        let mut auth = Auth::default();
+        self.write_to(&mut auth);
+        auth
+    }
+}
+
+/// The active scheme of an [`Auth`], read into its typed form.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedAuth {
+    NoAuth,
+    ApiKey(ApiKeyAuth),
+    AwsV4(AwsV4Auth),
+    Basic(BasicAuth),
+    Bearer(BearerAuth),
+    Digest(DigestAuth),
+    EdgeGrid(EdgeGridAuth),
+    Hawk(HawkAuth),
+    Ntlm(NtlmAuth),
+    OAuth1(OAuth1Auth),
+    OAuth2(OAuth2Auth),
+}
+
+impl TypedAuth {
+    /// Writes the scheme into `auth`. See [`AuthScheme::write_to`].
+    pub fn write_to(&self, auth: &mut Auth) {
+        match self {
+            TypedAuth::NoAuth => auth.auth_type = AuthType::Noauth,
+            TypedAuth::ApiKey(scheme) => scheme.write_to(auth),
+            TypedAuth::AwsV4(scheme) => scheme.write_to(auth),
+            TypedAuth::Basic(scheme) => scheme.write_to(auth),
+            TypedAuth::Bearer(scheme) => scheme.write_to(auth),
+            TypedAuth::Digest(scheme) => scheme.write_to(auth),
+            TypedAuth::EdgeGrid(scheme) => scheme.write_to(auth),
+            TypedAuth::Hawk(scheme) => scheme.write_to(auth),
+            TypedAuth::Ntlm(scheme) => scheme.write_to(auth),
+            TypedAuth::OAuth1(scheme) => scheme.write_to(auth),
+            TypedAuth::OAuth2(scheme) => scheme.write_to(auth),
+        }
+    }
+}
+
+impl From<&TypedAuth> for Auth {
+    fn from(typed: &TypedAuth) -> Self {
+        let mut auth = Auth::default();
+        typed.write_to(&mut auth);
+        auth
+    }
+}
+
+impl Auth {
+    /// Reads the active scheme, as selected by [`Auth::auth_type`].
+    pub fn typed(&self) -> Result<TypedAuth> {
+        Ok(match self.auth_type {
+            AuthType::Noauth => TypedAuth::NoAuth,
+            AuthType::Apikey => TypedAuth::ApiKey(ApiKeyAuth::from_auth(self)?),
+            AuthType::Awsv4 => TypedAuth::AwsV4(AwsV4Auth::from_auth(self)?),
+            AuthType::Basic => TypedAuth::Basic(BasicAuth::from_auth(self)?),
+            AuthType::Bearer => TypedAuth::Bearer(BearerAuth::from_auth(self)?),
+            AuthType::Digest => TypedAuth::Digest(DigestAuth::from_auth(self)?),
+            AuthType::Edgegrid => TypedAuth::EdgeGrid(EdgeGridAuth::from_auth(self)?),
+            AuthType::Hawk => TypedAuth::Hawk(HawkAuth::from_auth(self)?),
+            AuthType::Ntlm => TypedAuth::Ntlm(NtlmAuth::from_auth(self)?),
+            AuthType::Oauth1 => TypedAuth::OAuth1(OAuth1Auth::from_auth(self)?),
+            AuthType::Oauth2 => TypedAuth::OAuth2(OAuth2Auth::from_auth(self)?),
+        })
+    }
+}
+
+fn attributes<'a>(auth: &'a Auth, auth_type: &AuthType) -> Option<&'a [AuthAttribute]> {
+    match auth_type {
+        AuthType::Noauth => None,
+        AuthType::Apikey => auth.api_key.as_deref(),
+        AuthType::Awsv4 => auth.awsv4.as_deref(),
+        AuthType::Basic => auth.basic.as_deref(),
+        AuthType::Bearer => auth.bearer.as_deref(),
+        AuthType::Digest => auth.digest.as_deref(),
+        AuthType::Edgegrid => auth.edgegrid.as_deref(),
+        AuthType::Hawk => auth.hawk.as_deref(),
+        AuthType::Ntlm => auth.ntlm.as_deref(),
+        AuthType::Oauth1 => auth.oauth1.as_deref(),
+        AuthType::Oauth2 => auth.oauth2.as_deref(),
+    }
+}
+
+fn attributes_mut<'a>(
+    auth: &'a mut Auth,
+    auth_type: &AuthType,
+) -> Option<&'a mut Option<Vec<AuthAttribute>>> {
+    Some(match auth_type {
+        AuthType::Noauth => return None,
+        AuthType::Apikey => &mut auth.api_key,
+        AuthType::Awsv4 => &mut auth.awsv4,
+        AuthType::Basic => &mut auth.basic,
+        AuthType::Bearer => &mut auth.bearer,
+        AuthType::Digest => &mut auth.digest,
+        AuthType::Edgegrid => &mut auth.edgegrid,
+        AuthType::Hawk => &mut auth.hawk,
+        AuthType::Ntlm => &mut auth.ntlm,
+        AuthType::Oauth1 => &mut auth.oauth1,
+        AuthType::Oauth2 => &mut auth.oauth2,
+    })
+}
+
+/// Reads attribute values for one scheme, turning problems into errors that name it.
+struct Reader<'a> {
+    scheme: &'static str,
+    attributes: &'a [AuthAttribute],
+}
+
+impl Reader<'_> {
+    fn value(&self, key: &str) -> Option<&Value> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .and_then(|attribute| attribute.value.as_ref())
+            .filter(|value| !value.is_null())
+    }
+
+    /// A string value. Numbers are accepted as well, since Postman stores some numeric
+    /// settings either way.
+    fn string(&self, key: &'static str) -> Result<Option<String>> {
+        match self.value(key) {
+            None => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(Value::Number(value)) => Ok(Some(value.to_string())),
+            Some(value) => Err(self.invalid(key, "a string", value)),
+        }
+    }
+
+    fn required(&self, key: &'static str) -> Result<String> {
+        self.string(key)?.ok_or(Error::MissingAuthAttribute {
+            scheme: self.scheme,
+            key,
+        })
+    }
+
+    /// A boolean value, also accepting the strings `"true"` and `"false"`.
+    fn boolean(&self, key: &'static str) -> Result<Option<bool>> {
+        match self.value(key) {
+            None => Ok(None),
+            Some(Value::Bool(value)) => Ok(Some(*value)),
+            Some(Value::String(value)) if value == "true" => Ok(Some(true)),
+            Some(Value::String(value)) if value == "false" => Ok(Some(false)),
+            Some(value) => Err(self.invalid(key, "a boolean", value)),
+        }
+    }
+
+    fn invalid(&self, key: &'static str, expected: &'static str, found: &Value) -> Error {
+        Error::InvalidAuthAttribute {
+            scheme: self.scheme,
+            key,
+            expected,
+            found: found.to_string(),
+        }
+    }
+}
+
+/// Updates, adds or removes attributes by key, keeping everything else in place.
+struct Writer<'a> {
+    attributes: &'a mut Vec<AuthAttribute>,
+}
+
+impl Writer<'_> {
+    fn string(&mut self, key: &str, value: &str) {
+        self.set(key, "string", Some(Value::String(value.to_owned())));
+    }
+
+    fn optional_string(&mut self, key: &str, value: Option<&str>) {
+        self.set(
+            key,
+            "string",
+            value.map(|value| Value::String(value.to_owned())),
+        );
+    }
+
+    fn optional_boolean(&mut self, key: &str, value: Option<bool>) {
+        self.set(key, "boolean", value.map(Value::Bool));
+    }
+
+    fn set(&mut self, key: &str, kind: &str, value: Option<Value>) {
+        let Some(value) = value else {
+            self.attributes.retain(|attribute| attribute.key != key);
+            return;
+        };
+        match self
+            .attributes
+            .iter_mut()
+            .find(|attribute| attribute.key == key)
+        {
+            // Keep values that read back the same, such as a port stored as a number.
+            Some(attribute)
+                if attribute
+                    .value
+                    .as_ref()
+                    .is_some_and(|old| reads_as(old, &value)) => {}
+            Some(attribute) => {
+                attribute.value = Some(value);
+                attribute.auth_type = Some(kind.to_owned());
+            }
+            None => self.attributes.push(AuthAttribute {
+                key: key.to_owned(),
+                auth_type: Some(kind.to_owned()),
+                value: Some(value),
+            }),
+        }
+    }
+}
+
+/// Whether [`Reader`] reads `stored` as `value`.
+fn reads_as(stored: &Value, value: &Value) -> bool {
+    match (stored, value) {
+        (Value::Number(stored), Value::String(value)) => stored.to_string() == *value,
+        (Value::String(stored), Value::Bool(value)) => *stored == value.to_string(),
+        (stored, value) => stored == value,
+    }
+}
+
+/// [Basic authentication](https://en.wikipedia.org/wiki/Basic_access_authentication).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: Option<String>,
+}
+
+impl AuthScheme for BasicAuth {
+    const AUTH_TYPE: AuthType = AuthType::Basic;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "basic",
+            attributes,
+        };
+        Ok(BasicAuth {
+            username: reader.required("username")?,
+            password: reader.string("password")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("username", &self.username);
+        writer.optional_string("password", self.password.as_deref());
+    }
+}
+
+/// [Bearer token authentication](https://tools.ietf.org/html/rfc6750).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BearerAuth {
+    pub token: String,
+}
+
+impl AuthScheme for BearerAuth {
+    const AUTH_TYPE: AuthType = AuthType::Bearer;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "bearer",
+            attributes,
+        };
+        Ok(BearerAuth {
+            token: reader.required("token")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        Writer { attributes }.string("token", &self.token);
+    }
+}
+
+/// Where an API key is sent.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+}
+
+/// An API key sent as a header or query parameter.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ApiKeyAuth {
+    /// The header or query parameter name.
+    pub key: String,
+    pub value: String,
+    /// Postman's `in` attribute. Postman sends the key as a header when it is missing.
+    pub location: ApiKeyLocation,
+}
+
+impl AuthScheme for ApiKeyAuth {
+    const AUTH_TYPE: AuthType = AuthType::Apikey;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "apikey",
+            attributes,
+        };
+        let location = match reader.string("in")?.as_deref() {
+            None | Some("header") => ApiKeyLocation::Header,
+            Some("query") => ApiKeyLocation::Query,
+            Some(other) => {
+                return Err(Error::InvalidAuthAttribute {
+                    scheme: "apikey",
+                    key: "in",
+                    expected: "\"header\" or \"query\"",
+                    found: format!("{other:?}"),
+                });
+            }
+        };
+        Ok(ApiKeyAuth {
+            key: reader.required("key")?,
+            value: reader.required("value")?,
+            location,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("key", &self.key);
+        writer.string("value", &self.value);
+        writer.string(
+            "in",
+            match self.location {
+                ApiKeyLocation::Header => "header",
+                ApiKeyLocation::Query => "query",
+            },
+        );
+    }
+}
+
+/// [AWS Signature Version 4](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_aws-signing.html).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AwsV4Auth {
+    pub access_key: String,
+    pub secret_key: String,
+    pub region: Option<String>,
+    pub service: Option<String>,
+    pub session_token: Option<String>,
+    /// Sign through query parameters instead of headers.
+    pub add_auth_data_to_query: Option<bool>,
+}
+
+impl AuthScheme for AwsV4Auth {
+    const AUTH_TYPE: AuthType = AuthType::Awsv4;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "awsv4",
+            attributes,
+        };
+        Ok(AwsV4Auth {
+            access_key: reader.required("accessKey")?,
+            secret_key: reader.required("secretKey")?,
+            region: reader.string("region")?,
+            service: reader.string("service")?,
+            session_token: reader.string("sessionToken")?,
+            add_auth_data_to_query: reader.boolean("addAuthDataToQuery")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("accessKey", &self.access_key);
+        writer.string("secretKey", &self.secret_key);
+        writer.optional_string("region", self.region.as_deref());
+        writer.optional_string("service", self.service.as_deref());
+        writer.optional_string("sessionToken", self.session_token.as_deref());
+        writer.optional_boolean("addAuthDataToQuery", self.add_auth_data_to_query);
+    }
+}
+
+/// [Digest authentication](https://en.wikipedia.org/wiki/Digest_access_authentication).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DigestAuth {
+    pub username: String,
+    pub password: String,
+    pub realm: Option<String>,
+    pub nonce: Option<String>,
+    /// `MD5`, `MD5-sess`, `SHA-256` and so on.
+    pub algorithm: Option<String>,
+    pub qop: Option<String>,
+    pub nonce_count: Option<String>,
+    pub client_nonce: Option<String>,
+    pub opaque: Option<String>,
+    /// Do not retry with the server's challenge after an unauthorized response.
+    pub disable_retry_request: Option<bool>,
+}
+
+impl AuthScheme for DigestAuth {
+    const AUTH_TYPE: AuthType = AuthType::Digest;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "digest",
+            attributes,
+        };
+        Ok(DigestAuth {
+            username: reader.required("username")?,
+            password: reader.required("password")?,
+            realm: reader.string("realm")?,
+            nonce: reader.string("nonce")?,
+            algorithm: reader.string("algorithm")?,
+            qop: reader.string("qop")?,
+            nonce_count: reader.string("nonceCount")?,
+            client_nonce: reader.string("clientNonce")?,
+            opaque: reader.string("opaque")?,
+            disable_retry_request: reader.boolean("disableRetryRequest")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("username", &self.username);
+        writer.string("password", &self.password);
+        writer.optional_string("realm", self.realm.as_deref());
+        writer.optional_string("nonce", self.nonce.as_deref());
+        writer.optional_string("algorithm", self.algorithm.as_deref());
+        writer.optional_string("qop", self.qop.as_deref());
+        writer.optional_string("nonceCount", self.nonce_count.as_deref());
+        writer.optional_string("clientNonce", self.client_nonce.as_deref());
+        writer.optional_string("opaque", self.opaque.as_deref());
+        writer.optional_boolean("disableRetryRequest", self.disable_retry_request);
+    }
+}
+
+/// [Akamai EdgeGrid](https://techdocs.akamai.com/developer/docs/set-up-authentication-credentials).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EdgeGridAuth {
+    pub access_token: String,
+    pub client_token: String,
+    pub client_secret: String,
+    pub nonce: Option<String>,
+    pub timestamp: Option<String>,
+    pub base_url: Option<String>,
+    pub headers_to_sign: Option<String>,
+}
+
+impl AuthScheme for EdgeGridAuth {
+    const AUTH_TYPE: AuthType = AuthType::Edgegrid;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "edgegrid",
+            attributes,
+        };
+        Ok(EdgeGridAuth {
+            access_token: reader.required("accessToken")?,
+            client_token: reader.required("clientToken")?,
+            client_secret: reader.required("clientSecret")?,
+            nonce: reader.string("nonce")?,
+            timestamp: reader.string("timestamp")?,
+            base_url: reader.string("baseURL")?,
+            headers_to_sign: reader.string("headersToSign")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("accessToken", &self.access_token);
+        writer.string("clientToken", &self.client_token);
+        writer.string("clientSecret", &self.client_secret);
+        writer.optional_string("nonce", self.nonce.as_deref());
+        writer.optional_string("timestamp", self.timestamp.as_deref());
+        writer.optional_string("baseURL", self.base_url.as_deref());
+        writer.optional_string("headersToSign", self.headers_to_sign.as_deref());
+    }
+}
+
+/// [Hawk authentication](https://github.com/mozilla/hawk).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HawkAuth {
+    pub auth_id: String,
+    pub auth_key: String,
+    /// `sha256` or `sha1`.
+    pub algorithm: Option<String>,
+    pub user: Option<String>,
+    pub nonce: Option<String>,
+    pub extra_data: Option<String>,
+    pub app: Option<String>,
+    pub delegation: Option<String>,
+    pub timestamp: Option<String>,
+    pub include_payload_hash: Option<bool>,
+}
+
+impl AuthScheme for HawkAuth {
+    const AUTH_TYPE: AuthType = AuthType::Hawk;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "hawk",
+            attributes,
+        };
+        Ok(HawkAuth {
+            auth_id: reader.required("authId")?,
+            auth_key: reader.required("authKey")?,
+            algorithm: reader.string("algorithm")?,
+            user: reader.string("user")?,
+            nonce: reader.string("nonce")?,
+            extra_data: reader.string("extraData")?,
+            app: reader.string("app")?,
+            delegation: reader.string("delegation")?,
+            timestamp: reader.string("timestamp")?,
+            include_payload_hash: reader.boolean("includePayloadHash")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("authId", &self.auth_id);
+        writer.string("authKey", &self.auth_key);
+        writer.optional_string("algorithm", self.algorithm.as_deref());
+        writer.optional_string("user", self.user.as_deref());
+        writer.optional_string("nonce", self.nonce.as_deref());
+        writer.optional_string("extraData", self.extra_data.as_deref());
+        writer.optional_string("app", self.app.as_deref());
+        writer.optional_string("delegation", self.delegation.as_deref());
+        writer.optional_string("timestamp", self.timestamp.as_deref());
+        writer.optional_boolean("includePayloadHash", self.include_payload_hash);
+    }
+}
+
+/// [NTLM authentication](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-nlmp).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NtlmAuth {
+    pub username: String,
+    pub password: String,
+    pub domain: Option<String>,
+    pub workstation: Option<String>,
+    pub disable_retry_request: Option<bool>,
+}
+
+impl AuthScheme for NtlmAuth {
+    const AUTH_TYPE: AuthType = AuthType::Ntlm;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "ntlm",
+            attributes,
+        };
+        Ok(NtlmAuth {
+            username: reader.required("username")?,
+            password: reader.required("password")?,
+            domain: reader.string("domain")?,
+            workstation: reader.string("workstation")?,
+            disable_retry_request: reader.boolean("disableRetryRequest")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("username", &self.username);
+        writer.string("password", &self.password);
+        writer.optional_string("domain", self.domain.as_deref());
+        writer.optional_string("workstation", self.workstation.as_deref());
+        writer.optional_boolean("disableRetryRequest", self.disable_retry_request);
+    }
+}
+
+/// [OAuth 1.0](https://oauth.net/core/1.0a/) request signing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OAuth1Auth {
+    pub consumer_key: String,
+    pub consumer_secret: Option<String>,
+    pub token: Option<String>,
+    pub token_secret: Option<String>,
+    /// `HMAC-SHA1`, `HMAC-SHA256`, `PLAINTEXT` and so on.
+    pub signature_method: Option<String>,
+    pub timestamp: Option<String>,
+    pub nonce: Option<String>,
+    pub version: Option<String>,
+    pub realm: Option<String>,
+    /// Send the parameters in the `Authorization` header instead of the body or URL.
+    pub add_params_to_header: Option<bool>,
+    pub add_empty_params_to_sign: Option<bool>,
+}
+
+impl AuthScheme for OAuth1Auth {
+    const AUTH_TYPE: AuthType = AuthType::Oauth1;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "oauth1",
+            attributes,
+        };
+        Ok(OAuth1Auth {
+            consumer_key: reader.required("consumerKey")?,
+            consumer_secret: reader.string("consumerSecret")?,
+            token: reader.string("token")?,
+            token_secret: reader.string("tokenSecret")?,
+            signature_method: reader.string("signatureMethod")?,
+            timestamp: reader.string("timestamp")?,
+            nonce: reader.string("nonce")?,
+            version: reader.string("version")?,
+            realm: reader.string("realm")?,
+            add_params_to_header: reader.boolean("addParamsToHeader")?,
+            add_empty_params_to_sign: reader.boolean("addEmptyParamsToSign")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.string("consumerKey", &self.consumer_key);
+        writer.optional_string("consumerSecret", self.consumer_secret.as_deref());
+        writer.optional_string("token", self.token.as_deref());
+        writer.optional_string("tokenSecret", self.token_secret.as_deref());
+        writer.optional_string("signatureMethod", self.signature_method.as_deref());
+        writer.optional_string("timestamp", self.timestamp.as_deref());
+        writer.optional_string("nonce", self.nonce.as_deref());
+        writer.optional_string("version", self.version.as_deref());
+        writer.optional_string("realm", self.realm.as_deref());
+        writer.optional_boolean("addParamsToHeader", self.add_params_to_header);
+        writer.optional_boolean("addEmptyParamsToSign", self.add_empty_params_to_sign);
+    }
+}
+
+/// [OAuth 2.0](https://oauth.net/2/). Only the token and the settings needed to send it are
+/// required; the grant settings are kept for tools that fetch tokens.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OAuth2Auth {
+    pub access_token: Option<String>,
+    /// Usually `Bearer`.
+    pub token_type: Option<String>,
+    /// `header` or `queryParams`.
+    pub add_token_to: Option<String>,
+    pub header_prefix: Option<String>,
+    /// Postman's `grant_type`, such as `authorization_code` or `client_credentials`.
+    pub grant_type: Option<String>,
+    pub auth_url: Option<String>,
+    pub access_token_url: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub state: Option<String>,
+}
+
+impl AuthScheme for OAuth2Auth {
+    const AUTH_TYPE: AuthType = AuthType::Oauth2;
+
+    fn from_attributes(attributes: &[AuthAttribute]) -> Result<Self> {
+        let reader = Reader {
+            scheme: "oauth2",
+            attributes,
+        };
+        Ok(OAuth2Auth {
+            access_token: reader.string("accessToken")?,
+            token_type: reader.string("tokenType")?,
+            add_token_to: reader.string("addTokenTo")?,
+            header_prefix: reader.string("headerPrefix")?,
+            grant_type: reader.string("grant_type")?,
+            auth_url: reader.string("authUrl")?,
+            access_token_url: reader.string("accessTokenUrl")?,
+            client_id: reader.string("clientId")?,
+            client_secret: reader.string("clientSecret")?,
+            scope: reader.string("scope")?,
+            redirect_uri: reader.string("redirect_uri")?,
+            state: reader.string("state")?,
+        })
+    }
+
+    fn write_attributes(&self, attributes: &mut Vec<AuthAttribute>) {
+        let mut writer = Writer { attributes };
+        writer.optional_string("accessToken", self.access_token.as_deref());
+        writer.optional_string("tokenType", self.token_type.as_deref());
+        writer.optional_string("addTokenTo", self.add_token_to.as_deref());
+        writer.optional_string("headerPrefix", self.header_prefix.as_deref());
+        writer.optional_string("grant_type", self.grant_type.as_deref());
+        writer.optional_string("authUrl", self.auth_url.as_deref());
+        writer.optional_string("accessTokenUrl", self.access_token_url.as_deref());
+        writer.optional_string("clientId", self.client_id.as_deref());
+        writer.optional_string("clientSecret", self.client_secret.as_deref());
+        writer.optional_string("scope", self.scope.as_deref());
+        writer.optional_string("redirect_uri", self.redirect_uri.as_deref());
+        writer.optional_string("state", self.state.as_deref());
+    }
+}
@@ -2,6 +2,14 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod auth;
+pub mod iter;
+pub mod url;
+pub mod visit;
+
+/// The `info.schema` URL that identifies a v2.1.0 collection.
+pub const SCHEMA_URL: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Spec {
     #[serde(rename = "auth")]
@@ -24,7 +32,7 @@ pub struct Spec {
 }
 
 /// Represents authentication helpers provided by Postman
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct Auth {
     /// The attributes for [API key Auth](https://en.wikipedia.org/wiki/API_key).
     #[serde(rename = "apikey")]
@@ -137,7 +145,7 @@ pub struct Script {
     pub script_type: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct UrlClass {
     /// Contains the URL fragment (if any). Usually this is not transmitted over the network, but
     /// it could be useful to store this in some cases.
@@ -394,7 +402,7 @@ pub struct ProtocolProfileBehavior {
     pub disable_body_pruning: Option<bool>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct RequestClass {
     #[serde(rename = "auth")]
     pub auth: Option<Auth>,
@@ -422,7 +430,7 @@ pub struct RequestClass {
 }
 
 /// This field contains the data usually contained in the request body.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct Body {
     /// When set to true, prevents request body from being sent.
     #[serde(rename = "disabled")]
@@ -436,7 +444,7 @@ pub struct Body {
 
     /// GraphQL request payload as stored by Postman.
     #[serde(rename = "graphql")]
-    pub graphql: Option<serde_json::Value>,
+    pub graphql: Option<GraphQlBody>,
 
     /// Postman stores the type of data associated with this request in this field.
     #[serde(rename = "mode")]
@@ -444,7 +452,7 @@ pub struct Body {
 
     /// Additional configurations and options set for various body modes.
     #[serde(rename = "options")]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<BodyOptions>,
 
     #[serde(rename = "raw")]
     pub raw: Option<String>,
@@ -453,6 +461,47 @@ pub struct Body {
     pub urlencoded: Option<Vec<UrlEncodedParameter>>,
 }
 
+/// Options for each body mode, keyed by mode, such as `{"raw": {"language": "json"}}`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct BodyOptions {
+    #[serde(rename = "raw")]
+    pub raw: Option<RawOptions>,
+
+    /// Options for the other modes, such as `graphql` or `urlencoded`, kept as they are.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Options of a `raw` body.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RawOptions {
+    /// The language the editor highlights the body as, which also decides its `Content-Type`.
+    #[serde(rename = "language")]
+    pub language: Option<RawLanguage>,
+
+    /// Any other keys, kept as they are.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The payload of a `graphql` body.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct GraphQlBody {
+    #[serde(rename = "query")]
+    pub query: Option<String>,
+
+    /// Postman stores the variables as a JSON string, other tools as a JSON object.
+    #[serde(rename = "variables")]
+    pub variables: Option<GraphQlVariables>,
+
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+
+    /// Any other keys, kept as they are.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct File {
     #[serde(rename = "content")]
@@ -488,6 +537,15 @@ pub struct FormParameter {
     pub src: Option<FormParameterSrcUnion>,
 }
 
+/// The variables of a [`GraphQlBody`], either as the JSON text Postman writes or as JSON.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum GraphQlVariables {
+    String(String),
+
+    Json(serde_json::Value),
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum FormParameterSrcUnion {
@@ -771,6 +829,93 @@ pub enum HeaderUnion {
     String(String),
 }
 
+impl HeaderUnion {
+    /// Enabled headers as key/value pairs. In the string form, Postman marks disabled headers
+    /// with a leading `//`.
+    pub(crate) fn enabled(&self) -> Vec<(&str, &str)> {
+        match self {
+            HeaderUnion::HeaderArray(headers) => headers
+                .iter()
+                .filter(|header| !header.disabled.unwrap_or(false))
+                .map(|header| (header.key.as_str(), header.value.as_str()))
+                .collect(),
+            HeaderUnion::String(headers) => headers
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with("//"))
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .collect(),
+        }
+    }
+}
+
+impl Headers {
+    /// Enabled response headers as key/value pairs. Strings hold `Name: value` lines.
+    pub(crate) fn enabled(&self) -> Vec<(&str, &str)> {
+        match self {
+            Headers::String(lines) => header_lines(lines),
+            Headers::UnionArray(elements) => elements
+                .iter()
+                .flat_map(|element| match element {
+                    HeaderElement::Header(header) if header.disabled.unwrap_or(false) => Vec::new(),
+                    HeaderElement::Header(header) => {
+                        vec![(header.key.as_str(), header.value.as_str())]
+                    }
+                    HeaderElement::String(lines) => header_lines(lines),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ItemGroup {
+    /// An empty folder called `name`.
+    pub(crate) fn named(name: &str) -> Self {
+        ItemGroup {
+            auth: None,
+            description: None,
+            event: None,
+            item: Vec::new(),
+            name: Some(name.to_owned()),
+            variable: None,
+        }
+    }
+
+    /// The items of the folder called `name` in `items`, added at the end if there is none.
+    pub(crate) fn items_of<'a>(items: &'a mut Vec<Items>, name: &str) -> &'a mut Vec<Items> {
+        let position = items.iter().position(
+            |item| matches!(item, Items::ItemGroup(group) if group.name.as_deref() == Some(name)),
+        );
+        let index = position.unwrap_or_else(|| {
+            items.push(Items::ItemGroup(ItemGroup::named(name)));
+            items.len() - 1
+        });
+        match &mut items[index] {
+            Items::ItemGroup(group) => &mut group.item,
+            Items::Item(_) => unreachable!("position only matches folders"),
+        }
+    }
+}
+
+impl DescriptionUnion {
+    /// The description text, whichever form it is stored in.
+    pub(crate) fn text(&self) -> Option<&str> {
+        match self {
+            DescriptionUnion::String(description) => Some(description),
+            DescriptionUnion::Description(description) => description.content.as_deref(),
+        }
+    }
+}
+
+fn header_lines(lines: &str) -> Vec<(&str, &str)> {
+    lines
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
 /// A response represents an HTTP response.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
@@ -861,6 +1006,47 @@ impl Default for AuthType {
     }
 }
 
+/// The language of a `raw` body.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum RawLanguage {
+    #[serde(rename = "graphql")]
+    Graphql,
+
+    #[serde(rename = "html")]
+    Html,
+
+    #[serde(rename = "javascript")]
+    Javascript,
+
+    #[serde(rename = "json")]
+    Json,
+
+    #[serde(rename = "text")]
+    Text,
+
+    #[serde(rename = "xml")]
+    Xml,
+
+    /// A language this crate does not know, kept as written.
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl RawLanguage {
+    /// The language as Postman writes it.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RawLanguage::Graphql => "graphql",
+            RawLanguage::Html => "html",
+            RawLanguage::Javascript => "javascript",
+            RawLanguage::Json => "json",
+            RawLanguage::Text => "text",
+            RawLanguage::Xml => "xml",
+            RawLanguage::Other(language) => language,
+        }
+    }
+}
+
 /// A variable may have multiple types. This field specifies the type of the variable.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum VariableType {
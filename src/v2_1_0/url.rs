@@ -0,0 +1,454 @@
+//! Splitting raw URLs into [`UrlClass`] parts and rendering them back.
+//!
+//! Parsing keeps `{{variable}}` placeholders and `:pathVariable` segments exactly as written, so
+//! a URL that still needs variable resolution survives a parse/render round trip unchanged.
+//! Separators inside a placeholder, such as the dot in `{{base.url}}`, are not treated as URL
+//! syntax.
+//!
+//! [`UrlClass::expand_path_variables`] then fills in the `:pathVariable` segments from
+//! [`UrlClass::variable`], percent-encoding each value as a path segment.
+//!
+//! ```
+//! use postman_collection::v2_1_0::{Host, UrlClass};
+//!
+//! let url = UrlClass::parse("https://{{host}}:8080/users/:id?expand=true#top");
+//! assert_eq!(url.protocol.as_deref(), Some("https"));
+//! assert_eq!(url.host, Some(Host::StringArray(vec!["{{host}}".to_owned()])));
+//! assert_eq!(url.port.as_deref(), Some("8080"));
+//! assert_eq!(url.to_string(), "https://{{host}}:8080/users/:id?expand=true#top");
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde_json::Value;
+
+use super::{Host, PathClass, PathElement, QueryParam, Url, UrlClass, UrlPath, Variable};
+use crate::variables::Resolved;
+use crate::{Error, Result};
+
+impl UrlClass {
+    /// Splits a raw URL into its parts. `raw` is kept as given, and every `:name` path segment
+    /// is listed in [`variable`](UrlClass::variable).
+    ///
+    /// Parsing never fails: anything that does not look like a protocol, port, query or hash is
+    /// kept in the host or path.
+    pub fn parse(raw: &str) -> UrlClass {
+        let mut url = UrlClass {
+            raw: Some(raw.to_owned()),
+            ..UrlClass::default()
+        };
+        let mut rest = raw.trim();
+
+        if let Some(index) = find_outside(rest, '#') {
+            url.hash = Some(rest[index + 1..].to_owned());
+            rest = &rest[..index];
+        }
+
+        if let Some(index) = find_outside(rest, '?') {
+            let query = &rest[index + 1..];
+            if !query.is_empty() {
+                url.query = Some(split_outside(query, '&').map(query_param).collect());
+            }
+            rest = &rest[..index];
+        }
+
+        if let Some(index) = rest.find("://") {
+            let protocol = &rest[..index];
+            if !protocol.is_empty() && !protocol.contains(['/', '?', '#']) {
+                url.protocol = Some(protocol.to_owned());
+                rest = &rest[index + 3..];
+            }
+        }
+
+        let authority = match find_outside(rest, '/') {
+            Some(index) => {
+                let segments: Vec<_> = split_outside(&rest[index + 1..], '/')
+                    .map(|segment| PathElement::String(segment.to_owned()))
+                    .collect();
+                url.variable = path_variables(&segments);
+                url.path = Some(UrlPath::UnionArray(segments));
+                &rest[..index]
+            }
+            None => rest,
+        };
+
+        let host = match port_index(authority) {
+            Some(index) => {
+                url.port = Some(authority[index + 1..].to_owned());
+                &authority[..index]
+            }
+            None => authority,
+        };
+        if !host.is_empty() {
+            url.host = Some(Host::StringArray(
+                split_outside(host, '.').map(str::to_owned).collect(),
+            ));
+        }
+
+        url
+    }
+
+    /// Validates the URL rendered from the parts. Fails when `{{variables}}` or `:pathVariables`
+    /// are still present, or when the result is not a valid absolute URL. Like Postman, a URL
+    /// without a protocol is sent over `http`, unless its host brings one, as a resolved
+    /// `{{baseUrl}}` does.
+    pub fn to_url(&self) -> Result<::url::Url> {
+        let mut rendered = self.to_string();
+        let scheme = rendered
+            .split('/')
+            .next()
+            .is_some_and(|authority| authority.ends_with(':'));
+        if self.protocol.is_none() && self.host.is_some() && !scheme {
+            rendered.insert_str(0, "http://");
+        }
+
+        let mut unresolved: Vec<String> = crate::variables::placeholders(&rendered)
+            .into_iter()
+            .map(|name| format!("{{{{{name}}}}}"))
+            .collect();
+        unresolved.extend(
+            path_segments(self.path.as_ref())
+                .into_iter()
+                .filter(|segment| is_path_variable(segment))
+                .map(str::to_owned),
+        );
+        if !unresolved.is_empty() {
+            return Err(Error::UnresolvedUrl {
+                url: rendered,
+                variables: unresolved,
+            });
+        }
+
+        ::url::Url::parse(&rendered).map_err(|source| Error::InvalidUrl {
+            url: rendered,
+            source,
+        })
+    }
+
+    /// Replaces every `:name` path segment with the value of the enabled entry of
+    /// [`variable`](UrlClass::variable) whose `key` (or `id`) is `name`.
+    ///
+    /// Values are percent-encoded for a path segment; existing `%XX` escapes and `{{variables}}`
+    /// are kept as they are. Segments without a value are left in place and reported in
+    /// [`Resolved::unresolved`]. A `raw` string, if any, is rebuilt from the expanded parts.
+    pub fn expand_path_variables(&self) -> Resolved<UrlClass> {
+        let values: Vec<(&str, String)> = self
+            .variable
+            .iter()
+            .flatten()
+            .filter(|variable| !variable.disabled.unwrap_or(false))
+            .filter_map(|variable| {
+                let name = variable.key.as_deref().or(variable.id.as_deref())?;
+                let value = match variable.value.as_ref()? {
+                    Value::Null => return None,
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                Some((name, value))
+            })
+            .collect();
+
+        self.expand_path_variables_with(|name| {
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| Cow::Borrowed(value.as_str()))
+        })
+    }
+
+    /// Like [`expand_path_variables`](Self::expand_path_variables), but takes the values from
+    /// `lookup` instead of [`variable`](UrlClass::variable).
+    pub fn expand_path_variables_with<'a>(
+        &self,
+        lookup: impl Fn(&str) -> Option<Cow<'a, str>>,
+    ) -> Resolved<UrlClass> {
+        let mut url = self.clone();
+        let mut unresolved = Vec::new();
+        let mut expand = |segment: &str| -> Option<String> {
+            if !is_path_variable(segment) {
+                return None;
+            }
+            let name = &segment[1..];
+            match lookup(name) {
+                Some(value) => Some(encode_path_segment(&value)),
+                None => {
+                    if !unresolved.iter().any(|missing| missing == name) {
+                        unresolved.push(name.to_owned());
+                    }
+                    None
+                }
+            }
+        };
+
+        match url.path.as_mut() {
+            Some(UrlPath::String(path)) => {
+                let leading = if path.starts_with('/') { "/" } else { "" };
+                let segments: Vec<String> = path
+                    .strip_prefix('/')
+                    .unwrap_or(path)
+                    .split('/')
+                    .map(|segment| expand(segment).unwrap_or_else(|| segment.to_owned()))
+                    .collect();
+                *path = format!("{leading}{}", segments.join("/"));
+            }
+            Some(UrlPath::UnionArray(segments)) => {
+                for segment in segments {
+                    match segment {
+                        PathElement::String(value) => {
+                            if let Some(expanded) = expand(value) {
+                                *value = expanded;
+                            }
+                        }
+                        PathElement::PathClass(PathClass {
+                            value: Some(value), ..
+                        }) => {
+                            if let Some(expanded) = expand(value) {
+                                *value = expanded;
+                            }
+                        }
+                        PathElement::PathClass(_) => {}
+                    }
+                }
+            }
+            None => {}
+        }
+
+        if url.raw.is_some() {
+            url.raw = Some(url.to_string());
+        }
+        Resolved {
+            value: url,
+            unresolved,
+        }
+    }
+}
+
+/// Renders the URL from its parts, ignoring [`raw`](UrlClass::raw). Disabled query parameters
+/// are left out, as Postman does when it builds the raw string.
+impl fmt::Display for UrlClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(protocol) = self.protocol.as_deref() {
+            write!(f, "{protocol}://")?;
+        }
+        match self.host.as_ref() {
+            Some(Host::String(host)) => f.write_str(host)?,
+            Some(Host::StringArray(parts)) => f.write_str(&parts.join("."))?,
+            None => {}
+        }
+        if let Some(port) = self.port.as_deref() {
+            write!(f, ":{port}")?;
+        }
+        match self.path.as_ref() {
+            Some(UrlPath::String(path)) => {
+                if !path.starts_with('/') {
+                    f.write_str("/")?;
+                }
+                f.write_str(path)?;
+            }
+            Some(UrlPath::UnionArray(_)) => {
+                for segment in path_segments(self.path.as_ref()) {
+                    write!(f, "/{segment}")?;
+                }
+            }
+            None => {}
+        }
+
+        let mut separator = '?';
+        for param in self.query.iter().flatten() {
+            if param.disabled.unwrap_or(false) {
+                continue;
+            }
+            write!(f, "{separator}{}", param.key.as_deref().unwrap_or_default())?;
+            if let Some(value) = param.value.as_deref() {
+                write!(f, "={value}")?;
+            }
+            separator = '&';
+        }
+
+        if let Some(hash) = self.hash.as_deref() {
+            write!(f, "#{hash}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for UrlClass {
+    fn from(raw: &str) -> Self {
+        UrlClass::parse(raw)
+    }
+}
+
+impl Url {
+    /// The URL as a string: the string form itself, the `raw` field of the object form, or the
+    /// object rendered from its parts when it has no `raw`.
+    pub fn raw(&self) -> Cow<'_, str> {
+        match self {
+            Url::String(raw) => Cow::Borrowed(raw),
+            Url::UrlClass(UrlClass { raw: Some(raw), .. }) => Cow::Borrowed(raw),
+            Url::UrlClass(url) => Cow::Owned(url.to_string()),
+        }
+    }
+
+    /// The URL split into parts. The string form is parsed, as is an object that only carries
+    /// `raw`; any other object is borrowed as is.
+    pub fn to_class(&self) -> Cow<'_, UrlClass> {
+        match self {
+            Url::String(raw) => Cow::Owned(UrlClass::parse(raw)),
+            Url::UrlClass(url) if url.host.is_none() && url.path.is_none() => match &url.raw {
+                Some(raw) => Cow::Owned(UrlClass::parse(raw)),
+                None => Cow::Borrowed(url),
+            },
+            Url::UrlClass(url) => Cow::Borrowed(url),
+        }
+    }
+
+    /// Validates the URL once its variables are resolved. See [`UrlClass::to_url`].
+    pub fn to_url(&self) -> Result<::url::Url> {
+        self.to_class().to_url()
+    }
+}
+
+/// The byte index of the first `needle` that is not inside a `{{placeholder}}`.
+fn find_outside(input: &str, needle: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut chars = input.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '{' if chars.peek().map(|&(_, next)| next) == Some('{') => {
+                chars.next();
+                depth += 1;
+            }
+            '}' if depth > 0 && chars.peek().map(|&(_, next)| next) == Some('}') => {
+                chars.next();
+                depth -= 1;
+            }
+            ch if ch == needle && depth == 0 => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits on `separator` wherever it is not inside a `{{placeholder}}`.
+fn split_outside(input: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut rest = Some(input);
+    std::iter::from_fn(move || {
+        let current = rest?;
+        match find_outside(current, separator) {
+            Some(index) => {
+                rest = Some(&current[index + separator.len_utf8()..]);
+                Some(&current[..index])
+            }
+            None => {
+                rest = None;
+                Some(current)
+            }
+        }
+    })
+}
+
+fn query_param(pair: &str) -> QueryParam {
+    let (key, value) = match find_outside(pair, '=') {
+        Some(index) => (&pair[..index], Some(pair[index + 1..].to_owned())),
+        None => (pair, None),
+    };
+    QueryParam {
+        description: None,
+        disabled: None,
+        key: Some(key.to_owned()),
+        value,
+    }
+}
+
+/// Where the port starts in `host:port`. The port must be numeric or a single placeholder, so
+/// user info (`user:pass@host`) and IPv6 literals are left alone.
+fn port_index(authority: &str) -> Option<usize> {
+    let host_start = authority.rfind('@').map_or(0, |index| index + 1);
+    let host = &authority[host_start..];
+    let mut index = None;
+    let mut rest = host;
+    while let Some(found) = find_outside(rest, ':') {
+        let absolute = host.len() - rest.len() + found;
+        index = Some(absolute);
+        rest = &rest[found + 1..];
+    }
+    let index = index?;
+    let port = &host[index + 1..];
+    let numeric = !port.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit());
+    let placeholder = port
+        .strip_prefix("{{")
+        .and_then(|port| port.strip_suffix("}}"))
+        .is_some_and(|name| !name.contains("{{") && !name.contains("}}"));
+    (numeric || placeholder).then_some(host_start + index)
+}
+
+pub(crate) fn path_segments(path: Option<&UrlPath>) -> Vec<&str> {
+    match path {
+        Some(UrlPath::String(path)) => path.strip_prefix('/').unwrap_or(path).split('/').collect(),
+        Some(UrlPath::UnionArray(segments)) => segments
+            .iter()
+            .map(|segment| match segment {
+                PathElement::String(segment) => segment.as_str(),
+                PathElement::PathClass(segment) => segment.value.as_deref().unwrap_or_default(),
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 `pchar`s, valid `%XX` escapes and `{{variables}}`.
+pub(crate) fn encode_path_segment(value: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let bytes = value.as_bytes();
+    let mut encoded = String::with_capacity(value.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let rest = &value[index..];
+        if rest.starts_with("{{") {
+            if let Some(end) = rest.find("}}") {
+                encoded.push_str(&rest[..end + 2]);
+                index += end + 2;
+                continue;
+            }
+        }
+
+        let byte = bytes[index];
+        let escaped = byte == b'%'
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_hexdigit)
+            && bytes.get(index + 2).is_some_and(u8::is_ascii_hexdigit);
+        if escaped || byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push('%');
+            encoded.push(HEX[usize::from(byte >> 4)] as char);
+            encoded.push(HEX[usize::from(byte & 0xf)] as char);
+        }
+        index += 1;
+    }
+    encoded
+}
+
+fn is_path_variable(segment: &str) -> bool {
+    segment.len() > 1 && segment.starts_with(':')
+}
+
+fn path_variables(segments: &[PathElement]) -> Option<Vec<Variable>> {
+    let variables: Vec<_> = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            PathElement::String(segment) if is_path_variable(segment) => Some(Variable {
+                description: None,
+                disabled: None,
+                id: None,
+                key: Some(segment[1..].to_owned()),
+                name: None,
+                system: None,
+                variable_type: None,
+                value: None,
+            }),
+            _ => None,
+        })
+        .collect();
+    (!variables.is_empty()).then_some(variables)
+}
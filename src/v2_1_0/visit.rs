@@ -0,0 +1,46 @@
+//! Visitors over the collection tree.
+//!
+//! [`Visitor`] and [`VisitorMut`] have one method per node type. Every method receives the
+//! node and its location as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) into the
+//! serialized collection, such as `/item/0/request/header/1`; the collection itself is at the
+//! empty pointer `""`. The default implementations call the matching `walk_*` function, which
+//! visits the children of the node. An implementation that overrides a method skips the
+//! subtree unless it calls the `walk_*` function itself.
+//!
+//! ```
+//! use postman_collection::v2_1_0::{Header, Spec, visit::Visitor};
+//!
+//! #[derive(Default)]
+//! struct DisabledHeaders(Vec<String>);
+//!
+//! impl<'a> Visitor<'a> for DisabledHeaders {
+//!     fn visit_header(&mut self, header: &'a Header, location: &str) {
+//!         if header.disabled == Some(true) {
+//!             self.0.push(location.to_owned());
+//!         }
+//!     }
+//! }
+//!
+//! let spec: Spec = serde_json::from_str(
+//!     r#"{
+//!       "info": {
+//!         "name": "Example",
+//!         "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+//!       },
+//!       "item": [{
+//!         "name": "Ping",
+//!         "request": {
+//!           "url": "https://example.com/ping",
+//!           "header": [{ "key": "X-Debug", "value": "1", "disabled": true }]
+//!         }
+//!       }]
+//!     }"#,
+//! )?;
+//!
+//! let mut visitor = DisabledHeaders::default();
+//! visitor.visit_spec(&spec, "");
+//! assert_eq!(visitor.0, ["/item/0/request/header/0"]);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+crate::tree::visit!();
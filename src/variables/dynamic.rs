@@ -0,0 +1,433 @@
+//! Postman's built-in dynamic variables such as `{{$guid}}` and `{{$randomInt}}`.
+//!
+//! Dynamic variables are produced by a [`DynamicVariables`] provider attached to a
+//! [`Resolver`](super::Resolver). Every occurrence of a placeholder gets a fresh value, and
+//! names that a scope defines take precedence over the provider.
+//!
+//! [`Builtins`] implements the variables Postman documents, using a small internal PRNG and a
+//! [`Clock`]. Seed it and fix the clock to get the same output on every run:
+//!
+//! ```
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! use postman_collection::variables::Resolver;
+//! use postman_collection::variables::dynamic::{Builtins, FixedClock};
+//!
+//! let clock = FixedClock(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+//! let resolver = Resolver::new().with_dynamic_variables(Builtins::seeded(7).with_clock(clock));
+//!
+//! let resolved = resolver.resolve_str("{{$timestamp}} {{$isoTimestamp}}");
+//! assert_eq!(resolved.value, "1700000000 2023-11-14T22:13:20.000Z");
+//! ```
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::faker;
+
+/// Produces values for `$`-prefixed placeholder names.
+pub trait DynamicVariables: Send + Sync {
+    /// A fresh value for `name`, given without the surrounding braces (for example `$guid`), or
+    /// `None` if the provider does not know the name.
+    fn generate(&self, name: &str) -> Option<String>;
+}
+
+/// The source of the current time for [`Builtins`].
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// The system clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that always reports the same time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedClock(pub SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// Postman's built-in dynamic variables.
+pub struct Builtins {
+    rng: Mutex<Rng>,
+    clock: Box<dyn Clock>,
+}
+
+impl fmt::Debug for Builtins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builtins").finish_non_exhaustive()
+    }
+}
+
+impl Default for Builtins {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builtins {
+    /// A provider seeded from the system time.
+    pub fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        Self::seeded(seed)
+    }
+
+    /// A provider that produces the same sequence of values for the same seed.
+    pub fn seeded(seed: u64) -> Self {
+        Builtins {
+            rng: Mutex::new(Rng(seed)),
+            clock: Box::new(SystemClock),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// The names this provider knows, with the leading `$`.
+    pub fn names() -> &'static [&'static str] {
+        NAMES
+    }
+}
+
+const NAMES: &[&str] = &[
+    "$guid",
+    "$timestamp",
+    "$isoTimestamp",
+    "$randomInt",
+    "$randomUUID",
+    "$randomAlphaNumeric",
+    "$randomBoolean",
+    "$randomColor",
+    "$randomHexColor",
+    "$randomAbbreviation",
+    "$randomIP",
+    "$randomIPV6",
+    "$randomMACAddress",
+    "$randomPassword",
+    "$randomLocale",
+    "$randomUserAgent",
+    "$randomProtocol",
+    "$randomSemver",
+    "$randomFirstName",
+    "$randomLastName",
+    "$randomFullName",
+    "$randomNamePrefix",
+    "$randomNameSuffix",
+    "$randomJobTitle",
+    "$randomPhoneNumber",
+    "$randomPhoneNumberExt",
+    "$randomCity",
+    "$randomStreetName",
+    "$randomStreetAddress",
+    "$randomCountry",
+    "$randomCountryCode",
+    "$randomLatitude",
+    "$randomLongitude",
+    "$randomDateFuture",
+    "$randomDatePast",
+    "$randomDateRecent",
+    "$randomWeekday",
+    "$randomMonth",
+    "$randomDomainName",
+    "$randomDomainSuffix",
+    "$randomDomainWord",
+    "$randomEmail",
+    "$randomExampleEmail",
+    "$randomUserName",
+    "$randomUrl",
+    "$randomFileName",
+    "$randomFileExt",
+    "$randomMimeType",
+    "$randomCompanyName",
+    "$randomCompanySuffix",
+    "$randomPrice",
+    "$randomCurrencyCode",
+    "$randomWord",
+    "$randomWords",
+    "$randomLoremWord",
+    "$randomLoremWords",
+    "$randomLoremSentence",
+    "$randomLoremParagraph",
+];
+
+impl DynamicVariables for Builtins {
+    fn generate(&self, name: &str) -> Option<String> {
+        let mut rng = self
+            .rng
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let rng = &mut *rng;
+        let now = self.clock.now();
+
+        let value = match name {
+            "$guid" | "$randomUUID" => uuid(rng),
+            "$timestamp" => unix_seconds(now).to_string(),
+            "$isoTimestamp" => iso_timestamp(unix_millis(now)),
+            "$randomInt" => rng.below(1001).to_string(),
+            "$randomAlphaNumeric" => rng.pick_char(ALPHANUMERIC).to_string(),
+            "$randomBoolean" => (rng.below(2) == 1).to_string(),
+            "$randomColor" => rng.pick(faker::COLORS).to_owned(),
+            "$randomHexColor" => format!("#{:06x}", rng.below(0x100_0000)),
+            "$randomAbbreviation" => rng.pick(faker::ABBREVIATIONS).to_owned(),
+            "$randomIP" => format!(
+                "{}.{}.{}.{}",
+                rng.below(256),
+                rng.below(256),
+                rng.below(256),
+                rng.below(256)
+            ),
+            "$randomIPV6" => (0..8)
+                .map(|_| format!("{:x}", rng.below(0x1_0000)))
+                .collect::<Vec<_>>()
+                .join(":"),
+            "$randomMACAddress" => (0..6)
+                .map(|_| format!("{:02x}", rng.below(256)))
+                .collect::<Vec<_>>()
+                .join(":"),
+            "$randomPassword" => (0..15).map(|_| rng.pick_char(ALPHANUMERIC)).collect(),
+            "$randomLocale" => rng.pick(faker::LOCALES).to_owned(),
+            "$randomUserAgent" => rng.pick(faker::USER_AGENTS).to_owned(),
+            "$randomProtocol" => rng.pick(&["http", "https"]).to_owned(),
+            "$randomSemver" => format!("{}.{}.{}", rng.below(10), rng.below(10), rng.below(10)),
+            "$randomFirstName" => rng.pick(faker::FIRST_NAMES).to_owned(),
+            "$randomLastName" => rng.pick(faker::LAST_NAMES).to_owned(),
+            "$randomFullName" => format!(
+                "{} {}",
+                rng.pick(faker::FIRST_NAMES),
+                rng.pick(faker::LAST_NAMES)
+            ),
+            "$randomNamePrefix" => rng.pick(faker::NAME_PREFIXES).to_owned(),
+            "$randomNameSuffix" => rng.pick(faker::NAME_SUFFIXES).to_owned(),
+            "$randomJobTitle" => format!(
+                "{} {} {}",
+                rng.pick(faker::JOB_LEVELS),
+                rng.pick(faker::JOB_AREAS),
+                rng.pick(faker::JOB_TYPES)
+            ),
+            "$randomPhoneNumber" => format!(
+                "{:03}-{:03}-{:04}",
+                200 + rng.below(800),
+                rng.below(1000),
+                rng.below(10_000)
+            ),
+            "$randomPhoneNumberExt" => format!(
+                "{}-{:03}-{:03}-{:04}",
+                1 + rng.below(99),
+                200 + rng.below(800),
+                rng.below(1000),
+                rng.below(10_000)
+            ),
+            "$randomCity" => rng.pick(faker::CITIES).to_owned(),
+            "$randomStreetName" => street_name(rng),
+            "$randomStreetAddress" => format!("{} {}", 1 + rng.below(9999), street_name(rng)),
+            "$randomCountry" => rng.pick(faker::COUNTRIES).to_owned(),
+            "$randomCountryCode" => rng.pick(faker::COUNTRY_CODES).to_owned(),
+            "$randomLatitude" => format!("{:.4}", rng.unit() * 180.0 - 90.0),
+            "$randomLongitude" => format!("{:.4}", rng.unit() * 360.0 - 180.0),
+            "$randomDateFuture" => {
+                let offset = 1 + rng.below(365 * MILLIS_PER_DAY);
+                iso_timestamp(unix_millis(now) + offset as i64)
+            }
+            "$randomDatePast" => {
+                let offset = 1 + rng.below(365 * MILLIS_PER_DAY);
+                iso_timestamp(unix_millis(now) - offset as i64)
+            }
+            "$randomDateRecent" => {
+                let offset = 1 + rng.below(MILLIS_PER_DAY);
+                iso_timestamp(unix_millis(now) - offset as i64)
+            }
+            "$randomWeekday" => rng.pick(faker::WEEKDAYS).to_owned(),
+            "$randomMonth" => rng.pick(faker::MONTHS).to_owned(),
+            "$randomDomainName" => format!(
+                "{}.{}",
+                rng.pick(faker::WORDS),
+                rng.pick(faker::DOMAIN_SUFFIXES)
+            ),
+            "$randomDomainSuffix" => rng.pick(faker::DOMAIN_SUFFIXES).to_owned(),
+            "$randomDomainWord" => rng.pick(faker::WORDS).to_owned(),
+            "$randomEmail" => format!(
+                "{}.{}@{}.{}",
+                rng.pick(faker::FIRST_NAMES),
+                rng.pick(faker::LAST_NAMES),
+                rng.pick(faker::WORDS),
+                rng.pick(faker::DOMAIN_SUFFIXES)
+            ),
+            "$randomExampleEmail" => format!(
+                "{}.{}@example.{}",
+                rng.pick(faker::FIRST_NAMES),
+                rng.pick(faker::LAST_NAMES),
+                rng.pick(&["com", "net", "org"])
+            ),
+            "$randomUserName" => format!(
+                "{}.{}{}",
+                rng.pick(faker::FIRST_NAMES),
+                rng.pick(faker::LAST_NAMES),
+                rng.below(100)
+            ),
+            "$randomUrl" => format!(
+                "https://{}.{}",
+                rng.pick(faker::WORDS),
+                rng.pick(faker::DOMAIN_SUFFIXES)
+            ),
+            "$randomFileName" => {
+                format!(
+                    "{}.{}",
+                    rng.pick(faker::WORDS),
+                    rng.pick(faker::FILE_EXTENSIONS)
+                )
+            }
+            "$randomFileExt" => rng.pick(faker::FILE_EXTENSIONS).to_owned(),
+            "$randomMimeType" => rng.pick(faker::MIME_TYPES).to_owned(),
+            "$randomCompanyName" => format!(
+                "{} {}",
+                rng.pick(faker::LAST_NAMES),
+                rng.pick(faker::COMPANY_SUFFIXES)
+            ),
+            "$randomCompanySuffix" => rng.pick(faker::COMPANY_SUFFIXES).to_owned(),
+            "$randomPrice" => format!("{}.{:02}", rng.below(1000), rng.below(100)),
+            "$randomCurrencyCode" => rng.pick(faker::CURRENCY_CODES).to_owned(),
+            "$randomWord" | "$randomLoremWord" => rng.pick(faker::WORDS).to_owned(),
+            "$randomWords" | "$randomLoremWords" => words(rng, 3),
+            "$randomLoremSentence" => sentence(rng),
+            "$randomLoremParagraph" => (0..3).map(|_| sentence(rng)).collect::<Vec<_>>().join(" "),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+const ALPHANUMERIC: &str = "abcdefghijklmnopqrstuvwxyz0123456789";
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+fn uuid(rng: &mut Rng) -> String {
+    let high = rng.next_u64();
+    let low = rng.next_u64();
+    // Version 4, RFC 4122 variant.
+    let high = (high & 0xffff_ffff_ffff_0fff) | 0x0000_0000_0000_4000;
+    let low = (low & 0x3fff_ffff_ffff_ffff) | 0x8000_0000_0000_0000;
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+fn street_name(rng: &mut Rng) -> String {
+    format!(
+        "{} {}",
+        rng.pick(faker::LAST_NAMES),
+        rng.pick(faker::STREET_SUFFIXES)
+    )
+}
+
+fn words(rng: &mut Rng, count: usize) -> String {
+    (0..count)
+        .map(|_| rng.pick(faker::WORDS))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sentence(rng: &mut Rng) -> String {
+    let count = 4 + rng.below(6) as usize;
+    let mut sentence = words(rng, count);
+    if let Some(first) = sentence.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    sentence.push('.');
+    sentence
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    unix_millis(time).div_euclid(1000)
+}
+
+pub(crate) fn unix_millis(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_millis() as i64,
+        Err(error) => -(error.duration().as_millis() as i64),
+    }
+}
+
+/// Formats milliseconds since the Unix epoch the way JavaScript's `Date.toISOString` does.
+pub(crate) fn iso_timestamp(millis: i64) -> String {
+    let days = millis.div_euclid(MILLIS_PER_DAY as i64);
+    let millis_of_day = millis.rem_euclid(MILLIS_PER_DAY as i64);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        millis_of_day / 3_600_000,
+        millis_of_day / 60_000 % 60,
+        millis_of_day / 1000 % 60,
+        millis_of_day % 1000
+    )
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian date.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// SplitMix64. Not suitable for anything security related, which dynamic variables are not.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`.
+    fn below(&mut self, bound: u64) -> u64 {
+        // Widening multiplication keeps the bias negligible without a rejection loop.
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// A number in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn pick<'a>(&mut self, values: &[&'a str]) -> &'a str {
+        values[self.below(values.len() as u64) as usize]
+    }
+
+    fn pick_char(&mut self, characters: &str) -> char {
+        let bytes = characters.as_bytes();
+        char::from(bytes[self.below(bytes.len() as u64) as usize])
+    }
+}
@@ -0,0 +1,219 @@
+//! Word lists behind the `$random*` dynamic variables.
+
+pub(crate) const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Amara", "Bruno", "Camila", "Chen", "Dana", "Diego", "Elena", "Farah", "Grace",
+    "Hiro", "Ines", "Jonas", "Kai", "Leila", "Marco", "Nadia", "Omar", "Priya", "Quinn", "Rosa",
+    "Sam", "Tariq", "Uma", "Viktor", "Wen", "Yara", "Zoe",
+];
+
+pub(crate) const LAST_NAMES: &[&str] = &[
+    "Abbott",
+    "Baker",
+    "Castillo",
+    "Dubois",
+    "Eriksen",
+    "Fischer",
+    "Garcia",
+    "Hansen",
+    "Ito",
+    "Jensen",
+    "Kowalski",
+    "Lopez",
+    "Moreau",
+    "Nakamura",
+    "Okafor",
+    "Petrov",
+    "Quinn",
+    "Rossi",
+    "Schmidt",
+    "Tanaka",
+    "Urban",
+    "Varga",
+    "Walker",
+    "Xu",
+    "Young",
+    "Zimmermann",
+];
+
+pub(crate) const NAME_PREFIXES: &[&str] = &["Mr.", "Mrs.", "Ms.", "Miss", "Dr."];
+
+pub(crate) const NAME_SUFFIXES: &[&str] = &["Jr.", "Sr.", "I", "II", "III", "IV", "V", "PhD"];
+
+pub(crate) const JOB_LEVELS: &[&str] = &[
+    "Lead",
+    "Senior",
+    "Junior",
+    "Principal",
+    "Chief",
+    "Associate",
+    "Regional",
+    "Global",
+];
+
+pub(crate) const JOB_AREAS: &[&str] = &[
+    "Security",
+    "Data",
+    "Marketing",
+    "Infrastructure",
+    "Quality",
+    "Operations",
+    "Research",
+    "Accounts",
+    "Branding",
+    "Integration",
+];
+
+pub(crate) const JOB_TYPES: &[&str] = &[
+    "Engineer",
+    "Analyst",
+    "Manager",
+    "Designer",
+    "Consultant",
+    "Architect",
+    "Specialist",
+    "Coordinator",
+    "Strategist",
+    "Administrator",
+];
+
+pub(crate) const CITIES: &[&str] = &[
+    "Amsterdam",
+    "Auckland",
+    "Bangalore",
+    "Berlin",
+    "Bogota",
+    "Cairo",
+    "Chicago",
+    "Dublin",
+    "Helsinki",
+    "Lagos",
+    "Lisbon",
+    "Melbourne",
+    "Montreal",
+    "Nairobi",
+    "Osaka",
+    "Oslo",
+    "Prague",
+    "Santiago",
+    "Seoul",
+    "Vienna",
+];
+
+pub(crate) const STREET_SUFFIXES: &[&str] = &[
+    "Street",
+    "Avenue",
+    "Road",
+    "Lane",
+    "Boulevard",
+    "Drive",
+    "Court",
+    "Place",
+    "Way",
+    "Terrace",
+];
+
+pub(crate) const COUNTRIES: &[&str] = &[
+    "Argentina",
+    "Australia",
+    "Brazil",
+    "Canada",
+    "Chile",
+    "Denmark",
+    "Egypt",
+    "Finland",
+    "France",
+    "Germany",
+    "India",
+    "Ireland",
+    "Japan",
+    "Kenya",
+    "Mexico",
+    "Netherlands",
+    "Nigeria",
+    "Norway",
+    "Portugal",
+    "South Korea",
+];
+
+pub(crate) const COUNTRY_CODES: &[&str] = &[
+    "AR", "AU", "BR", "CA", "CL", "DK", "EG", "FI", "FR", "DE", "IN", "IE", "JP", "KE", "MX", "NL",
+    "NG", "NO", "PT", "KR",
+];
+
+pub(crate) const COLORS: &[&str] = &[
+    "red", "orange", "yellow", "green", "teal", "blue", "indigo", "violet", "magenta", "black",
+    "white", "silver", "maroon", "olive", "navy",
+];
+
+pub(crate) const ABBREVIATIONS: &[&str] = &[
+    "API", "CSS", "HTML", "HTTP", "JSON", "SQL", "SMTP", "TCP", "UDP", "XML", "RSS", "SAS", "PCI",
+    "SSL", "FTP",
+];
+
+pub(crate) const LOCALES: &[&str] = &["en", "de", "fr", "es", "it", "ja", "ko", "nl", "pt", "sv"];
+
+pub(crate) const USER_AGENTS: &[&str] = &[
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_2) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+    "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+];
+
+pub(crate) const WEEKDAYS: &[&str] = &[
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+pub(crate) const MONTHS: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub(crate) const DOMAIN_SUFFIXES: &[&str] = &["com", "net", "org", "io", "info", "biz", "name"];
+
+pub(crate) const FILE_EXTENSIONS: &[&str] = &[
+    "txt", "json", "csv", "pdf", "png", "jpg", "gif", "mp3", "mp4", "zip", "xml", "html",
+];
+
+pub(crate) const MIME_TYPES: &[&str] = &[
+    "application/json",
+    "application/pdf",
+    "application/xml",
+    "application/zip",
+    "audio/mpeg",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "text/csv",
+    "text/html",
+    "text/plain",
+    "video/mp4",
+];
+
+pub(crate) const COMPANY_SUFFIXES: &[&str] = &["Inc", "LLC", "Group", "and Sons", "Ltd"];
+
+pub(crate) const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CHF", "CAD", "AUD", "NZD", "SEK", "NOK", "INR", "BRL",
+];
+
+pub(crate) const WORDS: &[&str] = &[
+    "alpha", "amber", "anchor", "aurora", "beacon", "birch", "bridge", "canyon", "cedar", "cipher",
+    "cobalt", "comet", "coral", "delta", "ember", "falcon", "fjord", "garnet", "glacier", "harbor",
+    "horizon", "indigo", "jasper", "kernel", "lagoon", "lantern", "maple", "meadow", "nebula",
+    "nimbus", "onyx", "orbit", "pebble", "pixel", "quartz", "raven", "ripple", "sable", "signal",
+    "summit", "tundra", "vector", "willow", "zephyr",
+];
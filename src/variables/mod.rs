@@ -0,0 +1,555 @@
+//! Expansion of `{{name}}` placeholders.
+//!
+//! A [`Resolver`] holds a stack of [`Scope`]s and replaces placeholders in strings, JSON values
+//! and whole v2.1.0 requests. Scopes follow Postman's precedence: a variable defined in a
+//! [`ScopeKind::Local`] scope shadows the same name in [`ScopeKind::Data`],
+//! [`ScopeKind::Environment`], [`ScopeKind::Collection`] and finally [`ScopeKind::Global`]
+//! scopes. Within one kind, scopes pushed later win, which is how folder variables shadow
+//! collection variables in [`Resolver::for_entry`].
+//!
+//! Values may reference other variables, and placeholders may be nested, as in
+//! `{{host_{{stage}}}}`: the innermost placeholder is expanded first and its result becomes part
+//! of the outer name. A value is expanded before it is inserted, up to [`Resolver::max_depth`]
+//! levels deep. A placeholder that refers back to a variable whose value is being expanded is
+//! left as it is, and so is every placeholder once [`MAX_EXPANDED_LEN`] bytes of values have
+//! been inserted. Placeholders that are still left are reported in [`Resolved::unresolved`].
+//!
+//! ```
+//! use postman_collection::variables::{Resolver, Scope, ScopeKind};
+//!
+//! let mut resolver = Resolver::new();
+//! resolver.push(
+//!     Scope::new(ScopeKind::Collection)
+//!         .with("stage", "prod")
+//!         .with("host_prod", "api.example.com")
+//!         .with("baseUrl", "https://{{host_{{stage}}}}"),
+//! );
+//!
+//! let resolved = resolver.resolve_str("{{baseUrl}}/users/{{userId}}");
+//! assert_eq!(resolved.value, "https://api.example.com/users/{{userId}}");
+//! assert_eq!(resolved.unresolved, ["userId"]);
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::v2_1_0::{
+    self, Auth, FormParameterSrcUnion, GraphQlVariables, HeaderUnion, Host, Item, ItemGroup,
+    PathElement, RequestClass, Spec, Url, UrlPath,
+};
+
+use self::dynamic::DynamicVariables;
+
+pub mod dynamic;
+mod faker;
+
+/// The default for [`Resolver::max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 20;
+
+/// How many bytes of variable values one expansion inserts at most. This bounds the output of
+/// values that reference another variable several times.
+pub const MAX_EXPANDED_LEN: usize = 1 << 20;
+
+/// Where a set of variables comes from, in increasing order of precedence.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ScopeKind {
+    Global,
+    Collection,
+    Environment,
+    /// Values of the current iteration of a data file.
+    Data,
+    Local,
+}
+
+/// The variable values of one source, such as a collection or an environment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub values: Vec<(String, String)>,
+}
+
+impl Scope {
+    pub fn new(kind: ScopeKind) -> Self {
+        Scope {
+            kind,
+            values: Vec::new(),
+        }
+    }
+
+    /// Builds a scope from collection, folder or item variables. Disabled variables are left
+    /// out; values that are not strings are used in their JSON form.
+    pub fn from_variables(kind: ScopeKind, variables: &[v2_1_0::Variable]) -> Self {
+        let values = variables
+            .iter()
+            .filter(|variable| !variable.disabled.unwrap_or(false))
+            .filter_map(|variable| {
+                let key = variable
+                    .key
+                    .as_ref()
+                    .or(variable.id.as_ref())
+                    .or(variable.name.as_ref())?;
+                Some((key.clone(), value_to_string(variable.value.as_ref())))
+            })
+            .collect();
+        Scope { kind, values }
+    }
+
+    /// Adds a value, replacing an earlier value of the same name.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Sets a value, replacing an earlier value of the same name.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        let value = value.into();
+        match self
+            .values
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((key, value)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        // The last definition wins, as it does when Postman loads a variable list.
+        self.values
+            .iter()
+            .rev()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A value after expansion, with the names of the placeholders that could not be expanded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Resolved<T> {
+    pub value: T,
+    /// Unresolved names in the order they were first met, without repetitions.
+    pub unresolved: Vec<String>,
+}
+
+impl<T> Resolved<T> {
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// Expands placeholders from a stack of scopes. See the [module documentation](self).
+#[derive(Clone)]
+pub struct Resolver {
+    scopes: Vec<Scope>,
+    max_depth: usize,
+    dynamic: Option<Arc<dyn DynamicVariables>>,
+}
+
+impl fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resolver")
+            .field("scopes", &self.scopes)
+            .field("max_depth", &self.max_depth)
+            .field("dynamic", &self.dynamic.is_some())
+            .finish()
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Resolver {
+            scopes: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            dynamic: None,
+        }
+    }
+
+    /// The variables that apply to an item of a collection: collection variables, then the
+    /// variables of each enclosing folder from the outside in, then the item's own variables.
+    /// All of them are [`ScopeKind::Collection`] scopes.
+    ///
+    /// The ancestors are typically taken from [`Spec::items`].
+    pub fn for_item(spec: &Spec, ancestors: &[&ItemGroup], item: &Item) -> Self {
+        let mut resolver = Resolver::new();
+        let variables = std::iter::once(spec.variable.as_deref())
+            .chain(ancestors.iter().map(|group| group.variable.as_deref()))
+            .chain([item.variable.as_deref()]);
+        for variables in variables.flatten() {
+            resolver.push(Scope::from_variables(ScopeKind::Collection, variables));
+        }
+        resolver
+    }
+
+    /// Shorthand for [`for_item`](Self::for_item) with an entry of [`Spec::items`].
+    pub fn for_entry(spec: &Spec, entry: &v2_1_0::iter::ItemEntry<'_>) -> Self {
+        Self::for_item(spec, &entry.ancestors, entry.item)
+    }
+
+    /// Adds a scope. It shadows scopes of lower precedence and earlier scopes of the same kind.
+    pub fn push(&mut self, scope: Scope) {
+        let position = self
+            .scopes
+            .iter()
+            .position(|existing| existing.kind > scope.kind)
+            .unwrap_or(self.scopes.len());
+        self.scopes.insert(position, scope);
+    }
+
+    /// Builder form of [`push`](Self::push).
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.push(scope);
+        self
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// How deeply values are expanded within values before remaining placeholders are given up
+    /// on.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Expands `$`-prefixed names such as `{{$guid}}` with `provider` when no scope defines
+    /// them. Without a provider these placeholders are reported as unresolved.
+    pub fn with_dynamic_variables(mut self, provider: impl DynamicVariables + 'static) -> Self {
+        self.dynamic = Some(Arc::new(provider));
+        self
+    }
+
+    /// The effective value of a variable, before expansion of any placeholders it contains.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    pub fn resolve_str(&self, input: &str) -> Resolved<String> {
+        let mut unresolved = Vec::new();
+        let value = self.expand(input, &mut unresolved);
+        Resolved { value, unresolved }
+    }
+
+    /// Expands placeholders in every string of a JSON value. Object keys are kept as they are.
+    pub fn resolve_value(&self, input: &Value) -> Resolved<Value> {
+        let mut value = input.clone();
+        let mut unresolved = Vec::new();
+        self.expand_value(&mut value, &mut unresolved);
+        Resolved { value, unresolved }
+    }
+
+    /// Expands placeholders in the URL, headers, body and auth attributes of a request.
+    /// Descriptions, certificates and proxy settings are left alone.
+    pub fn resolve_request(&self, request: &RequestClass) -> Resolved<RequestClass> {
+        let mut request = request.clone();
+        let mut unresolved = Vec::new();
+
+        if let Some(url) = request.url.as_mut() {
+            self.expand_url(url, &mut unresolved);
+        }
+        match request.header.as_mut() {
+            Some(HeaderUnion::HeaderArray(headers)) => {
+                for header in headers {
+                    self.expand_in_place(&mut header.key, &mut unresolved);
+                    self.expand_in_place(&mut header.value, &mut unresolved);
+                }
+            }
+            Some(HeaderUnion::String(headers)) => {
+                self.expand_in_place(headers, &mut unresolved);
+            }
+            None => {}
+        }
+        if let Some(body) = request.body.as_mut() {
+            self.expand_option(&mut body.raw, &mut unresolved);
+            for param in body.urlencoded.iter_mut().flatten() {
+                self.expand_in_place(&mut param.key, &mut unresolved);
+                self.expand_option(&mut param.value, &mut unresolved);
+            }
+            for param in body.formdata.iter_mut().flatten() {
+                self.expand_in_place(&mut param.key, &mut unresolved);
+                self.expand_option(&mut param.value, &mut unresolved);
+                match param.src.as_mut() {
+                    Some(FormParameterSrcUnion::File(src)) => {
+                        self.expand_in_place(src, &mut unresolved)
+                    }
+                    Some(FormParameterSrcUnion::Files(sources)) => {
+                        for src in sources {
+                            self.expand_in_place(src, &mut unresolved);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            if let Some(file) = body.file.as_mut() {
+                self.expand_option(&mut file.src, &mut unresolved);
+            }
+            if let Some(graphql) = body.graphql.as_mut() {
+                self.expand_option(&mut graphql.query, &mut unresolved);
+                self.expand_option(&mut graphql.operation_name, &mut unresolved);
+                match graphql.variables.as_mut() {
+                    Some(GraphQlVariables::String(variables)) => {
+                        self.expand_in_place(variables, &mut unresolved)
+                    }
+                    Some(GraphQlVariables::Json(variables)) => {
+                        self.expand_value(variables, &mut unresolved)
+                    }
+                    None => {}
+                }
+            }
+        }
+        if let Some(auth) = request.auth.as_mut() {
+            self.expand_auth(auth, &mut unresolved);
+        }
+
+        Resolved {
+            value: request,
+            unresolved,
+        }
+    }
+
+    fn expand_url(&self, url: &mut Url, unresolved: &mut Vec<String>) {
+        let url = match url {
+            Url::String(raw) => return self.expand_in_place(raw, unresolved),
+            Url::UrlClass(url) => url,
+        };
+
+        self.expand_option(&mut url.raw, unresolved);
+        self.expand_option(&mut url.protocol, unresolved);
+        match url.host.as_mut() {
+            Some(Host::String(host)) => self.expand_in_place(host, unresolved),
+            Some(Host::StringArray(parts)) => {
+                for part in parts {
+                    self.expand_in_place(part, unresolved);
+                }
+            }
+            None => {}
+        }
+        self.expand_option(&mut url.port, unresolved);
+        match url.path.as_mut() {
+            Some(UrlPath::String(path)) => self.expand_in_place(path, unresolved),
+            Some(UrlPath::UnionArray(segments)) => {
+                for segment in segments {
+                    match segment {
+                        PathElement::String(segment) => self.expand_in_place(segment, unresolved),
+                        PathElement::PathClass(segment) => {
+                            self.expand_option(&mut segment.value, unresolved)
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+        for param in url.query.iter_mut().flatten() {
+            self.expand_option(&mut param.key, unresolved);
+            self.expand_option(&mut param.value, unresolved);
+        }
+        for variable in url.variable.iter_mut().flatten() {
+            if let Some(value) = variable.value.as_mut() {
+                self.expand_value(value, unresolved);
+            }
+        }
+        self.expand_option(&mut url.hash, unresolved);
+    }
+
+    fn expand_auth(&self, auth: &mut Auth, unresolved: &mut Vec<String>) {
+        let attributes = [
+            &mut auth.api_key,
+            &mut auth.awsv4,
+            &mut auth.basic,
+            &mut auth.bearer,
+            &mut auth.digest,
+            &mut auth.edgegrid,
+            &mut auth.hawk,
+            &mut auth.ntlm,
+            &mut auth.oauth1,
+            &mut auth.oauth2,
+        ];
+        for attribute in attributes.into_iter().flatten().flatten() {
+            if let Some(value) = attribute.value.as_mut() {
+                self.expand_value(value, unresolved);
+            }
+        }
+    }
+
+    fn expand_value(&self, value: &mut Value, unresolved: &mut Vec<String>) {
+        match value {
+            Value::String(string) => self.expand_in_place(string, unresolved),
+            Value::Array(values) => {
+                for value in values {
+                    self.expand_value(value, unresolved);
+                }
+            }
+            Value::Object(map) => {
+                for value in map.values_mut() {
+                    self.expand_value(value, unresolved);
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+
+    fn expand_option(&self, value: &mut Option<String>, unresolved: &mut Vec<String>) {
+        if let Some(value) = value.as_mut() {
+            self.expand_in_place(value, unresolved);
+        }
+    }
+
+    fn expand_in_place(&self, value: &mut String, unresolved: &mut Vec<String>) {
+        if value.contains("{{") {
+            *value = self.expand(value, unresolved);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<Cow<'_, str>> {
+        if let Some(value) = self.get(name) {
+            return Some(Cow::Borrowed(value));
+        }
+        match self.dynamic.as_ref() {
+            Some(dynamic) if name.starts_with('$') => dynamic.generate(name).map(Cow::Owned),
+            _ => None,
+        }
+    }
+
+    fn expand(&self, input: &str, unresolved: &mut Vec<String>) -> String {
+        let value = Expansion {
+            resolver: self,
+            stack: Vec::new(),
+            budget: MAX_EXPANDED_LEN,
+        }
+        .expand(input);
+
+        for name in placeholders(&value) {
+            if !unresolved.iter().any(|existing| existing == name) {
+                unresolved.push(name.to_owned());
+            }
+        }
+        value
+    }
+}
+
+/// The state of one expansion by a [`Resolver`].
+struct Expansion<'a> {
+    resolver: &'a Resolver,
+    /// The variables whose values are being expanded, outermost first.
+    stack: Vec<String>,
+    /// How many more bytes of values may be inserted.
+    budget: usize,
+}
+
+impl Expansion<'_> {
+    /// Replaces every placeholder of `input` that can be resolved, innermost first.
+    fn expand(&mut self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        // The names of the placeholders opened but not closed yet, outermost first.
+        let mut open: Vec<String> = Vec::new();
+        let mut rest = input;
+
+        loop {
+            let next = match (rest.find("{{"), rest.find("}}")) {
+                (Some(start), Some(end)) if start < end => Some((start, true)),
+                (_, Some(end)) => Some((end, false)),
+                (Some(start), None) => Some((start, true)),
+                (None, None) => None,
+            };
+            let Some((position, opens)) = next else {
+                break;
+            };
+            let text = &rest[..position];
+            rest = &rest[position + 2..];
+
+            if opens {
+                open.last_mut().unwrap_or(&mut output).push_str(text);
+                open.push(String::new());
+                continue;
+            }
+            match open.pop() {
+                Some(mut name) => {
+                    name.push_str(text);
+                    let value = self.resolve(&name);
+                    open.last_mut().unwrap_or(&mut output).push_str(&value);
+                }
+                None => {
+                    output.push_str(text);
+                    output.push_str("}}");
+                }
+            }
+        }
+
+        // Placeholders that are never closed are kept as written.
+        open.last_mut().unwrap_or(&mut output).push_str(rest);
+        for name in open {
+            output.push_str("{{");
+            output.push_str(&name);
+        }
+        output
+    }
+
+    /// The expanded value of `name`, or the placeholder itself when it cannot be resolved.
+    fn resolve(&mut self, name: &str) -> String {
+        let resolver = self.resolver;
+        let value = if self.stack.len() >= resolver.max_depth
+            || self.stack.iter().any(|expanding| expanding == name)
+        {
+            None
+        } else {
+            resolver.lookup(name)
+        };
+        let Some(value) = value.filter(|value| value.len() <= self.budget) else {
+            return format!("{{{{{name}}}}}");
+        };
+
+        self.budget -= value.len();
+        if !value.contains("{{") {
+            return value.into_owned();
+        }
+        self.stack.push(name.to_owned());
+        let expanded = self.expand(&value);
+        self.stack.pop();
+        expanded
+    }
+}
+
+/// The names of the innermost placeholders left in a string.
+pub(crate) fn placeholders(input: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = &after[..end];
+        match name.rfind("{{") {
+            Some(inner) => rest = &rest[start + 2 + inner..],
+            None => {
+                if !name.is_empty() {
+                    names.push(name);
+                }
+                rest = &after[end + 2..];
+            }
+        }
+    }
+    names
+}
+
+fn value_to_string(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+    }
+}
@@ -397,14 +397,20 @@ fn parses_v2_1_graphql_body_options_and_response_timings_explicitly() {
         .graphql
         .as_ref()
         .expect("GraphQL payload should be preserved");
-    assert_eq!(graphql["query"], "query Viewer { viewer { id name } }");
-    assert_eq!(graphql["variables"]["includeInactive"], false);
+    assert_eq!(
+        graphql.query.as_deref(),
+        Some("query Viewer { viewer { id name } }")
+    );
+    assert_eq!(
+        graphql.parse_variables().unwrap(),
+        Some(serde_json::json!({"includeInactive": false}))
+    );
 
     let options = body
         .options
         .as_ref()
         .expect("GraphQL body options should be preserved");
-    assert_eq!(options["graphql"]["output"], "json");
+    assert_eq!(options.other["graphql"]["output"], "json");
 
     let response = item
         .response
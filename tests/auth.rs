@@ -0,0 +1,271 @@
+use postman_collection::v2_1_0::auth::AuthSource;
+use postman_collection::{PostmanCollection, from_str, v2_0_0, v2_1_0};
+
+fn v2_1_spec(auth: &str) -> v2_1_0::Spec {
+    let input = format!(
+        r#"{{
+            "info": {{
+                "name": "Inherited",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            }},
+            {auth}
+            "item": [
+                {{
+                    "name": "Public",
+                    "auth": {{"type": "noauth"}},
+                    "item": [
+                        {{"name": "Health", "request": "https://example.com/health"}},
+                        {{
+                            "name": "Login",
+                            "request": {{
+                                "url": "https://example.com/login",
+                                "auth": {{"type": "basic", "basic": [{{"key": "username", "value": "alice"}}]}}
+                            }}
+                        }}
+                    ]
+                }},
+                {{
+                    "name": "Admin",
+                    "item": [
+                        {{
+                            "name": "Users",
+                            "item": [{{"name": "List users", "request": {{"url": "https://example.com/users"}}}}]
+                        }}
+                    ]
+                }}
+            ]
+        }}"#
+    );
+    let PostmanCollection::V2_1_0(spec) = from_str(&input).unwrap() else {
+        unreachable!();
+    };
+    spec
+}
+
+#[test]
+fn walks_up_to_the_closest_auth() {
+    let spec =
+        v2_1_spec(r#""auth": {"type": "bearer", "bearer": [{"key": "token", "value": "t"}]},"#);
+    let entries: Vec<_> = spec.items().collect();
+
+    let health = entries[0].effective_auth(&spec).unwrap();
+    assert!(health.is_noauth());
+    assert!(health.is_inherited());
+    let AuthSource::Folder { group, depth } = health.source else {
+        panic!("expected the folder to provide the auth");
+    };
+    assert_eq!(group.name.as_deref(), Some("Public"));
+    assert_eq!(depth, 0);
+
+    let login = entries[1].effective_auth(&spec).unwrap();
+    assert_eq!(login.source, AuthSource::Request);
+    assert_eq!(login.auth.auth_type, v2_1_0::AuthType::Basic);
+    assert!(!login.is_inherited());
+
+    let users = entries[2].effective_auth(&spec).unwrap();
+    assert_eq!(users.source, AuthSource::Collection);
+    assert_eq!(users.auth.auth_type, v2_1_0::AuthType::Bearer);
+}
+
+#[test]
+fn requests_without_any_auth_have_none() {
+    let spec = v2_1_spec("");
+    let entries: Vec<_> = spec.items().collect();
+
+    assert!(entries[0].effective_auth(&spec).is_some());
+    assert_eq!(entries[2].effective_auth(&spec), None);
+}
+
+#[test]
+fn explicit_noauth_on_the_request_is_not_inherited() {
+    let PostmanCollection::V2_1_0(spec) = from_str(
+        r#"{
+            "info": {
+                "name": "Explicit",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "auth": {"type": "apikey", "apikey": [{"key": "key", "value": "X-Key"}]},
+            "item": [{"name": "Open", "request": {"url": "https://example.com", "auth": {"type": "noauth"}}}]
+        }"#,
+    )
+    .unwrap() else {
+        unreachable!();
+    };
+
+    let entry = spec.items().next().unwrap();
+    let auth = v2_1_0::auth::effective_auth(&spec, &entry.ancestors, entry.item).unwrap();
+    assert!(auth.is_noauth());
+    assert!(!auth.is_inherited());
+}
+
+#[test]
+fn v2_0_collections_inherit_the_same_way() {
+    let PostmanCollection::V2_0_0(spec) = from_str(
+        r#"{
+            "info": {
+                "name": "Legacy",
+                "schema": "https://schema.getpostman.com/json/collection/v2.0.0/collection.json"
+            },
+            "item": [
+                {
+                    "name": "Folder",
+                    "auth": {"type": "basic", "basic": {"username": "alice"}},
+                    "item": [{"name": "Request", "request": "https://example.com"}]
+                }
+            ]
+        }"#,
+    )
+    .unwrap() else {
+        unreachable!();
+    };
+
+    let entry = spec.items().next().unwrap();
+    let auth = entry.effective_auth(&spec).unwrap();
+    assert_eq!(auth.auth.auth_type, v2_0_0::AuthType::Basic);
+    assert!(matches!(
+        auth.source,
+        v2_0_0::auth::AuthSource::Folder { depth: 0, .. }
+    ));
+}
+
+mod typed {
+    use postman_collection::Error;
+    use postman_collection::v2_1_0::Auth;
+    use postman_collection::v2_1_0::auth::{
+        ApiKeyAuth, ApiKeyLocation, AuthScheme, AwsV4Auth, BasicAuth, TypedAuth,
+    };
+
+    fn auth(input: &str) -> Auth {
+        serde_json::from_str(input).unwrap()
+    }
+
+    #[test]
+    fn reads_the_active_scheme() {
+        let auth = auth(
+            r#"{
+                "type": "apikey",
+                "apikey": [
+                    {"key": "key", "value": "X-Api-Key", "type": "string"},
+                    {"key": "value", "value": "{{apiKey}}", "type": "string"},
+                    {"key": "in", "value": "query", "type": "string"}
+                ],
+                "basic": [{"key": "username", "value": "ignored"}]
+            }"#,
+        );
+
+        assert_eq!(
+            auth.typed().unwrap(),
+            TypedAuth::ApiKey(ApiKeyAuth {
+                key: "X-Api-Key".to_owned(),
+                value: "{{apiKey}}".to_owned(),
+                location: ApiKeyLocation::Query,
+            })
+        );
+        assert_eq!(BasicAuth::from_auth(&auth).unwrap().username, "ignored");
+    }
+
+    #[test]
+    fn accepts_booleans_stored_as_strings() {
+        let auth = auth(
+            r#"{
+                "type": "awsv4",
+                "awsv4": [
+                    {"key": "accessKey", "value": "AKIA"},
+                    {"key": "secretKey", "value": "secret"},
+                    {"key": "addAuthDataToQuery", "value": "true"}
+                ]
+            }"#,
+        );
+
+        let aws = AwsV4Auth::from_auth(&auth).unwrap();
+        assert_eq!(aws.add_auth_data_to_query, Some(true));
+        assert_eq!(aws.region, None);
+    }
+
+    #[test]
+    fn reports_missing_and_mistyped_attributes() {
+        let missing = auth(r#"{"type": "bearer", "bearer": []}"#).typed();
+        assert!(matches!(
+            missing,
+            Err(Error::MissingAuthAttribute {
+                scheme: "bearer",
+                key: "token"
+            })
+        ));
+
+        let mistyped = auth(
+            r#"{"type": "ntlm", "ntlm": [
+                {"key": "username", "value": "u"},
+                {"key": "password", "value": "p"},
+                {"key": "disableRetryRequest", "value": "sometimes"}
+            ]}"#,
+        )
+        .typed()
+        .unwrap_err();
+        assert_eq!(
+            mistyped.to_string(),
+            r#"ntlm auth attribute `disableRetryRequest` should be a boolean, found "sometimes""#
+        );
+    }
+
+    #[test]
+    fn writes_back_without_dropping_unknown_attributes() {
+        let mut auth = auth(
+            r#"{
+                "type": "noauth",
+                "basic": [
+                    {"key": "showPassword", "value": false, "type": "boolean"},
+                    {"key": "username", "value": "alice", "type": "string"},
+                    {"key": "password", "value": "old", "type": "string"}
+                ]
+            }"#,
+        );
+
+        let mut basic = BasicAuth::from_auth(&auth).unwrap();
+        basic.username = "bob".to_owned();
+        basic.password = None;
+        basic.write_to(&mut auth);
+
+        let keys: Vec<_> = auth
+            .basic
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|attribute| attribute.key.as_str())
+            .collect();
+        assert_eq!(keys, ["showPassword", "username"]);
+        assert_eq!(
+            auth.typed().unwrap(),
+            TypedAuth::Basic(BasicAuth {
+                username: "bob".to_owned(),
+                password: None,
+            })
+        );
+
+        let fresh = Auth::from(&TypedAuth::Basic(basic));
+        assert_eq!(fresh.basic.as_ref().unwrap().len(), 1);
+        assert_eq!(fresh.bearer, None);
+    }
+
+    #[test]
+    fn keeps_the_stored_type_of_unchanged_values() {
+        let mut auth = auth(
+            r#"{
+                "type": "basic",
+                "basic": [
+                    {"key": "username", "value": 1042, "type": "number"},
+                    {"key": "password", "value": 7}
+                ]
+            }"#,
+        );
+
+        let mut basic = BasicAuth::from_auth(&auth).unwrap();
+        basic.password = Some("8".to_owned());
+        basic.write_to(&mut auth);
+
+        let attributes = auth.basic.as_ref().unwrap();
+        assert_eq!(attributes[0].value, Some(serde_json::json!(1042)));
+        assert_eq!(attributes[0].auth_type.as_deref(), Some("number"));
+        assert_eq!(attributes[1].value, Some(serde_json::json!("8")));
+    }
+}
@@ -0,0 +1,190 @@
+use std::io;
+
+use postman_collection::Error;
+use postman_collection::body::{Encoder, FsFileResolver};
+use postman_collection::v2_1_0::{Body, RawLanguage};
+use serde_json::json;
+
+fn body(value: serde_json::Value) -> Body {
+    serde_json::from_value(value).unwrap()
+}
+
+fn text(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn encodes_raw_bodies_with_their_language() {
+    let encoded = body(json!({
+        "mode": "raw",
+        "raw": "<a/>",
+        "options": {"raw": {"language": "xml"}}
+    }))
+    .encode()
+    .unwrap()
+    .unwrap();
+    assert_eq!(encoded.content_type.as_deref(), Some("application/xml"));
+    assert_eq!(encoded.bytes, b"<a/>");
+
+    let plain = body(json!({"mode": "raw", "raw": "hi"}))
+        .encode()
+        .unwrap()
+        .unwrap();
+    assert_eq!(plain.content_type.as_deref(), Some("text/plain"));
+}
+
+#[test]
+fn escapes_urlencoded_pairs_and_skips_disabled_ones() {
+    let encoded = body(json!({
+        "mode": "urlencoded",
+        "urlencoded": [
+            {"key": "q", "value": "a&b = c"},
+            {"key": "off", "value": "1", "disabled": true},
+            {"key": "empty"}
+        ]
+    }))
+    .encode()
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+        encoded.content_type.as_deref(),
+        Some("application/x-www-form-urlencoded")
+    );
+    assert_eq!(text(encoded.bytes), "q=a%26b+%3D+c&empty=");
+}
+
+#[test]
+fn reads_every_file_source_through_the_resolver() {
+    let form = body(json!({
+        "mode": "formdata",
+        "formdata": [
+            {"key": "meta", "value": "{}", "type": "text", "contentType": "application/json"},
+            {"key": "docs", "type": "file", "src": ["a.txt", "dir/b.txt"], "contentType": "text/plain"}
+        ]
+    }));
+    let encoder = Encoder::new()
+        .with_boundary("b")
+        .with_file_resolver(|src: &str| Ok(src.to_uppercase().into_bytes()));
+
+    let encoded = encoder.encode(&form).unwrap().unwrap();
+    assert_eq!(
+        text(encoded.bytes),
+        "--b\r\nContent-Disposition: form-data; name=\"meta\"\r\nContent-Type: application/json\r\n\r\n{}\r\n\
+         --b\r\nContent-Disposition: form-data; name=\"docs\"; filename=\"a.txt\"\r\nContent-Type: text/plain\r\n\r\nA.TXT\r\n\
+         --b\r\nContent-Disposition: form-data; name=\"docs\"; filename=\"b.txt\"\r\nContent-Type: text/plain\r\n\r\nDIR/B.TXT\r\n\
+         --b--\r\n"
+    );
+
+    let missing = Encoder::new()
+        .with_file_resolver(|_: &str| Err(io::Error::from(io::ErrorKind::NotFound)))
+        .encode(&form);
+    assert!(matches!(missing, Err(Error::File { src, .. }) if src == "a.txt"));
+}
+
+#[test]
+fn default_boundary_is_stable_and_absent_from_the_parts() {
+    let form = body(json!({
+        "mode": "formdata",
+        "formdata": [{"key": "k", "value": "v", "type": "text"}]
+    }));
+    let first = form.encode().unwrap().unwrap();
+    let second = form.encode().unwrap().unwrap();
+    assert_eq!(first, second);
+
+    let boundary = first
+        .content_type
+        .unwrap()
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .to_owned();
+    assert!(text(first.bytes).starts_with(&format!("--{boundary}\r\n")));
+    assert!(boundary.len() <= 70, "{boundary}");
+
+    // Other parts get a boundary of the same length that none of them contains.
+    let clash = body(json!({
+        "mode": "formdata",
+        "formdata": [{"key": "k", "value": boundary, "type": "text"}]
+    }));
+    let content_type = clash.encode().unwrap().unwrap().content_type.unwrap();
+    let other = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap();
+    assert!(!boundary.contains(other));
+    assert_eq!(other.len(), boundary.len());
+}
+
+#[test]
+fn reads_file_bodies_relative_to_a_root() {
+    let file = body(json!({"mode": "file", "file": {"src": "files/hello.txt"}}));
+    let encoded = Encoder::new()
+        .with_file_resolver(FsFileResolver::with_root("tests/fixtures"))
+        .encode(&file)
+        .unwrap()
+        .unwrap();
+    assert_eq!(encoded.content_type, None);
+    assert_eq!(encoded.bytes, b"hello from a file\n");
+}
+
+#[test]
+fn wraps_graphql_in_a_json_envelope() {
+    let encoded = body(json!({
+        "mode": "graphql",
+        "graphql": {"query": "{ me { id } }", "variables": ""}
+    }))
+    .encode()
+    .unwrap()
+    .unwrap();
+    assert_eq!(text(encoded.bytes), r#"{"query":"{ me { id } }"}"#);
+
+    let disabled = body(json!({"mode": "raw", "raw": "x", "disabled": true}));
+    assert_eq!(disabled.encode().unwrap(), None);
+}
+
+#[test]
+fn types_body_options_and_keeps_unknown_keys() {
+    let input = json!({
+        "mode": "raw",
+        "raw": "a,b",
+        "options": {
+            "raw": {"language": "csv", "headerFamily": "text"},
+            "urlencoded": {"trim": true}
+        }
+    });
+    let csv = body(input.clone());
+    let options = csv.options.as_ref().unwrap();
+    let raw = options.raw.as_ref().unwrap();
+    assert_eq!(raw.language, Some(RawLanguage::Other("csv".to_owned())));
+    assert_eq!(raw.other["headerFamily"], "text");
+    assert_eq!(options.other["urlencoded"], json!({"trim": true}));
+    assert_eq!(serde_json::to_value(options).unwrap(), input["options"]);
+
+    assert_eq!(
+        csv.encode().unwrap().unwrap().content_type.as_deref(),
+        Some("text/plain")
+    );
+    assert_eq!(RawLanguage::Html.content_type(), "text/html");
+}
+
+#[test]
+fn pretty_prints_json_raw_bodies_in_key_order() {
+    let json_body = body(json!({
+        "mode": "raw",
+        "raw": "{\"name\":\"Ada\",\"age\":36,\"tags\":[],\"meta\":{\"note\":\"a \\\"b\\\", c\"}}",
+        "options": {"raw": {"language": "json"}}
+    }));
+    assert_eq!(
+        json_body.pretty_raw().unwrap(),
+        "{\n  \"name\": \"Ada\",\n  \"age\": 36,\n  \"tags\": [],\n  \"meta\": {\n    \"note\": \"a \\\"b\\\", c\"\n  }\n}"
+    );
+
+    let templated = body(json!({
+        "mode": "raw",
+        "raw": "{\"id\": {{id}}}",
+        "options": {"raw": {"language": "json"}}
+    }));
+    assert_eq!(templated.pretty_raw().unwrap(), "{\"id\": {{id}}}");
+
+    let text_body = body(json!({"mode": "raw", "raw": "{\"a\":1}"}));
+    assert_eq!(text_body.pretty_raw().unwrap(), "{\"a\":1}");
+}
@@ -0,0 +1,340 @@
+use std::path::PathBuf;
+
+use postman_collection::convert::{self, ConversionWarningKind};
+use postman_collection::{Error, PostmanCollection, from_path, from_str, v1_0_0, v2_0_0, v2_1_0};
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("collection")
+        .join(name)
+}
+
+fn v1_fixture(name: &str) -> v1_0_0::Spec {
+    match from_path(fixture_path(name)).expect("fixture should parse") {
+        PostmanCollection::V1_0_0(spec) => spec,
+        collection => panic!("expected v1 collection, got {:?}", collection.version()),
+    }
+}
+
+fn v1_spec(input: &str) -> v1_0_0::Spec {
+    match from_str(input).expect("collection should parse") {
+        PostmanCollection::V1_0_0(spec) => spec,
+        collection => panic!("expected v1 collection, got {:?}", collection.version()),
+    }
+}
+
+fn v2_0_fixture(name: &str) -> v2_0_0::Spec {
+    match from_path(fixture_path(name)).expect("fixture should parse") {
+        PostmanCollection::V2_0_0(spec) => spec,
+        collection => panic!("expected v2.0.0 collection, got {:?}", collection.version()),
+    }
+}
+
+fn v2_1_fixture(name: &str) -> v2_1_0::Spec {
+    match from_path(fixture_path(name)).expect("fixture should parse") {
+        PostmanCollection::V2_1_0(spec) => spec,
+        collection => panic!("expected v2.1.0 collection, got {:?}", collection.version()),
+    }
+}
+
+fn request_of(item: &v2_1_0::Items) -> &v2_1_0::RequestClass {
+    let v2_1_0::Items::Item(item) = item else {
+        panic!("expected an item");
+    };
+    let v2_1_0::RequestUnion::RequestClass(request) = &item.request else {
+        panic!("expected a structured request");
+    };
+    request
+}
+
+#[test]
+fn converts_v1_petstore_into_folder_tree() {
+    let converted = convert::v1_0_0_to_v2_1_0(&v1_fixture("swagger-petstore-v1.0.0.json"));
+    let spec = converted.spec;
+
+    assert!(converted.report.is_lossless(), "{}", converted.report);
+    assert_eq!(spec.info.name, "Swagger Petstore");
+    assert_eq!(spec.info.schema, v2_1_0::SCHEMA_URL);
+
+    let folder_names: Vec<_> = spec
+        .item
+        .iter()
+        .map(|item| match item {
+            v2_1_0::Items::ItemGroup(group) => group.name.as_deref().unwrap(),
+            v2_1_0::Items::Item(_) => panic!("expected only folders at the root"),
+        })
+        .collect();
+    assert_eq!(folder_names, ["pet", "store", "user"]);
+
+    let v2_1_0::Items::ItemGroup(pet) = &spec.item[0] else {
+        unreachable!();
+    };
+    assert_eq!(pet.item.len(), 8);
+
+    let find_by_id = pet
+        .item
+        .iter()
+        .find_map(|item| match item {
+            v2_1_0::Items::Item(item) if item.name.as_deref() == Some("Find pet by ID") => {
+                Some(item)
+            }
+            _ => None,
+        })
+        .expect("request should be placed in its folder");
+    let v2_1_0::RequestUnion::RequestClass(request) = &find_by_id.request else {
+        panic!("expected a structured request");
+    };
+    let Some(v2_1_0::HeaderUnion::HeaderArray(headers)) = &request.header else {
+        panic!("expected a header list");
+    };
+    assert_eq!(headers[0].key, "Accept");
+    assert_eq!(headers[0].value, "application/xml, application/json");
+    let Some(v2_1_0::Url::UrlClass(url)) = &request.url else {
+        panic!("expected path variables to produce a structured URL");
+    };
+    let variables = url
+        .variable
+        .as_ref()
+        .expect("path variables should be kept");
+    assert_eq!(variables[0].key.as_deref(), Some("petId"));
+
+    // The converted collection must be a valid v2.1.0 document.
+    let json = postman_collection::to_json(&PostmanCollection::V2_1_0(spec.clone())).unwrap();
+    assert_eq!(from_str(&json).unwrap(), PostmanCollection::V2_1_0(spec));
+}
+
+#[test]
+fn converts_v1_headers_bodies_auth_helpers_and_scripts() {
+    let spec = v1_spec(
+        r#"{
+            "id": "collection",
+            "name": "Legacy",
+            "order": ["raw", "form"],
+            "requests": [
+                {
+                    "id": "raw",
+                    "name": "Raw",
+                    "method": "POST",
+                    "url": "https://example.com/raw",
+                    "headers": "Content-Type: application/json\n// X-Debug: 1\n",
+                    "dataMode": "raw",
+                    "rawModeData": "{\"a\":1}",
+                    "preRequestScript": "pm.variables.set('a', 1);\nconsole.log('a');",
+                    "tests": "pm.test('ok', () => {});",
+                    "currentHelper": "basic",
+                    "helperAttributes": {"id": "basic", "username": "alice", "password": "secret"}
+                },
+                {
+                    "id": "form",
+                    "name": "Form",
+                    "method": "POST",
+                    "url": "https://example.com/form",
+                    "headers": "",
+                    "dataMode": "params",
+                    "data": [
+                        {"key": "name", "value": "Rex", "type": "text", "enabled": true},
+                        {"key": "photo", "value": "/tmp/rex.png", "type": "file", "enabled": false}
+                    ]
+                }
+            ]
+        }"#,
+    );
+
+    let converted = convert::v1_0_0_to_v2_1_0(&spec);
+    assert!(converted.report.is_lossless(), "{}", converted.report);
+
+    let raw = request_of(&converted.spec.item[0]);
+    let Some(v2_1_0::HeaderUnion::HeaderArray(headers)) = &raw.header else {
+        panic!("expected a header list");
+    };
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[0].disabled, None);
+    assert_eq!(headers[1].key, "X-Debug");
+    assert_eq!(headers[1].disabled, Some(true));
+
+    let body = raw.body.as_ref().expect("raw body should be converted");
+    assert_eq!(body.mode, Some(v2_1_0::Mode::Raw));
+    assert_eq!(body.raw.as_deref(), Some("{\"a\":1}"));
+
+    let auth = raw
+        .auth
+        .as_ref()
+        .expect("helper attributes should become auth");
+    assert_eq!(auth.auth_type, v2_1_0::AuthType::Basic);
+    let keys: Vec<_> = auth
+        .basic
+        .iter()
+        .flatten()
+        .map(|attribute| attribute.key.as_str())
+        .collect();
+    assert_eq!(keys, ["password", "username"]);
+
+    let v2_1_0::Items::Item(raw_item) = &converted.spec.item[0] else {
+        unreachable!();
+    };
+    let events = raw_item
+        .event
+        .as_ref()
+        .expect("scripts should become events");
+    assert_eq!(events[0].listen, "prerequest");
+    assert_eq!(
+        events[0].script.as_ref().unwrap().exec,
+        Some(v2_1_0::Host::StringArray(vec![
+            "pm.variables.set('a', 1);".to_owned(),
+            "console.log('a');".to_owned(),
+        ]))
+    );
+    assert_eq!(events[1].listen, "test");
+
+    let form = request_of(&converted.spec.item[1]);
+    let formdata = form
+        .body
+        .as_ref()
+        .and_then(|body| body.formdata.as_ref())
+        .expect("params should become form data");
+    assert_eq!(formdata[0].value.as_deref(), Some("Rex"));
+    assert_eq!(
+        formdata[1].src,
+        Some(v2_1_0::FormParameterSrcUnion::File(
+            "/tmp/rex.png".to_owned()
+        ))
+    );
+    assert_eq!(formdata[1].disabled, Some(true));
+}
+
+#[test]
+fn reports_what_v1_conversion_could_not_carry_over() {
+    let spec = v1_spec(
+        r#"{
+            "id": "collection",
+            "name": "Broken",
+            "order": ["known", "missing"],
+            "folders_order": [],
+            "folders": [
+                {"id": "folder", "name": "Forgotten", "description": "", "order": []}
+            ],
+            "requests": [
+                {
+                    "id": "known",
+                    "name": "Known",
+                    "method": "GET",
+                    "url": "https://example.com",
+                    "headers": "not a header"
+                },
+                {
+                    "id": "orphan",
+                    "name": "Orphan",
+                    "method": "GET",
+                    "url": "https://example.com/orphan",
+                    "headers": "",
+                    "folder": "folder"
+                }
+            ]
+        }"#,
+    );
+
+    let converted = convert::v1_0_0_to_v2_1_0(&spec);
+    let warnings: Vec<_> = converted
+        .report
+        .warnings
+        .iter()
+        .map(|warning| (warning.location.as_str(), &warning.kind))
+        .collect();
+
+    assert!(warnings.contains(&(
+        "/order",
+        &ConversionWarningKind::MissingReference {
+            id: "missing".to_owned()
+        }
+    )));
+    assert!(warnings.contains(&("/folders/0", &ConversionWarningKind::Unordered)));
+    assert!(warnings.contains(&("/requests/1", &ConversionWarningKind::Unordered)));
+    assert!(
+        warnings
+            .iter()
+            .any(|(location, kind)| *location == "/requests/0/headers"
+                && matches!(kind, ConversionWarningKind::Dropped { .. }))
+    );
+
+    // Unordered entries are kept: the orphan request ends up in the folder it names.
+    let v2_1_0::Items::ItemGroup(folder) = &converted.spec.item[1] else {
+        panic!("expected the unordered folder to be appended to the root");
+    };
+    assert_eq!(folder.name.as_deref(), Some("Forgotten"));
+    let v2_1_0::Items::Item(orphan) = &folder.item[0] else {
+        panic!("expected the orphan request inside its folder");
+    };
+    assert_eq!(orphan.name.as_deref(), Some("Orphan"));
+}
+
+#[test]
+fn v2_0_collections_survive_an_upgrade_and_downgrade() {
+    for name in [
+        "swagger-petstore-v2.0.0.json",
+        "apikey-v2.0.0.json",
+        "coverage/v2.0.0-item-group.json",
+    ] {
+        let original = v2_0_fixture(name);
+        let upgraded = v2_1_0::Spec::from(original.clone());
+        assert_eq!(upgraded.info.schema, v2_1_0::SCHEMA_URL);
+
+        let downgraded = v2_0_0::Spec::try_from(upgraded).expect("downgrade should be lossless");
+        assert_eq!(downgraded, original, "round trip changed {name}");
+    }
+}
+
+#[test]
+fn upgrades_v2_0_auth_maps_into_attribute_lists() {
+    let upgraded = v2_1_0::Spec::from(v2_0_fixture("apikey-v2.0.0.json"));
+    let auth = upgraded.auth.expect("auth should be carried over");
+
+    assert_eq!(auth.auth_type, v2_1_0::AuthType::Apikey);
+    let attributes = auth
+        .api_key
+        .expect("API key attributes should be carried over");
+    let keys: Vec<_> = attributes
+        .iter()
+        .map(|attribute| attribute.key.as_str())
+        .collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted, "attributes should come out in a stable order");
+    assert!(keys.contains(&"key"));
+}
+
+#[test]
+fn refuses_to_downgrade_v2_1_only_constructs() {
+    let error = v2_0_0::Spec::try_from(v2_1_fixture("edgegrid-v2.1.0.json"))
+        .expect_err("EdgeGrid auth cannot be expressed in v2.0.0");
+    let Error::LossyConversion { report } = error else {
+        panic!("expected a lossy conversion error, got {error:?}");
+    };
+    assert_eq!(report.warnings[0].location, "/auth");
+
+    let converted = convert::v2_1_0_to_v2_0_0(&v2_1_fixture("graphql-query-v2.1.0.json"));
+    let locations: Vec<_> = converted
+        .report
+        .warnings
+        .iter()
+        .map(|warning| warning.location.as_str())
+        .collect();
+    assert!(locations.contains(&"/item/0/request/body/graphql"));
+    assert!(locations.contains(&"/item/0/request/body/mode"));
+    assert!(locations.contains(&"/item/0/response/0/timings"));
+    assert_eq!(converted.spec.info.schema, v2_0_0::SCHEMA_URL);
+}
+
+#[test]
+fn downgrade_reports_auth_attributes_a_map_cannot_hold() {
+    let mut spec = v2_1_fixture("apikey-v2.1.0.json");
+    let attributes = spec.auth.as_mut().unwrap().api_key.as_mut().unwrap();
+    let mut duplicate = attributes[0].clone();
+    duplicate.auth_type = Some("string".to_owned());
+    attributes.push(duplicate);
+
+    let converted = convert::v2_1_0_to_v2_0_0(&spec);
+    assert_eq!(converted.report.warnings.len(), 1, "{}", converted.report);
+    assert_eq!(converted.report.warnings[0].location, "/auth/apikey/1");
+}
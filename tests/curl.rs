@@ -0,0 +1,484 @@
+use postman_collection::curl::{Generator, Shell, parse_with};
+use postman_collection::v2_1_0::auth::{BasicAuth, TypedAuth};
+use postman_collection::v2_1_0::{
+    Body, FormParameter, Header, Item, RequestClass, RequestUnion, UrlEncodedParameter,
+};
+use postman_collection::{Error, PostmanCollection, from_str};
+
+fn request(json: serde_json::Value) -> RequestClass {
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn renders_the_method_url_and_headers() {
+    let get = request(serde_json::json!({
+        "method": "get",
+        "url": {
+            "raw": "https://example.com/users/:id?expand=true&debug",
+            "protocol": "https",
+            "host": ["example", "com"],
+            "path": ["users", ":id"],
+            "query": [
+                {"key": "expand", "value": "true"},
+                {"key": "debug", "disabled": true}
+            ],
+            "variable": [{"key": "id", "value": "a b"}]
+        },
+        "header": "Accept: */*\n// X-Debug: 1\nX-Empty:"
+    }));
+    assert_eq!(
+        get.to_curl().unwrap(),
+        "curl 'https://example.com/users/a%20b?expand=true' -H 'Accept: */*' -H 'X-Empty;'"
+    );
+
+    let delete = request(serde_json::json!({"method": "DELETE", "url": "http://localhost/x"}));
+    assert_eq!(
+        delete.to_curl().unwrap(),
+        "curl -X DELETE http://localhost/x"
+    );
+    let head = request(serde_json::json!({"method": "HEAD", "url": "http://localhost/x"}));
+    assert_eq!(head.to_curl().unwrap(), "curl --head http://localhost/x");
+
+    let item: Item = serde_json::from_str(r#"{"request": "http://localhost/it's"}"#).unwrap();
+    assert_eq!(item.to_curl().unwrap(), r"curl 'http://localhost/it'\''s'");
+}
+
+#[test]
+fn renders_every_body_mode() {
+    let body = |body: serde_json::Value| {
+        request(serde_json::json!({"method": "POST", "url": "http://localhost/", "body": body}))
+            .to_curl()
+            .unwrap()
+    };
+
+    assert_eq!(
+        body(serde_json::json!({"mode": "raw", "raw": "hello world"})),
+        "curl http://localhost/ -H 'Content-Type: text/plain' --data-raw 'hello world'"
+    );
+    assert_eq!(
+        body(serde_json::json!({"mode": "urlencoded", "urlencoded": [
+            {"key": "user name", "value": "ada & co"},
+            {"key": "skip", "value": "1", "disabled": true},
+            {"key": "a@b", "value": "@file"}
+        ]})),
+        "curl http://localhost/ --data-urlencode 'user+name=ada & co' \
+         --data-urlencode a%40b=@file"
+    );
+    assert_eq!(
+        body(serde_json::json!({"mode": "formdata", "formdata": [
+            {"key": "note", "value": "@not-a-file", "type": "text"},
+            {"key": "meta", "value": "{\"a\": 1}", "type": "text", "contentType": "application/json"},
+            {"key": "photos", "type": "file", "src": ["/tmp/a.png", "/tmp/b;c.png"]}
+        ]})),
+        "curl http://localhost/ --form-string note=@not-a-file \
+         -F 'meta=\"{\\\"a\\\": 1}\";type=application/json' \
+         -F 'photos=@\"/tmp/a.png\"' -F 'photos=@\"/tmp/b;c.png\"'"
+    );
+    assert_eq!(
+        body(serde_json::json!({"mode": "file", "file": {"src": "/tmp/upload.bin"}})),
+        "curl http://localhost/ --data-binary @/tmp/upload.bin"
+    );
+    assert_eq!(
+        body(serde_json::json!({"mode": "graphql", "graphql": {
+            "query": "query { me { id } }",
+            "variables": "{\"first\": 2}"
+        }})),
+        "curl http://localhost/ -H 'Content-Type: application/json' \
+         --data-raw '{\"query\":\"query { me { id } }\",\"variables\":{\"first\":2}}'"
+    );
+    assert_eq!(
+        body(serde_json::json!({"mode": "raw", "raw": "ignored", "disabled": true})),
+        "curl -X POST http://localhost/"
+    );
+
+    let get_with_body = request(serde_json::json!({
+        "method": "GET",
+        "url": "http://localhost/search",
+        "header": [{"key": "content-type", "value": "application/json"}],
+        "body": {"mode": "raw", "raw": "{}", "options": {"raw": {"language": "json"}}}
+    }));
+    assert_eq!(
+        get_with_body.to_curl().unwrap(),
+        "curl -X GET http://localhost/search -H 'content-type: application/json' --data-raw '{}'"
+    );
+}
+
+#[test]
+fn renders_auth_proxy_and_certificates() {
+    let basic = request(serde_json::json!({
+        "url": "https://example.com/",
+        "auth": {"type": "basic", "basic": [
+            {"key": "username", "value": "ada"},
+            {"key": "password", "value": "p@ss word"}
+        ]},
+        "proxy": {"host": "proxy.local", "port": 3128, "tunnel": true},
+        "certificate": {
+            "cert": {"src": "/certs/client.pem"},
+            "key": {"src": "/certs/client.key"},
+            "passphrase": "secret"
+        }
+    }));
+    assert_eq!(
+        basic.to_curl().unwrap(),
+        "curl https://example.com/ -u 'ada:p@ss word' --proxy proxy.local:3128 --proxytunnel \
+         --cert /certs/client.pem --key /certs/client.key --pass secret"
+    );
+
+    let bearer = request(serde_json::json!({
+        "url": "https://example.com/",
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "t-1"}]},
+        "proxy": {"host": "proxy.local", "disabled": true}
+    }));
+    assert_eq!(
+        bearer.to_curl().unwrap(),
+        "curl https://example.com/ -H 'Authorization: Bearer t-1'"
+    );
+
+    let api_key = |location: &str| {
+        request(serde_json::json!({
+            "url": "https://example.com/?page=2",
+            "auth": {"type": "apikey", "apikey": [
+                {"key": "key", "value": "X-Api-Key"},
+                {"key": "value", "value": "k-1"},
+                {"key": "in", "value": location}
+            ]}
+        }))
+        .to_curl()
+        .unwrap()
+    };
+    assert_eq!(
+        api_key("header"),
+        "curl 'https://example.com/?page=2' -H 'X-Api-Key: k-1'"
+    );
+    assert_eq!(
+        api_key("query"),
+        "curl 'https://example.com/?page=2&X-Api-Key=k-1'"
+    );
+
+    let overridden = request(serde_json::json!({
+        "url": "https://example.com/",
+        "header": [{"key": "authorization", "value": "Token abc"}],
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "t-1"}]}
+    }));
+    assert_eq!(
+        overridden.to_curl().unwrap(),
+        "curl https://example.com/ -H 'authorization: Token abc'"
+    );
+
+    let invalid = request(serde_json::json!({
+        "url": "https://example.com/",
+        "auth": {"type": "bearer", "bearer": []}
+    }));
+    assert!(invalid.to_curl().is_err());
+
+    let unrendered = request(serde_json::json!({
+        "url": "https://example.com/",
+        "auth": {"type": "digest", "digest": [{"key": "username", "value": "u"}]}
+    }));
+    assert_eq!(unrendered.to_curl().unwrap(), "curl https://example.com/");
+}
+
+#[test]
+fn writes_placeholders_as_shell_variables() {
+    let request = request(serde_json::json!({
+        "method": "PUT",
+        "url": "{{baseUrl}}/users/{{user-id}}?trace={{$guid}}",
+        "header": [{"key": "X-Note", "value": "Hi! \"{{name}}\" costs $5"}],
+        "body": {"mode": "raw", "raw": "{{payload}}"}
+    }));
+
+    assert_eq!(
+        request.to_curl().unwrap(),
+        "curl -X PUT '{{baseUrl}}/users/{{user-id}}?trace={{$guid}}' \
+         -H 'X-Note: Hi! \"{{name}}\" costs $5' -H 'Content-Type: text/plain' \
+         --data-raw '{{payload}}'"
+    );
+    assert_eq!(
+        Generator::new()
+            .with_shell_variables()
+            .with_line_breaks()
+            .generate(&request)
+            .unwrap(),
+        "curl -X PUT \"${baseUrl}/users/${user_id}?trace=${_guid}\" \\\n  \
+         -H \"X-Note: Hi\"'!'\" \\\"${name}\\\" costs \\$5\" \\\n  \
+         -H 'Content-Type: text/plain' \\\n  \
+         --data-raw \"${payload}\""
+    );
+}
+
+#[test]
+fn renders_v1_requests() {
+    let PostmanCollection::V1_0_0(spec) = from_str(
+        r#"{
+            "id": "c",
+            "name": "Legacy",
+            "order": ["r"],
+            "requests": [{
+                "id": "r",
+                "name": "Login",
+                "collectionId": "c",
+                "method": "POST",
+                "url": "https://example.com/login",
+                "headers": "Accept: application/json\n",
+                "dataMode": "urlencoded",
+                "data": [{"key": "user", "value": "ada", "type": "text"}]
+            }]
+        }"#,
+    )
+    .unwrap() else {
+        panic!("expected a v1.0.0 collection");
+    };
+
+    assert_eq!(
+        spec.requests[0].to_curl().unwrap(),
+        "curl https://example.com/login -H 'Accept: application/json' --data-urlencode user=ada"
+    );
+}
+
+/// `json` read into `T` and back, with the `null` fields the model writes.
+fn model<T: serde::de::DeserializeOwned + serde::Serialize>(
+    json: serde_json::Value,
+) -> serde_json::Value {
+    serde_json::to_value(serde_json::from_value::<T>(json).unwrap()).unwrap()
+}
+
+fn imported(command: &str) -> serde_json::Value {
+    let item = Item::from_curl(command).unwrap();
+    let RequestUnion::RequestClass(request) = item.request else {
+        panic!("expected a request object");
+    };
+    serde_json::to_value(request).unwrap()
+}
+
+#[test]
+fn imports_posix_commands() {
+    let request = imported(
+        r#"curl 'https://example.com/search' \
+            -XPUT -sSL \
+            -H $'X-Note: it\'s caf\u00e9' -H "Accept: \"*/*\"" \
+            -H 'X-Empty;' -H 'Host:' \
+            -d 'a=1' --data-urlencode 'b=x y&z' --compressed -o out.txt"#,
+    );
+
+    assert_eq!(request["method"], "PUT");
+    assert_eq!(request["url"]["raw"], "https://example.com/search");
+    assert_eq!(
+        request["header"],
+        model::<Vec<Header>>(serde_json::json!([
+            {"key": "X-Note", "value": "it's café"},
+            {"key": "Accept", "value": "\"*/*\""},
+            {"key": "X-Empty", "value": ""}
+        ]))
+    );
+    assert_eq!(request["body"]["mode"], "urlencoded");
+    assert_eq!(
+        request["body"]["urlencoded"],
+        model::<Vec<UrlEncodedParameter>>(serde_json::json!([
+            {"key": "a", "value": "1", "type": "text"},
+            {"key": "b", "value": "x y&z", "type": "text"}
+        ]))
+    );
+
+    let item =
+        Item::from_curl("curl -G https://x/search -d q=rust --data-urlencode 'tag=a b'").unwrap();
+    assert_eq!(
+        item.name.as_deref(),
+        Some("https://x/search?q=rust&tag=a+b")
+    );
+    assert_eq!(
+        item.to_curl().unwrap(),
+        "curl 'https://x/search?q=rust&tag=a+b'"
+    );
+}
+
+#[test]
+fn skips_the_values_of_options_it_does_not_import() {
+    for command in [
+        "curl --retry-delay 5 https://example.com",
+        "curl -U me:pw https://example.com",
+        "curl -C - -y 10 -Y 100 -z today https://example.com",
+        "curl --proxy-header 'X-Via: p' --url-query a=1 --no-keepalive https://example.com",
+        "curl --expand-url https://example.com --variable name=v",
+    ] {
+        let item = Item::from_curl(command).unwrap();
+        assert_eq!(
+            item.name.as_deref(),
+            Some("https://example.com"),
+            "{command}"
+        );
+    }
+
+    let item = Item::from_curl("curl --expand-header 'X-Name: {{name}}' https://x/").unwrap();
+    let RequestUnion::RequestClass(request) = item.request else {
+        unreachable!()
+    };
+    assert_eq!(
+        serde_json::to_value(request.header).unwrap(),
+        model::<Vec<Header>>(serde_json::json!([{"key": "X-Name", "value": "{{name}}"}]))
+    );
+}
+
+#[test]
+fn imports_windows_cmd_commands() {
+    // As copied from a browser's network panel.
+    let command = "curl ^\"https://example.com/api?q=1^&r=2^\" ^\r\n  \
+                   -H ^\"accept: application/json^\" ^\r\n  \
+                   -H ^\"content-type: application/json^\" ^\r\n  \
+                   --data-raw ^\"^{^\\^\"name^\\^\":^\\^\"Ada ^& co^\\^\"^}^\" ^\r\n  \
+                   --compressed";
+    assert_eq!(Shell::detect(command), Shell::Cmd);
+    let request = imported(command);
+    assert_eq!(request["method"], "POST");
+    assert_eq!(request["url"]["raw"], "https://example.com/api?q=1&r=2");
+    assert_eq!(request["header"][1]["value"], "application/json");
+    assert_eq!(request["body"]["raw"], r#"{"name":"Ada & co"}"#);
+    assert_eq!(request["body"]["options"]["raw"]["language"], "json");
+
+    let command =
+        r#"curl -X PATCH "http://localhost/a b" -d "{\"path\": \"C:\\temp\\\\\"}" -u "ada""#;
+    let item = parse_with(command, Shell::Cmd).unwrap();
+    let RequestUnion::RequestClass(request) = item.request else {
+        panic!("expected a request object");
+    };
+    assert_eq!(request.method.as_deref(), Some("PATCH"));
+    assert_eq!(
+        request.body.unwrap().raw.as_deref(),
+        Some(r#"{"path": "C:\\temp\\"}"#)
+    );
+    assert_eq!(
+        request.auth.unwrap().typed().unwrap(),
+        TypedAuth::Basic(BasicAuth {
+            username: "ada".to_owned(),
+            password: None
+        })
+    );
+}
+
+#[test]
+fn imports_every_body_mode() {
+    let json = imported(r#"curl https://x/ --json '{"a": 1}'"#);
+    assert_eq!(json["body"]["raw"], r#"{"a": 1}"#);
+    assert_eq!(json["body"]["options"]["raw"]["language"], "json");
+    assert_eq!(
+        json["header"],
+        model::<Vec<Header>>(serde_json::json!([
+            {"key": "Content-Type", "value": "application/json"},
+            {"key": "Accept", "value": "application/json"}
+        ]))
+    );
+
+    let text = imported("curl https://x/ -d 'just some text'");
+    assert_eq!(text["body"]["mode"], "raw");
+    assert_eq!(text["body"]["raw"], "just some text");
+    assert_eq!(text["body"]["options"], serde_json::Value::Null);
+
+    let forced = imported(
+        "curl https://x/ -H 'Content-Type: application/x-www-form-urlencoded' -d 'flag&x=%41'",
+    );
+    assert_eq!(
+        forced["body"]["urlencoded"],
+        model::<Vec<UrlEncodedParameter>>(serde_json::json!([
+            {"key": "flag", "value": "", "type": "text"},
+            {"key": "x", "value": "A", "type": "text"}
+        ]))
+    );
+
+    let file = imported("curl https://x/upload --data-binary @body.bin");
+    assert_eq!(
+        file["body"],
+        model::<Body>(serde_json::json!({"mode": "file", "file": {"src": "body.bin"}}))
+    );
+
+    let form = imported(
+        r#"curl https://x/ -F 'meta={"a":1};type=application/json' \
+            -F 'photo=@"/tmp/a;b.png";type=image/png' -F 'data=<notes.txt' \
+            --form-string 'note=@literal'"#,
+    );
+    assert_eq!(form["method"], "POST");
+    assert_eq!(
+        form["body"]["formdata"],
+        model::<Vec<FormParameter>>(serde_json::json!([
+            {"key": "meta", "value": "{\"a\":1}", "type": "text", "contentType": "application/json"},
+            {"key": "photo", "src": "/tmp/a;b.png", "type": "file", "contentType": "image/png"},
+            {"key": "data", "src": "notes.txt", "type": "file"},
+            {"key": "note", "value": "@literal", "type": "text"}
+        ]))
+    );
+}
+
+#[test]
+fn round_trips_through_the_generator() {
+    let original = request(serde_json::json!({
+        "method": "PUT",
+        "url": "https://example.com/users/7?fields=id,name",
+        "header": [
+            {"key": "Accept", "value": "application/json"},
+            {"key": "X-Quote", "value": "it's \"quoted\" & $HOME"}
+        ],
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "t-1"}]},
+        "body": {
+            "mode": "raw",
+            "raw": "{\n  \"name\": \"Ada\"\n}",
+            "options": {"raw": {"language": "json"}}
+        },
+        "proxy": {"host": "proxy.local", "port": 3128, "tunnel": true},
+        "certificate": {
+            "cert": {"src": "/certs/client.pem"},
+            "key": {"src": "/certs/client.key"},
+            "passphrase": "it's secret"
+        }
+    }));
+
+    let command = original.to_curl().unwrap();
+    let item = Item::from_curl(&command).unwrap();
+    assert_eq!(item.to_curl().unwrap(), command);
+
+    let RequestUnion::RequestClass(imported) = item.request else {
+        panic!("expected a request object");
+    };
+    assert_eq!(imported.body.unwrap().raw, original.body.unwrap().raw);
+    assert_eq!(imported.proxy, original.proxy);
+    assert_eq!(
+        imported.certificate.unwrap().passphrase.as_deref(),
+        Some("it's secret")
+    );
+}
+
+#[test]
+fn rejects_what_it_cannot_read() {
+    let error = |command: &str| Item::from_curl(command).unwrap_err().to_string();
+
+    assert_eq!(
+        error("wget https://x/"),
+        "invalid curl command: expected `curl`, found `wget`"
+    );
+    assert_eq!(error("curl -s"), "invalid curl command: no URL");
+    assert_eq!(
+        error("curl https://x/ 'https://y/"),
+        "invalid curl command: unterminated `'` quote"
+    );
+    assert_eq!(
+        error("curl https://x/ https://y/"),
+        "invalid curl command: expected one URL, found `https://x/` and `https://y/`"
+    );
+    assert_eq!(
+        error("curl https://x/ -H"),
+        "invalid curl command: `-H` needs a value"
+    );
+    assert_eq!(
+        error("curl --retry-later 5 https://x/"),
+        "invalid curl command: unknown option `--retry-later`"
+    );
+    assert_eq!(
+        error("curl -sj9 https://x/"),
+        "invalid curl command: unknown option `-9`"
+    );
+    assert_eq!(
+        error("curl --no-url https://x/"),
+        "invalid curl command: unknown option `--no-url`"
+    );
+    assert!(matches!(
+        Item::from_curl("curl https://x/ -d @a.json -d b=1"),
+        Err(Error::InvalidCurl { .. })
+    ));
+}
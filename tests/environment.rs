@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use postman_collection::variables::{Resolver, Scope, ScopeKind};
+use postman_collection::{PostmanCollection, environment, from_str, globals, v2_1_0};
+
+fn fixture_path(kind: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(kind)
+        .join(name)
+}
+
+#[test]
+fn parses_and_round_trips_environment_exports() {
+    let path = fixture_path("environment", "staging.postman_environment.json");
+    let env = environment::from_path(&path).unwrap();
+
+    assert_eq!(env.name, "Staging");
+    assert_eq!(env.values.len(), 3);
+    assert_eq!(env.values[1].variable_type.as_deref(), Some("secret"));
+    assert!(!env.values[2].is_enabled());
+    assert_eq!(env.postman_variable_scope.as_deref(), Some("environment"));
+
+    let json = environment::to_json(&env).unwrap();
+    assert_eq!(environment::from_str(&json).unwrap(), env);
+
+    let original: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let written: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(written, original);
+}
+
+#[test]
+fn parses_globals_in_both_export_shapes() {
+    let globals =
+        globals::from_path(fixture_path("globals", "workspace.postman_globals.json")).unwrap();
+    assert_eq!(globals.name.as_deref(), Some("Globals"));
+    assert_eq!(globals.values.len(), 2);
+
+    let legacy =
+        globals::from_str(r#"[{"key": "token", "value": "old", "enabled": true}]"#).unwrap();
+    assert_eq!(legacy.values[0].key, "token");
+    assert!(legacy.id.is_none());
+
+    let json = globals::to_json(&legacy).unwrap();
+    assert_eq!(globals::from_str(&json).unwrap(), legacy);
+}
+
+#[test]
+fn environments_and_globals_become_scopes() {
+    let env = environment::from_path(fixture_path(
+        "environment",
+        "staging.postman_environment.json",
+    ))
+    .unwrap();
+    let globals =
+        globals::from_path(fixture_path("globals", "workspace.postman_globals.json")).unwrap();
+
+    let env_scope = Scope::from(&env);
+    assert_eq!(env_scope.kind, ScopeKind::Environment);
+    assert_eq!(
+        env_scope.get("version"),
+        None,
+        "disabled values are skipped"
+    );
+
+    let global_scope = Scope::from(&globals);
+    assert_eq!(global_scope.kind, ScopeKind::Global);
+    assert_eq!(global_scope.get("userId"), Some("42"));
+
+    let collection = from_str(
+        r#"{
+            "info": {
+                "name": "Users",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "variable": [
+                {"key": "baseUrl", "value": "http://localhost:8080"},
+                {"key": "version", "value": "v1"}
+            ],
+            "item": [
+                {
+                    "name": "Get user",
+                    "request": {
+                        "url": "{{baseUrl}}/{{version}}/users/{{userId}}",
+                        "header": [{"key": "Authorization", "value": "Bearer {{token}}"}]
+                    }
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let PostmanCollection::V2_1_0(spec) = collection else {
+        unreachable!();
+    };
+    let entry = spec.items().next().unwrap();
+    let v2_1_0::RequestUnion::RequestClass(request) = &entry.item.request else {
+        unreachable!();
+    };
+
+    let resolved = Resolver::for_entry(&spec, &entry)
+        .with_scope(global_scope)
+        .with_scope(env_scope)
+        .resolve_request(request);
+    assert!(resolved.is_complete(), "{:?}", resolved.unresolved);
+
+    let request = resolved.value;
+    assert_eq!(
+        request.url,
+        Some(v2_1_0::Url::String(
+            "https://staging.example.com/v1/users/42".to_owned()
+        ))
+    );
+    let Some(v2_1_0::HeaderUnion::HeaderArray(headers)) = &request.header else {
+        unreachable!();
+    };
+    assert_eq!(headers[0].value, "Bearer staging-token");
+}
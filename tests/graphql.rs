@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::PathBuf;
+
+use postman_collection::v2_1_0::{self, GraphQlBody, GraphQlVariables};
+use postman_collection::{Error, PostmanCollection, from_path, graphql};
+use serde_json::json;
+
+fn error(query: &str) -> String {
+    match graphql::validate(query) {
+        Err(Error::GraphQlSyntax {
+            line,
+            column,
+            message,
+        }) => format!("{line}:{column}: {message}"),
+        other => panic!("expected a syntax error for {query:?}, got {other:?}"),
+    }
+}
+
+#[test]
+fn accepts_executable_documents() {
+    for query in [
+        "{ me { id } }",
+        "query { users(first: 10, after: null) { edges { node { id } } } }",
+        r#"query Search($term: String! = "rust", $tags: [String!]) @cached(ttl: 60) {
+            search(term: $term, tags: $tags, filter: {kind: REPO, stars: [1, 2.5e3, -0.5]}) {
+                ... on Repo { name owner: login }
+                ...Extra @include(if: true)
+            }
+        }
+        fragment Extra on Repo { url }"#,
+        "mutation Like($id: ID!) { like(id: $id) { count } }",
+        "subscription { messages { body } }",
+        "# leading comment\n{ a, b, c }",
+        "{ doc(text: \"\"\"\n  multi \\\"\"\" line\n\"\"\") }",
+        "{ escaped(text: \"tab\\t \\u00e9\") }",
+    ] {
+        assert!(
+            graphql::validate(query).is_ok(),
+            "{query:?}: {:?}",
+            graphql::validate(query)
+        );
+    }
+}
+
+#[test]
+fn reports_where_a_query_is_invalid() {
+    assert_eq!(
+        error(""),
+        "1:1: expected a definition, found the end of the document"
+    );
+    assert_eq!(
+        error("{ me { id }"),
+        "1:12: expected a name, found the end of the document"
+    );
+    assert_eq!(
+        error("query {\n  user(id: ) { name }\n}"),
+        "2:12: expected a value, found `)`"
+    );
+    assert_eq!(
+        error("type User { id: ID }"),
+        "1:1: expected `query`, `mutation`, `subscription`, `fragment` or `{`, found `type`"
+    );
+    assert_eq!(
+        error("{ a(x: 007) }"),
+        "1:8: numbers must not have leading zeros"
+    );
+    for number in ["00", "010", "-01", "00.5"] {
+        assert_eq!(
+            error(&format!("{{ a(x: {number}) }}")),
+            "1:8: numbers must not have leading zeros",
+            "{number}"
+        );
+    }
+    assert!(graphql::validate("{ a(x: 0, y: -0, z: 0.10, w: 10) }").is_ok());
+    assert_eq!(error("{ a(x: \"open) }"), "1:8: unterminated string");
+    assert_eq!(error("{ a(x: {{id}}) }"), "1:9: expected a name, found `{`");
+    assert_eq!(
+        error("query($id: ID = $other) { a }"),
+        "1:17: expected a value, found `$`"
+    );
+    assert_eq!(
+        error("fragment on on User { a }"),
+        "1:10: expected a fragment name, found `on`"
+    );
+}
+
+#[test]
+fn rejects_documents_nested_too_deeply() {
+    let nested = |depth: usize| format!("{}{}", "{a".repeat(depth), "}".repeat(depth));
+    assert!(graphql::validate(&nested(256)).is_ok());
+    assert_eq!(error(&nested(257)), "1:514: nesting exceeds 256 levels");
+    assert_eq!(
+        error(&"{a".repeat(200_000)),
+        "1:514: nesting exceeds 256 levels"
+    );
+
+    let list = format!("{{ a(x: {}) }}", "[".repeat(100_000));
+    assert_eq!(error(&list), "1:264: nesting exceeds 256 levels");
+    let list_type = format!("query($x: {}) {{ a }}", "[".repeat(100_000));
+    assert_eq!(error(&list_type), "1:268: nesting exceeds 256 levels");
+}
+
+#[test]
+fn parses_variables_from_strings_and_json() {
+    let mut body = GraphQlBody {
+        query: Some("{ a }".to_owned()),
+        variables: Some(GraphQlVariables::String(r#"{"first": 10}"#.to_owned())),
+        ..GraphQlBody::default()
+    };
+    assert_eq!(body.parse_variables().unwrap(), Some(json!({"first": 10})));
+
+    body.variables = Some(GraphQlVariables::String("  ".to_owned()));
+    assert_eq!(body.parse_variables().unwrap(), None);
+
+    body.variables = Some(GraphQlVariables::Json(json!({"first": 10})));
+    assert_eq!(body.parse_variables().unwrap(), Some(json!({"first": 10})));
+
+    body.variables = Some(GraphQlVariables::String("{first: 10}".to_owned()));
+    assert!(matches!(body.parse_variables(), Err(Error::Json(_))));
+
+    body.query = None;
+    assert!(body.validate().is_err());
+}
+
+#[test]
+fn round_trips_postman_graphql_bodies() {
+    let input = json!({
+        "query": "query Me { me { id } }",
+        "variables": "{\n  \"verbose\": true\n}",
+        "operationName": "Me",
+        "extensions": {"persisted": false}
+    });
+    let body: GraphQlBody = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(body.operation_name.as_deref(), Some("Me"));
+    assert_eq!(
+        body.variables,
+        Some(GraphQlVariables::String(
+            "{\n  \"verbose\": true\n}".to_owned()
+        ))
+    );
+    assert_eq!(body.other["extensions"], json!({"persisted": false}));
+    assert_eq!(serde_json::to_value(&body).unwrap(), input);
+
+    // Like the rest of the model, missing fields are written as `null`.
+    let body: GraphQlBody = serde_json::from_value(json!({"query": "{ a }"})).unwrap();
+    assert_eq!(
+        serde_json::to_value(&body).unwrap(),
+        json!({"query": "{ a }", "variables": null, "operationName": null})
+    );
+}
+
+#[test]
+fn fixture_queries_are_valid_graphql() {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("collection");
+    let mut checked = 0;
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        let Ok(PostmanCollection::V2_1_0(spec)) = from_path(&path) else {
+            continue;
+        };
+        for entry in spec.items() {
+            let v2_1_0::RequestUnion::RequestClass(request) = &entry.item.request else {
+                continue;
+            };
+            let Some(graphql) = request.body.as_ref().and_then(|body| body.graphql.as_ref()) else {
+                continue;
+            };
+            graphql
+                .validate()
+                .unwrap_or_else(|error| panic!("{}: {}: {error}", path.display(), entry.path));
+            graphql.parse_variables().unwrap();
+            checked += 1;
+        }
+    }
+    assert!(checked > 0, "no GraphQL fixtures found");
+}
@@ -0,0 +1,481 @@
+use postman_collection::environment::Environment;
+use postman_collection::har::{Exporter, Har, Importer};
+use postman_collection::v2_1_0::{
+    FormParameterSrcUnion, HeaderUnion, Mode, RawLanguage, RequestUnion, ResponseTime, Spec,
+};
+use postman_collection::{PostmanCollection, from_str};
+
+fn collection(json: serde_json::Value) -> PostmanCollection {
+    from_str(&json.to_string()).unwrap()
+}
+
+fn export(json: serde_json::Value) -> Har {
+    Exporter::new()
+        .with_started_date_time("2024-05-01T12:00:00.000Z")
+        .export(&collection(json))
+        .unwrap()
+}
+
+#[test]
+fn exports_resolved_requests_with_inherited_auth() {
+    let environment: Environment = serde_json::from_value(serde_json::json!({
+        "name": "Staging",
+        "values": [{"key": "host", "value": "staging.example.com", "enabled": true}]
+    }))
+    .unwrap();
+    let collection = collection(serde_json::json!({
+        "info": {
+            "name": "Users",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{token}}"}]},
+        "variable": [
+            {"key": "host", "value": "example.com"},
+            {"key": "token", "value": "t0k"}
+        ],
+        "item": [{
+            "name": "Admin",
+            "item": [{
+                "name": "Get user",
+                "request": {
+                    "method": "get",
+                    "url": {
+                        "raw": "https://{{host}}/users/:id?expand=true&debug=1",
+                        "protocol": "https",
+                        "host": ["{{host}}"],
+                        "path": ["users", ":id"],
+                        "query": [
+                            {"key": "expand", "value": "true"},
+                            {"key": "debug", "value": "1", "disabled": true}
+                        ],
+                        "variable": [{"key": "id", "value": "7"}]
+                    },
+                    "header": [
+                        {"key": "Cookie", "value": "session=abc; theme=dark"},
+                        {"key": "X-Debug", "value": "1", "disabled": true}
+                    ]
+                }
+            }]
+        }]
+    }));
+
+    let har = Exporter::new()
+        .with_environment(&environment)
+        .export(&collection)
+        .unwrap();
+    assert_eq!(har.log.version, "1.2");
+    assert_eq!(har.log.creator.name, "postman_collection");
+    assert_eq!(har.log.comment.as_deref(), Some("Users"));
+
+    let entry = &har.log.entries[0];
+    assert_eq!(entry.comment.as_deref(), Some("Admin/Get user"));
+    assert!(entry.started_date_time.ends_with('Z'));
+    let request = &entry.request;
+    assert_eq!(request.method, "GET");
+    assert_eq!(
+        request.url,
+        "https://staging.example.com/users/7?expand=true"
+    );
+    assert_eq!(request.query_string.len(), 1);
+    assert_eq!(request.query_string[0].name, "expand");
+    let headers: Vec<_> = request
+        .headers
+        .iter()
+        .map(|header| (header.name.as_str(), header.value.as_str()))
+        .collect();
+    assert_eq!(
+        headers,
+        [
+            ("Cookie", "session=abc; theme=dark"),
+            ("Authorization", "Bearer t0k")
+        ]
+    );
+    let cookies: Vec<_> = request
+        .cookies
+        .iter()
+        .map(|cookie| (cookie.name.as_str(), cookie.value.as_str()))
+        .collect();
+    assert_eq!(cookies, [("session", "abc"), ("theme", "dark")]);
+    assert_eq!(request.post_data, None);
+    assert_eq!(request.body_size, 0);
+
+    assert_eq!(entry.response.status, 0);
+    assert_eq!(entry.response.body_size, -1);
+}
+
+#[test]
+fn exports_unapplied_auth_schemes_without_headers() {
+    let har = export(serde_json::json!({
+        "info": {
+            "name": "Digest",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "digest", "digest": [{"key": "username", "value": "ada"}]},
+        "item": [{"name": "Get", "request": "https://example.com/digest"}]
+    }));
+
+    let request = &har.log.entries[0].request;
+    assert_eq!(request.url, "https://example.com/digest");
+    assert!(request.headers.is_empty());
+}
+
+#[test]
+fn exports_request_bodies() {
+    let har = export(serde_json::json!({
+        "info": {
+            "name": "Bodies",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "item": [
+            {
+                "name": "Raw",
+                "request": {
+                    "method": "POST",
+                    "url": "https://example.com/users",
+                    "body": {
+                        "mode": "raw",
+                        "raw": "{\"name\":\"Ada\"}",
+                        "options": {"raw": {"language": "json"}}
+                    }
+                }
+            },
+            {
+                "name": "Form",
+                "request": {
+                    "method": "POST",
+                    "url": "https://example.com/login",
+                    "body": {
+                        "mode": "urlencoded",
+                        "urlencoded": [
+                            {"key": "user", "value": "ada"},
+                            {"key": "note", "value": "hi there"},
+                            {"key": "skip", "value": "1", "disabled": true}
+                        ]
+                    }
+                }
+            },
+            {
+                "name": "Upload",
+                "request": {
+                    "method": "POST",
+                    "url": "https://example.com/upload",
+                    "body": {
+                        "mode": "formdata",
+                        "formdata": [
+                            {"key": "title", "value": "Report", "type": "text"},
+                            {"key": "file", "src": "/tmp/missing/report.pdf", "type": "file"}
+                        ]
+                    }
+                }
+            }
+        ]
+    }));
+
+    let raw = &har.log.entries[0].request;
+    let post_data = raw.post_data.as_ref().unwrap();
+    assert_eq!(post_data.mime_type, "application/json");
+    assert_eq!(post_data.text.as_deref(), Some("{\"name\":\"Ada\"}"));
+    assert_eq!(raw.body_size, 14);
+    assert!(
+        raw.headers
+            .iter()
+            .any(|header| header.name == "Content-Type" && header.value == "application/json")
+    );
+
+    let form = har.log.entries[1].request.post_data.as_ref().unwrap();
+    assert_eq!(form.mime_type, "application/x-www-form-urlencoded");
+    assert_eq!(form.text.as_deref(), Some("user=ada&note=hi+there"));
+    let params: Vec<_> = form
+        .params
+        .iter()
+        .map(|param| (param.name.as_str(), param.value.as_deref()))
+        .collect();
+    assert_eq!(params, [("user", Some("ada")), ("note", Some("hi there"))]);
+
+    let upload = &har.log.entries[2].request;
+    let post_data = upload.post_data.as_ref().unwrap();
+    assert_eq!(post_data.mime_type, "multipart/form-data");
+    assert_eq!(post_data.text, None);
+    assert_eq!(post_data.params[0].value.as_deref(), Some("Report"));
+    assert_eq!(post_data.params[1].file_name.as_deref(), Some("report.pdf"));
+    assert_eq!(upload.body_size, -1);
+}
+
+#[test]
+fn exports_the_first_saved_response() {
+    let har = export(serde_json::json!({
+        "info": {
+            "name": "Responses",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "item": [
+            {
+                "name": "Login",
+                "request": "https://example.com/login",
+                "response": [
+                    {
+                        "name": "Redirect",
+                        "code": 302,
+                        "status": "Found",
+                        "header": [
+                            {"key": "Location", "value": "/home"},
+                            {"key": "Content-Type", "value": "text/html"}
+                        ],
+                        "cookie": [{
+                            "name": "session",
+                            "value": "abc",
+                            "domain": "example.com",
+                            "path": "/",
+                            "httpOnly": true,
+                            "secure": true
+                        }],
+                        "body": "<p>moved</p>",
+                        "responseTime": "35",
+                        "timings": {
+                            "dns": 2.5,
+                            "tcp": 4,
+                            "secureHandshake": 6,
+                            "firstByte": 20,
+                            "download": 1.5
+                        }
+                    },
+                    {"name": "Other", "code": 500}
+                ]
+            },
+            {
+                "name": "Status",
+                "request": "https://example.com/status",
+                "response": [{
+                    "code": 200,
+                    "status": "OK",
+                    "header": "Content-Type: application/json\nX-Request-Id: 42",
+                    "body": "{}",
+                    "responseTime": 12
+                }]
+            }
+        ]
+    }));
+
+    let login = &har.log.entries[0];
+    assert_eq!(login.started_date_time, "2024-05-01T12:00:00.000Z");
+    let response = &login.response;
+    assert_eq!(response.status, 302);
+    assert_eq!(response.status_text, "Found");
+    assert_eq!(response.redirect_url, "/home");
+    assert_eq!(response.content.mime_type, "text/html");
+    assert_eq!(response.content.text.as_deref(), Some("<p>moved</p>"));
+    assert_eq!(response.content.size, 12);
+    assert_eq!(response.cookies[0].name, "session");
+    assert_eq!(response.cookies[0].path.as_deref(), Some("/"));
+    assert_eq!(response.cookies[0].http_only, Some(true));
+    assert_eq!(login.timings.dns, Some(2.5));
+    assert_eq!(login.timings.connect, Some(10.0));
+    assert_eq!(login.timings.ssl, Some(6.0));
+    assert_eq!(login.timings.wait, 20.0);
+    assert_eq!(login.timings.receive, 1.5);
+    assert_eq!(login.time, 34.0);
+
+    let status = &har.log.entries[1];
+    assert_eq!(status.response.headers.len(), 2);
+    assert_eq!(status.response.headers[1].name, "X-Request-Id");
+    assert_eq!(status.response.content.mime_type, "application/json");
+    assert_eq!(status.timings.wait, 12.0);
+    assert_eq!(status.timings.dns, None);
+    assert_eq!(status.time, 12.0);
+
+    let json = serde_json::to_value(&har).unwrap();
+    assert_eq!(json["log"]["entries"][1]["request"]["headersSize"], -1);
+    assert_eq!(json["log"]["entries"][1]["response"]["redirectURL"], "");
+    assert!(json["log"]["entries"][1]["timings"].get("dns").is_none());
+    let parsed: Har = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, har);
+}
+
+fn har(entries: serde_json::Value) -> Har {
+    serde_json::from_value(serde_json::json!({
+        "log": {
+            "version": "1.2",
+            "creator": {"name": "proxy", "version": "1.0"},
+            "entries": entries
+        }
+    }))
+    .unwrap()
+}
+
+fn entry(method: &str, url: &str, response: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "startedDateTime": "2024-05-01T12:00:00.000Z",
+        "time": 10,
+        "request": {"method": method, "url": url, "httpVersion": "HTTP/2"},
+        "response": response
+    })
+}
+
+#[test]
+fn imports_entries_grouped_by_host_and_path() {
+    let ok = serde_json::json!({"status": 200, "statusText": "OK"});
+    let har = har(serde_json::json!([
+        entry("GET", "https://api.example.com/users?page=1", ok.clone()),
+        entry("get", "https://api.example.com/users/7", ok.clone()),
+        entry("GET", "https://api.example.com/users?page=1", ok.clone()),
+        entry("DELETE", "https://api.example.com/users/7", ok.clone()),
+        entry("GET", "http://localhost:8080/health", ok.clone()),
+        entry("GET", "https://tracker.example.com/collect", ok.clone()),
+        entry("GET", "https://www.example.com/app.js", ok.clone()),
+        entry("GET", "https://other.org/", ok.clone()),
+        entry("GET", "data:text/plain,hi", ok.clone())
+    ]));
+
+    let paths = |spec: &Spec| spec.items().map(|entry| entry.path).collect::<Vec<_>>();
+    let spec = Importer::new()
+        .with_host("example.com")
+        .with_host("localhost")
+        .without_host("tracker.example.com")
+        .skip_static_assets()
+        .import(&har);
+    assert_eq!(spec.info.name, "HAR import");
+    assert_eq!(
+        paths(&spec),
+        [
+            "api.example.com/GET \\/users",
+            "api.example.com/users/GET \\/users\\/7",
+            "api.example.com/users/DELETE \\/users\\/7",
+            "localhost:8080/GET \\/health"
+        ]
+    );
+    let RequestUnion::RequestClass(request) = &spec.items().next().unwrap().item.request else {
+        unreachable!()
+    };
+    assert_eq!(
+        request.url.as_ref().unwrap().raw(),
+        "https://api.example.com/users?page=1"
+    );
+
+    let spec = Importer::new()
+        .with_path_depth(0)
+        .keep_duplicates()
+        .import(&har);
+    assert_eq!(spec.items().count(), 8);
+    assert_eq!(
+        paths(&spec)[..3],
+        [
+            "api.example.com/GET \\/users",
+            "api.example.com/GET \\/users\\/7",
+            "api.example.com/GET \\/users"
+        ]
+    );
+    assert_eq!(Spec::from_har(&har).items().count(), 7);
+}
+
+#[test]
+fn imports_requests_and_responses() {
+    let mut upload = entry(
+        "POST",
+        "https://example.com/upload",
+        serde_json::json!({"status": 0}),
+    );
+    upload["request"]["headers"] = serde_json::json!([
+        {"name": ":authority", "value": "example.com"},
+        {"name": "content-type", "value": "multipart/form-data; boundary=x"},
+        {"name": "content-length", "value": "120"},
+        {"name": "x-trace", "value": "1"}
+    ]);
+    upload["request"]["postData"] = serde_json::json!({
+        "mimeType": "multipart/form-data; boundary=x",
+        "params": [
+            {"name": "title", "value": "Report"},
+            {"name": "file", "fileName": "report.pdf", "contentType": "application/pdf"}
+        ]
+    });
+    let mut login = entry(
+        "POST",
+        "https://example.com/login",
+        serde_json::json!({
+            "status": 302,
+            "statusText": "Found",
+            "headers": [{"name": "Location", "value": "/home"}],
+            "cookies": [{"name": "session", "value": "abc", "path": "/", "httpOnly": true}],
+            "content": {"size": 2, "mimeType": "application/json", "text": "{}"},
+            "redirectURL": "/home"
+        }),
+    );
+    login["time"] = serde_json::json!(34.5);
+    login["request"]["postData"] = serde_json::json!({
+        "mimeType": "application/x-www-form-urlencoded",
+        "text": "user=ada&note=hi+there"
+    });
+    login["timings"] = serde_json::json!({
+        "blocked": -1, "dns": 2.5, "connect": 10, "ssl": 6, "send": 0, "wait": 20, "receive": 2
+    });
+    let mut create = entry(
+        "POST",
+        "https://example.com/users",
+        serde_json::json!({"status": 201, "content": {"encoding": "base64", "text": "AAE="}}),
+    );
+    create["request"]["postData"] = serde_json::json!({
+        "mimeType": "application/json; charset=utf-8",
+        "text": "{\"name\":\"Ada\"}"
+    });
+
+    let spec = Importer::new().import(&har(serde_json::json!([upload, login, create])));
+    let items: Vec<_> = spec.items().map(|entry| entry.item).collect();
+
+    let RequestUnion::RequestClass(upload) = &items[0].request else {
+        unreachable!()
+    };
+    let Some(HeaderUnion::HeaderArray(headers)) = &upload.header else {
+        unreachable!()
+    };
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].key, "x-trace");
+    let body = upload.body.as_ref().unwrap();
+    assert_eq!(body.mode, Some(Mode::Formdata));
+    let formdata = body.formdata.as_ref().unwrap();
+    assert_eq!(formdata[0].value.as_deref(), Some("Report"));
+    assert_eq!(formdata[1].form_parameter_type.as_deref(), Some("file"));
+    assert_eq!(
+        formdata[1].src,
+        Some(FormParameterSrcUnion::File("report.pdf".to_owned()))
+    );
+    assert_eq!(items[0].response, None);
+
+    let RequestUnion::RequestClass(login) = &items[1].request else {
+        unreachable!()
+    };
+    let urlencoded = login.body.as_ref().unwrap().urlencoded.as_ref().unwrap();
+    assert_eq!(urlencoded[1].key, "note");
+    assert_eq!(urlencoded[1].value.as_deref(), Some("hi there"));
+    let response = &items[1].response.as_ref().unwrap()[0];
+    assert_eq!(response.name.as_deref(), Some("302 Found"));
+    assert_eq!(response.code, Some(302));
+    assert_eq!(response.body.as_deref(), Some("{}"));
+    assert_eq!(response.response_time, Some(ResponseTime::Double(34.5)));
+    assert_eq!(
+        response.timings,
+        Some(serde_json::json!({
+            "dns": 2.5, "tcp": 4.0, "secureHandshake": 6.0, "firstByte": 20.0, "download": 2.0
+        }))
+    );
+    let cookie = &response.cookie.as_ref().unwrap()[0];
+    assert_eq!(cookie.name.as_deref(), Some("session"));
+    assert_eq!(cookie.path, "/");
+    assert_eq!(
+        response.original_request,
+        Some(RequestUnion::RequestClass(login.clone()))
+    );
+
+    let RequestUnion::RequestClass(create) = &items[2].request else {
+        unreachable!()
+    };
+    let body = create.body.as_ref().unwrap();
+    assert_eq!(body.raw.as_deref(), Some("{\"name\":\"Ada\"}"));
+    assert_eq!(body.raw_language(), Some(&RawLanguage::Json));
+    let response = &items[2].response.as_ref().unwrap()[0];
+    assert_eq!(response.body, None);
+    assert_eq!(response.status, None);
+    assert_eq!(response.name.as_deref(), Some("201"));
+
+    let exported = spec.to_har().unwrap();
+    assert_eq!(exported.log.entries[1].timings.connect, Some(10.0));
+    assert_eq!(exported.log.entries[1].time, 34.5);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "http")]
+
+use postman_collection::Error;
+use postman_collection::v1_0_0;
+use postman_collection::v2_1_0::{Item, ProtocolProfileBehavior, RequestClass};
+use serde_json::json;
+
+fn request(value: serde_json::Value) -> RequestClass {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn converts_method_url_headers_and_raw_body() {
+    let converted = request(json!({
+        "method": "put",
+        "url": {
+            "raw": "https://api.example.com/users/7?verbose=1",
+            "protocol": "https",
+            "host": ["api", "example", "com"],
+            "path": ["users", "7"],
+            "query": [
+                {"key": "verbose", "value": "1"},
+                {"key": "debug", "value": "true", "disabled": true}
+            ]
+        },
+        "header": [
+            {"key": "X-Request-Id", "value": "abc"},
+            {"key": "X-Skipped", "value": "no", "disabled": true}
+        ],
+        "body": {"mode": "raw", "raw": "{\"name\":\"Ada\"}", "options": {"raw": {"language": "json"}}}
+    }))
+    .to_http()
+    .unwrap();
+
+    assert_eq!(converted.method(), "PUT");
+    assert_eq!(converted.uri(), "https://api.example.com/users/7?verbose=1");
+    assert_eq!(converted.headers()["x-request-id"], "abc");
+    assert!(!converted.headers().contains_key("x-skipped"));
+    assert_eq!(converted.headers()["content-type"], "application/json");
+    assert_eq!(converted.body(), br#"{"name":"Ada"}"#);
+}
+
+#[test]
+fn prunes_get_bodies_unless_disabled() {
+    let get = request(json!({
+        "method": "GET",
+        "url": "https://example.com/search",
+        "body": {"mode": "raw", "raw": "payload"}
+    }));
+
+    let pruned = get.to_http().unwrap();
+    assert!(pruned.body().is_empty());
+    assert!(!pruned.headers().contains_key("content-type"));
+
+    let kept = get
+        .to_http_with(Some(&ProtocolProfileBehavior {
+            disable_body_pruning: Some(true),
+        }))
+        .unwrap();
+    assert_eq!(kept.body(), b"payload");
+
+    let item: Item = serde_json::from_value(json!({
+        "protocolProfileBehavior": {"disableBodyPruning": true},
+        "request": {
+            "method": "GET",
+            "url": "https://example.com/search",
+            "body": {"mode": "raw", "raw": "payload"}
+        }
+    }))
+    .unwrap();
+    assert_eq!(item.to_http().unwrap().body(), b"payload");
+}
+
+#[test]
+fn encodes_multipart_and_graphql_bodies() {
+    let form = request(json!({
+        "method": "POST",
+        "url": "https://example.com/upload",
+        "header": [{"key": "Content-Type", "value": "multipart/form-data"}],
+        "body": {
+            "mode": "formdata",
+            "formdata": [
+                {"key": "note", "value": "hi", "type": "text"},
+                {"key": "skip", "value": "x", "type": "text", "disabled": true},
+                {"key": "doc", "type": "file", "src": "tests/fixtures/files/hello.txt"}
+            ]
+        }
+    }))
+    .to_http()
+    .unwrap();
+
+    let content_type = form.headers()["content-type"].to_str().unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap();
+    assert_eq!(form.headers().get_all("content-type").iter().count(), 1);
+    let expected = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"note\"\r\n\r\nhi\r\n\
+         --{boundary}\r\nContent-Disposition: form-data; name=\"doc\"; filename=\"hello.txt\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\nhello from a file\n\r\n--{boundary}--\r\n"
+    );
+    assert_eq!(String::from_utf8(form.body().clone()).unwrap(), expected);
+
+    let graphql = request(json!({
+        "method": "POST",
+        "url": "https://example.com/graphql",
+        "body": {
+            "mode": "graphql",
+            "graphql": {"query": "query($id: ID!) { user(id: $id) { name } }", "variables": "{\"id\": 1}"}
+        }
+    }))
+    .to_http()
+    .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(graphql.body()).unwrap();
+    assert_eq!(body["variables"], json!({"id": 1}));
+    assert_eq!(graphql.headers()["content-type"], "application/json");
+}
+
+#[test]
+fn rejects_unresolved_urls() {
+    let unresolved = request(json!({"url": "{{baseUrl}}/ping"})).to_http();
+    assert!(matches!(unresolved, Err(Error::UnresolvedUrl { .. })));
+}
+
+#[test]
+fn converts_v1_requests() {
+    let request: v1_0_0::Request = serde_json::from_value(json!({
+        "id": "r1",
+        "name": "Create",
+        "method": "POST",
+        "url": "https://example.com/items",
+        "headers": "Accept: text/plain\n// X-Disabled: 1\n",
+        "dataMode": "urlencoded",
+        "data": [
+            {"key": "a", "value": "1", "type": "text", "enabled": true},
+            {"key": "b", "value": "2", "type": "text", "enabled": false}
+        ]
+    }))
+    .unwrap();
+
+    let converted = request.to_http().unwrap();
+    assert_eq!(converted.headers()["accept"], "text/plain");
+    assert!(!converted.headers().contains_key("x-disabled"));
+    assert_eq!(converted.body(), b"a=1");
+}
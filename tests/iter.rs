@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use postman_collection::{CollectionItemMut, PostmanCollection, from_path, from_str, v2_1_0};
+
+fn fixture(name: &str) -> PostmanCollection {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("collection")
+        .join(name);
+    from_path(path).expect("fixture should parse")
+}
+
+fn nested_spec() -> v2_1_0::Spec {
+    let collection = from_str(
+        r#"{
+            "info": {
+                "name": "Nested",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "item": [
+                {
+                    "name": "Users",
+                    "item": [
+                        {
+                            "name": "Admin",
+                            "item": [{"name": "Create user", "request": "https://example.com/users"}]
+                        },
+                        {"name": "List users", "request": "https://example.com/users"},
+                        {"item": [{"request": "https://example.com/unnamed"}]}
+                    ]
+                },
+                {"name": "Health", "request": "https://example.com/health"}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let PostmanCollection::V2_1_0(spec) = collection else {
+        unreachable!();
+    };
+    spec
+}
+
+#[test]
+fn yields_items_depth_first_with_their_folders() {
+    let spec = nested_spec();
+    let entries: Vec<_> = spec.items().collect();
+
+    let paths: Vec<_> = entries.iter().map(|entry| entry.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "Users/Admin/Create user",
+            "Users/List users",
+            "Users/2/0",
+            "Health"
+        ]
+    );
+
+    let ancestors: Vec<_> = entries[0]
+        .ancestors
+        .iter()
+        .map(|group| group.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(ancestors, ["Users", "Admin"]);
+    assert!(entries[3].ancestors.is_empty());
+
+    let indices: Vec<_> = entries
+        .iter()
+        .map(|entry| entry.indices.as_slice())
+        .collect();
+    assert_eq!(indices, [&[0, 0, 0][..], &[0, 1], &[0, 2, 0], &[1]]);
+}
+
+#[test]
+fn edits_items_in_place() {
+    let mut spec = nested_spec();
+    for entry in spec.items_mut() {
+        entry.item.name = Some(entry.path.to_uppercase());
+    }
+
+    let names: Vec<_> = spec
+        .items()
+        .map(|entry| entry.item.name.clone().unwrap())
+        .collect();
+    assert_eq!(names[0], "USERS/ADMIN/CREATE USER");
+    assert_eq!(names[3], "HEALTH");
+
+    let entries: Vec<_> = spec.items_mut().collect();
+    assert_eq!(entries[0].ancestors, [Some("Users"), Some("Admin")]);
+    assert_eq!(entries[2].ancestors, [Some("Users"), None]);
+    assert_eq!(entries[2].indices, [0, 2, 0]);
+}
+
+#[test]
+fn escapes_slashes_in_paths() {
+    let mut spec = nested_spec();
+    for entry in spec.items_mut() {
+        if entry.path == "Users/List users" {
+            entry.item.name = Some("GET /users".to_owned());
+        }
+        if entry.path == "Health" {
+            entry.item.name = Some(r"C:\health".to_owned());
+        }
+    }
+
+    let paths: Vec<_> = spec.items().map(|entry| entry.path).collect();
+    assert_eq!(paths[1], r"Users/GET \/users");
+    assert_eq!(paths[3], r"C:\\health");
+}
+
+#[test]
+fn every_version_walks_the_petstore_the_same_way() {
+    let paths = |name: &str| -> Vec<String> {
+        fixture(name)
+            .items()
+            .map(|entry| entry.path().to_owned())
+            .collect()
+    };
+
+    let v2_1 = paths("swagger-petstore-v2.1.0.json");
+    assert_eq!(v2_1.len(), 20);
+    assert_eq!(v2_1[0], "pet/Update an existing pet");
+    assert_eq!(paths("swagger-petstore-v2.0.0.json"), v2_1);
+
+    let mut v1 = paths("swagger-petstore-v1.0.0.json");
+    let mut expected = v2_1.clone();
+    v1.sort();
+    expected.sort();
+    assert_eq!(v1, expected);
+}
+
+#[test]
+fn edits_v1_requests_through_the_collection() {
+    let mut collection = fixture("swagger-petstore-v1.0.0.json");
+    for entry in collection.items_mut() {
+        let CollectionItemMut::V1_0_0(entry) = entry else {
+            panic!("expected a v1 entry");
+        };
+        if entry.path == "pet/Find pet by ID" {
+            entry.request.method = "HEAD".to_owned();
+        }
+    }
+
+    let PostmanCollection::V1_0_0(spec) = &collection else {
+        unreachable!();
+    };
+    let entry = spec
+        .items()
+        .find(|entry| entry.path == "pet/Find pet by ID")
+        .unwrap();
+    assert_eq!(entry.request.method, "HEAD");
+    assert_eq!(entry.ancestors[0].name, "pet");
+}
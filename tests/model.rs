@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+
+use postman_collection::convert::ConversionWarningKind;
+use postman_collection::model::{AuthKind, Body, Collection, Node};
+use postman_collection::{from_path, from_str};
+use serde_json::json;
+
+fn fixture(name: &str) -> Collection {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("collection")
+        .join(name);
+    Collection::from(from_path(path).expect("fixture should parse"))
+}
+
+fn request_paths(nodes: &[Node], prefix: &str, paths: &mut Vec<String>) {
+    for node in nodes {
+        match node {
+            Node::Folder(folder) => {
+                request_paths(&folder.items, &format!("{prefix}{}/", folder.name), paths)
+            }
+            Node::Request(request) => {
+                paths.push(format!("{} {prefix}{}", request.method, request.name))
+            }
+        }
+    }
+}
+
+fn sorted_request_paths(collection: &Collection) -> Vec<String> {
+    let mut paths = Vec::new();
+    request_paths(&collection.items, "", &mut paths);
+    paths.sort();
+    paths
+}
+
+#[test]
+fn every_petstore_version_yields_the_same_requests() {
+    let v1 = fixture("swagger-petstore-v1.0.0.json");
+    let v2_0 = fixture("swagger-petstore-v2.0.0.json");
+    let v2_1 = fixture("swagger-petstore-v2.1.0.json");
+
+    assert_eq!(v2_0.name, "Swagger Petstore");
+    assert_eq!(v2_0.requests().count(), 20);
+    assert_eq!(sorted_request_paths(&v2_0), sorted_request_paths(&v2_1));
+    assert!(sorted_request_paths(&v2_1).contains(&"PUT pet/Update an existing pet".to_owned()));
+    assert_eq!(v1.requests().count(), 20);
+}
+
+#[test]
+fn normalizes_auth_across_versions() {
+    for name in ["apikey-v2.0.0.json", "apikey-v2.1.0.json"] {
+        let collection = fixture(name);
+        let auth = collection.auth.expect("collection auth should be kept");
+        assert_eq!(auth.kind, AuthKind::ApiKey, "{name}");
+        assert_eq!(auth.param("key"), Some(&json!("X-API-Key")), "{name}");
+    }
+}
+
+#[test]
+fn exposes_graphql_bodies_and_examples() {
+    let collection = fixture("graphql-query-v2.1.0.json");
+    let request = collection.requests().next().unwrap();
+
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.url.raw, "https://example.com/graphql");
+    assert_eq!(
+        request.body,
+        Some(Body::GraphQl {
+            query: "query Viewer { viewer { id name } }".to_owned(),
+            variables: Some(json!({ "includeInactive": false })),
+        })
+    );
+
+    let example = &request.examples[0];
+    assert_eq!(example.name.as_deref(), Some("Viewer response"));
+    assert_eq!(example.code, Some(200));
+    assert_eq!(example.response_time, Some(123.0));
+}
+
+#[test]
+fn fills_in_what_the_schema_leaves_implicit() {
+    let collection = Collection::from(
+        from_str(
+            r#"{
+                "info": {
+                    "name": "Implicit",
+                    "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json",
+                    "version": {"major": 1, "minor": 2, "patch": 3, "identifier": "beta"}
+                },
+                "item": [
+                    {"name": "Plain", "request": "https://example.com/plain"},
+                    {
+                        "name": "Parts",
+                        "request": {
+                            "header": "Accept: text/plain\nX-Trace: 1",
+                            "body": {"mode": "raw", "raw": "ignored", "disabled": true},
+                            "url": {
+                                "protocol": "https",
+                                "host": ["example", "com"],
+                                "path": ["items", ":id"],
+                                "query": [
+                                    {"key": "a", "value": "1"},
+                                    {"key": "b", "value": "2", "disabled": true}
+                                ]
+                            }
+                        },
+                        "response": [{
+                            "name": "Saved",
+                            "header": ["Content-Type: text/plain", "not a header"],
+                            "body": "ok"
+                        }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap(),
+    );
+
+    assert_eq!(collection.version.as_deref(), Some("1.2.3-beta"));
+
+    let requests: Vec<_> = collection.requests().collect();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].url.raw, "https://example.com/plain");
+
+    assert_eq!(requests[1].method, "GET");
+    assert_eq!(requests[1].url.raw, "https://example.com/items/:id?a=1");
+    assert_eq!(requests[1].url.query.len(), 2);
+    assert!(!requests[1].url.query[1].enabled);
+    assert_eq!(requests[1].headers[1].key, "X-Trace");
+    assert_eq!(requests[1].body, None);
+    let headers: Vec<_> = requests[1].examples[0]
+        .headers
+        .iter()
+        .map(|header| (header.key.as_str(), header.value.as_str()))
+        .collect();
+    assert_eq!(headers, [("Content-Type", "text/plain")]);
+}
+
+#[test]
+fn keeps_the_report_of_converting_v1_collections() {
+    let collection = from_str(
+        r#"{
+            "id": "c1",
+            "name": "Legacy",
+            "order": ["r1", "missing"],
+            "requests": [{
+                "id": "r1",
+                "name": "Ping",
+                "method": "GET",
+                "url": "https://example.com/ping",
+                "headers": ""
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    let converted = Collection::from_collection(&collection);
+    assert_eq!(converted.spec, Collection::from(collection));
+    assert_eq!(converted.report.warnings.len(), 1);
+    assert_eq!(converted.report.warnings[0].location, "/order");
+    assert_eq!(
+        converted.report.warnings[0].kind,
+        ConversionWarningKind::MissingReference {
+            id: "missing".to_owned()
+        }
+    );
+
+    let v2 = from_str(
+        r#"{
+            "info": {
+                "name": "Current",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "item": []
+        }"#,
+    )
+    .unwrap();
+    assert!(Collection::from_collection(&v2).report.is_lossless());
+}
@@ -0,0 +1,705 @@
+use std::collections::BTreeMap;
+
+use postman_collection::Error;
+use postman_collection::openapi::{self, OpenApi, ParameterLocation, ReferenceOr, SecurityScheme};
+use postman_collection::v2_1_0::auth::{ApiKeyLocation, BearerAuth, TypedAuth};
+use postman_collection::v2_1_0::{
+    AuthType, HeaderUnion, Item, Items, Mode, RawLanguage, RequestClass, RequestUnion, Spec,
+};
+use serde_json::json;
+
+fn document(json: serde_json::Value) -> OpenApi {
+    openapi::from_str(&json.to_string()).unwrap()
+}
+
+fn request(item: &Item) -> &RequestClass {
+    let RequestUnion::RequestClass(request) = &item.request else {
+        unreachable!()
+    };
+    request
+}
+
+fn headers(request: &RequestClass) -> Vec<(&str, &str)> {
+    match &request.header {
+        Some(HeaderUnion::HeaderArray(headers)) => headers
+            .iter()
+            .map(|header| (header.key.as_str(), header.value.as_str()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn generates_folders_urls_and_parameters() {
+    let spec = Spec::from_openapi(&document(serde_json::json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Pets", "version": "1.0", "description": "Pet store"},
+        "servers": [{
+            "url": "https://{region}.example.com/v1/",
+            "variables": {"region": {"default": "eu", "enum": ["eu", "us"]}}
+        }],
+        "tags": [
+            {"name": "pets", "description": "Pet operations"},
+            {"name": "unused"}
+        ],
+        "paths": {
+            "/pets/{petId}": {
+                "parameters": [
+                    {"$ref": "#/components/parameters/PetId"},
+                    {"name": "X-Trace", "in": "header", "schema": {"type": "string"}}
+                ],
+                "get": {
+                    "tags": ["pets", "other"],
+                    "operationId": "getPet",
+                    "description": "Returns one pet",
+                    "parameters": [
+                        {"name": "X-Trace", "in": "header", "example": "abc"},
+                        {"name": "Accept", "in": "header", "example": "text/plain"},
+                        {"name": "fields", "in": "query", "schema": {
+                            "type": "array", "items": {"type": "string", "enum": ["name", "tag"]}
+                        }},
+                        {"name": "session", "in": "cookie", "schema": {"type": "string", "format": "uuid"}}
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "A pet",
+                            "content": {
+                                "application/xml": {"example": "<pet/>"},
+                                "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                            }
+                        },
+                        "default": {"description": "Error"}
+                    }
+                }
+            },
+            "/health": {"get": {"responses": {"204": {"description": ""}}}}
+        },
+        "components": {
+            "parameters": {
+                "PetId": {
+                    "name": "petId", "in": "path", "required": true,
+                    "description": "The pet", "schema": {"type": "integer", "minimum": 1}
+                }
+            },
+            "schemas": {
+                "Pet": {
+                    "allOf": [
+                        {"$ref": "#/components/schemas/Named"},
+                        {"type": "object", "properties": {
+                            "born": {"type": "string", "format": "date"},
+                            "tags": {"type": "array", "items": {"type": "string"}},
+                            "parent": {"$ref": "#/components/schemas/Pet"}
+                        }}
+                    ]
+                },
+                "Named": {"type": "object", "properties": {"name": {"type": "string", "example": "Rex"}}}
+            }
+        }
+    })));
+
+    assert_eq!(spec.info.name, "Pets");
+    let variable = &spec.variable.as_ref().unwrap()[0];
+    assert_eq!(variable.key.as_deref(), Some("baseUrl"));
+    assert_eq!(
+        variable.value,
+        Some(serde_json::json!("https://eu.example.com/v1"))
+    );
+
+    let names: Vec<_> = spec
+        .item
+        .iter()
+        .map(|item| match item {
+            Items::ItemGroup(group) => group.name.clone().unwrap(),
+            Items::Item(item) => item.name.clone().unwrap(),
+        })
+        .collect();
+    assert_eq!(names, ["pets", "GET /health"]);
+
+    let entry = spec.items().next().unwrap();
+    assert_eq!(entry.path, "pets/getPet");
+    let get = request(entry.item);
+    let url = get.url.as_ref().unwrap();
+    assert_eq!(url.raw(), "{{baseUrl}}/pets/:petId?fields=name");
+    let url = url.to_class();
+    let variable = &url.variable.as_ref().unwrap()[0];
+    assert_eq!(variable.key.as_deref(), Some("petId"));
+    assert_eq!(variable.value, Some(serde_json::json!("1")));
+    assert_eq!(
+        headers(get),
+        [
+            ("X-Trace", "abc"),
+            ("Cookie", "session=00000000-0000-0000-0000-000000000000"),
+            ("Accept", "application/json")
+        ]
+    );
+
+    let responses = entry.item.response.as_ref().unwrap();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].code, Some(200));
+    assert_eq!(responses[0].name.as_deref(), Some("A pet"));
+    let body: serde_json::Value =
+        serde_json::from_str(responses[0].body.as_deref().unwrap()).unwrap();
+    assert_eq!(body["name"], "Rex");
+    assert_eq!(body["born"], "1970-01-01");
+    assert_eq!(body["tags"], serde_json::json!(["string"]));
+    assert_eq!(body["parent"]["name"], "Rex");
+
+    let health = spec.items().nth(1).unwrap();
+    assert_eq!(
+        request(health.item).url.as_ref().unwrap().raw(),
+        "{{baseUrl}}/health"
+    );
+    assert_eq!(
+        health.item.response.as_ref().unwrap()[0].name.as_deref(),
+        Some("204")
+    );
+}
+
+#[test]
+fn generates_request_bodies() {
+    let spec = Spec::from_openapi(&document(serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "Bodies", "version": "1"},
+        "paths": {
+            "/users": {
+                "post": {
+                    "summary": "Create",
+                    "requestBody": {"$ref": "#/components/requestBodies/User"},
+                    "responses": {}
+                },
+                "put": {
+                    "summary": "Login",
+                    "requestBody": {"content": {"application/x-www-form-urlencoded": {
+                        "schema": {"type": "object", "properties": {
+                            "user": {"type": "string"}, "remember": {"type": ["boolean", "null"]}
+                        }}
+                    }}},
+                    "responses": {}
+                },
+                "patch": {
+                    "summary": "Upload",
+                    "requestBody": {"content": {"multipart/form-data": {
+                        "schema": {"type": "object", "properties": {
+                            "title": {"type": "string", "default": "Report"},
+                            "file": {"type": "string", "format": "binary"}
+                        }}
+                    }}},
+                    "responses": {}
+                }
+            }
+        },
+        "components": {
+            "requestBodies": {
+                "User": {"content": {"application/json": {
+                    "examples": {"ada": {"$ref": "#/components/examples/Ada"}}
+                }}}
+            },
+            "examples": {"Ada": {"value": {"name": "Ada"}}}
+        }
+    })));
+    assert_eq!(
+        spec.variable.as_ref().unwrap()[0].value,
+        Some(serde_json::json!(""))
+    );
+    let requests: Vec<_> = spec
+        .items()
+        .map(|entry| request(entry.item).clone())
+        .collect();
+
+    let create = &requests[0];
+    assert_eq!(create.method.as_deref(), Some("POST"));
+    let body = create.body.as_ref().unwrap();
+    assert_eq!(body.raw.as_deref(), Some("{\n  \"name\": \"Ada\"\n}"));
+    assert_eq!(body.raw_language(), Some(&RawLanguage::Json));
+    assert_eq!(headers(create), [("Content-Type", "application/json")]);
+
+    let login = requests[1].body.as_ref().unwrap();
+    assert_eq!(login.mode, Some(Mode::Urlencoded));
+    let fields: Vec<_> = login
+        .urlencoded
+        .iter()
+        .flatten()
+        .map(|field| (field.key.as_str(), field.value.as_deref()))
+        .collect();
+    assert_eq!(
+        fields,
+        [("remember", Some("true")), ("user", Some("string"))]
+    );
+
+    let upload = &requests[2];
+    assert!(headers(upload).is_empty());
+    let formdata = upload.body.as_ref().unwrap().formdata.as_ref().unwrap();
+    assert_eq!(formdata[0].key, "file");
+    assert_eq!(formdata[0].form_parameter_type.as_deref(), Some("file"));
+    assert_eq!(formdata[1].value.as_deref(), Some("Report"));
+}
+
+#[test]
+fn maps_security_schemes_to_auth() {
+    let spec = Spec::from_openapi(&document(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Secure", "version": "1"},
+        "security": [{"mtls": []}, {"token": []}],
+        "paths": {
+            "/a": {"get": {"summary": "Inherit", "responses": {}}},
+            "/b": {"get": {"summary": "Public", "security": [], "responses": {}}},
+            "/c": {"get": {"summary": "Key", "security": [{"key": []}], "responses": {}}},
+            "/d": {"get": {
+                "summary": "OAuth",
+                "security": [{"oauth": ["read", "write"]}],
+                "responses": {}
+            }}
+        },
+        "components": {"securitySchemes": {
+            "mtls": {"type": "mutualTLS"},
+            "token": {"type": "http", "scheme": "bearer", "bearerFormat": "JWT"},
+            "key": {"type": "apiKey", "name": "api_key", "in": "query"},
+            "oauth": {"type": "oauth2", "flows": {
+                "implicit": {"authorizationUrl": "https://auth.example.com/authorize", "scopes": {}},
+                "clientCredentials": {"tokenUrl": "https://auth.example.com/token", "scopes": {}}
+            }}
+        }}
+    })));
+
+    let TypedAuth::Bearer(bearer) = spec.auth.as_ref().unwrap().typed().unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(bearer.token, "{{bearerToken}}");
+
+    let requests: Vec<_> = spec
+        .items()
+        .map(|entry| request(entry.item).clone())
+        .collect();
+    assert_eq!(requests[0].auth, None);
+    assert_eq!(
+        requests[1].auth.as_ref().unwrap().auth_type,
+        AuthType::Noauth
+    );
+    let TypedAuth::ApiKey(key) = requests[2].auth.as_ref().unwrap().typed().unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(key.key, "api_key");
+    assert_eq!(key.location, ApiKeyLocation::Query);
+    let TypedAuth::OAuth2(oauth) = requests[3].auth.as_ref().unwrap().typed().unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(oauth.grant_type.as_deref(), Some("client_credentials"));
+    assert_eq!(
+        oauth.access_token_url.as_deref(),
+        Some("https://auth.example.com/token")
+    );
+    assert_eq!(oauth.scope.as_deref(), Some("read write"));
+}
+
+#[test]
+fn rejects_other_document_versions() {
+    for (input, version) in [
+        (
+            r#"{"swagger": "2.0", "info": {"title": "x", "version": "1"}}"#,
+            "2.0",
+        ),
+        (
+            r#"{"openapi": "4.0.0", "info": {"title": "x", "version": "1"}}"#,
+            "4.0.0",
+        ),
+        (r#"{"info": {"title": "x", "version": "1"}}"#, ""),
+    ] {
+        match openapi::from_str(input) {
+            Err(Error::UnsupportedOpenApiVersion { version: found }) => assert_eq!(found, version),
+            other => panic!("expected an unsupported version error, got {other:?}"),
+        }
+    }
+}
+
+fn collection(json: serde_json::Value) -> Spec {
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn exports_paths_parameters_and_tags() {
+    let spec = collection(serde_json::json!({
+        "info": {
+            "name": "Users",
+            "description": "User service",
+            "version": {"major": 2, "minor": 1, "patch": 0},
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "variable": [{"key": "baseUrl", "value": "https://api.example.com/v1"}],
+        "item": [{
+            "name": "Admin",
+            "description": {"content": "Administration"},
+            "item": [{
+                "name": "Get a user",
+                "request": {
+                    "method": "get",
+                    "description": "Fetches one user",
+                    "header": [
+                        {"key": "X-Trace", "value": "abc", "description": "Trace ID"},
+                        {"key": "X-Debug", "value": "1", "disabled": true},
+                        {"key": "Accept", "value": "application/json"},
+                        {"key": "Cookie", "value": "session=xyz; theme=dark"}
+                    ],
+                    "url": {
+                        "raw": "{{baseUrl}}/users/:id?expand=true&limit=10",
+                        "host": ["{{baseUrl}}"],
+                        "path": ["users", ":id"],
+                        "query": [
+                            {"key": "expand", "value": "true"},
+                            {"key": "limit", "value": "10"}
+                        ],
+                        "variable": [{"key": "id", "value": "42", "description": "User ID"}]
+                    }
+                }
+            }]
+        }, {
+            "name": "Get a user",
+            "request": {"method": "PUT", "url": "https://other.example.com/users/{{userId}}"}
+        }, {
+            "name": "Duplicate",
+            "request": {"method": "GET", "url": "{{baseUrl}}/users/:id"}
+        }, {
+            "name": "Custom method",
+            "request": {"method": "PURGE", "url": "{{baseUrl}}/cache"}
+        }]
+    }));
+
+    let document = spec.to_openapi();
+    assert_eq!(document.openapi, "3.1.0");
+    assert_eq!(document.info.title, "Users");
+    assert_eq!(document.info.description.as_deref(), Some("User service"));
+    assert_eq!(document.info.version, "2.1.0");
+    assert_eq!(document.tags.len(), 1);
+    assert_eq!(document.tags[0].name, "Admin");
+    assert_eq!(
+        document.tags[0].description.as_deref(),
+        Some("Administration")
+    );
+
+    assert_eq!(document.servers.len(), 2);
+    assert_eq!(document.servers[0].url, "{baseUrl}");
+    assert_eq!(
+        document.servers[0].variables["baseUrl"].default,
+        "https://api.example.com/v1"
+    );
+    assert_eq!(document.servers[1].url, "https://other.example.com");
+    assert_eq!(
+        document.paths.keys().collect::<Vec<_>>(),
+        ["/users/{id}", "/users/{userId}"]
+    );
+
+    let get = document.paths["/users/{id}"].get.as_ref().unwrap();
+    assert_eq!(get.tags, ["Admin"]);
+    assert_eq!(get.summary.as_deref(), Some("Get a user"));
+    assert_eq!(get.description.as_deref(), Some("Fetches one user"));
+    assert_eq!(get.operation_id.as_deref(), Some("getAUser"));
+    assert!(get.servers.is_empty());
+    let parameters: Vec<_> = get
+        .parameters
+        .iter()
+        .map(|parameter| {
+            let ReferenceOr::Item(parameter) = parameter else {
+                unreachable!()
+            };
+            (
+                parameter.name.as_str(),
+                parameter.location,
+                parameter.example.clone(),
+                parameter.schema.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        parameters,
+        [
+            (
+                "id",
+                ParameterLocation::Path,
+                Some(json!(42)),
+                Some(json!({"type": "integer"}))
+            ),
+            (
+                "expand",
+                ParameterLocation::Query,
+                Some(json!(true)),
+                Some(json!({"type": "boolean"}))
+            ),
+            (
+                "limit",
+                ParameterLocation::Query,
+                Some(json!(10)),
+                Some(json!({"type": "integer"}))
+            ),
+            (
+                "X-Trace",
+                ParameterLocation::Header,
+                Some(json!("abc")),
+                Some(json!({"type": "string"}))
+            ),
+            (
+                "session",
+                ParameterLocation::Cookie,
+                Some(json!("xyz")),
+                Some(json!({"type": "string"}))
+            ),
+            (
+                "theme",
+                ParameterLocation::Cookie,
+                Some(json!("dark")),
+                Some(json!({"type": "string"}))
+            ),
+        ]
+    );
+    let ReferenceOr::Item(id) = &get.parameters[0] else {
+        unreachable!()
+    };
+    assert_eq!(id.required, Some(true));
+    assert_eq!(id.description.as_deref(), Some("User ID"));
+
+    let put = document.paths["/users/{userId}"].put.as_ref().unwrap();
+    assert!(put.tags.is_empty());
+    assert_eq!(put.operation_id.as_deref(), Some("getAUser_2"));
+    assert_eq!(put.servers[0].url, "https://other.example.com");
+    let ReferenceOr::Item(response) = &put.responses["200"] else {
+        unreachable!()
+    };
+    assert_eq!(response.description, "Successful response");
+}
+
+#[test]
+fn exports_bodies_and_responses_with_inferred_schemas() {
+    let spec = collection(serde_json::json!({
+        "info": {
+            "name": "Orders",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "item": [{
+            "name": "Create order",
+            "request": {
+                "method": "POST",
+                "url": "https://shop.example.com/orders",
+                "body": {
+                    "mode": "raw",
+                    "raw": "{\"sku\": \"A-1\", \"quantity\": 2, \"price\": 9.5}",
+                    "options": {"raw": {"language": "json"}}
+                }
+            },
+            "response": [{
+                "name": "Created",
+                "code": 201,
+                "header": [
+                    {"key": "Content-Type", "value": "application/json; charset=utf-8"},
+                    {"key": "Location", "value": "/orders/1"}
+                ],
+                "body": "{\"id\": \"3f0c1a3e-9a8b-4c1d-8e2f-0a1b2c3d4e5f\", \"created\": \"2024-05-01T10:00:00Z\", \"total\": 19}"
+            }, {
+                "name": "Created with note",
+                "code": 201,
+                "header": [{"key": "Content-Type", "value": "application/json"}],
+                "body": "{\"id\": \"3f0c1a3e-9a8b-4c1d-8e2f-0a1b2c3d4e5f\", \"created\": \"2024-05-01T10:00:00Z\", \"total\": 19.5, \"note\": null}"
+            }, {
+                "name": "Bad request",
+                "code": 400,
+                "body": "missing sku"
+            }]
+        }, {
+            "name": "Upload invoice",
+            "request": {
+                "method": "POST",
+                "url": "https://shop.example.com/invoices",
+                "body": {
+                    "mode": "formdata",
+                    "formdata": [
+                        {"key": "order", "value": "1", "type": "text"},
+                        {"key": "file", "src": "invoice.pdf", "type": "file"},
+                        {"key": "skipped", "value": "x", "disabled": true}
+                    ]
+                }
+            }
+        }]
+    }));
+
+    let document = spec.to_openapi();
+    let create = document.paths["/orders"].post.as_ref().unwrap();
+    let ReferenceOr::Item(body) = create.request_body.as_ref().unwrap() else {
+        unreachable!()
+    };
+    let media = &body.content["application/json"];
+    assert_eq!(
+        media.example,
+        Some(json!({"sku": "A-1", "quantity": 2, "price": 9.5}))
+    );
+    assert_eq!(
+        media.schema,
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "price": {"type": "number"},
+                "quantity": {"type": "integer"},
+                "sku": {"type": "string"}
+            },
+            "required": ["price", "quantity", "sku"]
+        }))
+    );
+
+    let ReferenceOr::Item(created) = &create.responses["201"] else {
+        unreachable!()
+    };
+    assert_eq!(created.description, "Created");
+    assert_eq!(created.headers.keys().collect::<Vec<_>>(), ["Location"]);
+    let media = &created.content["application/json"];
+    assert_eq!(
+        media.examples.keys().collect::<Vec<_>>(),
+        ["Created", "Created with note"]
+    );
+    assert_eq!(
+        media.schema,
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "created": {"type": "string", "format": "date-time"},
+                "id": {"type": "string", "format": "uuid"},
+                "note": {"type": "null"},
+                "total": {"type": "number"}
+            },
+            "required": ["created", "id", "total"]
+        }))
+    );
+    let ReferenceOr::Item(bad_request) = &create.responses["400"] else {
+        unreachable!()
+    };
+    assert_eq!(
+        bad_request.content["text/plain"].example,
+        Some(json!("missing sku"))
+    );
+
+    let upload = document.paths["/invoices"].post.as_ref().unwrap();
+    let ReferenceOr::Item(body) = upload.request_body.as_ref().unwrap() else {
+        unreachable!()
+    };
+    let media = &body.content["multipart/form-data"];
+    assert_eq!(media.example, Some(json!({"order": 1})));
+    assert_eq!(
+        media.schema,
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "file": {"type": "string", "format": "binary"},
+                "order": {"type": "integer"}
+            }
+        }))
+    );
+
+    assert_eq!(
+        openapi::infer_schema(&json!([1, 2.5, "a", {"x": 1}, {"x": 2, "y": true}, null])),
+        json!({
+            "type": "array",
+            "items": {
+                "anyOf": [
+                    {"type": "number"},
+                    {"type": "string"},
+                    {
+                        "type": "object",
+                        "properties": {"x": {"type": "integer"}, "y": {"type": "boolean"}},
+                        "required": ["x"]
+                    },
+                    {"type": "null"}
+                ]
+            }
+        })
+    );
+}
+
+#[test]
+fn exports_auth_as_security_and_round_trips() {
+    let spec = collection(serde_json::json!({
+        "info": {
+            "name": "Secured",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{bearerToken}}"}]},
+        "item": [{
+            "name": "Reports",
+            "auth": {"type": "apikey", "apikey": [
+                {"key": "key", "value": "X-Api-Key"},
+                {"key": "value", "value": "{{apiKey}}"},
+                {"key": "in", "value": "header"}
+            ]},
+            "item": [{
+                "name": "List reports",
+                "request": {"method": "GET", "url": "https://api.example.com/reports"}
+            }]
+        }, {
+            "name": "Health",
+            "request": {
+                "method": "GET",
+                "url": "https://api.example.com/health",
+                "auth": {"type": "noauth"}
+            }
+        }, {
+            "name": "Me",
+            "request": {"method": "GET", "url": "https://api.example.com/me"}
+        }]
+    }));
+
+    let document = spec.to_openapi();
+    let bearer = BTreeMap::from([("bearerAuth".to_owned(), Vec::new())]);
+    assert_eq!(document.security, Some(vec![bearer]));
+    let schemes = &document.components.as_ref().unwrap().security_schemes;
+    assert_eq!(
+        schemes.keys().collect::<Vec<_>>(),
+        ["apiKeyAuth", "bearerAuth"]
+    );
+    assert!(matches!(
+        &schemes["apiKeyAuth"],
+        ReferenceOr::Item(SecurityScheme::ApiKey { name, location, .. })
+            if name == "X-Api-Key" && location == "header"
+    ));
+
+    let operation = |path: &str| document.paths[path].get.as_ref().unwrap();
+    let api_key = BTreeMap::from([("apiKeyAuth".to_owned(), Vec::new())]);
+    assert_eq!(operation("/reports").security, Some(vec![api_key]));
+    assert_eq!(operation("/health").security, Some(Vec::new()));
+    assert_eq!(operation("/me").security, None);
+
+    let imported = Spec::from_openapi(&document);
+    assert_eq!(imported.info.name, "Secured");
+    assert_eq!(
+        imported.auth.as_ref().unwrap().typed().unwrap(),
+        TypedAuth::Bearer(BearerAuth {
+            token: "{{bearerToken}}".to_owned()
+        })
+    );
+    let base_url = imported.variable.as_ref().unwrap()[0].value.as_ref();
+    assert_eq!(base_url, Some(&json!("https://api.example.com")));
+    let names: Vec<_> = imported
+        .items()
+        .map(|entry| {
+            (
+                entry.path,
+                request(entry.item).url.as_ref().unwrap().raw().into_owned(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (
+                "Reports/List reports".to_owned(),
+                "{{baseUrl}}/reports".to_owned()
+            ),
+            ("Health".to_owned(), "{{baseUrl}}/health".to_owned()),
+            ("Me".to_owned(), "{{baseUrl}}/me".to_owned()),
+        ]
+    );
+    let health = imported
+        .items()
+        .find(|entry| entry.path == "Health")
+        .unwrap();
+    assert_eq!(
+        request(health.item)
+            .auth
+            .as_ref()
+            .map(|auth| &auth.auth_type),
+        Some(&AuthType::Noauth)
+    );
+}
@@ -0,0 +1,415 @@
+#![cfg(feature = "http")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use http::{Request, Response, StatusCode};
+use postman_collection::runner::{Runner, ScriptContext, ScriptHook, TestResult};
+use postman_collection::{Error, PostmanCollection, environment, from_str};
+
+type Sent = Rc<RefCell<Vec<String>>>;
+
+fn collection(items: serde_json::Value) -> PostmanCollection {
+    let input = serde_json::json!({
+        "info": {
+            "name": "Runner",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "variable": [{"key": "baseUrl", "value": "http://localhost:8080"}],
+        "item": items
+    });
+    from_str(&input.to_string()).unwrap()
+}
+
+/// A stand-in server that records `METHOD uri` for every request. `/down` cannot be reached and
+/// paths containing `missing` answer `404`.
+fn server(
+    sent: &Sent,
+) -> impl FnMut(Request<Vec<u8>>) -> postman_collection::Result<Response<Vec<u8>>> {
+    let sent = Rc::clone(sent);
+    move |request| {
+        sent.borrow_mut()
+            .push(format!("{} {}", request.method(), request.uri()));
+        if request.uri().path() == "/down" {
+            return Err(Error::Backend("connection refused".into()));
+        }
+        let status = if request.uri().path().contains("missing") {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::OK
+        };
+        let mut response = Response::new(request.uri().path().as_bytes().to_vec());
+        *response.status_mut() = status;
+        Ok(response)
+    }
+}
+
+#[test]
+fn sends_every_request_of_every_iteration_in_order() {
+    let sent = Sent::default();
+    let collection = collection(serde_json::json!([
+        {"name": "Users", "item": [
+            {"name": "List", "request": "{{baseUrl}}/users?limit={{limit}}"},
+            {"name": "Create", "request": {"method": "POST", "url": "{{baseUrl}}/users"}}
+        ]},
+        {"name": "Health", "request": "{{baseUrl}}/health"}
+    ]));
+    let env =
+        environment::from_str(r#"{"name": "Local", "values": [{"key": "limit", "value": "5"}]}"#)
+            .unwrap();
+
+    let summary = Runner::new(server(&sent))
+        .with_environment(&env)
+        .with_iterations(2)
+        .run(&collection)
+        .unwrap();
+
+    assert!(summary.is_success());
+    assert_eq!(
+        *sent.borrow(),
+        [
+            "GET http://localhost:8080/users?limit=5",
+            "POST http://localhost:8080/users",
+            "GET http://localhost:8080/health",
+        ]
+        .repeat(2)
+    );
+    let execution = &summary.executions[5];
+    assert_eq!(
+        (execution.iteration, execution.path.as_str()),
+        (1, "Health")
+    );
+    assert_eq!(execution.response.as_ref().unwrap().body(), b"/health");
+    assert!(execution.duration.is_some());
+}
+
+#[test]
+fn runs_only_the_selected_folders() {
+    let sent = Sent::default();
+    let collection = collection(serde_json::json!([
+        {"name": "Users", "item": [{"name": "List", "request": "{{baseUrl}}/users"}]},
+        {"name": "Orders", "item": [{"name": "List", "request": "{{baseUrl}}/orders"}]},
+        {"name": "Health", "request": "{{baseUrl}}/health"}
+    ]));
+
+    let summary = Runner::new(server(&sent))
+        .with_folder("Health")
+        .with_folder("Users")
+        .run(&collection)
+        .unwrap();
+    let paths: Vec<_> = summary.executions.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["Users/List", "Health"]);
+
+    let error = Runner::new(server(&sent))
+        .with_folder("Billing")
+        .run(&collection)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "no folder or request named `Billing`");
+}
+
+#[test]
+fn applies_inherited_auth() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let headers = Rc::clone(&sent);
+    let collection = from_str(
+        r#"{
+            "info": {
+                "name": "Auth",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "auth": {"type": "basic", "basic": [
+                {"key": "username", "value": "ada"},
+                {"key": "password", "value": "{{password}}"}
+            ]},
+            "variable": [{"key": "password", "value": "s3cret"}],
+            "item": [
+                {"name": "Basic", "request": "http://localhost/basic"},
+                {"name": "Keys", "auth": {"type": "apikey", "apikey": [
+                    {"key": "key", "value": "api_key"},
+                    {"key": "value", "value": "k-1"},
+                    {"key": "in", "value": "query"}
+                ]}, "item": [
+                    {"name": "Query", "request": "http://localhost/query?page=2"},
+                    {"name": "Replace", "request": "http://localhost/query?api_key=old&page=3"},
+                    {"name": "Bearer", "request": {
+                        "url": "http://localhost/bearer",
+                        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "t-1"}]}
+                    }},
+                    {"name": "None", "request": {
+                        "url": "http://localhost/none",
+                        "auth": {"type": "noauth"}
+                    }}
+                ]}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let summary = Runner::new(move |request: Request<Vec<u8>>| {
+        let authorization = request
+            .headers()
+            .get("authorization")
+            .map(|value| value.to_str().unwrap().to_owned());
+        headers
+            .borrow_mut()
+            .push((request.uri().to_string(), authorization));
+        Ok(Response::new(Vec::new()))
+    })
+    .run(&collection)
+    .unwrap();
+
+    assert!(summary.is_success());
+    assert_eq!(
+        *sent.borrow(),
+        [
+            (
+                "http://localhost/basic".to_owned(),
+                Some("Basic YWRhOnMzY3JldA==".to_owned())
+            ),
+            ("http://localhost/query?page=2&api_key=k-1".to_owned(), None),
+            ("http://localhost/query?api_key=k-1&page=3".to_owned(), None),
+            (
+                "http://localhost/bearer".to_owned(),
+                Some("Bearer t-1".to_owned())
+            ),
+            ("http://localhost/none".to_owned(), None),
+        ]
+    );
+}
+
+#[test]
+fn sends_unapplied_schemes_without_auth() {
+    let sent = Sent::default();
+    let collection = collection(serde_json::json!([{
+        "name": "Digest",
+        "request": {
+            "url": "{{baseUrl}}/digest",
+            "auth": {"type": "digest", "digest": [{"key": "username", "value": "ada"}]}
+        }
+    }]));
+
+    let summary = Runner::new(server(&sent)).run(&collection).unwrap();
+    assert!(summary.is_success());
+    assert_eq!(*sent.borrow(), ["GET http://localhost:8080/digest"]);
+}
+
+#[test]
+fn fills_in_path_variables() {
+    let sent = Sent::default();
+    let collection = collection(serde_json::json!([{
+        "name": "User",
+        "request": {
+            "url": {
+                "raw": "{{baseUrl}}/users/:id/posts/:post",
+                "host": ["{{baseUrl}}"],
+                "path": ["users", ":id", "posts", ":post"],
+                "variable": [
+                    {"key": "id", "value": "42"},
+                    {"key": "post", "value": "{{post}}"}
+                ]
+            }
+        }
+    }]));
+
+    let summary = Runner::new(server(&sent))
+        .with_environment(
+            &environment::from_str(
+                r#"{"name": "Local", "values": [{"key": "post", "value": "a b"}]}"#,
+            )
+            .unwrap(),
+        )
+        .run(&collection)
+        .unwrap();
+    assert!(summary.is_success(), "{:?}", summary.executions[0].errors);
+    assert_eq!(
+        *sent.borrow(),
+        ["GET http://localhost:8080/users/42/posts/a%20b"]
+    );
+}
+
+#[test]
+fn bails_on_the_first_failure_when_asked() {
+    let items = serde_json::json!([
+        {"name": "Down", "request": "{{baseUrl}}/down"},
+        {"name": "Unresolved", "request": "{{baseUrl}}/users/{{userId}}"},
+        {"name": "Health", "request": "{{baseUrl}}/health"}
+    ]);
+
+    let sent = Sent::default();
+    let summary = Runner::new(server(&sent))
+        .run(&collection(items.clone()))
+        .unwrap();
+    assert!(!summary.bailed);
+    let failures: Vec<_> = summary
+        .failures()
+        .map(|e| e.errors[0].to_string())
+        .collect();
+    assert_eq!(
+        failures,
+        [
+            "request failed: connection refused",
+            "URL `http://localhost:8080/users/{{userId}}` still contains variables: {{userId}}",
+        ]
+    );
+    assert_eq!(sent.borrow().len(), 2);
+
+    let sent = Sent::default();
+    let summary = Runner::new(server(&sent))
+        .bail_on_failure()
+        .with_iterations(3)
+        .run(&collection(items))
+        .unwrap();
+    assert!(summary.bailed);
+    assert_eq!(summary.executions.len(), 1);
+    assert_eq!(*sent.borrow(), ["GET http://localhost:8080/down"]);
+}
+
+#[test]
+fn waits_between_requests() {
+    let sent = Sent::default();
+    let collection = collection(serde_json::json!([
+        {"request": "{{baseUrl}}/a"},
+        {"request": "{{baseUrl}}/b"},
+        {"request": "{{baseUrl}}/c"}
+    ]));
+
+    let started = Instant::now();
+    Runner::new(server(&sent))
+        .with_delay(Duration::from_millis(20))
+        .run(&collection)
+        .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(40));
+}
+
+/// Understands two made-up statements: `set name value` sets a local variable, and
+/// `expect status` tests the response status.
+struct MiniScript {
+    ran: Rc<RefCell<Vec<String>>>,
+}
+
+impl ScriptHook for MiniScript {
+    fn run(
+        &mut self,
+        context: &mut ScriptContext<'_>,
+    ) -> postman_collection::Result<Vec<TestResult>> {
+        self.ran.borrow_mut().push(format!(
+            "{:?} {}: {}",
+            context.listen, context.path, context.source
+        ));
+        let mut tests = Vec::new();
+        for line in context.source.lines() {
+            let words: Vec<_> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["set", name, value] => context.variables.local.set(*name, *value),
+                ["shadow", name, value] => context.variables.collection.set(*name, *value),
+                ["show", name] => self.ran.borrow_mut().push(format!(
+                    "{name}: set {:?}, defined {:?}",
+                    context.variables.collection.get(name),
+                    context.collection().get(name)
+                )),
+                ["expect", status] => {
+                    let actual = context.response.unwrap().status().as_u16().to_string();
+                    tests.push(TestResult {
+                        name: format!("status is {status}"),
+                        error: (actual != *status).then(|| format!("got {actual}")),
+                    });
+                }
+                ["throw"] => return Err(Error::Backend("script error".into())),
+                _ => {}
+            }
+        }
+        Ok(tests)
+    }
+}
+
+#[test]
+fn runs_enabled_scripts_around_each_request() {
+    let sent = Sent::default();
+    let ran = Rc::new(RefCell::new(Vec::new()));
+    let input = serde_json::json!({
+        "info": {
+            "name": "Scripts",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "event": [
+            {"listen": "prerequest", "script": {"exec": ["set resource users"]}},
+            {"listen": "test", "script": {"exec": "expect 200"}}
+        ],
+        "item": [
+            {"name": "Users", "request": "http://localhost/{{resource}}", "event": [
+                {"listen": "test", "script": {"exec": ["expect 201"]}},
+                {"listen": "test", "disabled": true, "script": {"exec": ["throw"]}}
+            ]},
+            {"name": "Missing", "request": "http://localhost/missing/{{resource}}", "event": [
+                {"listen": "prerequest", "script": {"exec": ["set resource orders"]}}
+            ]}
+        ]
+    });
+
+    let summary = Runner::new(server(&sent))
+        .with_script_hook(MiniScript {
+            ran: Rc::clone(&ran),
+        })
+        .run(&from_str(&input.to_string()).unwrap())
+        .unwrap();
+
+    assert_eq!(
+        *sent.borrow(),
+        [
+            "GET http://localhost/users",
+            "GET http://localhost/missing/orders"
+        ]
+    );
+    assert_eq!(
+        *ran.borrow(),
+        [
+            "Prerequest Users: set resource users",
+            "Test Users: expect 200",
+            "Test Users: expect 201",
+            "Prerequest Missing: set resource users",
+            "Prerequest Missing: set resource orders",
+            "Test Missing: expect 200",
+        ]
+    );
+
+    let failed: Vec<_> = summary
+        .executions
+        .iter()
+        .flat_map(|execution| &execution.tests)
+        .filter(|test| !test.passed())
+        .map(|test| format!("{}: {}", test.name, test.error.as_deref().unwrap()))
+        .collect();
+    assert_eq!(failed, ["status is 201: got 200", "status is 200: got 404"]);
+    assert_eq!(summary.variables.local.get("resource"), Some("orders"));
+    assert_eq!(summary.executions[0].tests.len(), 2);
+}
+
+#[test]
+fn shows_hooks_the_collection_variables_scripts_shadow() {
+    let ran = Rc::new(RefCell::new(Vec::new()));
+    let input = serde_json::json!({
+        "info": {
+            "name": "Scripts",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "variable": [{"key": "owner", "value": "ada"}],
+        "item": [{"name": "Owner", "request": "http://localhost/", "event": [
+            {"listen": "prerequest", "script": {"exec": ["show owner", "shadow owner bob", "show owner"]}}
+        ]}]
+    });
+
+    Runner::new(server(&Sent::default()))
+        .with_script_hook(MiniScript {
+            ran: Rc::clone(&ran),
+        })
+        .run(&from_str(&input.to_string()).unwrap())
+        .unwrap();
+    assert_eq!(
+        ran.borrow()[1..],
+        [
+            r#"owner: set None, defined Some("ada")"#,
+            r#"owner: set Some("bob"), defined Some("ada")"#
+        ]
+    );
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "scripts")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use http::{Request, Response, StatusCode};
+use postman_collection::runner::sandbox::Sandbox;
+use postman_collection::runner::{RunSummary, Runner};
+use postman_collection::{Error, environment, from_str};
+use serde_json::json;
+
+/// Runs one request to `http://localhost/echo` with the given scripts. The stand-in server
+/// answers `201` with a JSON description of the request and an `X-Served-By` header.
+fn run(prerequest: &[&str], test: &[&str]) -> RunSummary {
+    let input = json!({
+        "info": {
+            "name": "Sandbox",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "variable": [
+            {"key": "host", "value": "localhost"},
+            {"key": "shadowed", "value": "collection"}
+        ],
+        "item": [{
+            "name": "Echo",
+            "request": {
+                "method": "POST",
+                "url": "http://{{host}}/echo",
+                "header": [{"key": "X-Trace", "value": "{{trace}}"}],
+                "body": {"mode": "raw", "raw": "{\"id\": 7}"}
+            },
+            "event": [
+                {"listen": "prerequest", "script": {"exec": prerequest}},
+                {"listen": "test", "script": {"exec": test}}
+            ]
+        }]
+    });
+    let env = environment::from_str(
+        r#"{"name": "Local", "values": [{"key": "shadowed", "value": "environment"}]}"#,
+    )
+    .unwrap();
+
+    Runner::new(|request: Request<Vec<u8>>| {
+        let headers: serde_json::Map<_, _> = request
+            .headers()
+            .iter()
+            .map(|(key, value)| (key.to_string(), json!(value.to_str().unwrap())))
+            .collect();
+        let body = json!({
+            "method": request.method().as_str(),
+            "uri": request.uri().to_string(),
+            "headers": headers,
+            "body": String::from_utf8_lossy(request.body()),
+            "items": [1, 2, 3]
+        });
+        let mut response = Response::new(body.to_string().into_bytes());
+        *response.status_mut() = StatusCode::CREATED;
+        response
+            .headers_mut()
+            .insert("x-served-by", "stand-in".parse().unwrap());
+        Ok(response)
+    })
+    .with_environment(&env)
+    .with_script_hook(Sandbox::new())
+    .run(&from_str(&input.to_string()).unwrap())
+    .unwrap()
+}
+
+fn failures(summary: &RunSummary) -> Vec<String> {
+    summary.executions[0]
+        .tests
+        .iter()
+        .filter_map(|test| Some(format!("{}: {}", test.name, test.error.as_ref()?)))
+        .collect()
+}
+
+#[test]
+fn prerequest_scripts_change_variables_and_the_request() {
+    let summary = run(
+        &[
+            "pm.environment.set('trace', 'abc-' + pm.info.iteration);",
+            "pm.variables.set('attempt', 2);",
+            "pm.request.headers.upsert({ key: 'X-Attempt', value: pm.variables.get('attempt') });",
+            "pm.request.headers.remove('x-missing');",
+            "pm.request.url = pm.request.url.toString() + '?source=script';",
+            "pm.request.method = 'PUT';",
+            "pm.request.body.update({ id: JSON.parse(pm.request.body.raw).id + 1 });",
+        ],
+        &[
+            "const sent = pm.response.json();",
+            "pm.test('sent the script changes', () => {",
+            "  pm.expect(sent.method).to.equal('PUT');",
+            "  pm.expect(sent.uri).to.equal('http://localhost/echo?source=script');",
+            "  pm.expect(sent.headers).to.include({ 'x-trace': 'abc-0', 'x-attempt': '2' });",
+            "  pm.expect(sent.body).to.equal('{\"id\":8}');",
+            "});",
+        ],
+    );
+
+    assert_eq!(failures(&summary), Vec::<String>::new());
+    assert!(summary.is_success(), "{:?}", summary.executions[0].errors);
+    assert_eq!(summary.variables.environment.get("trace"), Some("abc-0"));
+    assert_eq!(summary.variables.local.get("attempt"), Some("2"));
+}
+
+#[test]
+fn test_scripts_see_the_response_and_every_variable_scope() {
+    let summary = run(
+        &[],
+        &[
+            "pm.test('status', () => pm.response.to.have.status(201));",
+            "pm.test('status text', () => pm.response.to.have.status('Created'));",
+            "pm.test('header', () => pm.response.to.have.header('X-Served-By', 'stand-in'));",
+            "pm.test('json path', () => pm.response.to.have.jsonBody('headers.x-trace'));",
+            "pm.test('precedence', () => {",
+            "  pm.expect(pm.variables.get('shadowed')).to.equal('environment');",
+            "  pm.expect(pm.collectionVariables.get('shadowed')).to.equal('collection');",
+            "  pm.expect(pm.collectionVariables.toObject()).to.eql({ host: 'localhost', shadowed: 'collection' });",
+            "  pm.collectionVariables.set('shadowed', 'script');",
+            "  pm.expect(pm.collectionVariables.get('shadowed')).to.equal('script');",
+            "  pm.collectionVariables.unset('shadowed');",
+            "  pm.expect(pm.collectionVariables.get('shadowed')).to.equal('collection');",
+            "  pm.expect(pm.variables.replaceIn('{{host}}/{{unknown}}')).to.eql('localhost/{{unknown}}');",
+            "});",
+            "pm.test('chai', () => {",
+            "  const body = pm.response.json();",
+            "  pm.expect(body.items).to.be.an('array').that.has.lengthOf(3);",
+            "  pm.expect(body.items).to.deep.include(2).and.not.include(4);",
+            "  pm.expect(body).to.have.property('method', 'POST');",
+            "  pm.expect(body).to.have.keys('method', 'uri');",
+            "  pm.expect(pm.response.responseTime).to.be.at.least(0);",
+            "  pm.expect(pm.response.text()).to.match(/^\\{/);",
+            "  pm.expect(null).to.not.exist;",
+            "  pm.expect({ a: [1] }).to.eql({ a: [1] });",
+            "});",
+            "pm.test('fails with chai messages', () => pm.expect(pm.response.code).to.be.below(200));",
+            "pm.test('negated', () => pm.expect('abc').to.not.include('b'));",
+            "pm.test('property value', () => pm.expect({ id: 1 }).to.have.property('id', 2));",
+            "pm.test('status mismatch', () => pm.response.to.be.ok);",
+            "tests['legacy pass'] = responseCodeIsCreated();",
+            "tests['legacy fail'] = false;",
+            "function responseCodeIsCreated() { return pm.response.code === 201; }",
+        ],
+    );
+
+    assert_eq!(
+        failures(&summary),
+        [
+            "fails with chai messages: AssertionError: expected 201 to be below 200",
+            "negated: AssertionError: expected 'abc' to not include 'b'",
+            "property value: AssertionError: expected {\"id\":1} to have property 'id' of 2, but got 1",
+            "status mismatch: AssertionError: expected response to have status code 200 but got 201",
+            "legacy fail: expected a truthy value",
+        ]
+    );
+    assert_eq!(summary.executions[0].tests.len(), 12);
+    assert!(summary.executions[0].errors.is_empty());
+}
+
+#[test]
+fn uncaught_errors_fail_the_request_but_keep_variable_changes() {
+    let summary = run(
+        &["pm.globals.set('before', true);", "undefinedFunction();"],
+        &[],
+    );
+    let execution = &summary.executions[0];
+    assert!(execution.is_failure());
+    assert!(matches!(
+        &execution.errors[..],
+        [Error::Script { message }] if message.starts_with("ReferenceError")
+    ));
+    assert_eq!(summary.variables.globals.get("before"), Some("true"));
+    // The request is still sent, with `{{trace}}` left as it is.
+    assert!(execution.response.is_some());
+
+    let summary = run(&["if (true {"], &[]);
+    assert!(matches!(
+        &summary.executions[0].errors[..],
+        [Error::Script { message }] if message.starts_with("SyntaxError")
+    ));
+
+    // A script cannot close the function it runs in and add code outside of it.
+    let summary = run(
+        &["}); pm.globals.set('escaped', true); (function () {"],
+        &[],
+    );
+    assert!(matches!(
+        &summary.executions[0].errors[..],
+        [Error::Script { message }] if message.starts_with("SyntaxError")
+    ));
+    assert_eq!(summary.variables.globals.get("escaped"), None);
+
+    // Scripts run as a function body, so they may return early.
+    let summary = run(
+        &["if (true) { return; }", "pm.globals.set('after', true);"],
+        &[],
+    );
+    assert!(summary.is_success());
+    assert_eq!(summary.variables.globals.get("after"), None);
+}
+
+#[test]
+fn stops_runaway_loops() {
+    let sent = Rc::new(RefCell::new(0));
+    let counter = Rc::clone(&sent);
+    let input = json!({
+        "info": {
+            "name": "Loop",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "item": [{
+            "request": "http://localhost/",
+            "event": [{"listen": "prerequest", "script": {"exec": "while (true) {}"}}]
+        }]
+    });
+    let summary = Runner::new(move |_: Request<Vec<u8>>| {
+        *counter.borrow_mut() += 1;
+        Ok(Response::new(Vec::new()))
+    })
+    .with_script_hook(Sandbox::new().with_loop_iteration_limit(1_000))
+    .run(&from_str(&input.to_string()).unwrap())
+    .unwrap();
+
+    assert_eq!(summary.executions[0].errors.len(), 1);
+    assert_eq!(*sent.borrow(), 1);
+}
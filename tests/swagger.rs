@@ -0,0 +1,312 @@
+use postman_collection::Error;
+use postman_collection::openapi::ReferenceOr;
+use postman_collection::swagger::{self, Swagger};
+use postman_collection::v2_1_0::auth::{ApiKeyLocation, TypedAuth};
+use postman_collection::v2_1_0::{
+    HeaderUnion, Item, Items, Mode, RawLanguage, RequestClass, RequestUnion, Spec,
+};
+use serde_json::json;
+
+fn document(json: serde_json::Value) -> Swagger {
+    swagger::from_str(&json.to_string()).unwrap()
+}
+
+fn request(item: &Item) -> &RequestClass {
+    let RequestUnion::RequestClass(request) = &item.request else {
+        unreachable!()
+    };
+    request
+}
+
+fn headers(request: &RequestClass) -> Vec<(&str, &str)> {
+    match &request.header {
+        Some(HeaderUnion::HeaderArray(headers)) => headers
+            .iter()
+            .map(|header| (header.key.as_str(), header.value.as_str()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn imports_base_path_parameters_and_media_types() {
+    let document = document(json!({
+        "swagger": "2.0",
+        "info": {"title": "Pets", "version": "1.0"},
+        "host": "api.example.com:8443",
+        "basePath": "/v2/",
+        "schemes": ["https", "http"],
+        "consumes": ["application/xml"],
+        "produces": ["application/xml", "application/json"],
+        "tags": [{"name": "pets"}],
+        "paths": {
+            "/pets/{petId}": {
+                "parameters": [{"$ref": "#/parameters/petId"}],
+                "get": {
+                    "tags": ["pets"],
+                    "summary": "Get a pet",
+                    "produces": ["application/json"],
+                    "parameters": [
+                        {"name": "fields", "in": "query", "type": "array",
+                         "items": {"type": "string"}, "collectionFormat": "csv",
+                         "x-example": "name,tag"},
+                        {"name": "X-Request-Id", "in": "header", "type": "string",
+                         "format": "uuid"}
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "A pet",
+                            "schema": {"$ref": "#/definitions/Pet"}
+                        },
+                        "404": {"$ref": "#/responses/NotFound"}
+                    }
+                },
+                "put": {
+                    "tags": ["pets"],
+                    "summary": "Replace a pet",
+                    "parameters": [
+                        {"name": "pet", "in": "body", "required": true,
+                         "schema": {"$ref": "#/definitions/Pet"}}
+                    ],
+                    "responses": {"204": {"description": "Replaced"}}
+                }
+            }
+        },
+        "parameters": {
+            "petId": {"name": "petId", "in": "path", "required": true,
+                      "type": "integer", "minimum": 1}
+        },
+        "responses": {
+            "NotFound": {"description": "Not found",
+                         "examples": {"application/json": {"message": "no such pet"}}}
+        },
+        "definitions": {
+            "Pet": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "example": "Rex"},
+                    "owner": {"$ref": "#/definitions/Owner"}
+                }
+            },
+            "Owner": {"type": "object", "properties": {"email": {"type": "string", "format": "email"}}}
+        }
+    }));
+
+    let converted = document.to_openapi();
+    let servers: Vec<_> = converted
+        .servers
+        .iter()
+        .map(|server| server.url.as_str())
+        .collect();
+    assert_eq!(
+        servers,
+        [
+            "https://api.example.com:8443/v2",
+            "http://api.example.com:8443/v2"
+        ]
+    );
+    let get = converted.paths["/pets/{petId}"].get.as_ref().unwrap();
+    let ReferenceOr::Item(pet_id) = &get.parameters[0] else {
+        unreachable!()
+    };
+    assert_eq!(
+        pet_id.schema,
+        Some(json!({"type": "integer", "minimum": 1}))
+    );
+    let ReferenceOr::Item(ok) = &get.responses["200"] else {
+        unreachable!()
+    };
+    assert_eq!(
+        ok.content["application/json"].schema,
+        Some(json!({"$ref": "#/components/schemas/Pet"}))
+    );
+
+    let spec = Spec::from_swagger(&document);
+    assert_eq!(spec.info.name, "Pets");
+    assert_eq!(
+        spec.variable.as_ref().unwrap()[0].value,
+        Some(json!("https://api.example.com:8443/v2"))
+    );
+    let Items::ItemGroup(pets) = &spec.item[0] else {
+        unreachable!()
+    };
+    assert_eq!(pets.name.as_deref(), Some("pets"));
+
+    let items: Vec<_> = spec.items().map(|entry| entry.item).collect();
+    let get = request(items[0]);
+    assert_eq!(
+        get.url.as_ref().unwrap().raw(),
+        "{{baseUrl}}/pets/:petId?fields=name,tag"
+    );
+    assert_eq!(
+        headers(get),
+        [
+            ("X-Request-Id", "00000000-0000-0000-0000-000000000000"),
+            ("Accept", "application/json")
+        ]
+    );
+    let responses = items[0].response.as_ref().unwrap();
+    assert_eq!(
+        responses[0].body.as_deref(),
+        Some(
+            "{\n  \"name\": \"Rex\",\n  \"owner\": {\n    \"email\": \"user@example.com\"\n  }\n}"
+        )
+    );
+    assert_eq!(responses[1].name.as_deref(), Some("Not found"));
+    assert_eq!(
+        responses[1].body.as_deref(),
+        Some("{\n  \"message\": \"no such pet\"\n}")
+    );
+
+    let put = request(items[1]);
+    assert_eq!(headers(put), [("Content-Type", "application/xml")]);
+    let body = put.body.as_ref().unwrap();
+    assert_eq!(body.raw_language(), Some(&RawLanguage::Xml));
+}
+
+#[test]
+fn imports_form_data_as_form_parameters() {
+    let spec = Spec::from_swagger(&document(json!({
+        "swagger": "2.0",
+        "info": {"title": "Forms", "version": "1"},
+        "basePath": "/api",
+        "paths": {
+            "/upload": {
+                "post": {
+                    "summary": "Upload",
+                    "parameters": [
+                        {"name": "title", "in": "formData", "type": "string",
+                         "default": "Report"},
+                        {"name": "file", "in": "formData", "type": "file", "required": true}
+                    ],
+                    "responses": {"201": {"description": "Created"}}
+                }
+            },
+            "/login": {
+                "post": {
+                    "summary": "Login",
+                    "consumes": ["application/x-www-form-urlencoded"],
+                    "parameters": [
+                        {"name": "user", "in": "formData", "type": "string", "x-example": "ada"},
+                        {"name": "remember", "in": "formData", "type": "boolean"}
+                    ],
+                    "responses": {"204": {"description": "Logged in"}}
+                }
+            }
+        }
+    })));
+    assert_eq!(
+        spec.variable.as_ref().unwrap()[0].value,
+        Some(json!("/api"))
+    );
+
+    let requests: Vec<_> = spec
+        .items()
+        .map(|entry| request(entry.item).clone())
+        .collect();
+    let login = requests[0].body.as_ref().unwrap();
+    assert_eq!(requests[0].url.as_ref().unwrap().raw(), "{{baseUrl}}/login");
+    assert_eq!(login.mode, Some(Mode::Urlencoded));
+    let fields: Vec<_> = login
+        .urlencoded
+        .iter()
+        .flatten()
+        .map(|field| (field.key.as_str(), field.value.as_deref()))
+        .collect();
+    assert_eq!(fields, [("remember", Some("true")), ("user", Some("ada"))]);
+
+    let upload = requests[1].body.as_ref().unwrap();
+    assert_eq!(upload.mode, Some(Mode::Formdata));
+    let fields: Vec<_> = upload
+        .formdata
+        .iter()
+        .flatten()
+        .map(|field| {
+            (
+                field.key.as_str(),
+                field.form_parameter_type.as_deref(),
+                field.value.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("file", Some("file"), None),
+            ("title", Some("text"), Some("Report"))
+        ]
+    );
+}
+
+#[test]
+fn maps_security_definitions_to_auth() {
+    let spec = Spec::from_swagger(&document(json!({
+        "swagger": "2.0",
+        "info": {"title": "Secure", "version": "1"},
+        "host": "api.example.com",
+        "security": [{"basic": []}],
+        "securityDefinitions": {
+            "basic": {"type": "basic"},
+            "key": {"type": "apiKey", "name": "X-Api-Key", "in": "header"},
+            "oauth": {"type": "oauth2", "flow": "accessCode",
+                      "authorizationUrl": "https://auth.example.com/authorize",
+                      "tokenUrl": "https://auth.example.com/token",
+                      "scopes": {"read": "Read access"}}
+        },
+        "paths": {
+            "/a": {"get": {"summary": "Inherit", "responses": {}}},
+            "/b": {"get": {"summary": "Public", "security": [], "responses": {}}},
+            "/c": {"get": {"summary": "Key", "security": [{"key": []}], "responses": {}}},
+            "/d": {"get": {"summary": "OAuth", "security": [{"oauth": ["read"]}],
+                           "responses": {}}}
+        }
+    })));
+
+    let TypedAuth::Basic(basic) = spec.auth.as_ref().unwrap().typed().unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(basic.username, "{{username}}");
+
+    let requests: Vec<_> = spec
+        .items()
+        .map(|entry| request(entry.item).clone())
+        .collect();
+    assert_eq!(requests[0].auth, None);
+    assert_eq!(
+        requests[1].auth.as_ref().unwrap().typed().unwrap(),
+        TypedAuth::NoAuth
+    );
+    let TypedAuth::ApiKey(key) = requests[2].auth.as_ref().unwrap().typed().unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(key.key, "X-Api-Key");
+    assert_eq!(key.location, ApiKeyLocation::Header);
+    let TypedAuth::OAuth2(oauth) = requests[3].auth.as_ref().unwrap().typed().unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(oauth.grant_type.as_deref(), Some("authorization_code"));
+    assert_eq!(
+        oauth.auth_url.as_deref(),
+        Some("https://auth.example.com/authorize")
+    );
+    assert_eq!(oauth.scope.as_deref(), Some("read"));
+}
+
+#[test]
+fn rejects_other_document_versions() {
+    for (input, version) in [
+        (
+            r#"{"openapi": "3.0.3", "info": {"title": "x", "version": "1"}}"#,
+            "3.0.3",
+        ),
+        (
+            r#"{"swagger": "1.2", "info": {"title": "x", "version": "1"}}"#,
+            "1.2",
+        ),
+    ] {
+        match swagger::from_str(input) {
+            Err(Error::UnsupportedSwaggerVersion { version: found }) => assert_eq!(found, version),
+            other => panic!("expected an unsupported version error, got {other:?}"),
+        }
+    }
+}
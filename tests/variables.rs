@@ -0,0 +1,292 @@
+use postman_collection::variables::{MAX_EXPANDED_LEN, Resolver, Scope, ScopeKind};
+use postman_collection::{PostmanCollection, from_str, v2_1_0};
+use serde_json::json;
+
+fn spec() -> v2_1_0::Spec {
+    let collection = from_str(
+        r#"{
+            "info": {
+                "name": "Scoped",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "variable": [
+                {"key": "baseUrl", "value": "https://{{host}}/{{version}}"},
+                {"key": "host", "value": "api.example.com"},
+                {"key": "version", "value": "v1"},
+                {"key": "token", "value": "collection-token"},
+                {"key": "limit", "value": 10},
+                {"key": "ignored", "value": "x", "disabled": true}
+            ],
+            "item": [
+                {
+                    "name": "Admin",
+                    "variable": [{"key": "version", "value": "v2"}],
+                    "item": [
+                        {
+                            "name": "List users",
+                            "request": {
+                                "method": "POST",
+                                "auth": {
+                                    "type": "bearer",
+                                    "bearer": [{"key": "token", "value": "{{token}}"}]
+                                },
+                                "url": {
+                                    "raw": "{{baseUrl}}/users?limit={{limit}}",
+                                    "host": ["{{baseUrl}}"],
+                                    "path": ["users"],
+                                    "query": [{"key": "limit", "value": "{{limit}}"}]
+                                },
+                                "header": [{"key": "X-Trace", "value": "{{traceId}}"}],
+                                "body": {
+                                    "mode": "graphql",
+                                    "graphql": {"query": "{ users }", "variables": {"first": "{{limit}}"}}
+                                }
+                            }
+                        }
+                    ]
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let PostmanCollection::V2_1_0(spec) = collection else {
+        unreachable!();
+    };
+    spec
+}
+
+#[test]
+fn folder_variables_shadow_collection_variables() {
+    let spec = spec();
+    let entry = spec.items().next().unwrap();
+    let resolver = Resolver::for_entry(&spec, &entry);
+
+    assert_eq!(resolver.get("version"), Some("v2"));
+    assert_eq!(resolver.get("limit"), Some("10"));
+    assert_eq!(resolver.get("ignored"), None);
+    assert_eq!(
+        resolver.resolve_str("{{baseUrl}}").value,
+        "https://api.example.com/v2"
+    );
+}
+
+#[test]
+fn expands_urls_headers_bodies_and_auth() {
+    let spec = spec();
+    let entry = spec.items().next().unwrap();
+    let v2_1_0::RequestUnion::RequestClass(request) = &entry.item.request else {
+        panic!("expected a structured request");
+    };
+
+    let resolved = Resolver::for_entry(&spec, &entry).resolve_request(request);
+    assert_eq!(resolved.unresolved, ["traceId"]);
+    assert!(!resolved.is_complete());
+
+    let request = resolved.value;
+    let Some(v2_1_0::Url::UrlClass(url)) = &request.url else {
+        panic!("expected a structured URL");
+    };
+    assert_eq!(
+        url.raw.as_deref(),
+        Some("https://api.example.com/v2/users?limit=10")
+    );
+    assert_eq!(
+        url.host,
+        Some(v2_1_0::Host::StringArray(vec![
+            "https://api.example.com/v2".to_owned()
+        ]))
+    );
+    assert_eq!(url.query.as_ref().unwrap()[0].value.as_deref(), Some("10"));
+
+    let Some(v2_1_0::HeaderUnion::HeaderArray(headers)) = &request.header else {
+        panic!("expected a header list");
+    };
+    assert_eq!(headers[0].value, "{{traceId}}");
+
+    let graphql = request.body.unwrap().graphql.unwrap();
+    assert_eq!(graphql.query.as_deref(), Some("{ users }"));
+    assert_eq!(
+        graphql.variables,
+        Some(v2_1_0::GraphQlVariables::Json(json!({"first": "10"})))
+    );
+    assert_eq!(
+        request.auth.unwrap().bearer.unwrap()[0].value,
+        Some(json!("collection-token"))
+    );
+}
+
+#[test]
+fn scopes_follow_postman_precedence() {
+    let resolver = Resolver::new()
+        .with_scope(Scope::new(ScopeKind::Local).with("name", "local"))
+        .with_scope(Scope::new(ScopeKind::Global).with("name", "global"))
+        .with_scope(
+            Scope::new(ScopeKind::Environment)
+                .with("name", "environment")
+                .with("only", "environment"),
+        )
+        .with_scope(Scope::new(ScopeKind::Collection).with("name", "collection"));
+
+    assert_eq!(resolver.get("name"), Some("local"));
+    assert_eq!(resolver.get("only"), Some("environment"));
+
+    let kinds: Vec<_> = resolver.scopes().iter().map(|scope| scope.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ScopeKind::Global,
+            ScopeKind::Collection,
+            ScopeKind::Environment,
+            ScopeKind::Local
+        ]
+    );
+}
+
+#[test]
+fn stops_expanding_self_references() {
+    let resolver = Resolver::new().with_scope(
+        Scope::new(ScopeKind::Collection)
+            .with("loop", "a{{loop}}")
+            .with("ping", "{{pong}}")
+            .with("pong", "{{ping}}")
+            .with("fork", "{{fork}}{{fork}}{{fork}}{{fork}}"),
+    );
+
+    let resolved = resolver.resolve_str("{{loop}}");
+    assert_eq!(resolved.value, "a{{loop}}");
+    assert_eq!(resolved.unresolved, ["loop"]);
+
+    let resolved = resolver.resolve_str("{{ping}} {{missing}} {{missing}}");
+    assert_eq!(resolved.value, "{{ping}} {{missing}} {{missing}}");
+    assert_eq!(resolved.unresolved, ["ping", "missing"]);
+
+    let resolved = resolver.resolve_str("{{fork}}");
+    assert_eq!(resolved.value, "{{fork}}".repeat(4));
+    assert_eq!(resolved.unresolved, ["fork"]);
+}
+
+#[test]
+fn bounds_the_depth_and_size_of_expansions() {
+    // `v20` doubles `v19`, and so on: fully expanded it would be 3 MiB.
+    let mut scope = Scope::new(ScopeKind::Collection).with("v0", "abc");
+    for level in 1..=20 {
+        scope = scope.with(
+            format!("v{level}"),
+            format!("{{{{v{0}}}}}{{{{v{0}}}}}", level - 1),
+        );
+    }
+    let resolver = Resolver::new().with_scope(scope);
+
+    let resolved = resolver.resolve_str("{{v20}}");
+    assert!(
+        resolved.value.len() <= MAX_EXPANDED_LEN + 1024,
+        "{}",
+        resolved.value.len()
+    );
+    assert!(!resolved.is_complete());
+    assert_eq!(resolver.resolve_str("{{v3}}").value, "abc".repeat(8));
+
+    let resolver = resolver.with_max_depth(2);
+    let resolved = resolver.resolve_str("{{v3}}");
+    assert_eq!(resolved.value, "{{v1}}".repeat(4));
+    assert_eq!(resolved.unresolved, ["v1"]);
+}
+
+#[test]
+fn leaves_unbalanced_braces_alone() {
+    let resolver = Resolver::new().with_scope(Scope::new(ScopeKind::Collection).with("a", "1"));
+
+    let resolved = resolver.resolve_str("{{a}} {{ {{a");
+    assert_eq!(resolved.value, "1 {{ {{a");
+    assert!(resolved.is_complete());
+    assert_eq!(resolver.resolve_str("{{}}").value, "{{}}");
+}
+
+mod dynamic {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use postman_collection::variables::dynamic::{Builtins, DynamicVariables, FixedClock};
+    use postman_collection::variables::{Resolver, Scope, ScopeKind};
+
+    fn builtins(seed: u64) -> Builtins {
+        Builtins::seeded(seed).with_clock(FixedClock(
+            UNIX_EPOCH + Duration::from_millis(951_782_400_123),
+        ))
+    }
+
+    #[test]
+    fn seeded_providers_repeat_their_output() {
+        let template = "{{$guid}} {{$randomInt}} {{$randomFullName}} {{$randomEmail}}";
+        let first = Resolver::new()
+            .with_dynamic_variables(builtins(42))
+            .resolve_str(template);
+        let second = Resolver::new()
+            .with_dynamic_variables(builtins(42))
+            .resolve_str(template);
+        let other = Resolver::new()
+            .with_dynamic_variables(builtins(43))
+            .resolve_str(template);
+
+        assert!(first.is_complete());
+        assert_eq!(first.value, second.value);
+        assert_ne!(first.value, other.value);
+    }
+
+    #[test]
+    fn every_occurrence_gets_a_fresh_value() {
+        let resolved = Resolver::new()
+            .with_dynamic_variables(builtins(1))
+            .resolve_str("{{$guid}} {{$guid}}");
+        let (a, b) = resolved.value.split_once(' ').unwrap();
+
+        assert_ne!(a, b);
+        for guid in [a, b] {
+            let groups: Vec<_> = guid.split('-').map(str::len).collect();
+            assert_eq!(groups, [8, 4, 4, 4, 12]);
+            assert_eq!(&guid[14..15], "4");
+            assert!(matches!(&guid[19..20], "8" | "9" | "a" | "b"));
+        }
+    }
+
+    #[test]
+    fn uses_the_injected_clock() {
+        let provider = builtins(0);
+        assert_eq!(provider.generate("$timestamp").unwrap(), "951782400");
+        // 2000-02-29 is a leap day.
+        assert_eq!(
+            provider.generate("$isoTimestamp").unwrap(),
+            "2000-02-29T00:00:00.123Z"
+        );
+
+        let future = provider.generate("$randomDateFuture").unwrap();
+        let past = provider.generate("$randomDatePast").unwrap();
+        assert!(future.as_str() > "2000-02-29T00:00:00.123Z", "{future}");
+        assert!(past.as_str() < "2000-02-29T00:00:00.123Z", "{past}");
+    }
+
+    #[test]
+    fn knows_every_name_it_lists() {
+        let provider = builtins(9);
+        for name in Builtins::names() {
+            let value = provider
+                .generate(name)
+                .unwrap_or_else(|| panic!("{name} should be generated"));
+            assert!(!value.is_empty(), "{name}");
+        }
+        assert_eq!(provider.generate("$randomNothing"), None);
+    }
+
+    #[test]
+    fn scopes_win_and_missing_providers_leave_placeholders() {
+        let resolver = Resolver::new()
+            .with_scope(Scope::new(ScopeKind::Environment).with("$guid", "fixed"))
+            .with_dynamic_variables(builtins(3));
+        assert_eq!(resolver.resolve_str("{{$guid}}").value, "fixed");
+
+        let resolved = Resolver::new().resolve_str("{{$guid}} {{$randomNothing}}");
+        assert_eq!(resolved.unresolved, ["$guid", "$randomNothing"]);
+
+        let resolved = resolver.resolve_str("{{$randomNothing}}");
+        assert_eq!(resolved.unresolved, ["$randomNothing"]);
+    }
+}
@@ -0,0 +1,227 @@
+use std::path::PathBuf;
+
+use postman_collection::{PostmanCollection, from_path, from_str, v1_0_0, v2_0_0, v2_1_0};
+
+fn fixture(name: &str) -> PostmanCollection {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("collection")
+        .join(name);
+    from_path(path).expect("fixture should parse")
+}
+
+fn v2_1_spec() -> v2_1_0::Spec {
+    let collection = from_str(
+        r#"{
+            "info": {
+                "name": "Visited",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{token}}"}]},
+            "variable": [{"key": "token", "value": "secret"}],
+            "item": [
+                {
+                    "name": "Internal",
+                    "item": [
+                        {
+                            "name": "Debug",
+                            "request": {
+                                "url": "https://example.com/debug",
+                                "header": [{"key": "X-Debug", "value": "1"}]
+                            }
+                        }
+                    ]
+                },
+                {
+                    "name": "Search",
+                    "event": [{"listen": "test", "script": {"exec": ["pm.test('ok')"]}}],
+                    "request": {
+                        "method": "POST",
+                        "url": {
+                            "raw": "https://example.com/search?q=a",
+                            "query": [{"key": "q", "value": "a"}]
+                        },
+                        "header": [{"key": "Accept", "value": "application/json"}],
+                        "body": {"mode": "raw", "raw": "{}"}
+                    },
+                    "response": [
+                        {
+                            "name": "Found",
+                            "originalRequest": {
+                                "url": "https://example.com/search",
+                                "header": [{"key": "Accept", "value": "*/*"}]
+                            },
+                            "header": [{"key": "Content-Type", "value": "application/json"}]
+                        }
+                    ]
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let PostmanCollection::V2_1_0(spec) = collection else {
+        unreachable!();
+    };
+    spec
+}
+
+#[derive(Default)]
+struct Locations {
+    visited: Vec<String>,
+    skip_folders: bool,
+}
+
+impl<'a> v2_1_0::visit::Visitor<'a> for Locations {
+    fn visit_item_group(&mut self, group: &'a v2_1_0::ItemGroup, location: &str) {
+        self.visited.push(format!("group {location}"));
+        if !self.skip_folders {
+            v2_1_0::visit::walk_item_group(self, group, location);
+        }
+    }
+
+    fn visit_body(&mut self, _body: &'a v2_1_0::Body, location: &str) {
+        self.visited.push(format!("body {location}"));
+    }
+
+    fn visit_header(&mut self, _header: &'a v2_1_0::Header, location: &str) {
+        self.visited.push(format!("header {location}"));
+    }
+
+    fn visit_query_param(&mut self, _param: &'a v2_1_0::QueryParam, location: &str) {
+        self.visited.push(format!("query {location}"));
+    }
+
+    fn visit_variable(&mut self, _variable: &'a v2_1_0::Variable, location: &str) {
+        self.visited.push(format!("variable {location}"));
+    }
+
+    fn visit_event(&mut self, _event: &'a v2_1_0::Event, location: &str) {
+        self.visited.push(format!("event {location}"));
+    }
+
+    fn visit_auth(&mut self, _auth: &'a v2_1_0::Auth, location: &str) {
+        self.visited.push(format!("auth {location}"));
+    }
+}
+
+#[test]
+fn visits_every_node_with_its_json_pointer() {
+    use v2_1_0::visit::Visitor;
+
+    let spec = v2_1_spec();
+    let mut visitor = Locations::default();
+    visitor.visit_spec(&spec, "");
+
+    assert_eq!(
+        visitor.visited,
+        [
+            "auth /auth",
+            "variable /variable/0",
+            "group /item/0",
+            "header /item/0/item/0/request/header/0",
+            "event /item/1/event/0",
+            "query /item/1/request/url/query/0",
+            "header /item/1/request/header/0",
+            "body /item/1/request/body",
+            "header /item/1/response/0/originalRequest/header/0",
+            "header /item/1/response/0/header/0",
+        ]
+    );
+
+    let value = serde_json::to_value(&spec).unwrap();
+    for entry in &visitor.visited {
+        let (_, location) = entry.split_once(' ').unwrap();
+        assert!(
+            value.pointer(location).is_some(),
+            "{location} should resolve"
+        );
+    }
+}
+
+#[test]
+fn skips_subtrees_that_are_not_walked() {
+    use v2_1_0::visit::Visitor;
+
+    let spec = v2_1_spec();
+    let mut visitor = Locations {
+        skip_folders: true,
+        ..Locations::default()
+    };
+    visitor.visit_spec(&spec, "");
+
+    assert!(visitor.visited.contains(&"group /item/0".to_owned()));
+    assert!(
+        !visitor
+            .visited
+            .iter()
+            .any(|entry| entry.contains("/item/0/"))
+    );
+}
+
+struct Uppercase;
+
+impl v2_1_0::visit::VisitorMut for Uppercase {
+    fn visit_header_mut(&mut self, header: &mut v2_1_0::Header, _location: &str) {
+        header.key = header.key.to_uppercase();
+    }
+}
+
+#[test]
+fn rewrites_nodes_in_place() {
+    use v2_1_0::visit::VisitorMut;
+
+    let mut spec = v2_1_spec();
+    Uppercase.visit_spec_mut(&mut spec, "");
+
+    let json = serde_json::to_string(&spec).unwrap();
+    assert!(json.contains("\"X-DEBUG\""));
+    assert!(json.contains("\"CONTENT-TYPE\""));
+    assert!(!json.contains("\"Accept\""));
+}
+
+#[test]
+fn v2_0_visitor_reaches_nested_requests() {
+    struct Requests(Vec<String>);
+
+    impl<'a> v2_0_0::visit::Visitor<'a> for Requests {
+        fn visit_request(&mut self, _request: &'a v2_0_0::RequestClass, location: &str) {
+            self.0.push(location.to_owned());
+        }
+    }
+
+    let PostmanCollection::V2_0_0(spec) = fixture("coverage/v2.0.0-item-group.json") else {
+        panic!("expected a v2.0.0 fixture");
+    };
+    let mut visitor = Requests(Vec::new());
+    v2_0_0::visit::Visitor::visit_spec(&mut visitor, &spec, "");
+    assert_eq!(visitor.0, ["/item/0/item/0/request"]);
+}
+
+#[test]
+fn v1_visitor_follows_the_flat_lists() {
+    #[derive(Default)]
+    struct Counts {
+        folders: Vec<String>,
+        headers: usize,
+    }
+
+    impl<'a> v1_0_0::visit::Visitor<'a> for Counts {
+        fn visit_folder(&mut self, _folder: &'a v1_0_0::Folder, location: &str) {
+            self.folders.push(location.to_owned());
+        }
+
+        fn visit_header(&mut self, _header: &'a v1_0_0::HeaderClass, _location: &str) {
+            self.headers += 1;
+        }
+    }
+
+    let PostmanCollection::V1_0_0(spec) = fixture("swagger-petstore-v1.0.0.json") else {
+        panic!("expected a v1 fixture");
+    };
+    let mut visitor = Counts::default();
+    v1_0_0::visit::Visitor::visit_spec(&mut visitor, &spec, "");
+
+    assert_eq!(visitor.folders, ["/folders/0", "/folders/1", "/folders/2"]);
+    assert!(visitor.headers > 0);
+}